@@ -1,5 +1,7 @@
 use criterion::{criterion_group, criterion_main, Criterion};
 
+bs58::static_alphabet!(Bitcoin = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz");
+
 macro_rules! group_decode {
     ($criterion:ident, $name:expr, $encoded:expr => $decoded:expr, $decoded_length:expr) => {{
         let mut group = $criterion.benchmark_group($name);
@@ -16,6 +18,26 @@ macro_rules! group_decode {
         group.bench_function("decode_bs58", |b| {
             b.iter(|| bs58::decode($encoded).into_vec().unwrap())
         });
+        group.bench_function("decode_bs58_reserve_exact", |b| {
+            b.iter(|| {
+                bs58::decode($encoded)
+                    .reserve_exact_output()
+                    .into_vec()
+                    .unwrap()
+            })
+        });
+        group.bench_function("decode_bs58_reuse_vec", |b| {
+            let mut output = Vec::new();
+            b.iter(|| bs58::decode($encoded).into_vec_reuse(&mut output).unwrap())
+        });
+        group.bench_function("decode_bs58_static_alphabet", |b| {
+            b.iter(|| {
+                bs58::decode($encoded)
+                    .with_static_alphabet::<Bitcoin>()
+                    .into_vec()
+                    .unwrap()
+            })
+        });
         group.bench_function("decode_bs58_noalloc_slice", |b| {
             let mut output = [0; $decoded_length];
             b.iter(|| bs58::decode($encoded).onto(&mut output[..]).unwrap());
@@ -40,6 +62,26 @@ macro_rules! group_decode_long {
         group.bench_function("decode_bs58", |b| {
             b.iter(|| bs58::decode($encoded).into_vec().unwrap())
         });
+        group.bench_function("decode_bs58_reserve_exact", |b| {
+            b.iter(|| {
+                bs58::decode($encoded)
+                    .reserve_exact_output()
+                    .into_vec()
+                    .unwrap()
+            })
+        });
+        group.bench_function("decode_bs58_reuse_vec", |b| {
+            let mut output = Vec::new();
+            b.iter(|| bs58::decode($encoded).into_vec_reuse(&mut output).unwrap())
+        });
+        group.bench_function("decode_bs58_static_alphabet", |b| {
+            b.iter(|| {
+                bs58::decode($encoded)
+                    .with_static_alphabet::<Bitcoin>()
+                    .into_vec()
+                    .unwrap()
+            })
+        });
         group.bench_function("decode_bs58_noalloc_slice", |b| {
             let mut output = [0; $decoded_length];
             b.iter(|| bs58::decode($encoded).onto(&mut output[..]).unwrap());
@@ -97,5 +139,29 @@ fn bench_decode(c: &mut Criterion) {
     );
 }
 
-criterion_group!(benches, bench_decode);
+fn bench_transcode(c: &mut Criterion) {
+    use bs58::Alphabet;
+
+    for len in [32, 256] {
+        let input: Vec<u8> = (0..len).map(|i| (i * 7 + i / 256) as u8).collect();
+        let encoded = bs58::encode(&input).into_string();
+        let mut group = c.benchmark_group(format!("transcode_{len}_bytes"));
+        group.bench_function("transcode_bs58", |b| {
+            b.iter(|| {
+                bs58::decode::transcode(&encoded, Alphabet::BITCOIN, Alphabet::RIPPLE).unwrap()
+            })
+        });
+        group.bench_function("decode_encode_bs58", |b| {
+            b.iter(|| {
+                let decoded = bs58::decode(&encoded).into_vec().unwrap();
+                bs58::encode(decoded)
+                    .with_alphabet(Alphabet::RIPPLE)
+                    .into_string()
+            })
+        });
+        group.finish();
+    }
+}
+
+criterion_group!(benches, bench_decode, bench_transcode);
 criterion_main!(benches);
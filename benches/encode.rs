@@ -1,5 +1,7 @@
 use criterion::{criterion_group, criterion_main, Criterion};
 
+bs58::static_alphabet!(Bitcoin = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz");
+
 macro_rules! group_encode {
     ($criterion:ident, $name:expr, $decoded:expr => $encoded:expr) => {{
         let mut group = $criterion.benchmark_group($name);
@@ -20,6 +22,17 @@ macro_rules! group_encode {
             let mut output = String::with_capacity($encoded.len());
             b.iter(|| bs58::encode($decoded).onto(&mut output));
         });
+        group.bench_function("encode_bs58_scratch", |b| {
+            let mut scratch = bs58::encode::Scratch::new();
+            b.iter(|| bs58::encode($decoded).with_scratch(&mut scratch).len());
+        });
+        group.bench_function("encode_bs58_static_alphabet", |b| {
+            b.iter(|| {
+                bs58::encode($decoded)
+                    .with_static_alphabet::<Bitcoin>()
+                    .into_string()
+            })
+        });
         group.finish();
     }};
 }
@@ -30,6 +43,7 @@ fn bench_encode(c: &mut Criterion) {
     group_encode!(c, "5_bytes", vec![0x51, 0x6b, 0x6f, 0xcd, 0x0f] => "ABnLTmg");
     group_encode!(c, "10_bytes", vec![0xec, 0xac, 0x89, 0xca, 0xd9, 0x39, 0x23, 0xc0, 0x23, 0x21] => "EJDM8drfXA6uyA");
     group_encode!(c, "10_bytes_zero", vec![0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00] => "1111111111");
+    group_encode!(c, "1024_bytes_zero", vec![0x00; 1024] => "1".repeat(1024));
     group_encode!(c, "10_bytes_max", vec![0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff] => "FPBt6CHo3fovdL");
     group_encode!(c, "32_bytes",
         vec![
@@ -71,5 +85,35 @@ fn bench_encode(c: &mut Criterion) {
     );
 }
 
-criterion_group!(benches, bench_encode);
+fn bench_vanity(c: &mut Criterion) {
+    // Search candidates for one starting with a rare prefix, most are discarded
+    let candidates: Vec<[u8; 32]> = (0u32..256)
+        .map(|i| {
+            let mut candidate = [0x5a; 32];
+            candidate[28..].copy_from_slice(&i.to_be_bytes());
+            candidate
+        })
+        .collect();
+
+    let mut group = c.benchmark_group("vanity_search");
+    group.bench_function("into_string", |b| {
+        b.iter(|| {
+            candidates
+                .iter()
+                .filter(|candidate| bs58::encode(candidate).into_string().starts_with("Bob"))
+                .count()
+        })
+    });
+    group.bench_function("starts_with", |b| {
+        b.iter(|| {
+            candidates
+                .iter()
+                .filter(|candidate| bs58::encode(candidate).starts_with("Bob").unwrap())
+                .count()
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_encode, bench_vanity);
 criterion_main!(benches);
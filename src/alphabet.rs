@@ -2,13 +2,65 @@
 
 use core::fmt;
 
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
 /// Prepared Alphabet for
 /// [`EncodeBuilder::with_alphabet`](crate::encode::EncodeBuilder::with_alphabet) and
 /// [`DecodeBuilder::with_alphabet`](crate::decode::DecodeBuilder::with_alphabet).
-#[derive(Clone, Copy)]
+///
+/// With the `compact-tables` feature the 128 byte decode table is replaced by a 58 byte table of
+/// the digits in the order of their characters, which is binary searched when decoding. This is
+/// slower to decode but makes each alphabet 70 bytes smaller, for targets where flash is more
+/// constrained than time. The results are identical either way.
+///
+/// The built-in alphabets are `const`s, so only the ones that are used are included in a binary.
+///
+/// # Use in `const` and `static` items
+///
+/// The built-in alphabets are `&'static Alphabet` constants, so can be stored directly in `const`
+/// and `static` items. Custom alphabets can be built in them too with [`new`](Self::new) (matching
+/// on the result), [`new_unwrap`](Self::new_unwrap) or [`shuffled`](Self::shuffled), and their
+/// [`encode_table`](Self::encode_table) and [`decode_table`](Self::decode_table) read. As
+/// `Alphabet` has structural equality the built-in constants can also be used as patterns, where
+/// [`MONERO`](Self::MONERO) is the same as [`BITCOIN`](Self::BITCOIN).
+///
+/// These are all tested, so will keep working. They need nothing newer than the current stable
+/// Rust this crate supports.
+///
+/// ```rust
+/// use bs58::Alphabet;
+///
+/// struct Config {
+///     alphabet: &'static Alphabet,
+/// }
+///
+/// const CUSTOM: Alphabet =
+///     match Alphabet::new(b"0123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxy") {
+///         Ok(alphabet) => alphabet,
+///         Err(_) => panic!("invalid alphabet"),
+///     };
+///
+/// static CONFIGS: [Config; 2] = [
+///     Config { alphabet: Alphabet::RIPPLE },
+///     Config { alphabet: &CUSTOM },
+/// ];
+///
+/// fn is_ripple(alphabet: &Alphabet) -> bool {
+///     matches!(alphabet, Alphabet::RIPPLE)
+/// }
+///
+/// assert!(is_ripple(CONFIGS[0].alphabet));
+/// assert!(!is_ripple(CONFIGS[1].alphabet));
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq)]
 pub struct Alphabet {
     pub(crate) encode: [u8; 58],
-    pub(crate) decode: [u8; 128],
+    lead: u8,
+    #[cfg(not(feature = "compact-tables"))]
+    decode: [u8; 128],
+    #[cfg(feature = "compact-tables")]
+    sorted: [u8; 58],
 }
 
 /// Errors that could occur when preparing a Base58 alphabet.
@@ -30,6 +82,13 @@ pub enum Error {
         /// The index at which the non-ASCII character was seen.
         index: usize,
     },
+
+    /// The lead character given to [`Alphabet::with_lead_char`] was non-ASCII or another
+    /// character of the alphabet.
+    InvalidLeadCharacter {
+        /// The lead character given.
+        lead: u8,
+    },
 }
 
 impl Alphabet {
@@ -61,6 +120,21 @@ impl Alphabet {
     /// [`BITCOIN`](Self::BITCOIN) alphabet.
     pub const DEFAULT: &'static Self = Self::BITCOIN;
 
+    /// The value in the [`decode_table`](Self::decode_table) for characters that are not part of
+    /// the alphabet.
+    pub const INVALID: u8 = 0xFF;
+
+    /// The distinct built-in alphabets along with their names.
+    ///
+    /// [`MONERO`](Self::MONERO) uses the same characters as [`BITCOIN`](Self::BITCOIN) so is
+    /// not listed separately.
+    #[cfg(feature = "alloc")]
+    pub(crate) const BUILTIN: [(&'static str, &'static Self); 3] = [
+        ("bitcoin", Self::BITCOIN),
+        ("ripple", Self::RIPPLE),
+        ("flickr", Self::FLICKR),
+    ];
+
     /// Create prepared alphabet, checks that the alphabet is pure ASCII and that there are no
     /// duplicate characters, which would result in inconsistent encoding/decoding
     ///
@@ -101,14 +175,14 @@ impl Alphabet {
     /// ```
     pub const fn new(base: &[u8; 58]) -> Result<Self, Error> {
         let mut encode = [0x00; 58];
-        let mut decode = [0xFF; 128];
+        let mut decode = [Self::INVALID; 128];
 
         let mut i = 0;
         while i < encode.len() {
             if base[i] >= 128 {
                 return Err(Error::NonAsciiCharacter { index: i });
             }
-            if decode[base[i] as usize] != 0xFF {
+            if decode[base[i] as usize] != Self::INVALID {
                 return Err(Error::DuplicateCharacter {
                     character: base[i] as char,
                     first: decode[base[i] as usize] as usize,
@@ -120,7 +194,84 @@ impl Alphabet {
             i += 1;
         }
 
-        Ok(Self { encode, decode })
+        Ok(Self::from_encode(encode))
+    }
+
+    /// Create a deterministic permutation of the characters of `base` from `seed`, for example to
+    /// make sequential IDs encode to visibly unrelated strings.
+    ///
+    /// The permutation is a Fisher–Yates shuffle driven by the [SplitMix64][] generator, starting
+    /// from `seed` as its state: for each index `i` from 57 down to 1, the next output of the
+    /// generator modulo `i + 1` gives the index `j` to swap `i` with. This algorithm is part of the
+    /// API and will not change, so the same `base` and `seed` always give the same alphabet.
+    ///
+    /// This only obscures the encoding, it provides no cryptographic protection: the alphabet can
+    /// be recovered from enough encoded values.
+    ///
+    /// [SplitMix64]: https://prng.di.unimi.it/splitmix64.c
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// const ALPHA: bs58::Alphabet = bs58::Alphabet::shuffled(bs58::Alphabet::DEFAULT, 42);
+    ///
+    /// let encoded = bs58::encode([0x01, 0x02]).with_alphabet(&ALPHA).into_string();
+    /// assert_eq!("Se", encoded);
+    /// assert_eq!(vec![0x01, 0x02], bs58::decode(encoded).with_alphabet(&ALPHA).into_vec()?);
+    /// # Ok::<(), bs58::decode::Error>(())
+    /// ```
+    pub const fn shuffled(base: &Self, seed: u64) -> Self {
+        let mut encode = base.encode;
+        let mut state = seed;
+
+        let mut i = encode.len() - 1;
+        while i > 0 {
+            state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+            let mut z = state;
+            z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+            z ^= z >> 31;
+
+            let j = (z % (i as u64 + 1)) as usize;
+            let swap = encode[i];
+            encode[i] = encode[j];
+            encode[j] = swap;
+            i -= 1;
+        }
+
+        Self::from_encode(encode)
+    }
+
+    /// Prepare the alphabet for an encode table that is known to be valid.
+    pub(crate) const fn from_encode(encode: [u8; 58]) -> Self {
+        #[cfg(not(feature = "compact-tables"))]
+        return Self {
+            encode,
+            lead: encode[0],
+            decode: decode_table(&encode),
+        };
+
+        #[cfg(feature = "compact-tables")]
+        {
+            // The digits in order of their characters, read from the decode table so this
+            // doesn't need a const sort
+            let decode = decode_table(&encode);
+            let mut sorted = [0; 58];
+            let mut len = 0;
+            let mut c = 0;
+            while c < decode.len() {
+                if decode[c] != Self::INVALID {
+                    sorted[len] = decode[c];
+                    len += 1;
+                }
+                c += 1;
+            }
+            Self {
+                encode,
+                lead: encode[0],
+                sorted,
+            }
+        }
     }
 
     /// Same as [`Self::new`], but gives a panic instead of an [`Err`] on bad input.
@@ -158,12 +309,481 @@ impl Alphabet {
             Err(_) => 0,
         }]
     }
+
+    /// The table of the character for each digit, as used when encoding.
+    ///
+    /// This is exactly the table this crate uses, so external implementations (e.g. SIMD or GPU
+    /// kernels) using it will give the same results.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let table = bs58::Alphabet::BITCOIN.encode_table();
+    /// assert_eq!(b'1', table[0]);
+    /// assert_eq!(b'z', table[57]);
+    /// ```
+    pub const fn encode_table(&self) -> &[u8; 58] {
+        &self.encode
+    }
+
+    /// The character for digit 0, which each leading zero byte is encoded as unless a separate
+    /// [`lead_char`](Self::lead_char) is set.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// assert_eq!(b'1', bs58::Alphabet::BITCOIN.zero_char());
+    /// assert_eq!(b'r', bs58::Alphabet::RIPPLE.zero_char());
+    ///
+    /// // Pad to a fixed width with leading zeros
+    /// let encoded = bs58::encode([0x02, 0x01]).into_string();
+    /// let padded = format!("{}{encoded}", char::from(bs58::Alphabet::DEFAULT.zero_char()));
+    /// assert_eq!(vec![0x00, 0x02, 0x01], bs58::decode(padded).into_vec()?);
+    /// # Ok::<(), bs58::decode::Error>(())
+    /// ```
+    pub const fn zero_char(&self) -> u8 {
+        self.encode[0]
+    }
+
+    /// Use `lead` as the character for leading zero bytes, while zero digits after the first
+    /// non-zero one are still the [`zero_char`](Self::zero_char), for alphabets that want the
+    /// padding to stand out from the number.
+    ///
+    /// The lead character must be ASCII and must not be another character of the alphabet,
+    /// otherwise an encoding starting with that character couldn't be told apart from one
+    /// starting with a leading zero byte. Giving the zero character itself removes any lead
+    /// character set before.
+    ///
+    /// Encoding writes the lead character for each leading zero byte. Decoding reads both the lead
+    /// character and the zero character as a leading zero byte, but only while every character
+    /// before them has been one too: after the first non-zero digit the lead character is an
+    /// [`InvalidCharacter`](crate::decode::Error::InvalidCharacter), like any other character
+    /// outside the alphabet, and the zero character is the zero digit. So every encoding decodes
+    /// back to its input, as do encodings made without the lead character.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bs58::Alphabet;
+    ///
+    /// const PADDED: Alphabet = match Alphabet::BITCOIN.with_lead_char(b'0') {
+    ///     Ok(alphabet) => alphabet,
+    ///     Err(_) => panic!("invalid lead character"),
+    /// };
+    ///
+    /// let input = [0x00, 0x00, 0x04, 0x30, 0x5e, 0x2b, 0x24, 0x73, 0xf0, 0x58];
+    /// let encoded = bs58::encode(input).with_alphabet(&PADDED).into_string();
+    /// assert_eq!("00he11owor1d", encoded);
+    /// assert_eq!(input.to_vec(), bs58::decode(&encoded).with_alphabet(&PADDED).into_vec()?);
+    /// assert_eq!(input.to_vec(), bs58::decode("11he11owor1d").with_alphabet(&PADDED).into_vec()?);
+    ///
+    /// assert_eq!(
+    ///     Err(bs58::decode::Error::InvalidCharacter { character: '0', index: 3 }),
+    ///     bs58::decode("0he0").with_alphabet(&PADDED).into_vec());
+    ///
+    /// assert_eq!(
+    ///     Err(bs58::alphabet::Error::InvalidLeadCharacter { lead: b'z' }),
+    ///     Alphabet::BITCOIN.with_lead_char(b'z'));
+    /// # Ok::<(), bs58::decode::Error>(())
+    /// ```
+    pub const fn with_lead_char(self, lead: u8) -> Result<Self, Error> {
+        if lead >= 128 || (lead != self.encode[0] && self.digit(lead) != Self::INVALID) {
+            return Err(Error::InvalidLeadCharacter { lead });
+        }
+        Ok(Self { lead, ..self })
+    }
+
+    /// The character each leading zero byte is encoded as, the [`zero_char`](Self::zero_char)
+    /// unless another was set with [`with_lead_char`](Self::with_lead_char).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bs58::Alphabet;
+    ///
+    /// assert_eq!(b'1', Alphabet::BITCOIN.lead_char());
+    /// assert_eq!(b'0', Alphabet::BITCOIN.with_lead_char(b'0')?.lead_char());
+    /// # Ok::<(), bs58::alphabet::Error>(())
+    /// ```
+    pub const fn lead_char(&self) -> u8 {
+        self.lead
+    }
+
+    /// Whether `c` is read as a zero byte while in the leading run of zero characters.
+    #[inline]
+    pub(crate) const fn is_leading_zero(&self, c: u8) -> bool {
+        c == self.encode[0] || c == self.lead
+    }
+
+    /// The table of the digit for each byte, so it can be indexed by any `u8` without a bounds
+    /// check.
+    ///
+    /// Bytes that are not part of the alphabet map to [`INVALID`](Self::INVALID), including every
+    /// byte of 128 and above. This gives the same digits as the crate decodes with, so external
+    /// implementations (e.g. SIMD or GPU kernels) using it will accept and reject the same input.
+    ///
+    /// Without the `compact-tables` feature this is a copy of the table the alphabet stores and
+    /// decodes with, extended to 256 entries. With it the alphabet stores no such table (it binary
+    /// searches its digits instead), so this builds the table from the characters on each call;
+    /// keep the result, e.g. in a `const`, rather than calling it per byte.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let table = bs58::Alphabet::BITCOIN.decode_table();
+    /// assert_eq!(0, table[usize::from(b'1')]);
+    /// assert_eq!(57, table[usize::from(b'z')]);
+    /// assert_eq!(bs58::Alphabet::INVALID, table[usize::from(b'0')]);
+    /// assert_eq!(bs58::Alphabet::INVALID, table[0xff]);
+    /// ```
+    pub const fn decode_table(&self) -> [u8; 256] {
+        #[cfg(not(feature = "compact-tables"))]
+        let ascii = self.decode;
+        #[cfg(feature = "compact-tables")]
+        let ascii = decode_table(&self.encode);
+        let mut table = [Self::INVALID; 256];
+        let mut i = 0;
+        while i < ascii.len() {
+            table[i] = ascii[i];
+            i += 1;
+        }
+        table
+    }
+
+    /// The digit for the byte `c`, or [`INVALID`](Self::INVALID) if it is not part of the
+    /// alphabet.
+    #[inline]
+    pub(crate) const fn digit(&self, c: u8) -> u8 {
+        #[cfg(not(feature = "compact-tables"))]
+        if c < 128 {
+            self.decode[c as usize]
+        } else {
+            Self::INVALID
+        }
+
+        #[cfg(feature = "compact-tables")]
+        {
+            let (mut low, mut high) = (0, self.sorted.len());
+            while low < high {
+                let mid = (low + high) / 2;
+                let digit = self.sorted[mid];
+                let found = self.encode[digit as usize];
+                if found == c {
+                    return digit;
+                } else if found < c {
+                    low = mid + 1;
+                } else {
+                    high = mid;
+                }
+            }
+            Self::INVALID
+        }
+    }
+
+    /// Whether `c` is one of the characters of this alphabet, so could appear in an encoding and
+    /// must not be chosen as a delimiter, separator, or other reserved character framing
+    /// encodings.
+    ///
+    /// This includes the [lead character](Self::lead_char). Bytes of 128 and above never
+    /// conflict, as encodings are always ASCII.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bs58::Alphabet;
+    ///
+    /// assert!(Alphabet::BITCOIN.would_conflict_with(b'z'));
+    /// assert!(!Alphabet::BITCOIN.would_conflict_with(b'0'));
+    /// assert!(!Alphabet::BITCOIN.would_conflict_with(b'l'));
+    /// assert!(Alphabet::FLICKR.would_conflict_with(b'L'));
+    ///
+    /// // Pick the first separator that works with both alphabets
+    /// let alphabets = [Alphabet::BITCOIN, Alphabet::FLICKR];
+    /// let separator = [b'z', b'L', b'0', b'-']
+    ///     .into_iter()
+    ///     .find(|&c| !alphabets.iter().any(|alpha| alpha.would_conflict_with(c)));
+    /// assert_eq!(Some(b'0'), separator);
+    /// ```
+    pub const fn would_conflict_with(&self, c: u8) -> bool {
+        self.digit(c) != Self::INVALID || c == self.lead
+    }
+
+    /// The digits whose character differs between this alphabet and `other`, along with the
+    /// character for the digit in each, for inspecting how two custom alphabets relate when
+    /// transcoding between them.
+    ///
+    /// The differences are returned in order of digit, identical alphabets return an empty
+    /// vector.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bs58::Alphabet;
+    ///
+    /// let diff = Alphabet::BITCOIN.diff(Alphabet::RIPPLE);
+    /// assert_eq!(23, diff.len());
+    /// assert_eq!((0, b'1', b'r'), diff[0]);
+    /// assert_eq!((9, b'A', b'w'), diff[8]);
+    ///
+    /// assert!(Alphabet::BITCOIN.diff(Alphabet::MONERO).is_empty());
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn diff(&self, other: &Alphabet) -> Vec<(usize, u8, u8)> {
+        self.encode
+            .iter()
+            .zip(&other.encode)
+            .enumerate()
+            .filter(|(_, (a, b))| a != b)
+            .map(|(digit, (&a, &b))| (digit, a, b))
+            .collect()
+    }
+
+    /// Whether `c` is one of the characters of this alphabet.
+    #[cfg(all(feature = "check", feature = "alloc"))]
+    pub(crate) const fn contains(&self, c: char) -> bool {
+        c.is_ascii() && self.would_conflict_with(c as u8)
+    }
+}
+
+/// The table of the digit for each ASCII byte of a valid encode table.
+const fn decode_table(encode: &[u8; 58]) -> [u8; 128] {
+    let mut decode = [Alphabet::INVALID; 128];
+    let mut i = 0;
+    while i < encode.len() {
+        decode[encode[i] as usize] = i as u8;
+        i += 1;
+    }
+    decode
+}
+
+/// A transformation of encoded strings into a form for display, for
+/// [`EncodeBuilder::display_fold`](crate::encode::EncodeBuilder::display_fold) and reversed by
+/// [`DecodeBuilder::display_fold`](crate::decode::DecodeBuilder::display_fold).
+///
+/// Only the Base58 characters are folded, not any text prefix. Reversing a fold maps each
+/// character back to the one character of the alphabet that folds to it, which is worked out from
+/// the characters of the alphabet being decoded with, so applies the same way to custom
+/// alphabets. Where more than one character of the alphabet folds to the same character the
+/// display form is ambiguous and decoding it fails with
+/// [`Error::AmbiguousFold`](crate::decode::Error::AmbiguousFold).
+#[non_exhaustive]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Fold {
+    /// Convert ASCII letters to uppercase.
+    ///
+    /// The input to reverse this may use either case (it is folded again first), so lowercase
+    /// letters are read the same as their uppercase. Each of the built-in alphabets contains both
+    /// cases of every letter except `I`, `L` and `O`, so only strings of digits and those three
+    /// letters can be decoded from this form (`I` to `i`, `L` to `L` and `O` to `o`), every
+    /// other letter is ambiguous.
+    Upper,
+}
+
+impl Fold {
+    /// The entry of an [`unfold_table`](Self::unfold_table) for characters that more than one
+    /// character of the alphabet folds to.
+    pub(crate) const AMBIGUOUS: u8 = 0x80;
+
+    /// The display form of the character `c`.
+    #[inline]
+    pub(crate) const fn apply(self, c: u8) -> u8 {
+        match self {
+            Fold::Upper => c.to_ascii_uppercase(),
+        }
+    }
+
+    /// For each ASCII byte, the character of `alphabet` with the same display form, or
+    /// [`AMBIGUOUS`](Self::AMBIGUOUS) if there is more than one. Bytes with the display form of no
+    /// character are left as they are, so are rejected as usual.
+    pub(crate) const fn unfold_table(self, alphabet: &Alphabet) -> [u8; 128] {
+        const NONE: u8 = 0xFF;
+
+        let mut unfolded = [NONE; 128];
+        let mut i = 0;
+        while i < alphabet.encode.len() {
+            let c = alphabet.encode[i];
+            let folded = self.apply(c) as usize;
+            unfolded[folded] = match unfolded[folded] {
+                NONE => c,
+                _ => Self::AMBIGUOUS,
+            };
+            i += 1;
+        }
+
+        let mut table = [0; 128];
+        let mut c = 0;
+        while c < table.len() {
+            table[c] = match unfolded[self.apply(c as u8) as usize] {
+                NONE => c as u8,
+                unfolded => unfolded,
+            };
+            c += 1;
+        }
+        table
+    }
+}
+
+/// The number of unique characters an alphabet must have.
+pub const REQUIRED_UNIQUE_CHARS: usize = 58;
+
+/// The number of unique characters an alphabet must have, see [`REQUIRED_UNIQUE_CHARS`].
+///
+/// # Examples
+///
+/// ```rust
+/// assert_eq!(58, bs58::alphabet::required_unique_chars());
+/// ```
+pub const fn required_unique_chars() -> usize {
+    REQUIRED_UNIQUE_CHARS
+}
+
+/// Count the unique bytes in `bytes`, for giving feedback when building an alphabet
+/// programmatically.
+///
+/// This only counts bytes, [`Alphabet::new`] also requires every character to be ASCII.
+///
+/// # Examples
+///
+/// ```rust
+/// let chars = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwx11";
+/// let unique = bs58::alphabet::count_unique(chars);
+/// assert_eq!(
+///     "you have 56 unique characters, need 58",
+///     format!(
+///         "you have {unique} unique characters, need {}",
+///         bs58::alphabet::required_unique_chars()));
+/// ```
+pub const fn count_unique(bytes: &[u8]) -> usize {
+    let mut seen = [false; 256];
+    let mut count = 0;
+
+    let mut i = 0;
+    while i < bytes.len() {
+        if !seen[bytes[i] as usize] {
+            seen[bytes[i] as usize] = true;
+            count += 1;
+        }
+        i += 1;
+    }
+
+    count
+}
+
+/// An alphabet whose characters are a compile time constant, for embedding a custom alphabet in a
+/// type rather than passing an [`Alphabet`] reference around.
+///
+/// Implement this with [`static_alphabet!`](crate::static_alphabet), then use it with
+/// [`EncodeBuilder::with_static_alphabet`](crate::encode::EncodeBuilder::with_static_alphabet)
+/// and [`DecodeBuilder::with_static_alphabet`](crate::decode::DecodeBuilder::with_static_alphabet).
+/// The prepared [`Alphabet`] is built from [`ENCODE`](Self::ENCODE) with
+/// [`Alphabet::new_unwrap`] in a single constant per type, so an invalid alphabet fails to
+/// compile wherever it is used, including in a hand written implementation. Encoding and
+/// decoding then use that constant exactly as [`with_alphabet`] would, so are as fast as with any
+/// other `const` alphabet.
+///
+/// [`with_alphabet`]: crate::decode::DecodeBuilder::with_alphabet
+///
+/// ```compile_fail
+/// struct Invalid;
+///
+/// impl bs58::alphabet::StaticAlphabet for Invalid {
+///     const ENCODE: [u8; 58] = [b'a'; 58];
+/// }
+///
+/// let _ = bs58::encode([0]).with_static_alphabet::<Invalid>().into_string();
+/// ```
+pub trait StaticAlphabet {
+    /// The table of the character for each digit, see [`Alphabet::encode_table`].
+    const ENCODE: [u8; 58];
+}
+
+/// The prepared [`Alphabet`] for a [`StaticAlphabet`].
+pub(crate) trait StaticAlphabetExt: StaticAlphabet {
+    const ALPHABET: &'static Alphabet = &Alphabet::new_unwrap(&Self::ENCODE);
+}
+
+impl<A: StaticAlphabet + ?Sized> StaticAlphabetExt for A {}
+
+/// Define a unit struct implementing [`StaticAlphabet`] for the given 58 byte alphabet.
+///
+/// The alphabet is checked as with [`Alphabet::new_unwrap`], so an invalid alphabet fails to
+/// compile.
+///
+/// # Examples
+///
+/// ```rust
+/// bs58::static_alphabet!(
+///     /// The alphabet Ripple uses.
+///     pub Ripple = b"rpshnaf39wBUDNEGHJKLM4PQRST7VWXYZ2bcdeCg65jkm8oFqi1tuvAxyz"
+/// );
+///
+/// let input = [0x60, 0x65, 0xe7, 0x9b, 0xba, 0x2f, 0x78];
+/// let encoded = bs58::encode(input).with_static_alphabet::<Ripple>().into_string();
+/// assert_eq!("he11owor1d", encoded);
+/// assert_eq!(
+///     input.to_vec(),
+///     bs58::decode(encoded).with_static_alphabet::<Ripple>().into_vec()?);
+/// # Ok::<(), bs58::decode::Error>(())
+/// ```
+///
+/// ```compile_fail
+/// bs58::static_alphabet!(
+///     Invalid = b"aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa"
+/// );
+/// let _ = bs58::encode([0]).with_static_alphabet::<Invalid>().into_string();
+/// ```
+#[macro_export]
+macro_rules! static_alphabet {
+    ($(#[$attr:meta])* $vis:vis $name:ident = $base:expr) => {
+        $(#[$attr])*
+        #[derive(Copy, Clone, Debug)]
+        $vis struct $name;
+
+        impl $crate::alphabet::StaticAlphabet for $name {
+            const ENCODE: [u8; 58] = *$crate::Alphabet::new_unwrap($base).encode_table();
+        }
+    };
+}
+
+/// Construct an [`Alphabet`] from a 58 byte literal at compile time.
+///
+/// This expands to a constant built with [`Alphabet::new_unwrap`], so the alphabet is checked and
+/// its encode and decode tables are computed during compilation wherever the macro is used, even
+/// outside a `const` context, and a duplicate or non-ASCII character fails to compile.
+///
+/// # Examples
+///
+/// ```rust
+/// const RIPPLE: bs58::Alphabet =
+///     bs58::alphabet!(b"rpshnaf39wBUDNEGHJKLM4PQRST7VWXYZ2bcdeCg65jkm8oFqi1tuvAxyz");
+///
+/// let input = [0x60, 0x65, 0xe7, 0x9b, 0xba, 0x2f, 0x78];
+/// assert_eq!("he11owor1d", bs58::encode(input).with_alphabet(&RIPPLE).into_string());
+///
+/// let flickr = bs58::alphabet!(b"123456789abcdefghijkmnopqrstuvwxyzABCDEFGHJKLMNPQRSTUVWXYZ");
+/// assert_eq!(bs58::Alphabet::FLICKR.encode_table(), flickr.encode_table());
+/// ```
+///
+/// ```compile_fail
+/// let _ = bs58::alphabet!(b"aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa");
+/// ```
+#[macro_export]
+macro_rules! alphabet {
+    ($base:expr) => {{
+        const ALPHABET: $crate::Alphabet = $crate::Alphabet::new_unwrap($base);
+        ALPHABET
+    }};
 }
 
 impl fmt::Debug for Alphabet {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         if let Ok(s) = core::str::from_utf8(&self.encode) {
-            f.debug_tuple("Alphabet").field(&s).finish()
+            let mut f = f.debug_tuple("Alphabet");
+            f.field(&s);
+            if self.lead != self.encode[0] {
+                f.field(&char::from(self.lead));
+            }
+            f.finish()
         } else {
             unreachable!()
         }
@@ -188,6 +808,11 @@ impl fmt::Display for Error {
             Error::NonAsciiCharacter { index } => {
                 write!(f, "alphabet contained a non-ascii character at {}", index)
             }
+            Error::InvalidLeadCharacter { lead } => write!(
+                f,
+                "lead character {:#04x} is non-ascii or another character of the alphabet",
+                lead
+            ),
         }
     }
 }
@@ -206,3 +831,18 @@ const _: () = {
 fn test_new_unwrap_does_panic() {
     Alphabet::new_unwrap(b"aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa");
 }
+
+#[test]
+fn test_count_unique() {
+    assert_eq!(0, count_unique(b""));
+    assert_eq!(1, count_unique(b"aaaa"));
+    assert_eq!(3, count_unique(&[0x00, 0xff, 0x80, 0xff]));
+    assert_eq!(
+        required_unique_chars(),
+        count_unique(&Alphabet::RIPPLE.encode)
+    );
+    assert_eq!(
+        57,
+        count_unique(b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxy1")
+    );
+}
@@ -0,0 +1,113 @@
+//! A fixed-capacity buffer usable as an encode or decode target without any dependencies.
+
+use core::{fmt, ops::Deref};
+
+use crate::{decode, encode};
+
+/// A fixed-capacity byte buffer that can be used as the target of
+/// [`EncodeBuilder::onto`](crate::encode::EncodeBuilder::onto) and
+/// [`DecodeBuilder::onto`](crate::decode::DecodeBuilder::onto) without needing any allocator or
+/// extra dependencies.
+///
+/// Like other resizeable targets new data is appended after any existing contents; if the
+/// remaining capacity is not enough to hold it [`BufferTooSmall`](encode::Error::BufferTooSmall)
+/// is returned. The capacity is easiest to pick with [`max_encoded_len`](crate::max_encoded_len)
+/// and [`max_decoded_len`](crate::max_decoded_len).
+///
+/// # Examples
+///
+/// ```rust
+/// let mut encoded = bs58::Buffer::<{ bs58::max_encoded_len(32) }>::new();
+/// bs58::encode([0xff; 32]).onto(&mut encoded)?;
+/// assert_eq!(Some("JEKNVnkbo3jma5nREBBJCDoXFVeKkD56V3xKrvRmWxFG"), encoded.as_str());
+///
+/// let mut decoded = bs58::Buffer::<{ bs58::max_decoded_len(44) }>::new();
+/// bs58::decode(&*encoded).onto(&mut decoded)?;
+/// assert_eq!([0xff; 32], *decoded);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Clone, Copy)]
+pub struct Buffer<const N: usize> {
+    bytes: [u8; N],
+    len: usize,
+}
+
+impl<const N: usize> Buffer<N> {
+    /// Create a new empty buffer.
+    pub const fn new() -> Self {
+        Self {
+            bytes: [0; N],
+            len: 0,
+        }
+    }
+
+    /// The total number of bytes this buffer can hold.
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Remove all contents from the buffer.
+    pub fn clear(&mut self) {
+        self.len = 0;
+    }
+
+    /// View the contents of the buffer as a string.
+    ///
+    /// Returns `None` if the contents are not valid UTF-8, which can't happen if only encoded data
+    /// has been written to the buffer as that is always ASCII.
+    pub fn as_str(&self) -> Option<&str> {
+        core::str::from_utf8(self).ok()
+    }
+}
+
+impl<const N: usize> Default for Buffer<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> Deref for Buffer<N> {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        &self.bytes[..self.len]
+    }
+}
+
+impl<const N: usize> AsRef<[u8]> for Buffer<N> {
+    fn as_ref(&self) -> &[u8] {
+        self
+    }
+}
+
+impl<const N: usize> fmt::Debug for Buffer<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Buffer").field(&&**self).finish()
+    }
+}
+
+impl<const N: usize> encode::EncodeTarget for Buffer<N> {
+    fn encode_with(
+        &mut self,
+        max_len: usize,
+        f: impl for<'a> FnOnce(&'a mut [u8]) -> encode::Result<usize>,
+    ) -> encode::Result<usize> {
+        let _ = max_len;
+        let len = f(&mut self.bytes[self.len..])?;
+        self.len += len;
+        Ok(len)
+    }
+}
+
+impl<const N: usize> decode::DecodeTarget for Buffer<N> {
+    fn decode_with(
+        &mut self,
+        max_len: usize,
+        f: impl for<'a> FnOnce(&'a mut [u8]) -> decode::Result<usize>,
+    ) -> decode::Result<usize> {
+        let _ = max_len;
+        let len = f(&mut self.bytes[self.len..])?;
+        self.len += len;
+        Ok(len)
+    }
+}
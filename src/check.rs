@@ -0,0 +1,356 @@
+//! Types for working with [Base58Check][] encoded data.
+//!
+//! [Base58Check]: https://en.bitcoin.it/wiki/Base58Check_encoding
+
+#[cfg(feature = "alloc")]
+use alloc::{borrow::ToOwned, string::String, vec::Vec};
+use core::marker::PhantomData;
+
+use crate::CHECKSUM_LEN;
+#[cfg(feature = "alloc")]
+use core::{fmt, str::FromStr};
+
+#[cfg(feature = "alloc")]
+use crate::decode::{Error, Result};
+
+/// A type-level [Base58Check][] version, for use with
+/// [`EncodeBuilder::with_check_version_typed`](crate::encode::EncodeBuilder::with_check_version_typed),
+/// [`DecodeBuilder::with_check_version_typed`](crate::decode::DecodeBuilder::with_check_version_typed)
+/// and [`Checked`].
+///
+/// Encoding and decoding with a typed version behaves the same as giving the version bytes at
+/// runtime, except that when decoding the version is removed from the output. Putting the version
+/// in the type means it can be part of function signatures so mixing up data for different
+/// networks fails to compile.
+///
+/// [Base58Check]: https://en.bitcoin.it/wiki/Base58Check_encoding
+///
+/// # Examples
+///
+/// Defining your own version:
+///
+/// ```rust
+/// use bs58::check::{Checked, Version};
+///
+/// #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+/// struct BitcoinMainnetP2sh;
+///
+/// impl Version for BitcoinMainnetP2sh {
+///     const BYTES: &'static [u8] = &[0x05];
+/// }
+///
+/// let address: Checked<BitcoinMainnetP2sh, [u8; 20]> = Checked::new([0; 20]);
+/// assert_eq!("31h1vYVSYuKP6AhS86fbRdMw9XHieotbST", address.to_string());
+/// ```
+///
+/// Data for one network can't be used where another is expected:
+///
+/// ```compile_fail
+/// use bs58::check::{BitcoinMainnetP2pkh, BitcoinTestnetP2pkh, Checked};
+///
+/// fn send_to(address: Checked<BitcoinMainnetP2pkh, [u8; 20]>) {}
+///
+/// let address: Checked<BitcoinTestnetP2pkh, [u8; 20]> = Checked::new([0; 20]);
+/// send_to(address);
+/// ```
+pub trait Version {
+    /// The version bytes that are prepended to the payload before calculating the checksum.
+    const BYTES: &'static [u8];
+}
+
+/// Version for Bitcoin mainnet pay-to-public-key-hash addresses.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct BitcoinMainnetP2pkh;
+
+impl Version for BitcoinMainnetP2pkh {
+    const BYTES: &'static [u8] = &[0x00];
+}
+
+/// Version for Bitcoin testnet pay-to-public-key-hash addresses.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct BitcoinTestnetP2pkh;
+
+impl Version for BitcoinTestnetP2pkh {
+    const BYTES: &'static [u8] = &[0x6f];
+}
+
+/// Version for Bitcoin mainnet private keys in Wallet Import Format.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Wif;
+
+impl Version for Wif {
+    const BYTES: &'static [u8] = &[0x80];
+}
+
+/// An incremental [Base58Check][] checksum, for payloads that are built up in pieces.
+///
+/// Feeding the version bytes and payload through [`update`](Self::update) in any number of
+/// pieces gives the same checksum as [`EncodeBuilder::with_check`] computes over the whole payload
+/// at once, without needing to buffer it.
+///
+/// [Base58Check]: https://en.bitcoin.it/wiki/Base58Check_encoding
+/// [`EncodeBuilder::with_check`]: crate::encode::EncodeBuilder::with_check
+///
+/// # Examples
+///
+/// ```rust
+/// use bs58::check::Checksummer;
+///
+/// let mut checksummer = Checksummer::new();
+/// checksummer.update(&[0x2d, 0x31]);
+/// checksummer.update(&[0x9e, 0x5f, 0xa8]);
+/// assert_eq!([0x74, 0x84, 0x94, 0x39], checksummer.finalize());
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Checksummer {
+    hash: sha2::Sha256,
+}
+
+impl Checksummer {
+    /// A checksum of no bytes so far.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add the next bytes of the version and payload.
+    pub fn update(&mut self, bytes: &[u8]) {
+        use sha2::Digest;
+
+        self.hash.update(bytes);
+    }
+
+    /// The 4 byte checksum of all the bytes added, as appended to the payload when encoding.
+    pub fn finalize(self) -> [u8; CHECKSUM_LEN] {
+        use sha2::{Digest, Sha256};
+
+        let second_hash = Sha256::digest(self.hash.finalize());
+
+        let mut checksum = [0; CHECKSUM_LEN];
+        checksum.copy_from_slice(&second_hash[0..CHECKSUM_LEN]);
+        checksum
+    }
+}
+
+/// A payload that is [Base58Check][] encoded with the version `V`.
+///
+/// With the `alloc` feature this can be parsed from a string when the payload is a byte array,
+/// verifying the checksum, version and payload length, and displayed as the encoded string.
+///
+/// [Base58Check]: https://en.bitcoin.it/wiki/Base58Check_encoding
+///
+/// # Examples
+///
+/// ```rust
+/// use bs58::check::{BitcoinMainnetP2pkh, Checked};
+///
+/// let address: Checked<BitcoinMainnetP2pkh, [u8; 20]> =
+///     "1F9v11cupBVMpz3CrVfCppv9Rw2xEtU1c6".parse()?;
+/// assert_eq!(0x9b, address.payload()[0]);
+/// assert_eq!("1F9v11cupBVMpz3CrVfCppv9Rw2xEtU1c6", address.to_string());
+/// # Ok::<(), bs58::decode::Error>(())
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Checked<V, T> {
+    payload: T,
+    version: PhantomData<fn() -> V>,
+}
+
+impl<V: Version, T> Checked<V, T> {
+    /// Wrap a payload to be encoded with the version `V`.
+    pub const fn new(payload: T) -> Self {
+        Self {
+            payload,
+            version: PhantomData,
+        }
+    }
+
+    /// The payload, without the version bytes or checksum.
+    pub const fn payload(&self) -> &T {
+        &self.payload
+    }
+
+    /// Unwrap the payload.
+    pub fn into_payload(self) -> T {
+        self.payload
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<V: Version, const N: usize> FromStr for Checked<V, [u8; N]> {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let decoded = crate::decode(s)
+            .with_check_version_typed::<V>()
+            .into_vec()?;
+        let found = decoded.len();
+        let payload = decoded
+            .try_into()
+            .map_err(|_| Error::WrongLength { expected: N, found })?;
+        Ok(Self::new(payload))
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<V: Version, T: AsRef<[u8]>> fmt::Display for Checked<V, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let encoded = crate::encode(&self.payload)
+            .with_check_version_typed::<V>()
+            .into_string();
+        f.write_str(&encoded)
+    }
+}
+
+/// A [Base58Check][] encoded string along with its decoded version and payload.
+///
+/// This can only be constructed by successfully check-decoding a string (via [`FromStr`] or
+/// [`CheckString::parse_with_version`]) or by check-encoding a payload (via [`CheckString::new`]),
+/// so the string and the decoded data are always known to agree and never need re-verifying. The
+/// [default alphabet](crate::Alphabet::DEFAULT) is always used.
+///
+/// With the `serde` feature this (de)serializes as the encoded string, verifying the checksum when
+/// deserializing.
+///
+/// [Base58Check]: https://en.bitcoin.it/wiki/Base58Check_encoding
+///
+/// # Examples
+///
+/// ```rust
+/// use bs58::check::CheckString;
+///
+/// let parsed: CheckString = "1F9v11cupBVMpz3CrVfCppv9Rw2xEtU1c6".parse()?;
+/// assert_eq!(0x00, parsed.version());
+/// assert_eq!(20, parsed.payload().len());
+///
+/// let encoded = CheckString::new(parsed.version(), parsed.payload());
+/// assert_eq!(parsed, encoded);
+/// assert_eq!("1F9v11cupBVMpz3CrVfCppv9Rw2xEtU1c6", encoded.as_str());
+/// # Ok::<(), bs58::decode::Error>(())
+/// ```
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CheckString {
+    encoded: String,
+    decoded: Vec<u8>,
+}
+
+#[cfg(feature = "alloc")]
+impl CheckString {
+    /// Check-encode the given version and payload.
+    pub fn new(version: u8, payload: &[u8]) -> Self {
+        let encoded = crate::encode(payload)
+            .with_check_version(version)
+            .into_string();
+        let mut decoded = Vec::with_capacity(payload.len() + 1);
+        decoded.push(version);
+        decoded.extend_from_slice(payload);
+        Self { encoded, decoded }
+    }
+
+    /// Check-decode the given string, verifying that it has the expected version.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bs58::check::CheckString;
+    ///
+    /// assert!(CheckString::parse_with_version("1F9v11cupBVMpz3CrVfCppv9Rw2xEtU1c6", 0x00).is_ok());
+    /// assert_eq!(
+    ///     bs58::decode::Error::InvalidVersion { ver: 0x00, expected_ver: 0x05 },
+    ///     CheckString::parse_with_version("1F9v11cupBVMpz3CrVfCppv9Rw2xEtU1c6", 0x05).unwrap_err());
+    /// ```
+    pub fn parse_with_version(s: &str, version: u8) -> Result<Self> {
+        Self::parse(s, Some(version))
+    }
+
+    fn parse(s: &str, expected_ver: Option<u8>) -> Result<Self> {
+        let decoded = crate::decode(s).with_check(expected_ver).into_vec()?;
+        if decoded.is_empty() {
+            // There must be at least a version byte in addition to the checksum
+            return Err(Error::NoChecksum);
+        }
+        Ok(Self {
+            encoded: s.to_owned(),
+            decoded,
+        })
+    }
+
+    /// The version byte.
+    pub fn version(&self) -> u8 {
+        self.decoded[0]
+    }
+
+    /// The payload bytes, after the version byte and excluding the checksum.
+    pub fn payload(&self) -> &[u8] {
+        &self.decoded[1..]
+    }
+
+    /// The encoded string.
+    pub fn as_str(&self) -> &str {
+        &self.encoded
+    }
+
+    /// Convert into the encoded string.
+    pub fn into_string(self) -> String {
+        self.encoded
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl FromStr for CheckString {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::parse(s, None)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl AsRef<str> for CheckString {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl fmt::Display for CheckString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.encoded)
+    }
+}
+
+#[cfg(all(feature = "alloc", feature = "serde"))]
+impl serde::Serialize for CheckString {
+    fn serialize<S: serde::Serializer>(
+        &self,
+        serializer: S,
+    ) -> core::result::Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.encoded)
+    }
+}
+
+#[cfg(all(feature = "alloc", feature = "serde"))]
+impl<'de> serde::Deserialize<'de> for CheckString {
+    fn deserialize<D: serde::Deserializer<'de>>(
+        deserializer: D,
+    ) -> core::result::Result<Self, D::Error> {
+        struct Visitor;
+
+        impl serde::de::Visitor<'_> for Visitor {
+            type Value = CheckString;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("a Base58Check encoded string")
+            }
+
+            fn visit_str<E: serde::de::Error>(
+                self,
+                s: &str,
+            ) -> core::result::Result<CheckString, E> {
+                s.parse().map_err(E::custom)
+            }
+        }
+
+        deserializer.deserialize_str(Visitor)
+    }
+}
@@ -5,11 +5,16 @@ use core::fmt;
 #[cfg(feature = "alloc")]
 use alloc::vec::Vec;
 
-use crate::Check;
 #[cfg(any(feature = "check", feature = "cb58"))]
 use crate::CHECKSUM_LEN;
+use crate::{raw, Check, Record};
 
-use crate::Alphabet;
+#[cfg(feature = "metrics")]
+use crate::metrics::Stats;
+use crate::{
+    alphabet::{Fold, StaticAlphabet, StaticAlphabetExt},
+    Alphabet,
+};
 
 /// A builder for setting up the alphabet and output of a base58 decode.
 ///
@@ -20,6 +25,142 @@ pub struct DecodeBuilder<'a, I: AsRef<[u8]>> {
     input: I,
     alpha: &'a Alphabet,
     check: Check,
+    #[cfg(feature = "check")]
+    aad: &'a [u8],
+    reserve_exact: bool,
+    rtl: bool,
+    nonzero: bool,
+    nonempty: bool,
+    prefix: &'a str,
+    hrp_separator: Option<char>,
+    canonical: Option<usize>,
+    expected_len: Option<usize>,
+    allowed_lengths: Option<&'static [usize]>,
+    salt: &'a [u8],
+    ordered: Option<usize>,
+    map: Option<&'a dyn Fn(u8) -> u8>,
+    fallback: Option<&'a Alphabet>,
+    fold: Option<Unfold>,
+    #[cfg(feature = "metrics")]
+    stats: Option<&'a core::cell::Cell<Stats>>,
+}
+
+/// A [`Fold`] along with the tables reversing it for the alphabet and fallback alphabet of a
+/// [`DecodeBuilder`], worked out when they are set rather than on every decode.
+#[derive(Clone, Copy)]
+struct Unfold {
+    fold: Fold,
+    table: [u8; 128],
+    fallback: [u8; 128],
+}
+
+impl Unfold {
+    const fn new(fold: Fold, alpha: &Alphabet, fallback: Option<&Alphabet>) -> Unfold {
+        let table = fold.unfold_table(alpha);
+        let fallback = match fallback {
+            Some(fallback) => fold.unfold_table(fallback),
+            None => table,
+        };
+        Unfold {
+            fold,
+            table,
+            fallback,
+        }
+    }
+}
+
+/// A reusable configuration of the alphabet, checksum and prefix for base58 decodes, independent
+/// of the input.
+///
+/// This is cheap to copy and can be built in a `const` or `static`, then used to setup a
+/// [`DecodeBuilder`] for each input with [`with_input`](Self::with_input).
+///
+/// # Examples
+///
+/// ```rust
+/// use bs58::{decode::DecodeConfig, Alphabet};
+///
+/// static DECODER: DecodeConfig = DecodeConfig::new(Alphabet::RIPPLE).with_text_prefix("r:");
+///
+/// assert_eq!(
+///     vec![0x60, 0x65, 0xe7, 0x9b, 0xba, 0x2f, 0x78],
+///     DECODER.with_input("r:he11owor1d").into_vec()?);
+/// # Ok::<(), bs58::decode::Error>(())
+/// ```
+#[derive(Clone, Copy)]
+#[allow(missing_debug_implementations)]
+pub struct DecodeConfig<'a> {
+    alpha: &'a Alphabet,
+    check: Check,
+    prefix: &'a str,
+}
+
+/// An iterator decoding each field of delimited input, see
+/// [`bs58::decode_delimited`](crate::decode_delimited()).
+#[allow(missing_debug_implementations)]
+pub struct Delimited<'a, 'b> {
+    input: &'b [u8],
+    delimiter: &'b [u8],
+    alpha: &'a Alphabet,
+    check: Check,
+    prefix: &'a str,
+    reject_empty: bool,
+    field: usize,
+    /// The start of the next field, or `None` once every field has been decoded.
+    start: Option<usize>,
+}
+
+/// A decode of a fixed-point number, see [`DecodeBuilder::fixed_point`].
+#[allow(missing_debug_implementations)]
+pub struct FixedPoint<'a, I: AsRef<[u8]>> {
+    builder: DecodeBuilder<'a, I>,
+    frac_digits: u32,
+}
+
+/// The decoded data, distinguishing empty and all zero data from other values, see
+/// [`DecodeBuilder::into_decode_result`].
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DecodeResult {
+    /// There were no decoded bytes.
+    Empty,
+    /// Every decoded byte was zero.
+    Zero {
+        /// The number of zero bytes, each of which is encoded as a leading `1` (the zero
+        /// character of the alphabet).
+        leading_ones: usize,
+    },
+    /// The decoded bytes, at least one of which is non-zero.
+    Value(Vec<u8>),
+}
+
+/// A decode waiting to be written to its target, see [`DecodeBuilder::into_deferred`].
+#[allow(missing_debug_implementations)]
+pub struct Commit<'t> {
+    target: &'t mut [u8],
+    decoded: Deferred<'t>,
+}
+
+/// Where the bytes of a [`Commit`] are held until they are written.
+#[allow(variant_size_differences)]
+enum Deferred<'t> {
+    Stack([u8; SCRATCH_STACK_LEN], usize),
+    Scratch(&'t [u8]),
+    #[cfg(feature = "alloc")]
+    Heap(Vec<u8>),
+}
+
+/// An error decoding a field of delimited input, see
+/// [`bs58::decode_delimited`](crate::decode_delimited()).
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct DelimitedError {
+    /// The index of the field that failed to decode, e.g. `1` for the second field.
+    pub field: usize,
+    /// The (byte) index in the whole input that the field starts at.
+    pub field_start: usize,
+    /// The error decoding the field, with any character index relative to the whole input, see
+    /// [`error_in_field`](Self::error_in_field) for the index within the field.
+    pub error: Error,
 }
 
 /// A specialized [`Result`](core::result::Result) type for [`bs58::decode`](module@crate::decode)
@@ -34,6 +175,10 @@ pub enum Error {
 
     /// The input contained a character that was not part of the current Base58
     /// alphabet.
+    ///
+    /// The built-in alphabets all use the same set of characters, so this error cannot be caused
+    /// by decoding with the wrong built-in alphabet (that will instead silently decode to
+    /// different bytes), see [`detect`] for diagnosing that case.
     InvalidCharacter {
         /// The unexpected character.
         character: char,
@@ -49,6 +194,92 @@ pub enum Error {
         index: usize,
     },
 
+    /// The decoded data was not the expected length.
+    WrongLength {
+        /// The expected decoded length.
+        expected: usize,
+        /// The decoded length that was found. If the decoded data is longer than expected
+        /// decoding may stop early, in which case this is only a lower bound, and if the input
+        /// is too short it may be rejected before decoding, in which case this is only an upper
+        /// bound.
+        found: usize,
+    },
+
+    /// Allocating space for the output failed, see [`DecodeBuilder::try_into_vec`].
+    ///
+    /// The `TryReserveError` from the allocation isn't included, as this error type is `Copy`
+    /// and that one isn't.
+    AllocationFailed,
+
+    /// The decoded value was zero, see [`DecodeBuilder::require_nonzero`].
+    ZeroValue,
+
+    /// The decoded data could not be parsed, see [`DecodeBuilder::into_struct`].
+    ParseFailed,
+
+    /// A field of delimited input was empty, see [`Delimited::reject_empty`].
+    EmptyField,
+
+    /// The input did not start with the expected text prefix, see
+    /// [`DecodeBuilder::with_text_prefix`].
+    WrongPrefix,
+
+    /// The decoded value was too large for the requested integer type, see [`to_u64_pair`],
+    /// [`to_timestamp_millis`] and [`FixedPoint::into_fixed`], or for the maximum length of
+    /// [`DecodeBuilder::order_preserving`].
+    Overflow,
+
+    /// The input was not the canonical encoding of a value of the expected length, see
+    /// [`DecodeBuilder::canonical`].
+    NonCanonical {
+        /// The rule that the input did not meet.
+        rule: CanonicalRule,
+        /// The expected decoded length, including any version and checksum bytes.
+        expected_len: usize,
+        /// The decoded length that was found, including any version and checksum bytes.
+        found_len: usize,
+    },
+
+    /// The input contained a character whose display form is shared by more than one character
+    /// of the alphabet, so could not be mapped back, see [`DecodeBuilder::display_fold`].
+    AmbiguousFold {
+        /// The ambiguous character, as it was in the input.
+        character: char,
+        /// The (byte) index in the input string the character was at.
+        index: usize,
+    },
+
+    /// A byte of the decoded data did not match the pattern, see
+    /// [`DecodeBuilder::into_vec_matching`].
+    PatternMismatch {
+        /// The index in the decoded data of the first byte that did not match.
+        index: usize,
+    },
+
+    /// The scheme string named no known alphabet or mode, see [`from_scheme`].
+    UnknownScheme,
+
+    /// The decoded payload was empty, see [`DecodeBuilder::disallow_empty_payload`].
+    EmptyPayload,
+
+    /// The input ended part way through a field, see [`read_all_length_prefixed`].
+    Truncated {
+        /// The (byte) index in the input of the length prefix of the field.
+        index: usize,
+    },
+
+    /// The decoded data was not any of the allowed lengths, see
+    /// [`DecodeBuilder::allowed_lengths`].
+    DisallowedLength {
+        /// The allowed decoded lengths.
+        allowed: &'static [usize],
+        /// The decoded length that was found.
+        found: usize,
+    },
+
+    /// The decoded data did not start with the expected salt, see [`DecodeBuilder::strip_salt`].
+    WrongSalt,
+
     #[cfg(any(feature = "check", feature = "cb58"))]
     /// The checksum did not match the payload bytes
     InvalidChecksum {
@@ -70,6 +301,211 @@ pub enum Error {
     #[cfg(any(feature = "check", feature = "cb58"))]
     ///Not enough bytes to have both a checksum and a payload (less than to CHECKSUM_LEN)
     NoChecksum,
+
+    #[cfg(any(feature = "check", feature = "cb58"))]
+    /// The version and payload length matched none of the expected kinds, see
+    /// [`DecodeBuilder::into_classified`].
+    Unclassified {
+        /// The version that was found.
+        version: u8,
+        /// The payload length that was found, not including the version.
+        payload_len: usize,
+    },
+
+    #[cfg(feature = "check")]
+    /// The checksum of a block did not match its bytes, see
+    /// [`DecodeBuilder::with_block_checksums`].
+    BlockChecksumFailed {
+        /// The index of the first block whose checksum did not match, counting from 0.
+        block_index: usize,
+    },
+}
+
+/// A rule of [`DecodeBuilder::canonical`], identifying why input was
+/// [`NonCanonical`](Error::NonCanonical).
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum CanonicalRule {
+    /// The value of the input (ignoring leading zero characters) needs more bytes than the
+    /// expected length.
+    Length,
+    /// The input had more or fewer leading zero characters than needed to pad its value to the
+    /// expected length.
+    LeadingZeros,
+}
+
+/// The binary length prefix before each field of input to [`read_all_length_prefixed`], giving
+/// the number of Base58 characters in the field.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum LenPrefix {
+    /// A single byte.
+    U8,
+    /// Two bytes, big-endian.
+    U16Be,
+    /// Two bytes, little-endian.
+    U16Le,
+    /// Four bytes, big-endian.
+    U32Be,
+    /// Four bytes, little-endian.
+    U32Le,
+}
+
+#[cfg(feature = "alloc")]
+impl LenPrefix {
+    /// The number of bytes in the prefix.
+    const fn width(self) -> usize {
+        match self {
+            LenPrefix::U8 => 1,
+            LenPrefix::U16Be | LenPrefix::U16Le => 2,
+            LenPrefix::U32Be | LenPrefix::U32Le => 4,
+        }
+    }
+
+    /// The length given by the prefix `bytes`, which are exactly [`width`](Self::width) long.
+    fn read(self, bytes: &[u8]) -> usize {
+        let len = match self {
+            LenPrefix::U8 => u32::from(bytes[0]),
+            LenPrefix::U16Be => u32::from(u16::from_be_bytes([bytes[0], bytes[1]])),
+            LenPrefix::U16Le => u32::from(u16::from_le_bytes([bytes[0], bytes[1]])),
+            LenPrefix::U32Be => u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]),
+            LenPrefix::U32Le => u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]),
+        };
+        // Too long for the input anyway
+        usize::try_from(len).unwrap_or(usize::MAX)
+    }
+}
+
+/// A structured view of an [`Error`], for presenting errors without matching on its variants,
+/// see [`Error::report`].
+///
+/// # Stability
+///
+/// Unlike [`Error`], whose variants may be reshaped between releases, the report is stable: the
+/// meaning of each [`ErrorCode`] and field, and the [string codes](ErrorCode::as_str), won't
+/// change in a semver compatible release. New codes and fields may be added, so both types are
+/// `#[non_exhaustive]`, but existing ones will not be removed or renamed. With the `serde` feature
+/// this serializes as a map of the fields, with the code as its string code and absent fields as
+/// none (e.g. `null` in JSON).
+///
+/// # Examples
+///
+/// ```rust
+/// use bs58::decode::ErrorCode;
+///
+/// let report = bs58::decode("he11o0").into_vec().unwrap_err().report();
+/// assert_eq!(ErrorCode::InvalidCharacter, report.code);
+/// assert_eq!(Some('0'), report.character);
+/// assert_eq!(Some(5), report.index);
+/// assert_eq!("invalid_character", report.code.as_str());
+/// ```
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct ErrorReport {
+    /// The kind of error.
+    pub code: ErrorCode,
+    /// The (byte) index in the input of the character the error occurred at.
+    pub index: Option<usize>,
+    /// The character the error occurred at.
+    pub character: Option<char>,
+    /// The expected value: a decoded length, a version, or for [`ErrorCode::InvalidChecksum`] the
+    /// checksum calculated for the payload as a big-endian integer.
+    pub expected: Option<u64>,
+    /// The value that was found, in the same terms as `expected`. For
+    /// [`ErrorCode::Unclassified`] this is the payload length, for
+    /// [`ErrorCode::BlockChecksumFailed`] the index of the block, and for
+    /// [`ErrorCode::PatternMismatch`] the index of the byte in the decoded data.
+    pub found: Option<u64>,
+    /// The version found in the decoded data, for [`ErrorCode::Unclassified`].
+    pub version: Option<u8>,
+    /// The allowed decoded lengths, for [`ErrorCode::DisallowedLength`].
+    pub allowed: Option<&'static [usize]>,
+}
+
+/// The kind of error described by an [`ErrorReport`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+#[non_exhaustive]
+pub enum ErrorCode {
+    /// See [`Error::BufferTooSmall`].
+    BufferTooSmall,
+    /// See [`Error::InvalidCharacter`].
+    InvalidCharacter,
+    /// See [`Error::NonAsciiCharacter`].
+    NonAsciiCharacter,
+    /// See [`Error::WrongLength`].
+    WrongLength,
+    /// See [`Error::AllocationFailed`].
+    AllocationFailed,
+    /// See [`Error::ZeroValue`].
+    ZeroValue,
+    /// See [`Error::ParseFailed`].
+    ParseFailed,
+    /// See [`Error::EmptyField`].
+    EmptyField,
+    /// See [`Error::WrongPrefix`].
+    WrongPrefix,
+    /// See [`Error::Overflow`].
+    Overflow,
+    /// See [`Error::NonCanonical`] and [`CanonicalRule::Length`].
+    NonCanonicalLength,
+    /// See [`Error::NonCanonical`] and [`CanonicalRule::LeadingZeros`].
+    NonCanonicalLeadingZeros,
+    /// The checksum did not match the payload bytes.
+    InvalidChecksum,
+    /// The version did not match the expected version.
+    InvalidVersion,
+    /// Not enough bytes to have both a checksum and a payload.
+    NoChecksum,
+    /// The version and payload length matched none of the expected kinds.
+    Unclassified,
+    /// The checksum of a block did not match its bytes.
+    BlockChecksumFailed,
+    /// See [`Error::AmbiguousFold`].
+    AmbiguousFold,
+    /// See [`Error::PatternMismatch`].
+    PatternMismatch,
+    /// See [`Error::UnknownScheme`].
+    UnknownScheme,
+    /// See [`Error::EmptyPayload`].
+    EmptyPayload,
+    /// See [`Error::Truncated`].
+    Truncated,
+    /// See [`Error::DisallowedLength`].
+    DisallowedLength,
+    /// See [`Error::WrongSalt`].
+    WrongSalt,
+}
+
+impl ErrorCode {
+    /// A stable string identifying this code, e.g. for use as a translation key.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            ErrorCode::BufferTooSmall => "buffer_too_small",
+            ErrorCode::InvalidCharacter => "invalid_character",
+            ErrorCode::NonAsciiCharacter => "non_ascii_character",
+            ErrorCode::WrongLength => "wrong_length",
+            ErrorCode::AllocationFailed => "allocation_failed",
+            ErrorCode::ZeroValue => "zero_value",
+            ErrorCode::ParseFailed => "parse_failed",
+            ErrorCode::EmptyField => "empty_field",
+            ErrorCode::WrongPrefix => "wrong_prefix",
+            ErrorCode::Overflow => "overflow",
+            ErrorCode::NonCanonicalLength => "non_canonical_length",
+            ErrorCode::NonCanonicalLeadingZeros => "non_canonical_leading_zeros",
+            ErrorCode::InvalidChecksum => "invalid_checksum",
+            ErrorCode::InvalidVersion => "invalid_version",
+            ErrorCode::NoChecksum => "no_checksum",
+            ErrorCode::Unclassified => "unclassified",
+            ErrorCode::BlockChecksumFailed => "block_checksum_failed",
+            ErrorCode::AmbiguousFold => "ambiguous_fold",
+            ErrorCode::PatternMismatch => "pattern_mismatch",
+            ErrorCode::UnknownScheme => "unknown_scheme",
+            ErrorCode::EmptyPayload => "empty_payload",
+            ErrorCode::Truncated => "truncated",
+            ErrorCode::DisallowedLength => "disallowed_length",
+            ErrorCode::WrongSalt => "wrong_salt",
+        }
+    }
 }
 
 /// Represents a buffer that can be decoded into. See [`DecodeBuilder::onto`] and the provided
@@ -110,6 +546,24 @@ impl DecodeTarget for Vec<u8> {
     }
 }
 
+#[cfg(feature = "allocator-api2")]
+impl<A: allocator_api2::alloc::Allocator> DecodeTarget for allocator_api2::vec::Vec<u8, A> {
+    /// Decodes data into an [`allocator_api2::vec::Vec`], reserving exactly the space requested so only
+    /// memory from its allocator is used.
+    fn decode_with(
+        &mut self,
+        max_len: usize,
+        f: impl for<'a> FnOnce(&'a mut [u8]) -> Result<usize>,
+    ) -> Result<usize> {
+        let original = self.len();
+        self.reserve_exact(max_len);
+        self.resize(original + max_len, 0);
+        let len = f(&mut self[original..])?;
+        self.truncate(original + len);
+        Ok(len)
+    }
+}
+
 #[cfg(feature = "smallvec")]
 impl<A: smallvec::Array<Item = u8>> DecodeTarget for smallvec::SmallVec<A> {
     /// Decodes data into a [`smallvec::SmallVec`].
@@ -193,25 +647,192 @@ impl<const N: usize> DecodeTarget for [u8; N] {
     }
 }
 
-impl<'a, I: AsRef<[u8]>> DecodeBuilder<'a, I> {
-    /// Setup decoder for the given string using the given alphabet.
-    /// Preferably use [`bs58::decode`](crate::decode()) instead of this directly.
-    pub const fn new(input: I, alpha: &'a Alphabet) -> DecodeBuilder<'a, I> {
-        DecodeBuilder {
-            input,
+/// A destination that decoded bytes can be pushed into one at a time, see
+/// [`DecodeBuilder::into_sink`].
+///
+/// This is useful for outputs that are not contiguous, such as ring buffers.
+pub trait Sink {
+    /// Push a single byte onto the end of this sink, erroring if it is full.
+    #[allow(clippy::result_unit_err)]
+    fn push_byte(&mut self, b: u8) -> core::result::Result<(), ()>;
+}
+
+impl<S: Sink + ?Sized> Sink for &mut S {
+    fn push_byte(&mut self, b: u8) -> core::result::Result<(), ()> {
+        S::push_byte(self, b)
+    }
+}
+
+impl Sink for &mut [u8] {
+    /// Writes the byte to the start of the slice, then advances the slice past it.
+    fn push_byte(&mut self, b: u8) -> core::result::Result<(), ()> {
+        let (first, rest) = core::mem::take(self).split_first_mut().ok_or(())?;
+        *first = b;
+        *self = rest;
+        Ok(())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Sink for Vec<u8> {
+    fn push_byte(&mut self, b: u8) -> core::result::Result<(), ()> {
+        self.push(b);
+        Ok(())
+    }
+}
+
+/// Inputs that decode to at most this many bytes are buffered on the stack by
+/// [`DecodeBuilder::into_sink`], [`DecodeBuilder::extend_into`] and
+/// [`DecodeBuilder::into_struct`].
+const SCRATCH_STACK_LEN: usize = 128;
+
+impl<'a> DecodeConfig<'a> {
+    /// Setup a configuration using the given alphabet, without a checksum or prefix.
+    pub const fn new(alpha: &'a Alphabet) -> DecodeConfig<'a> {
+        DecodeConfig {
             alpha,
             check: Check::Disabled,
+            prefix: "",
         }
     }
 
-    /// Setup decoder for the given string using default prepared alphabet.
-    pub(crate) const fn from_input(input: I) -> DecodeBuilder<'static, I> {
+    /// Change the alphabet that will be used for decoding, see
+    /// [`DecodeBuilder::with_alphabet`].
+    pub const fn with_alphabet(self, alpha: &'a Alphabet) -> DecodeConfig<'a> {
+        DecodeConfig { alpha, ..self }
+    }
+
+    /// Expect and check a [Base58Check][] checksum, and the version byte if provided, see
+    /// [`DecodeBuilder::with_check`].
+    ///
+    /// [Base58Check]: https://en.bitcoin.it/wiki/Base58Check_encoding
+    #[cfg(feature = "check")]
+    pub const fn with_check(self, expected_ver: Option<u8>) -> DecodeConfig<'a> {
+        let check = Check::Enabled(expected_ver);
+        DecodeConfig { check, ..self }
+    }
+
+    /// Expect and check a [Base58Check][] checksum along with the version bytes of `V`, see
+    /// [`DecodeBuilder::with_check_version_typed`].
+    ///
+    /// [Base58Check]: https://en.bitcoin.it/wiki/Base58Check_encoding
+    #[cfg(feature = "check")]
+    pub const fn with_check_version_typed<V: crate::check::Version>(self) -> DecodeConfig<'a> {
+        let check = Check::Versioned(V::BYTES);
+        DecodeConfig { check, ..self }
+    }
+
+    /// Expect and check a [CB58][] checksum, and the version byte if provided, see
+    /// [`DecodeBuilder::as_cb58`].
+    ///
+    /// [CB58]: https://support.avax.network/en/articles/4587395-what-is-cb58
+    #[cfg(feature = "cb58")]
+    pub const fn as_cb58(self, expected_ver: Option<u8>) -> DecodeConfig<'a> {
+        let check = Check::CB58(expected_ver);
+        DecodeConfig { check, ..self }
+    }
+
+    /// Expect the input to start with the literal text `prefix`, see
+    /// [`DecodeBuilder::with_text_prefix`].
+    pub const fn with_text_prefix(self, prefix: &'a str) -> DecodeConfig<'a> {
+        DecodeConfig { prefix, ..self }
+    }
+
+    /// Setup a decoder for `input` using this configuration.
+    pub const fn with_input<I: AsRef<[u8]>>(self, input: I) -> DecodeBuilder<'a, I> {
         DecodeBuilder {
             input,
-            alpha: Alphabet::DEFAULT,
-            check: Check::Disabled,
+            alpha: self.alpha,
+            check: self.check,
+            reserve_exact: false,
+            rtl: false,
+            nonzero: false,
+            nonempty: false,
+            prefix: self.prefix,
+            hrp_separator: None,
+            canonical: None,
+            expected_len: None,
+            allowed_lengths: None,
+            salt: &[],
+            ordered: None,
+            #[cfg(feature = "check")]
+            aad: &[],
+            map: None,
+            fallback: None,
+            fold: None,
+            #[cfg(feature = "metrics")]
+            stats: None,
+        }
+    }
+
+    /// Decode `input` into a new vector using this configuration.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bs58::{decode::DecodeConfig, Alphabet};
+    ///
+    /// const DECODER: DecodeConfig = DecodeConfig::new(Alphabet::DEFAULT);
+    ///
+    /// assert_eq!(
+    ///     vec![0x04, 0x30, 0x5e, 0x2b, 0x24, 0x73, 0xf0, 0x58],
+    ///     DECODER.decode("he11owor1d")?);
+    /// # Ok::<(), bs58::decode::Error>(())
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn decode<I: AsRef<[u8]>>(self, input: I) -> Result<Vec<u8>> {
+        self.with_input(input).into_vec()
+    }
+
+    /// Setup decoder for each field of `input` separated by `separator` using this
+    /// configuration, reversing [`EncodeConfig::join`](crate::encode::EncodeConfig::join), see
+    /// [`bs58::decode_split`](crate::decode_split()).
+    ///
+    /// # Panics
+    ///
+    /// If `separator` is empty, contains a character of the alphabet, or appears in the text
+    /// prefix.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bs58::{decode::DecodeConfig, Alphabet};
+    ///
+    /// const DECODER: DecodeConfig = DecodeConfig::new(Alphabet::RIPPLE).with_text_prefix("r:");
+    ///
+    /// let fields = DECODER
+    ///     .split("r:he11owor1d, r:", ", ")
+    ///     .collect::<Result<Vec<_>, _>>()?;
+    /// assert_eq!(vec![vec![0x60, 0x65, 0xe7, 0x9b, 0xba, 0x2f, 0x78], vec![]], fields);
+    /// # Ok::<(), bs58::decode::DelimitedError>(())
+    /// ```
+    #[track_caller]
+    pub fn split<'b, I: AsRef<[u8]> + ?Sized>(
+        self,
+        input: &'b I,
+        separator: &'b str,
+    ) -> Delimited<'a, 'b> {
+        crate::assert_separator(separator, self.alpha, self.prefix);
+        Delimited {
+            alpha: self.alpha,
+            check: self.check,
+            prefix: self.prefix,
+            ..Delimited::from_input(input.as_ref(), separator.as_bytes())
         }
     }
+}
+
+impl<'a, I: AsRef<[u8]>> DecodeBuilder<'a, I> {
+    /// Setup decoder for the given string using the given alphabet.
+    /// Preferably use [`bs58::decode`](crate::decode()) instead of this directly.
+    pub const fn new(input: I, alpha: &'a Alphabet) -> DecodeBuilder<'a, I> {
+        DecodeConfig::new(alpha).with_input(input)
+    }
+
+    /// Setup decoder for the given string using default prepared alphabet.
+    pub(crate) const fn from_input(input: I) -> DecodeBuilder<'static, I> {
+        DecodeConfig::new(Alphabet::DEFAULT).with_input(input)
+    }
 
     /// Change the alphabet that will be used for decoding.
     ///
@@ -227,7 +848,26 @@ impl<'a, I: AsRef<[u8]>> DecodeBuilder<'a, I> {
     /// ```
     pub const fn with_alphabet(mut self, alpha: &'a Alphabet) -> DecodeBuilder<'a, I> {
         self.alpha = alpha;
-        self
+        self.refold()
+    }
+
+    /// Change the alphabet that will be used for decoding to a compile time constant, see
+    /// [`StaticAlphabet`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// bs58::static_alphabet!(Ripple = b"rpshnaf39wBUDNEGHJKLM4PQRST7VWXYZ2bcdeCg65jkm8oFqi1tuvAxyz");
+    ///
+    /// assert_eq!(
+    ///     vec![0x60, 0x65, 0xe7, 0x9b, 0xba, 0x2f, 0x78],
+    ///     bs58::decode("he11owor1d")
+    ///         .with_static_alphabet::<Ripple>()
+    ///         .into_vec()?);
+    /// # Ok::<(), bs58::decode::Error>(())
+    /// ```
+    pub const fn with_static_alphabet<A: StaticAlphabet>(self) -> DecodeBuilder<'a, I> {
+        self.with_alphabet(A::ALPHABET)
     }
 
     /// Expect and check checksum using the [Base58Check][] algorithm when
@@ -236,6 +876,12 @@ impl<'a, I: AsRef<[u8]>> DecodeBuilder<'a, I> {
     /// Optional parameter for version byte. If provided, the version byte will
     /// be used in verification.
     ///
+    /// Input that decodes to fewer bytes than a checksum returns [`Error::NoChecksum`]. Input
+    /// that decodes to exactly a valid checksum, as encoding an empty payload gives, decodes to
+    /// an empty payload, or returns [`Error::NoChecksum`] if a version byte is expected since
+    /// there is none. See [`disallow_empty_payload`](Self::disallow_empty_payload) to reject
+    /// empty payloads.
+    ///
     /// [Base58Check]: https://en.bitcoin.it/wiki/Base58Check_encoding
     ///
     /// # Examples
@@ -246,6 +892,9 @@ impl<'a, I: AsRef<[u8]>> DecodeBuilder<'a, I> {
     ///     bs58::decode("PWEu9GGN")
     ///         .with_check(None)
     ///         .into_vec()?);
+    ///
+    /// // Only a checksum
+    /// assert_eq!(Vec::<u8>::new(), bs58::decode("3QJmnh").with_check(None).into_vec()?);
     /// # Ok::<(), bs58::decode::Error>(())
     /// ```
     #[cfg(feature = "check")]
@@ -254,53 +903,1624 @@ impl<'a, I: AsRef<[u8]>> DecodeBuilder<'a, I> {
         DecodeBuilder { check, ..self }
     }
 
-    /// Expect and check checksum using the [CB58][] algorithm when
-    /// decoding.
+    /// Expect and check a [Base58Check][] checksum calculated over the associated data `aad`
+    /// followed by the decoded bytes, as encoded by
+    /// [`EncodeBuilder::with_check_aad`](crate::encode::EncodeBuilder::with_check_aad).
     ///
-    /// Optional parameter for version byte. If provided, the version byte will
-    /// be used in verification.
+    /// The checksum must be the first 4 bytes of `SHA256(SHA256(aad || version || payload))`,
+    /// input encoded for a different context fails with [`Error::InvalidChecksum`]. Any version
+    /// set with [`with_check`](Self::with_check) or
+    /// [`with_check_version_typed`](Self::with_check_version_typed) is kept and verified as
+    /// usual, otherwise this is the same as `with_check(None)`.
     ///
-    /// [CB58]: https://support.avax.network/en/articles/4587395-what-is-cb58
+    /// [Base58Check]: https://en.bitcoin.it/wiki/Base58Check_encoding
     ///
     /// # Examples
     ///
     /// ```rust
+    /// let input = [0x60, 0x65, 0xe7, 0x9b, 0xba, 0x2f, 0x78];
+    /// let encoded = bs58::encode(input).with_check_aad(b"session:1").into_string();
     /// assert_eq!(
-    ///     vec![0x2d, 0x31],
-    ///     bs58::decode("PWHVMzdR")
-    ///         .as_cb58(None)
-    ///         .into_vec()?);
+    ///     input.to_vec(),
+    ///     bs58::decode(&encoded).with_check_aad(b"session:1").into_vec()?);
+    ///
+    /// assert!(matches!(
+    ///     bs58::decode(&encoded).with_check_aad(b"session:2").into_vec(),
+    ///     Err(bs58::decode::Error::InvalidChecksum { .. })));
     /// # Ok::<(), bs58::decode::Error>(())
     /// ```
-    #[cfg(feature = "cb58")]
-    pub fn as_cb58(self, expected_ver: Option<u8>) -> DecodeBuilder<'a, I> {
-        let check = Check::CB58(expected_ver);
-        DecodeBuilder { check, ..self }
+    #[cfg(feature = "check")]
+    pub fn with_check_aad(self, aad: &'a [u8]) -> DecodeBuilder<'a, I> {
+        let check = match self.check {
+            check @ (Check::Enabled(_) | Check::Versioned(_)) => check,
+            _ => Check::Enabled(None),
+        };
+        DecodeBuilder { check, aad, ..self }
     }
-    /// Decode into a new vector of bytes.
+
+    /// Expect and check checksum using the [Base58Check][] algorithm when decoding, along with the
+    /// version bytes of `V`.
     ///
-    /// See the documentation for [`bs58::decode`](crate::decode()) for an
-    /// explanation of the errors that may occur.
+    /// Unlike [`with_check`](Self::with_check) the version bytes are verified and then removed, so
+    /// only the payload is output. See [`check::Version`](crate::check::Version) for more details.
+    ///
+    /// [Base58Check]: https://en.bitcoin.it/wiki/Base58Check_encoding
     ///
     /// # Examples
     ///
     /// ```rust
+    /// use bs58::check::{BitcoinMainnetP2pkh, BitcoinTestnetP2pkh};
+    ///
     /// assert_eq!(
-    ///     vec![0x04, 0x30, 0x5e, 0x2b, 0x24, 0x73, 0xf0, 0x58],
-    ///     bs58::decode("he11owor1d").into_vec()?);
+    ///     vec![
+    ///         0x9b, 0x41, 0x54, 0xbb, 0xf2, 0x03, 0xe4, 0x13, 0x0c, 0x4b,
+    ///         0x86, 0x25, 0x93, 0x18, 0xa4, 0x98, 0x75, 0xdd, 0x04, 0x56,
+    ///     ],
+    ///     bs58::decode("1F9v11cupBVMpz3CrVfCppv9Rw2xEtU1c6")
+    ///         .with_check_version_typed::<BitcoinMainnetP2pkh>()
+    ///         .into_vec()?);
+    ///
+    /// assert_eq!(
+    ///     bs58::decode::Error::InvalidVersion { ver: 0x00, expected_ver: 0x6f },
+    ///     bs58::decode("1F9v11cupBVMpz3CrVfCppv9Rw2xEtU1c6")
+    ///         .with_check_version_typed::<BitcoinTestnetP2pkh>()
+    ///         .into_vec()
+    ///         .unwrap_err());
     /// # Ok::<(), bs58::decode::Error>(())
     /// ```
-    ///
-    #[cfg(feature = "alloc")]
-    pub fn into_vec(self) -> Result<Vec<u8>> {
-        let mut output = Vec::new();
-        self.onto(&mut output)?;
-        Ok(output)
+    #[cfg(feature = "check")]
+    pub fn with_check_version_typed<V: crate::check::Version>(self) -> DecodeBuilder<'a, I> {
+        let check = Check::Versioned(V::BYTES);
+        DecodeBuilder { check, ..self }
     }
 
-    /// Decode into the given buffer.
+    /// Expect and check a [Base58Check][] checksum after every `block_size` bytes of the payload
+    /// when decoding, as encoded by
+    /// [`EncodeBuilder::with_block_checksums`](crate::encode::EncodeBuilder::with_block_checksums)
+    /// which describes the format.
     ///
-    /// Returns the length written into the buffer.
+    /// The checksums are removed from the output. If a checksum does not match
+    /// [`Error::BlockChecksumFailed`] gives the index of its block, to localize the corruption.
+    ///
+    /// [Base58Check]: https://en.bitcoin.it/wiki/Base58Check_encoding
+    ///
+    /// # Panics
+    ///
+    /// If `block_size` is 0.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let input = [0x04, 0x30, 0x5e, 0x2b, 0x24, 0x73, 0xf0, 0x58];
+    /// let encoded = bs58::encode(input).with_block_checksums(4).into_string();
+    /// assert_eq!(
+    ///     input.to_vec(),
+    ///     bs58::decode(&encoded).with_block_checksums(4).into_vec()?);
+    /// # Ok::<(), bs58::decode::Error>(())
+    /// ```
+    #[cfg(feature = "check")]
+    pub fn with_block_checksums(self, block_size: usize) -> DecodeBuilder<'a, I> {
+        assert!(block_size > 0, "block size must be greater than 0");
+        let check = Check::Blocks(block_size);
+        DecodeBuilder { check, ..self }
+    }
+
+    /// Expect and check checksum using the [CB58][] algorithm when
+    /// decoding.
+    ///
+    /// Optional parameter for version byte. If provided, the version byte will
+    /// be used in verification.
+    ///
+    /// [CB58]: https://support.avax.network/en/articles/4587395-what-is-cb58
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// assert_eq!(
+    ///     vec![0x2d, 0x31],
+    ///     bs58::decode("PWHVMzdR")
+    ///         .as_cb58(None)
+    ///         .into_vec()?);
+    /// # Ok::<(), bs58::decode::Error>(())
+    /// ```
+    #[cfg(feature = "cb58")]
+    pub fn as_cb58(self, expected_ver: Option<u8>) -> DecodeBuilder<'a, I> {
+        let check = Check::CB58(expected_ver);
+        DecodeBuilder { check, ..self }
+    }
+
+    /// Estimate the decoded length up front and only reserve that much space in the output,
+    /// instead of reserving space for the worst case of one byte per input character.
+    ///
+    /// The estimate is computed from the number of input characters without any arithmetic on
+    /// their values. It is never smaller than the decoded length, and is at most one byte larger
+    /// for inputs of up to 500 characters (after that the overestimate grows by roughly one byte
+    /// per 1300 characters). This means resizeable buffers such as [`Vec`] will only need a single
+    /// allocation close to the final size, at the cost of scanning the leading zeros of the input
+    /// an extra time.
+    ///
+    /// This has no effect on buffers that are not resizeable.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let decoded = bs58::decode("2gPihUTjt3FJqf1VpidgrY5cZ6PuyMccGVwQHRfjMPZG")
+    ///     .reserve_exact_output()
+    ///     .into_vec()?;
+    /// assert_eq!(32, decoded.len());
+    /// assert!(decoded.capacity() <= 33);
+    /// # Ok::<(), bs58::decode::Error>(())
+    /// ```
+    pub fn reserve_exact_output(self) -> DecodeBuilder<'a, I> {
+        DecodeBuilder {
+            reserve_exact: true,
+            ..self
+        }
+    }
+
+    /// Expect the input characters to be in reverse order, as produced by
+    /// [`EncodeBuilder::into_string_rtl`](crate::encode::EncodeBuilder::into_string_rtl).
+    ///
+    /// This is only a display convenience for contexts that render right-to-left and whose
+    /// protocol expects the characters in reversed display order; both the encoding and decoding
+    /// side must agree to use it. Indexes in errors still refer to the position in the input as
+    /// given.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// assert_eq!(
+    ///     vec![0x04, 0x30, 0x5e, 0x2b, 0x24, 0x73, 0xf0, 0x58],
+    ///     bs58::decode("d1rowo11eh").rtl().into_vec()?);
+    /// # Ok::<(), bs58::decode::Error>(())
+    /// ```
+    pub fn rtl(self) -> DecodeBuilder<'a, I> {
+        DecodeBuilder { rtl: true, ..self }
+    }
+
+    /// Error with [`Error::ZeroValue`] if the decoded value is zero, for protocols that treat it
+    /// as invalid.
+    ///
+    /// Each leading zero character decodes to a zero byte, so this rejects empty input and input
+    /// consisting of only zero characters (such as `"111"` with the default alphabet) but accepts
+    /// any input with another character after the leading zeros. When decoding with a checksum
+    /// only the payload is considered, the checksum itself is ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// assert_eq!(
+    ///     Err(bs58::decode::Error::ZeroValue),
+    ///     bs58::decode("111").require_nonzero().into_vec());
+    /// assert_eq!(
+    ///     Ok(vec![0x00, 0x00, 0x01]),
+    ///     bs58::decode("112").require_nonzero().into_vec());
+    /// ```
+    pub fn require_nonzero(self) -> DecodeBuilder<'a, I> {
+        DecodeBuilder {
+            nonzero: true,
+            ..self
+        }
+    }
+
+    /// Error with [`Error::EmptyPayload`] if the decoded payload is empty.
+    ///
+    /// Without a checksum this rejects empty input. With a checksum the payload is what remains
+    /// after verifying and removing the checksum (and the version, if it is removed), so this
+    /// also rejects input that decodes to only a valid checksum, such as the encoding of an empty
+    /// payload. A payload of zero bytes is not empty, see
+    /// [`require_nonzero`](Self::require_nonzero) to reject those too.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// assert_eq!(Ok(vec![]), bs58::decode("").into_vec());
+    /// assert_eq!(
+    ///     Err(bs58::decode::Error::EmptyPayload),
+    ///     bs58::decode("").disallow_empty_payload().into_vec());
+    /// assert_eq!(
+    ///     Ok(vec![0x00]),
+    ///     bs58::decode("1").disallow_empty_payload().into_vec());
+    /// ```
+    pub fn disallow_empty_payload(self) -> DecodeBuilder<'a, I> {
+        DecodeBuilder {
+            nonempty: true,
+            ..self
+        }
+    }
+
+    /// Error with [`Error::NonCanonical`] unless the input is the one canonical encoding of a
+    /// payload of exactly `expected_len` bytes.
+    ///
+    /// For fixed length payloads the only way to give multiple encodings of the same value is by
+    /// adding or removing leading zero characters, which naive code may then truncate or pad
+    /// away. This rejects input whose value needs more than `expected_len` bytes
+    /// ([`CanonicalRule::Length`]) and input that has more or fewer leading zero characters than
+    /// needed to pad its value to `expected_len` bytes ([`CanonicalRule::LeadingZeros`]). When
+    /// decoding with a checksum `expected_len` is the length of the decoded output, which does not
+    /// include the checksum (or a [typed version](Self::with_check_version_typed)), and this is
+    /// checked before the checksum is verified.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bs58::decode::{CanonicalRule, Error};
+    ///
+    /// assert_eq!(
+    ///     Ok(vec![0x00, 0x01, 0x02, 0x03]),
+    ///     bs58::decode("1Ldp").canonical(4).into_vec());
+    /// assert_eq!(
+    ///     Err(Error::NonCanonical {
+    ///         rule: CanonicalRule::LeadingZeros,
+    ///         expected_len: 4,
+    ///         found_len: 5,
+    ///     }),
+    ///     bs58::decode("11Ldp").canonical(4).into_vec());
+    /// assert_eq!(
+    ///     Err(Error::NonCanonical {
+    ///         rule: CanonicalRule::Length,
+    ///         expected_len: 2,
+    ///         found_len: 4,
+    ///     }),
+    ///     bs58::decode("1Ldp").canonical(2).into_vec());
+    /// ```
+    pub fn canonical(self, expected_len: usize) -> DecodeBuilder<'a, I> {
+        DecodeBuilder {
+            canonical: Some(expected_len),
+            ..self
+        }
+    }
+
+    /// Expect the decoded data to be exactly `expected_len` bytes, erroring with
+    /// [`Error::WrongLength`] if it isn't.
+    ///
+    /// As well as checking the length this is used to size the output, so resizeable buffers
+    /// such as [`Vec`] are allocated exactly once with exactly the needed space, and
+    /// [`into_sink`](Self::into_sink) and similar methods use a stack buffer when it is small.
+    /// Input that is too short to decode to `expected_len` bytes is rejected before decoding, in
+    /// which case the `found` length of the error is only an upper bound; decoding stops as soon
+    /// as the data is longer than expected, in which case it is only a lower bound. When
+    /// decoding with a checksum `expected_len` is the length of the decoded output, as for
+    /// [`canonical`](Self::canonical).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let decoded = bs58::decode("he11owor1d").with_expected_len(8).into_vec()?;
+    /// assert_eq!(vec![0x04, 0x30, 0x5e, 0x2b, 0x24, 0x73, 0xf0, 0x58], decoded);
+    /// assert_eq!(8, decoded.capacity());
+    ///
+    /// assert_eq!(
+    ///     Err(bs58::decode::Error::WrongLength { expected: 7, found: 8 }),
+    ///     bs58::decode("he11owor1d").with_expected_len(7).into_vec());
+    /// # Ok::<(), bs58::decode::Error>(())
+    /// ```
+    pub fn with_expected_len(self, expected_len: usize) -> DecodeBuilder<'a, I> {
+        DecodeBuilder {
+            expected_len: Some(expected_len),
+            ..self
+        }
+    }
+
+    /// Expect the decoded data to be one of the `allowed` lengths, erroring with
+    /// [`Error::DisallowedLength`] if it isn't, for protocols accepting several fixed sizes.
+    ///
+    /// The error gives the length that was found along with the allowed lengths, so they must be
+    /// `'static`, such as a literal or a `const`. Unlike [`with_expected_len`](Self::with_expected_len) the whole input is decoded before
+    /// the length is checked. When decoding with a checksum the lengths are of the decoded
+    /// output, after removing the checksum.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let decoded = bs58::decode("he11owor1d").allowed_lengths(&[8, 32]).into_vec()?;
+    /// assert_eq!(vec![0x04, 0x30, 0x5e, 0x2b, 0x24, 0x73, 0xf0, 0x58], decoded);
+    ///
+    /// assert_eq!(
+    ///     Err(bs58::decode::Error::DisallowedLength { allowed: &[20, 32], found: 8 }),
+    ///     bs58::decode("he11owor1d").allowed_lengths(&[20, 32]).into_vec());
+    /// # Ok::<(), bs58::decode::Error>(())
+    /// ```
+    pub fn allowed_lengths(self, allowed: &'static [usize]) -> DecodeBuilder<'a, I> {
+        DecodeBuilder {
+            allowed_lengths: Some(allowed),
+            ..self
+        }
+    }
+
+    /// Check the decoded data starts with the `salt` bytes and remove them, erroring with
+    /// [`Error::WrongSalt`] if it doesn't, for input encoded with
+    /// [`EncodeBuilder::with_salt`](crate::encode::EncodeBuilder::with_salt).
+    ///
+    /// The salt is removed after verifying any checksum. When checking for a version byte that is
+    /// kept in the output, as with [`with_check`](Self::with_check), the salt is expected after
+    /// it. Lengths set with [`with_expected_len`](Self::with_expected_len),
+    /// [`allowed_lengths`](Self::allowed_lengths) or
+    /// [`disallow_empty_payload`](Self::disallow_empty_payload) are of the decoded data including
+    /// the salt.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let decoded = bs58::decode("he11owor1d").strip_salt(&[0x04, 0x30, 0x5e]).into_vec()?;
+    /// assert_eq!(vec![0x2b, 0x24, 0x73, 0xf0, 0x58], decoded);
+    ///
+    /// assert_eq!(
+    ///     Err(bs58::decode::Error::WrongSalt),
+    ///     bs58::decode("he11owor1d").strip_salt(&[0x04, 0x31]).into_vec());
+    /// # Ok::<(), bs58::decode::Error>(())
+    /// ```
+    pub fn strip_salt(self, salt: &'a [u8]) -> DecodeBuilder<'a, I> {
+        DecodeBuilder { salt, ..self }
+    }
+
+    /// Decode a value encoded by
+    /// [`EncodeBuilder::order_preserving`](crate::encode::EncodeBuilder::order_preserving) with the
+    /// same `max_bytes`, giving it as exactly `max_bytes` big-endian bytes.
+    ///
+    /// The padding is removed along with any other leading zeros of the value, which is then
+    /// padded back to `max_bytes` with zero bytes, so encoded inputs shorter than `max_bytes` come
+    /// back with leading zero bytes added. Values that need more than `max_bytes` bytes give
+    /// [`Error::Overflow`]. The width of the input isn't checked, so a value encoded without this
+    /// mode decodes too. As when encoding this is for input without a checksum, the other options
+    /// of the builder apply to the padded value.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// assert_eq!(
+    ///     vec![0x00, 0x00, 0x01, 0x00],
+    ///     bs58::decode("11115R").order_preserving(4).into_vec()?);
+    /// assert_eq!(vec![0x00, 0x01, 0x00], bs58::decode("5R").order_preserving(3).into_vec()?);
+    ///
+    /// assert_eq!(
+    ///     Err(bs58::decode::Error::Overflow),
+    ///     bs58::decode("11115R").order_preserving(1).into_vec());
+    /// # Ok::<(), bs58::decode::Error>(())
+    /// ```
+    pub fn order_preserving(self, max_bytes: usize) -> DecodeBuilder<'a, I> {
+        let ordered = Some(max_bytes);
+        DecodeBuilder { ordered, ..self }
+    }
+
+    /// Also accept input encoded with the `secondary` alphabet.
+    ///
+    /// Deciding per character which alphabet was meant is ambiguous for closely related alphabets,
+    /// such as the case swapped [`BITCOIN`](Alphabet::BITCOIN) and [`FLICKR`](Alphabet::FLICKR)
+    /// alphabets where most characters are valid in both with different values. Instead the input
+    /// is first decoded entirely with the primary alphabet, and if that fails for any reason it is
+    /// retried entirely with the secondary alphabet. If both fail the error from the primary
+    /// alphabet is returned. Input valid in both alphabets always decodes with the primary one,
+    /// so this should only be used when the data is known to be in one of them, use
+    /// [`onto_reporting_alphabet`](Self::onto_reporting_alphabet) to find out which.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// // Ripple has no zero bytes so decodes to too few bytes, then Bitcoin is tried
+    /// assert_eq!(
+    ///     vec![0x00, 0x00, 0x00, 0x39],
+    ///     bs58::decode("111z")
+    ///         .with_alphabet(bs58::Alphabet::RIPPLE)
+    ///         .also_accept_alphabet(bs58::Alphabet::BITCOIN)
+    ///         .with_expected_len(4)
+    ///         .into_vec()?);
+    /// # Ok::<(), bs58::decode::Error>(())
+    /// ```
+    pub fn also_accept_alphabet(self, secondary: &'a Alphabet) -> DecodeBuilder<'a, I> {
+        DecodeBuilder {
+            fallback: Some(secondary),
+            ..self
+        }
+        .refold()
+    }
+
+    /// Pass each decoded byte through `f` before it is output.
+    ///
+    /// `f` is applied to the final output bytes in order, after any checksum has been verified
+    /// and removed, so it can be used to mask or remap the data without a second pass over it.
+    ///
+    /// `f` is borrowed rather than taken by value so the builder stays usable in `const` (such as
+    /// [`into_array_const`](Self::into_array_const), which can't drop an owned closure) and
+    /// without `alloc`. A closure can still be written inline as `&|b| ...`, it lives until the
+    /// end of the statement. The cost is one indirect call for each output byte, which is small
+    /// next to the decoding itself.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let key = 0x5a;
+    /// assert_eq!(
+    ///     vec![0x5e, 0x6a, 0x04, 0x71, 0x7e, 0x29, 0xaa, 0x02],
+    ///     bs58::decode("he11owor1d").map_bytes(&|b| b ^ key).into_vec()?);
+    /// # Ok::<(), bs58::decode::Error>(())
+    /// ```
+    pub fn map_bytes(self, f: &'a dyn Fn(u8) -> u8) -> DecodeBuilder<'a, I> {
+        DecodeBuilder {
+            map: Some(f),
+            ..self
+        }
+    }
+
+    /// Expect the input in the display form given by `fold`, as produced by
+    /// [`EncodeBuilder::display_fold`](crate::encode::EncodeBuilder::display_fold), mapping each
+    /// character back to the character of the alphabet it is the display form of.
+    ///
+    /// Where more than one character of the alphabet has the same display form it can't be
+    /// mapped back, and decoding errors with [`Error::AmbiguousFold`]. See [`Fold`] for which
+    /// characters this applies to with the built-in alphabets.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bs58::alphabet::Fold;
+    ///
+    /// let input = bs58::decode("2Lio9").into_vec()?;
+    /// assert_eq!("2LIO9", bs58::encode(&input).display_fold(Fold::Upper).into_string());
+    /// assert_eq!(input, bs58::decode("2LIO9").display_fold(Fold::Upper).into_vec()?);
+    ///
+    /// // Both `h` and `H` are in the alphabet and display as `H`
+    /// assert_eq!(
+    ///     Err(bs58::decode::Error::AmbiguousFold { character: 'H', index: 0 }),
+    ///     bs58::decode("HE11OWOR1D").display_fold(Fold::Upper).into_vec());
+    /// # Ok::<(), bs58::decode::Error>(())
+    /// ```
+    pub fn display_fold(self, fold: Fold) -> DecodeBuilder<'a, I> {
+        let fold = Some(Unfold::new(fold, self.alpha, self.fallback));
+        DecodeBuilder { fold, ..self }
+    }
+
+    /// Work out the tables reversing the [display fold](Self::display_fold) again after the
+    /// alphabets have changed.
+    const fn refold(mut self) -> DecodeBuilder<'a, I> {
+        if let Some(Unfold { fold, .. }) = self.fold {
+            self.fold = Some(Unfold::new(fold, self.alpha, self.fallback));
+        }
+        self
+    }
+
+    /// Reset `stats` and fill them in with what the decode does, see [`Stats`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bs58::metrics::Stats;
+    ///
+    /// let mut stats = Stats::default();
+    /// bs58::decode("he11owor1d").with_stats(&mut stats).into_vec()?;
+    /// assert_eq!((10, 8), (stats.input_len, stats.output_len));
+    /// # Ok::<(), bs58::decode::Error>(())
+    /// ```
+    #[cfg(feature = "metrics")]
+    pub fn with_stats(self, stats: &'a mut Stats) -> DecodeBuilder<'a, I> {
+        *stats = Stats {
+            input_len: self.input.as_ref().len(),
+            ..Stats::default()
+        };
+        DecodeBuilder {
+            stats: Some(core::cell::Cell::from_mut(stats)),
+            ..self
+        }
+    }
+
+    /// Expect the input to start with the literal text `prefix`, as produced by
+    /// [`EncodeBuilder::with_text_prefix`](crate::encode::EncodeBuilder::with_text_prefix),
+    /// erroring with [`Error::WrongPrefix`] if it doesn't.
+    ///
+    /// The prefix is removed before decoding the rest of the input, indexes in errors still refer
+    /// to the position in the input as given.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// assert_eq!(
+    ///     vec![0x04, 0x30, 0x5e, 0x2b, 0x24, 0x73, 0xf0, 0x58],
+    ///     bs58::decode("acct_he11owor1d").with_text_prefix("acct_").into_vec()?);
+    /// assert_eq!(
+    ///     Err(bs58::decode::Error::WrongPrefix),
+    ///     bs58::decode("he11owor1d").with_text_prefix("acct_").into_vec());
+    /// # Ok::<(), bs58::decode::Error>(())
+    /// ```
+    pub fn with_text_prefix(self, prefix: &'a str) -> DecodeBuilder<'a, I> {
+        DecodeBuilder {
+            prefix,
+            hrp_separator: None,
+            ..self
+        }
+    }
+
+    /// Expect the input to start with the human-readable part `hrp` followed by `separator`, as
+    /// written by [`EncodeBuilder::with_hrp`](crate::encode::EncodeBuilder::with_hrp), erroring
+    /// with [`Error::WrongPrefix`] if it doesn't. This replaces any
+    /// [text prefix](Self::with_text_prefix).
+    ///
+    /// When the separator is not in the alphabet this is the same as splitting the input on the
+    /// last separator and checking the part before it is `hrp`. As the expected HRP is known it
+    /// is stripped from the start instead, so a separator that is also in the alphabet (such as
+    /// the `1` of BIP173 style addresses) is still unambiguous. Indexes in errors refer to the
+    /// position in the input as given.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// assert_eq!(
+    ///     vec![0x04, 0x30, 0x5e, 0x2b, 0x24, 0x73, 0xf0, 0x58],
+    ///     bs58::decode("xrb_he11owor1d").with_hrp("xrb", '_').into_vec()?);
+    /// assert_eq!(
+    ///     Err(bs58::decode::Error::WrongPrefix),
+    ///     bs58::decode("nano_he11owor1d").with_hrp("xrb", '_').into_vec());
+    /// # Ok::<(), bs58::decode::Error>(())
+    /// ```
+    pub fn with_hrp(self, hrp: &'a str, separator: char) -> DecodeBuilder<'a, I> {
+        DecodeBuilder {
+            prefix: hrp,
+            hrp_separator: Some(separator),
+            ..self
+        }
+    }
+    /// Decode into a new vector of bytes.
+    ///
+    /// See the documentation for [`bs58::decode`](crate::decode()) for an
+    /// explanation of the errors that may occur.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// assert_eq!(
+    ///     vec![0x04, 0x30, 0x5e, 0x2b, 0x24, 0x73, 0xf0, 0x58],
+    ///     bs58::decode("he11owor1d").into_vec()?);
+    /// # Ok::<(), bs58::decode::Error>(())
+    /// ```
+    ///
+    #[cfg(feature = "alloc")]
+    pub fn into_vec(self) -> Result<Vec<u8>> {
+        let mut output = Vec::new();
+        self.onto(&mut output)?;
+        Ok(output)
+    }
+
+    /// Decode into a new vector of bytes, checking it against a fixed layout such as a header of
+    /// magic bytes.
+    ///
+    /// Each `Some(byte)` of `pattern` requires the decoded byte at the same index to be `byte`,
+    /// and `None` allows any byte. The decoded data must be exactly as long as `pattern`,
+    /// otherwise this errors with [`Error::WrongLength`] as with
+    /// [`with_expected_len`](Self::with_expected_len), and the first byte that doesn't match
+    /// errors with [`Error::PatternMismatch`]. When decoding with a checksum the pattern is
+    /// matched against the decoded output, after removing the checksum.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let pattern = [Some(0x04), Some(0x30), None, None, None, None, None, None];
+    /// assert_eq!(
+    ///     vec![0x04, 0x30, 0x5e, 0x2b, 0x24, 0x73, 0xf0, 0x58],
+    ///     bs58::decode("he11owor1d").into_vec_matching(&pattern)?);
+    ///
+    /// assert_eq!(
+    ///     Err(bs58::decode::Error::PatternMismatch { index: 1 }),
+    ///     bs58::decode("he11owor1d").into_vec_matching(&[Some(0x04), Some(0x31), None, None, None,
+    ///         None, None, None]));
+    /// # Ok::<(), bs58::decode::Error>(())
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn into_vec_matching(self, pattern: &[Option<u8>]) -> Result<Vec<u8>> {
+        let output = self.with_expected_len(pattern.len()).into_vec()?;
+        match pattern
+            .iter()
+            .zip(&output)
+            .position(|(expected, &byte)| expected.is_some_and(|expected| expected != byte))
+        {
+            Some(index) => Err(Error::PatternMismatch { index }),
+            None => Ok(output),
+        }
+    }
+
+    /// Decode into a new vector of bytes for each of the consecutive fields of a fixed layout,
+    /// such as a version byte followed by a hash.
+    ///
+    /// Each element of `layout` is the length of a field, and the sum of `layout` must equal the
+    /// decoded length, otherwise this errors with [`Error::WrongLength`] as with
+    /// [`with_expected_len`](Self::with_expected_len). Fields may be empty. When decoding with a
+    /// checksum the fields are split from the decoded output, after removing the checksum.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let fields = bs58::decode("he11owor1d").into_fields(&[1, 4, 3])?;
+    /// assert_eq!(
+    ///     vec![vec![0x04], vec![0x30, 0x5e, 0x2b, 0x24], vec![0x73, 0xf0, 0x58]],
+    ///     fields);
+    ///
+    /// assert_eq!(
+    ///     Err(bs58::decode::Error::WrongLength { expected: 7, found: 8 }),
+    ///     bs58::decode("he11owor1d").into_fields(&[1, 4, 2]));
+    /// # Ok::<(), bs58::decode::Error>(())
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn into_fields(self, layout: &[usize]) -> Result<Vec<Vec<u8>>> {
+        let output = self.with_expected_len(layout.iter().sum()).into_vec()?;
+        let mut rest = &output[..];
+        Ok(layout
+            .iter()
+            .map(|&len| {
+                let (field, tail) = rest.split_at(len);
+                rest = tail;
+                field.to_vec()
+            })
+            .collect())
+    }
+
+    /// Decode into a new vector of bytes, along with the CRC-32 of the decoded bytes, for storing
+    /// alongside them to verify later.
+    ///
+    /// This is the CRC-32 used by zlib, gzip and PNG (polynomial `0x04C11DB7`, reflected). Base58
+    /// decoding isn't incremental, none of the bytes are known until all of the input has been
+    /// read, so the CRC is calculated straight after decoding while the bytes are still in cache.
+    /// When decoding with a checksum it covers the decoded output, after removing the checksum.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let (decoded, crc) = bs58::decode("he11owor1d").into_vec_with_crc32()?;
+    /// assert_eq!(vec![0x04, 0x30, 0x5e, 0x2b, 0x24, 0x73, 0xf0, 0x58], decoded);
+    /// assert_eq!(0xc175_d548, crc);
+    /// # Ok::<(), bs58::decode::Error>(())
+    /// ```
+    #[cfg(feature = "crc")]
+    pub fn into_vec_with_crc32(self) -> Result<(Vec<u8>, u32)> {
+        let output = self.into_vec()?;
+        let crc = crc32(&output);
+        Ok((output, crc))
+    }
+
+    /// Decode into a new vector of bytes, validating the characters without timing that depends
+    /// on where the first invalid character is, for decoding secrets such as authentication
+    /// tokens.
+    ///
+    /// The usual decode stops at the first character that isn't in the alphabet, so the time it
+    /// takes to reject an input reveals how many of its leading characters are valid. This
+    /// instead checks every character against every character of the alphabet, with
+    /// [`subtle`]'s constant-time comparisons and no branches or table lookups on the input,
+    /// before reporting the first invalid character or decoding. An invalid input takes the same
+    /// time to reject as a valid input of the same length takes to validate.
+    ///
+    /// Only the validation is constant-time. The threat model is an attacker who submits
+    /// guesses or modifications of a token and measures how long they take to be rejected, not
+    /// one who can observe the decoding of valid tokens:
+    ///
+    ///  * The length of the input, and the text prefix, are not hidden.
+    ///  * Once the characters are known to be valid they are decoded as by
+    ///    [`into_vec`](Self::into_vec), which looks up each character in a table and does bignum
+    ///    arithmetic whose cost depends on the number of leading zeros and the length of the
+    ///    decoded value, so it is not constant-time in the value.
+    ///  * The checksum, [expected length](Self::with_expected_len) and other checks of the
+    ///    decoded value are not constant-time, compare a decoded secret to its expected value
+    ///    with [`subtle::ConstantTimeEq`] rather than relying on these.
+    ///
+    /// Characters are validated against the alphabet only, so [`map_bytes`](Self::map_bytes),
+    /// [`also_accept_alphabet`](Self::also_accept_alphabet) and
+    /// [`display_fold`](Self::display_fold) are not supported. Decoding
+    /// [right-to-left](Self::rtl) is, with the same result as [`into_vec`](Self::into_vec).
+    ///
+    /// # Panics
+    ///
+    /// If [`map_bytes`](Self::map_bytes), [`also_accept_alphabet`](Self::also_accept_alphabet) or
+    /// [`display_fold`](Self::display_fold) has been set.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// assert_eq!(
+    ///     vec![0x04, 0x30, 0x5e, 0x2b, 0x24, 0x73, 0xf0, 0x58],
+    ///     bs58::decode("he11owor1d").into_vec_constant_time()?);
+    ///
+    /// assert_eq!(
+    ///     Err(bs58::decode::Error::InvalidCharacter { character: 'l', index: 2 }),
+    ///     bs58::decode("hello world").into_vec_constant_time());
+    /// # Ok::<(), bs58::decode::Error>(())
+    /// ```
+    #[cfg(feature = "subtle")]
+    pub fn into_vec_constant_time(self) -> Result<Vec<u8>> {
+        assert!(
+            self.map.is_none() && self.fallback.is_none() && self.fold.is_none(),
+            "into_vec_constant_time does not support map_bytes, also_accept_alphabet or display_fold"
+        );
+        let input = self.unprefixed_input()?;
+        let offset = self.text_prefix_len();
+        if self.rtl {
+            validate_constant_time(input.iter().enumerate().rev(), 0, self.alpha).map_err(
+                |err| {
+                    match err {
+                        Error::NonAsciiCharacter { index } => Error::NonAsciiCharacter {
+                            index: char_start(input, index),
+                        },
+                        err => err,
+                    }
+                    .offset_index(offset)
+                },
+            )?;
+        } else {
+            validate_constant_time(input.iter().enumerate(), offset, self.alpha)?;
+        }
+        self.into_vec()
+    }
+
+    /// Decode and feed the decoded bytes into the hasher `d`, returning the number of bytes, for
+    /// verifying a payload against an externally supplied hash without keeping it.
+    ///
+    /// Base58 can't be decoded incrementally, none of the bytes are known until all of the input
+    /// has been read, so the whole input is decoded into a temporary buffer then hashed while it
+    /// is still in cache. The buffer is on the stack for up to 128 decoded bytes, otherwise it
+    /// is allocated for the duration of the call and freed before returning, or without `alloc`
+    /// [`Error::BufferTooSmall`] is returned. Nothing is fed into `d` if decoding fails. When
+    /// decoding with a checksum only the decoded output, after removing the checksum, is hashed.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sha2::{Digest, Sha256};
+    ///
+    /// let mut hasher = Sha256::new();
+    /// assert_eq!(8, bs58::decode("he11owor1d").decode_to_digest(&mut hasher)?);
+    /// assert_eq!(
+    ///     Sha256::digest([0x04, 0x30, 0x5e, 0x2b, 0x24, 0x73, 0xf0, 0x58]),
+    ///     hasher.finalize());
+    /// # Ok::<(), bs58::decode::Error>(())
+    /// ```
+    #[cfg(feature = "digest")]
+    pub fn decode_to_digest<D: digest::Update>(self, d: &mut D) -> Result<usize> {
+        self.with_scratch(|decoded| {
+            d.update(decoded);
+            Ok(decoded.len())
+        })
+    }
+
+    /// Clear `output` then decode into it, reusing its allocation.
+    ///
+    /// Unlike decoding [`onto`](Self::onto) a slice this grows the vector as needed, so when
+    /// decoding many values in a loop only the first few need to allocate. If decoding fails
+    /// `output` is left empty.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let mut output = Vec::with_capacity(16);
+    /// for input in ["he11owor1d", "EUYUqQf"] {
+    ///     bs58::decode(input).into_vec_reuse(&mut output)?;
+    ///     assert!(output.capacity() >= 16);
+    /// }
+    /// assert_eq!(b"world", output.as_slice());
+    /// # Ok::<(), bs58::decode::Error>(())
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn into_vec_reuse(self, output: &mut Vec<u8>) -> Result<()> {
+        output.clear();
+        let result = self.onto(&mut *output);
+        if result.is_err() {
+            output.clear();
+        }
+        result.map(|_| ())
+    }
+
+    /// Decode into a new vector of bytes from input with the checksum as a separate group after
+    /// `sep`, as produced by
+    /// [`EncodeBuilder::into_string_with_checksum_separator`](crate::encode::EncodeBuilder::into_string_with_checksum_separator).
+    ///
+    /// The input is split at the last `sep`, then both groups are decoded and recombined before
+    /// verifying the checksum. If no checksum has been configured a [Base58Check][] checksum
+    /// without a version is verified, as with [`with_check(None)`](Self::with_check). The group
+    /// after `sep` must decode to exactly the 4 checksum bytes, otherwise
+    /// [`Error::WrongLength`] is returned. Decoding [right-to-left](Self::rtl) is not supported
+    /// and is ignored.
+    ///
+    /// [Base58Check]: https://en.bitcoin.it/wiki/Base58Check_encoding
+    ///
+    /// # Panics
+    ///
+    /// If `sep` is a character of the alphabet, as the groups could not be told apart.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// assert_eq!(
+    ///     vec![0x2a, 0x60, 0x65, 0xe7, 0x9b, 0xba, 0x2f, 0x78],
+    ///     bs58::decode("8678ThZWZ7M-4NnGCJ")
+    ///         .with_check(Some(42))
+    ///         .into_vec_with_checksum_separator('-')?);
+    /// # Ok::<(), bs58::decode::Error>(())
+    /// ```
+    #[cfg(all(feature = "check", feature = "alloc"))]
+    pub fn into_vec_with_checksum_separator(self, sep: char) -> Result<Vec<u8>> {
+        assert!(
+            !self.alpha.contains(sep) && !self.fallback.is_some_and(|alpha| alpha.contains(sep)),
+            "checksum separator {sep:?} is part of the alphabet"
+        );
+
+        let prefix_len = self.text_prefix_len();
+        let input = self.unprefixed_input()?;
+        let mut sep_bytes = [0; 4];
+        let sep_bytes = sep.encode_utf8(&mut sep_bytes).as_bytes();
+        let split = input
+            .windows(sep_bytes.len())
+            .rposition(|window| window == sep_bytes)
+            .ok_or(Error::NoChecksum)?;
+        let checksum_start = split + sep_bytes.len();
+
+        let decode = |alpha: &'a Alphabet| {
+            // Each group is decoded with one alphabet at a time, so any display fold is reversed
+            // for that alphabet, and right-to-left is ignored as the groups are split in reading
+            // order
+            let group = |input| {
+                DecodeBuilder {
+                    alpha,
+                    rtl: false,
+                    ..self.plain(input)
+                }
+                .refold()
+            };
+
+            let mut output = group(&input[..split])
+                .into_vec()
+                .map_err(|err| err.offset_index(prefix_len))?;
+            let checksum_len = group(&input[checksum_start..])
+                .onto(&mut output)
+                .map_err(|err| err.offset_index(prefix_len + checksum_start))?;
+            if checksum_len != CHECKSUM_LEN {
+                return Err(Error::WrongLength {
+                    expected: CHECKSUM_LEN,
+                    found: checksum_len,
+                });
+            }
+
+            let check = match self.check {
+                Check::Disabled => Check::Enabled(None),
+                check => check,
+            };
+            check_canonical(self.canonical, &check, &output)?;
+            let len = verify(&check, self.aad(), &mut output)?;
+            output.truncate(len);
+            if let Some(expected) = self.expected_len.filter(|&expected| expected != len) {
+                return Err(Error::WrongLength {
+                    expected,
+                    found: len,
+                });
+            }
+            if let Some(allowed) = self
+                .allowed_lengths
+                .filter(|allowed| !allowed.contains(&len))
+            {
+                return Err(Error::DisallowedLength {
+                    allowed,
+                    found: len,
+                });
+            }
+            if self.nonempty && output.is_empty() {
+                return Err(Error::EmptyPayload);
+            }
+            if self.nonzero && output.iter().all(|&b| b == 0) {
+                return Err(Error::ZeroValue);
+            }
+            let len = self.remove_salt(&mut output)?;
+            output.truncate(len);
+            if let Some(map) = self.map {
+                output.iter_mut().for_each(|b| *b = map(*b));
+            }
+            Ok(output)
+        };
+
+        match (decode(self.alpha), self.fallback) {
+            (Err(err), Some(fallback)) => decode(fallback).map_err(|_| err),
+            (result, _) => result,
+        }
+    }
+
+    /// Decode and verify a [Base58Check][] checksum allowing any version, then classify the data
+    /// by its version and payload length.
+    ///
+    /// Returns the tag of the first `(version, payload_len, tag)` rule matching the decoded data,
+    /// along with the payload (not including the version), or [`Error::Unclassified`] giving the
+    /// version and payload length found if no rule matches. This replaces any check set on the
+    /// builder, except that [CB58][] checksums are verified if [`as_cb58`](Self::as_cb58) was
+    /// used.
+    ///
+    /// [Base58Check]: https://en.bitcoin.it/wiki/Base58Check_encoding
+    /// [CB58]: https://support.avax.network/en/articles/4587395-what-is-cb58
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// #[derive(Copy, Clone, Debug, PartialEq)]
+    /// enum Address {
+    ///     P2pkh,
+    ///     P2sh,
+    /// }
+    ///
+    /// let rules = [(0x00, 20, Address::P2pkh), (0x05, 20, Address::P2sh)];
+    /// let (kind, payload) =
+    ///     bs58::decode("1F9v11cupBVMpz3CrVfCppv9Rw2xEtU1c6").into_classified(&rules)?;
+    /// assert_eq!(Address::P2pkh, kind);
+    /// assert_eq!(20, payload.len());
+    ///
+    /// assert_eq!(
+    ///     Err(bs58::decode::Error::Unclassified { version: 0x00, payload_len: 20 }),
+    ///     bs58::decode("1F9v11cupBVMpz3CrVfCppv9Rw2xEtU1c6").into_classified(&rules[1..]));
+    /// # Ok::<(), bs58::decode::Error>(())
+    /// ```
+    #[cfg(all(feature = "check", feature = "alloc"))]
+    pub fn into_classified<T: Copy>(self, rules: &[(u8, usize, T)]) -> Result<(T, Vec<u8>)> {
+        let check = match self.check {
+            #[cfg(feature = "cb58")]
+            Check::CB58(_) => Check::CB58(None),
+            _ => Check::Enabled(None),
+        };
+        let mut output = DecodeBuilder { check, ..self }.into_vec()?;
+
+        let (&version, payload) = output.split_first().ok_or(Error::NoChecksum)?;
+        let payload_len = payload.len();
+        let tag = rules
+            .iter()
+            .find(|&&(rule_version, rule_len, _)| {
+                rule_version == version && rule_len == payload_len
+            })
+            .map(|&(_, _, tag)| tag)
+            .ok_or(Error::Unclassified {
+                version,
+                payload_len,
+            })?;
+        output.remove(0);
+        Ok((tag, output))
+    }
+
+    /// Decode into a [`DecodeResult`], so that empty data, data that is all zero bytes, and other
+    /// values are distinct cases rather than all being a (possibly empty) [`Vec`] that may be only
+    /// zeros.
+    ///
+    /// Any checksum is verified and removed first, so this classifies the payload. The data is
+    /// only kept for a [`Value`](DecodeResult::Value).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bs58::decode::DecodeResult;
+    ///
+    /// assert_eq!(DecodeResult::Empty, bs58::decode("").into_decode_result()?);
+    /// assert_eq!(
+    ///     DecodeResult::Zero { leading_ones: 3 },
+    ///     bs58::decode("111").into_decode_result()?);
+    /// assert_eq!(
+    ///     DecodeResult::Value(vec![0x00, 0x01]),
+    ///     bs58::decode("12").into_decode_result()?);
+    /// # Ok::<(), bs58::decode::Error>(())
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn into_decode_result(self) -> Result<DecodeResult> {
+        self.with_scratch(|decoded| match decoded {
+            [] => Ok(DecodeResult::Empty),
+            _ if decoded.iter().all(|&b| b == 0) => Ok(DecodeResult::Zero {
+                leading_ones: decoded.len(),
+            }),
+            _ => Ok(DecodeResult::Value(decoded.to_vec())),
+        })
+    }
+
+    /// Decode and verify the checksum, returning whether it matched without keeping the decoded
+    /// bytes.
+    ///
+    /// A [Base58Check][] checksum without a version is verified unless another checksum has been
+    /// configured. A mismatched checksum gives `Ok(false)`, other problems with the input (such
+    /// as an invalid character or mismatched version) are still errors.
+    ///
+    /// Base58 can't be decoded incrementally, so the whole input is decoded into a temporary
+    /// buffer of the decoded length. It is on the stack for up to 128 decoded bytes, otherwise
+    /// it is allocated for the duration of the call and freed before returning, or without
+    /// `alloc` [`Error::BufferTooSmall`] is returned. Nothing is kept once this returns.
+    ///
+    /// [Base58Check]: https://en.bitcoin.it/wiki/Base58Check_encoding
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// assert!(bs58::decode("QuT57JNzzWTu7mW").verify_check_only()?);
+    /// assert!(!bs58::decode("QuT57JNzzWTu7mX").verify_check_only()?);
+    /// # Ok::<(), bs58::decode::Error>(())
+    /// ```
+    #[cfg(feature = "check")]
+    pub fn verify_check_only(self) -> Result<bool> {
+        let check = match self.check {
+            Check::Disabled => Check::Enabled(None),
+            check => check,
+        };
+        match (DecodeBuilder { check, ..self }).with_scratch(|_| Ok(())) {
+            Ok(()) => Ok(true),
+            Err(Error::InvalidChecksum { .. } | Error::BlockChecksumFailed { .. }) => Ok(false),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Decode into a new [`allocator_api2::vec::Vec`] using the given allocator.
+    ///
+    /// The output is sized using [`reserve_exact_output`](Self::reserve_exact_output), so this
+    /// makes a single allocation close to the final size from `alloc` and nothing is allocated
+    /// from the global allocator.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use allocator_api2::alloc::Global;
+    ///
+    /// let decoded = bs58::decode("he11owor1d").into_vec_in(Global)?;
+    /// assert_eq!(&[0x04, 0x30, 0x5e, 0x2b, 0x24, 0x73, 0xf0, 0x58], &*decoded);
+    /// # Ok::<(), bs58::decode::Error>(())
+    /// ```
+    #[cfg(feature = "allocator-api2")]
+    pub fn into_vec_in<A: allocator_api2::alloc::Allocator>(
+        self,
+        alloc: A,
+    ) -> Result<allocator_api2::vec::Vec<u8, A>> {
+        let mut output = allocator_api2::vec::Vec::new_in(alloc);
+        self.reserve_exact_output().onto(&mut output)?;
+        Ok(output)
+    }
+
+    /// Decode into a new vector of bytes, returning [`Error::AllocationFailed`] instead of
+    /// aborting if the space for it cannot be allocated.
+    ///
+    /// This is the only allocation made while decoding, including when verifying a checksum.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// assert_eq!(
+    ///     vec![0x04, 0x30, 0x5e, 0x2b, 0x24, 0x73, 0xf0, 0x58],
+    ///     bs58::decode("he11owor1d").try_into_vec()?);
+    /// # Ok::<(), bs58::decode::Error>(())
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn try_into_vec(self) -> Result<Vec<u8>> {
+        let mut output = Vec::new();
+        output
+            .try_reserve_exact(self.max_output_len())
+            .map_err(|_| Error::AllocationFailed)?;
+        self.onto(&mut output)?;
+        Ok(output)
+    }
+
+    /// Decode into a plain-old-data value, erroring with [`Error::WrongLength`] unless the decoded
+    /// data is exactly the size of `T`.
+    ///
+    /// The bytes are read in memory order, see [`bs58::encode_pod`](crate::encode_pod) for
+    /// encoding a value.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// assert_eq!([0x00, 0x01, 0x00, 0x02], bs58::decode("1LUy").into_pod::<[u8; 4]>()?);
+    ///
+    /// assert_eq!(
+    ///     bs58::decode::Error::WrongLength { expected: 8, found: 4 },
+    ///     bs58::decode("1LUy").into_pod::<u64>().unwrap_err());
+    /// # Ok::<(), bs58::decode::Error>(())
+    /// ```
+    #[cfg(all(feature = "bytemuck", feature = "alloc"))]
+    pub fn into_pod<T: bytemuck::AnyBitPattern>(self) -> Result<T> {
+        let decoded = self.into_vec()?;
+        if decoded.len() != core::mem::size_of::<T>() {
+            return Err(Error::WrongLength {
+                expected: core::mem::size_of::<T>(),
+                found: decoded.len(),
+            });
+        }
+        Ok(bytemuck::pod_read_unaligned(&decoded))
+    }
+
+    /// Decode into big-endian (network order) 16-bit integers, for data that is semantically an
+    /// array of them, see [`encode::from_u16_be`](crate::encode::from_u16_be).
+    ///
+    /// The decoded bytes are a byte sequence whatever the platform, this reads each pair of them
+    /// as a big-endian `u16` so the values are the same on any platform. Leading zero characters
+    /// decode to leading zero bytes, which are part of the first values. If the decoded length
+    /// is not a multiple of 2 this errors with [`Error::WrongLength`], with the next multiple as
+    /// the expected length.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// assert_eq!(
+    ///     vec![0x0430, 0x5e2b, 0x2473, 0xf058],
+    ///     bs58::decode("he11owor1d").into_u16_be_vec()?);
+    ///
+    /// assert_eq!(
+    ///     Err(bs58::decode::Error::WrongLength { expected: 6, found: 5 }),
+    ///     bs58::decode("EUYUqQf").into_u16_be_vec());
+    /// # Ok::<(), bs58::decode::Error>(())
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn into_u16_be_vec(self) -> Result<Vec<u16>> {
+        self.into_be_vec(u16::from_be_bytes)
+    }
+
+    /// Decode into big-endian (network order) 32-bit integers, for data that is semantically an
+    /// array of them, see [`encode::from_u32_be`](crate::encode::from_u32_be).
+    ///
+    /// As with [`into_u16_be_vec`](Self::into_u16_be_vec) the values are the same on any
+    /// platform, and if the decoded length is not a multiple of 4 this errors with
+    /// [`Error::WrongLength`], with the next multiple as the expected length.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// assert_eq!(
+    ///     vec![0x0430_5e2b, 0x2473_f058],
+    ///     bs58::decode("he11owor1d").into_u32_be_vec()?);
+    /// # Ok::<(), bs58::decode::Error>(())
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn into_u32_be_vec(self) -> Result<Vec<u32>> {
+        self.into_be_vec(u32::from_be_bytes)
+    }
+
+    /// Decode into integers of `N` bytes each, converted with `from_bytes`.
+    #[cfg(feature = "alloc")]
+    fn into_be_vec<T, const N: usize>(self, from_bytes: fn([u8; N]) -> T) -> Result<Vec<T>> {
+        let decoded = self.into_vec()?;
+        if decoded.len() % N != 0 {
+            return Err(Error::WrongLength {
+                expected: decoded.len().next_multiple_of(N),
+                found: decoded.len(),
+            });
+        }
+        Ok(decoded
+            .chunks_exact(N)
+            .map(|chunk| from_bytes(chunk.try_into().unwrap()))
+            .collect())
+    }
+
+    /// Decode as a fixed-point number whose last `frac_digits` Base58 digits are fractional,
+    /// see [`FixedPoint::into_fixed`].
+    ///
+    /// # Panics
+    ///
+    /// If `frac_digits` is larger than [`u32::MAX`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// // 3 + 57/58
+    /// assert_eq!((3 * 58 + 57, 1), bs58::decode("4z").fixed_point(1).into_fixed()?);
+    /// # Ok::<(), bs58::decode::Error>(())
+    /// ```
+    pub fn fixed_point(self, frac_digits: usize) -> FixedPoint<'a, I> {
+        let frac_digits = u32::try_from(frac_digits).expect("too many fractional digits");
+        FixedPoint {
+            builder: self,
+            frac_digits,
+        }
+    }
+
+    /// Decode into a new heap-allocated array, erroring with [`Error::WrongLength`] unless the
+    /// decoded data is exactly `N` bytes long.
+    ///
+    /// The array is decoded into in place on the heap, so large keys are never copied through the
+    /// stack.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let key: Box<[u8; 5]> = bs58::decode("EUYUqQf").into_boxed_array()?;
+    /// assert_eq!(b"world", &*key);
+    ///
+    /// assert_eq!(
+    ///     bs58::decode::Error::WrongLength { expected: 4, found: 5 },
+    ///     bs58::decode("EUYUqQf").into_boxed_array::<4>().unwrap_err());
+    /// # Ok::<(), bs58::decode::Error>(())
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn into_boxed_array<const N: usize>(self) -> Result<alloc::boxed::Box<[u8; N]>> {
+        let mut output: alloc::boxed::Box<[u8; N]> = alloc::vec![0; N]
+            .into_boxed_slice()
+            .try_into()
+            .expect("boxed slice has length N");
+        self.onto_exact(&mut output[..])?;
+        Ok(output)
+    }
+
+    /// Decode into a [`GenericArray`](generic_array::GenericArray), erroring with
+    /// [`Error::WrongLength`] unless the decoded data is exactly `N` bytes long, for passing to
+    /// crates such as `digest` and `aead` that take their keys and nonces as one.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use generic_array::{typenum::U5, GenericArray};
+    ///
+    /// let key: GenericArray<u8, U5> = bs58::decode("EUYUqQf").into_generic_array()?;
+    /// assert_eq!(b"world", key.as_slice());
+    /// # Ok::<(), bs58::decode::Error>(())
+    /// ```
+    #[cfg(feature = "generic-array")]
+    pub fn into_generic_array<N: generic_array::ArrayLength<u8>>(
+        self,
+    ) -> Result<generic_array::GenericArray<u8, N>> {
+        let mut output = generic_array::GenericArray::default();
+        self.onto_exact(&mut output)?;
+        Ok(output)
+    }
+
+    /// Decode into a new vector of bytes, on failure also returning what could be decoded.
+    ///
+    /// Base58 decoding is not incremental (each character can affect every decoded byte) so the
+    /// partial result is not a prefix of what the full result would have been. Instead it is the
+    /// decoding of the longest valid prefix of the input: for an invalid or non-ASCII character
+    /// the input up to (not including) that character, for other errors (such as an invalid
+    /// checksum) the whole input. The partial result is a plain decode without any checksum
+    /// verification or removal.
+    ///
+    /// When decoding [right-to-left](Self::rtl) the valid "prefix" is the input after the
+    /// invalid character, as that is the order the characters are read in.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// assert_eq!(
+    ///     Err((
+    ///         vec![0x04, 0x30, 0x5e, 0x2b, 0x24, 0x73, 0xf0, 0x58],
+    ///         bs58::decode::Error::InvalidCharacter { character: '0', index: 10 },
+    ///     )),
+    ///     bs58::decode("he11owor1d0rld").into_vec_best_effort());
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn into_vec_best_effort(self) -> core::result::Result<Vec<u8>, (Vec<u8>, Error)> {
+        let err = match self.as_slice_builder().into_vec() {
+            Ok(output) => return Ok(output),
+            Err(err) => err,
+        };
+
+        let Ok(input) = self.unprefixed_input() else {
+            return Err((Vec::new(), err));
+        };
+        let valid = match err {
+            Error::InvalidCharacter { index, .. }
+            | Error::NonAsciiCharacter { index }
+            | Error::AmbiguousFold { index, .. } => {
+                let index = index - self.text_prefix_len();
+                if self.rtl {
+                    &input[char_end(input, index)..]
+                } else {
+                    &input[..index]
+                }
+            }
+            _ => input,
+        };
+
+        // The partial result is output like the full result would have been, so is mapped too
+        let partial = DecodeBuilder {
+            map: self.map,
+            ..self.plain(valid)
+        }
+        .into_vec()
+        .unwrap_or_default();
+
+        Err((partial, err))
+    }
+
+    /// Decode into a new vector of bytes, skipping characters that aren't part of the alphabet
+    /// and returning an error for each of them, for recovering what can be from corrupted input.
+    ///
+    /// The skipped characters are reported as [`Error::InvalidCharacter`],
+    /// [`Error::NonAsciiCharacter`] or [`Error::AmbiguousFold`] with their index in the original
+    /// input, in the order they are read. At most `max_errors` are collected: if there are more,
+    /// the first one over the budget is returned as the error instead. Other errors, such as an
+    /// invalid checksum of what remains, are also returned as the error.
+    ///
+    /// Skipping a character changes every decoded byte, so the output is only meaningful if the
+    /// invalid characters were inserted into otherwise valid input. The input is decoded again
+    /// after each skipped character, so this takes up to `max_errors + 1` times as long as
+    /// [`into_vec`](Self::into_vec).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bs58::decode::Error;
+    ///
+    /// let (decoded, errors) = bs58::decode("he11o0wor1ld").lenient_collect(2)?;
+    /// assert_eq!(vec![0x04, 0x30, 0x5e, 0x2b, 0x24, 0x73, 0xf0, 0x58], decoded);
+    /// assert_eq!(
+    ///     vec![
+    ///         Error::InvalidCharacter { character: '0', index: 5 },
+    ///         Error::InvalidCharacter { character: 'l', index: 10 },
+    ///     ],
+    ///     errors);
+    ///
+    /// assert_eq!(
+    ///     Err(Error::InvalidCharacter { character: 'l', index: 10 }),
+    ///     bs58::decode("he11o0wor1ld").lenient_collect(1));
+    /// # Ok::<(), bs58::decode::Error>(())
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn lenient_collect(self, max_errors: usize) -> Result<(Vec<u8>, Vec<Error>)> {
+        let mut input = self.input.as_ref().to_vec();
+        // The index in the original input of each byte that is left
+        let mut positions: Vec<usize> = (0..input.len()).collect();
+        let mut errors = Vec::new();
+        loop {
+            // This is the full decode with the skipped characters removed, so keeps every option
+            let err = match (DecodeBuilder {
+                input: &input[..],
+                ..self.as_slice_builder()
+            })
+            .into_vec()
+            {
+                Ok(output) => return Ok((output, errors)),
+                Err(err) => err,
+            };
+            let is_continuation = |b: u8| b & 0xc0 == 0x80;
+            let (start, end, err) = match err {
+                Error::InvalidCharacter { character, index } => (
+                    index,
+                    index + 1,
+                    Error::InvalidCharacter {
+                        character,
+                        index: positions[index],
+                    },
+                ),
+                Error::AmbiguousFold { character, index } => (
+                    index,
+                    index + 1,
+                    Error::AmbiguousFold {
+                        character,
+                        index: positions[index],
+                    },
+                ),
+                // Skip the whole of a multi-byte character, which when decoding right-to-left
+                // is found from its last byte
+                Error::NonAsciiCharacter { index } => {
+                    let mut start = index;
+                    while start > 0 && index - start < 3 && is_continuation(input[start]) {
+                        start -= 1;
+                    }
+                    if input[start] < 0xc0 {
+                        start = index;
+                    }
+                    let mut end = start + 1;
+                    while end < input.len() && end - start < 4 && is_continuation(input[end]) {
+                        end += 1;
+                    }
+                    let index = positions[start];
+                    (start, end, Error::NonAsciiCharacter { index })
+                }
+                err => return Err(err),
+            };
+            if errors.len() == max_errors {
+                return Err(err);
+            }
+            errors.push(err);
+            input.drain(start..end);
+            positions.drain(start..end);
+        }
+    }
+
+    /// Decode into the given sink.
+    ///
+    /// All of the input must be decoded before the first byte is known, so this first decodes
+    /// into a temporary buffer. When the decoded data could be longer than 128 bytes this buffer
+    /// is allocated, without the `alloc` feature [`Error::BufferTooSmall`] is returned instead. If
+    /// the sink becomes full [`Error::BufferTooSmall`] is returned, after the bytes that fit have
+    /// been pushed.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let mut output = [0xff; 10];
+    /// let mut sink = &mut output[..];
+    /// bs58::decode("he11owor1d").into_sink(&mut sink)?;
+    /// assert_eq!(2, sink.len());
+    /// assert_eq!([0x04, 0x30, 0x5e, 0x2b, 0x24, 0x73, 0xf0, 0x58, 0xff, 0xff], output);
+    /// # Ok::<(), bs58::decode::Error>(())
+    /// ```
+    pub fn into_sink<S: Sink>(self, sink: &mut S) -> Result<()> {
+        self.with_scratch(|decoded| {
+            for &b in decoded {
+                sink.push_byte(b).map_err(|()| Error::BufferTooSmall)?;
+            }
+            Ok(())
+        })
+    }
+
+    /// Decode and extend the given collection with the decoded bytes, returning the number of
+    /// bytes it was extended with.
+    ///
+    /// This works with any [`Extend<u8>`] collection, such as `VecDeque`. As with
+    /// [`into_sink`](Self::into_sink) the input is first decoded into a temporary buffer, which is
+    /// allocated when the decoded data could be longer than 128 bytes, without the `alloc` feature
+    /// [`Error::BufferTooSmall`] is returned instead. The collection is only extended if decoding
+    /// succeeds.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let mut output = std::collections::VecDeque::from([0xff]);
+    /// assert_eq!(8, bs58::decode("he11owor1d").extend_into(&mut output)?);
+    /// assert_eq!([0xff, 0x04, 0x30, 0x5e, 0x2b, 0x24, 0x73, 0xf0, 0x58], *output.make_contiguous());
+    /// # Ok::<(), bs58::decode::Error>(())
+    /// ```
+    pub fn extend_into<E: Extend<u8>>(self, target: &mut E) -> Result<usize> {
+        self.with_scratch(|decoded| {
+            target.extend(decoded.iter().copied());
+            Ok(decoded.len())
+        })
+    }
+
+    /// Decode then parse the decoded bytes with `parse`, erroring with [`Error::ParseFailed`] if
+    /// it returns `None`.
+    ///
+    /// As with [`into_sink`](Self::into_sink) the input is decoded into a temporary buffer, which
+    /// is allocated when the decoded data could be longer than 128 bytes, without the `alloc`
+    /// feature [`Error::BufferTooSmall`] is returned instead.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let parse = |bytes: &[u8]| Some(u32::from_be_bytes(bytes.try_into().ok()?));
+    ///
+    /// assert_eq!(Ok(0x0001_0002), bs58::decode("1LUy").into_struct(parse));
+    /// assert_eq!(
+    ///     Err(bs58::decode::Error::ParseFailed),
+    ///     bs58::decode("he11owor1d").into_struct(parse));
+    /// ```
+    pub fn into_struct<T>(self, parse: impl FnOnce(&[u8]) -> Option<T>) -> Result<T> {
+        self.with_scratch(|decoded| parse(decoded).ok_or(Error::ParseFailed))
+    }
+
+    /// Decode into `buf` starting at `offset`, returning the number of bytes written.
+    ///
+    /// Before decoding this checks there is space after `offset` for the longest data the input
+    /// could decode to, returning [`Error::BufferTooSmall`] if there isn't. Only the bytes from
+    /// `offset` up to the returned length are written, everything around them is left untouched,
+    /// and if decoding fails `buf` is not modified at all. To guarantee this the input is first
+    /// decoded into a temporary buffer as with [`into_sink`](Self::into_sink), which is allocated
+    /// when the decoded data could be longer than 128 bytes, without the `alloc` feature
+    /// [`Error::BufferTooSmall`] is returned instead.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let mut frame = [0xff; 12];
+    /// assert_eq!(8, bs58::decode("he11owor1d").into_at(&mut frame, 2)?);
+    /// assert_eq!(
+    ///     [0xff, 0xff, 0x04, 0x30, 0x5e, 0x2b, 0x24, 0x73, 0xf0, 0x58, 0xff, 0xff],
+    ///     frame);
+    ///
+    /// assert_eq!(
+    ///     Err(bs58::decode::Error::BufferTooSmall),
+    ///     bs58::decode("he11owor1d").into_at(&mut frame, 5));
+    /// # Ok::<(), bs58::decode::Error>(())
+    /// ```
+    pub fn into_at(self, buf: &mut [u8], offset: usize) -> Result<usize> {
+        // Without an expected length block checksums aren't subtracted, so this may overestimate
+        let max_len = match self.expected_len {
+            Some(expected) => expected,
+            None => self
+                .max_decoded_len()
+                .saturating_sub(check_overhead(&self.check, 0)),
+        };
+        let output = buf
+            .get_mut(offset..)
+            .filter(|output| output.len() >= max_len)
+            .ok_or(Error::BufferTooSmall)?;
+        self.with_scratch(|decoded| {
+            output[..decoded.len()].copy_from_slice(decoded);
+            Ok(decoded.len())
+        })
+    }
+
+    /// Decode across `segments` in order, returning the total length written.
+    ///
+    /// The decoded bytes fill each segment before moving on to the next, so a segment boundary
+    /// can fall anywhere in the output, including within leading zeros. Bytes after the decoded
+    /// length are left untouched, and if the segments can't hold the whole output
+    /// [`Error::BufferTooSmall`] is returned without writing anything.
+    ///
+    /// The conversion needs contiguous space, so this decodes into a temporary buffer then
+    /// copies it into the segments. The buffer is on the stack for up to 128 decoded bytes and
+    /// allocated for longer outputs, or without `alloc` [`Error::BufferTooSmall`] is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let (mut first, mut second) = ([0xff; 3], [0xff; 8]);
+    /// assert_eq!(8, bs58::decode("he11owor1d").into_segments(&mut [&mut first, &mut second])?);
+    /// assert_eq!([0x04, 0x30, 0x5e], first);
+    /// assert_eq!([0x2b, 0x24, 0x73, 0xf0, 0x58, 0xff, 0xff, 0xff], second);
+    /// # Ok::<(), bs58::decode::Error>(())
+    /// ```
+    pub fn into_segments(self, segments: &mut [&mut [u8]]) -> Result<usize> {
+        self.with_scratch(|decoded| {
+            if crate::scatter(decoded, segments) {
+                Ok(decoded.len())
+            } else {
+                Err(Error::BufferTooSmall)
+            }
+        })
+    }
+
+    /// Decode for `target` without writing to it yet, returning a [`Commit`] that writes the
+    /// decoded bytes to it in a single copy when [`commit`](Commit::commit) is called.
+    ///
+    /// This is for targets that another thread or process may read at any time, such as shared
+    /// memory, which must never see a partly decoded value. All of the conversion is done in a
+    /// private buffer, and if decoding fails (including when the output wouldn't fit in
+    /// `target`, exactly as with [`onto`](Self::onto)) nothing is written at all. Bytes of
+    /// `target` after the decoded length are never written.
+    ///
+    /// The buffer is held in the [`Commit`] for up to 128 bytes, otherwise it is allocated, or
+    /// without `alloc` [`Error::BufferTooSmall`] is returned; see
+    /// [`into_deferred_with_scratch`](Self::into_deferred_with_scratch) to provide it instead.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let mut target = [0xff; 10];
+    /// let commit = bs58::decode("he11owor1d").into_deferred(&mut target)?;
+    /// assert_eq!(8, commit.len());
+    /// assert_eq!(8, commit.commit());
+    /// assert_eq!([0x04, 0x30, 0x5e, 0x2b, 0x24, 0x73, 0xf0, 0x58, 0xff, 0xff], target);
+    ///
+    /// let mut target = [0xff; 10];
+    /// assert!(bs58::decode("he11o0").into_deferred(&mut target).is_err());
+    /// assert_eq!([0xff; 10], target);
+    /// # Ok::<(), bs58::decode::Error>(())
+    /// ```
+    pub fn into_deferred(self, target: &mut [u8]) -> Result<Commit<'_>> {
+        let max_len = self.max_decoded_len().min(target.len());
+        let decoded = if max_len <= SCRATCH_STACK_LEN {
+            let mut scratch = [0; SCRATCH_STACK_LEN];
+            let len = self.onto(&mut scratch[..max_len])?;
+            Deferred::Stack(scratch, len)
+        } else {
+            #[cfg(feature = "alloc")]
+            {
+                let mut scratch = alloc::vec![0; max_len];
+                let len = self.onto(&mut scratch[..])?;
+                scratch.truncate(len);
+                Deferred::Heap(scratch)
+            }
+            #[cfg(not(feature = "alloc"))]
+            return Err(Error::BufferTooSmall);
+        };
+        Ok(Commit { target, decoded })
+    }
+
+    /// Decode for `target` using `scratch` as the private buffer, as with
+    /// [`into_deferred`](Self::into_deferred) but never allocating.
+    ///
+    /// Returns [`Error::BufferTooSmall`] if the decoded data could be too long for either
+    /// `scratch` or `target`. Any of `scratch` may be overwritten, even when decoding fails.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let (mut target, mut scratch) = ([0xff; 8], [0; 8]);
+    /// let commit = bs58::decode("he11owor1d")
+    ///     .into_deferred_with_scratch(&mut target, &mut scratch)?;
+    /// assert_eq!(8, commit.commit());
+    /// assert_eq!([0x04, 0x30, 0x5e, 0x2b, 0x24, 0x73, 0xf0, 0x58], target);
+    /// # Ok::<(), bs58::decode::Error>(())
+    /// ```
+    pub fn into_deferred_with_scratch<'t>(
+        self,
+        target: &'t mut [u8],
+        scratch: &'t mut [u8],
+    ) -> Result<Commit<'t>> {
+        let max_len = target.len().min(scratch.len());
+        let len = self.onto(&mut scratch[..max_len])?;
+        let decoded = Deferred::Scratch(&scratch[..len]);
+        Ok(Commit { target, decoded })
+    }
+
+    /// Decode into the given buffer.
+    ///
+    /// Returns the length written into the buffer.
     ///
     /// If the buffer is resizeable it will be extended and the new data will be written to the end
     /// of it.
@@ -308,6 +2528,11 @@ impl<'a, I: AsRef<[u8]>> DecodeBuilder<'a, I> {
     /// If the buffer is not resizeable bytes will be written from the beginning and bytes after
     /// the final encoded byte will not be touched.
     ///
+    /// The conversion is done in place in the buffer, so the stack used is independent of the
+    /// input size. Decoding into a buffer that is not resizeable never allocates, and with
+    /// optimizations enabled uses under 1 KiB of stack, including the SHA-256 state when
+    /// verifying a checksum (unoptimized builds use several times more).
+    ///
     /// See the documentation for [`bs58::decode`](crate::decode()) for an
     /// explanation of the errors that may occur.
     ///
@@ -330,22 +2555,1101 @@ impl<'a, I: AsRef<[u8]>> DecodeBuilder<'a, I> {
     /// assert_eq!(b"world ", output.as_ref());
     /// # Ok::<(), bs58::decode::Error>(())
     /// ```
-    pub fn onto(self, mut output: impl DecodeTarget) -> Result<usize> {
-        let max_decoded_len = self.input.as_ref().len();
-        match self.check {
-            Check::Disabled => output.decode_with(max_decoded_len, |output| {
-                decode_into(self.input.as_ref(), output, self.alpha)
-            }),
+    pub fn onto(self, output: impl DecodeTarget) -> Result<usize> {
+        self.onto_reporting_alphabet(output).map(|(len, _)| len)
+    }
+
+    /// Decode into the start of `buf` as with [`onto`](Self::onto), returning the number of bytes
+    /// written and the number of bytes left unused after them, for packing more data into the
+    /// same buffer.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let mut buf = [0; 12];
+    /// let (written, remaining) = bs58::decode("he11owor1d").into_with_remaining(&mut buf)?;
+    /// assert_eq!((8, 4), (written, remaining));
+    /// assert_eq!([0x04, 0x30, 0x5e, 0x2b, 0x24, 0x73, 0xf0, 0x58], buf[..written]);
+    /// # Ok::<(), bs58::decode::Error>(())
+    /// ```
+    pub fn into_with_remaining(self, buf: &mut [u8]) -> Result<(usize, usize)> {
+        let written = self.onto(&mut *buf)?;
+        Ok((written, buf.len() - written))
+    }
+
+    /// Decode into the given buffer as with [`onto`](Self::onto), also returning the alphabet
+    /// that the input was decoded with, see [`also_accept_alphabet`](Self::also_accept_alphabet).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let mut output = Vec::new();
+    /// let (len, alpha) = bs58::decode("111z")
+    ///     .with_alphabet(bs58::Alphabet::RIPPLE)
+    ///     .also_accept_alphabet(bs58::Alphabet::BITCOIN)
+    ///     .with_expected_len(4)
+    ///     .onto_reporting_alphabet(&mut output)?;
+    /// assert_eq!(4, len);
+    /// assert_eq!(bs58::Alphabet::BITCOIN, alpha);
+    /// # Ok::<(), bs58::decode::Error>(())
+    /// ```
+    pub fn onto_reporting_alphabet(
+        self,
+        mut output: impl DecodeTarget,
+    ) -> Result<(usize, &'a Alphabet)> {
+        let max_decoded_len = self.max_output_len();
+        let input = self.unprefixed_input()?;
+
+        if let Some(expected) = self.expected_len {
+            let overhead = check_overhead(&self.check, expected);
+            let max_len = self.max_input_decoded_len(input);
+            if max_len < expected + overhead {
+                return Err(Error::WrongLength {
+                    expected,
+                    found: max_len.saturating_sub(overhead),
+                });
+            }
+        }
+
+        let mut alpha = self.alpha;
+        let len = output.decode_with(max_decoded_len, |output| {
+            #[cfg(feature = "metrics")]
+            if let Some(stats) = self.stats {
+                let mut record = stats.get();
+                let result = self.decode_accepted(input, output, &mut alpha, &mut record);
+                record.output_len = *result.as_ref().unwrap_or(&0);
+                stats.set(record);
+                return result;
+            }
+            self.decode_accepted(input, output, &mut alpha, &mut ())
+        })?;
+        Ok((len, alpha))
+    }
+}
+
+impl<'a, I: AsRef<[u8]>> DecodeBuilder<'a, I> {
+    /// A copy of this builder borrowing the input.
+    #[cfg(feature = "alloc")]
+    fn as_slice_builder(&self) -> DecodeBuilder<'a, &[u8]> {
+        DecodeBuilder {
+            input: self.input.as_ref(),
+            alpha: self.alpha,
+            check: self.check,
+            reserve_exact: self.reserve_exact,
+            rtl: self.rtl,
+            nonzero: self.nonzero,
+            nonempty: self.nonempty,
+            prefix: self.prefix,
+            hrp_separator: self.hrp_separator,
+            canonical: self.canonical,
+            expected_len: self.expected_len,
+            allowed_lengths: self.allowed_lengths,
+            salt: self.salt,
+            ordered: self.ordered,
             #[cfg(feature = "check")]
-            Check::Enabled(expected_ver) => output.decode_with(max_decoded_len, |output| {
-                decode_check_into(self.input.as_ref(), output, self.alpha, expected_ver)
-            }),
+            aad: self.aad,
+            map: self.map,
+            fallback: self.fallback,
+            fold: self.fold,
+            #[cfg(feature = "metrics")]
+            stats: self.stats,
+        }
+    }
+
+    /// A builder decoding `input`, which has no text prefix, with the alphabet of this builder and
+    /// the options for how characters are read ([`rtl`](Self::rtl) and
+    /// [`display_fold`](Self::display_fold)), but none of the options checking or changing the
+    /// decoded data, for decoding parts of the input.
+    ///
+    /// Every field is listed so each new option has to be sorted into one or the other here.
+    #[cfg(feature = "alloc")]
+    fn plain<'i>(&self, input: &'i [u8]) -> DecodeBuilder<'a, &'i [u8]> {
+        DecodeBuilder {
+            input,
+            alpha: self.alpha,
+            check: Check::Disabled,
+            reserve_exact: self.reserve_exact,
+            rtl: self.rtl,
+            nonzero: false,
+            nonempty: false,
+            prefix: "",
+            hrp_separator: None,
+            canonical: None,
+            expected_len: None,
+            allowed_lengths: None,
+            salt: &[],
+            ordered: None,
+            #[cfg(feature = "check")]
+            aad: &[],
+            map: None,
+            fallback: None,
+            fold: self.fold,
+            #[cfg(feature = "metrics")]
+            stats: None,
+        }
+    }
+
+    /// Decode `input`, without the text prefix, into `output` using the alphabet, or if that fails
+    /// the fallback alphabet, setting `alpha` to the fallback if it was used.
+    fn decode_accepted(
+        &self,
+        input: &[u8],
+        output: &mut [u8],
+        alpha: &mut &'a Alphabet,
+        record: &mut impl Record,
+    ) -> Result<usize> {
+        let unfold = self.fold.as_ref();
+        match (
+            self.decode_slice(input, self.alpha, unfold.map(|u| &u.table), output, record),
+            self.fallback,
+        ) {
+            (Err(err), Some(fallback)) if err != Error::BufferTooSmall => {
+                *alpha = fallback;
+                let unfold = unfold.map(|u| &u.fallback);
+                self.decode_slice(input, fallback, unfold, output, record)
+                    .map_err(|_| err)
+            }
+            (result, _) => result,
+        }
+    }
+
+    /// Decode `input`, without the text prefix, into `output` using `alpha`, reading it through
+    /// `unfold`, the table reversing the display fold for `alpha`, if there is one.
+    fn decode_slice(
+        &self,
+        input: &[u8],
+        alpha: &Alphabet,
+        unfold: Option<&[u8; 128]>,
+        output: &mut [u8],
+        record: &mut impl Record,
+    ) -> Result<usize> {
+        // The padding of order-preserving input is only zeros, so skip it rather than needing
+        // room for it in the output
+        let padding = match self.ordered {
+            Some(_) if !self.rtl => leading_zeros(input, alpha, unfold),
+            _ => 0,
+        };
+        let input = &input[padding..];
+        let overhead = check_overhead(&self.check, self.expected_len.unwrap_or_default());
+        // Limit the output so decoding stops as soon as it is longer than expected, once
+        // there is room for the expected length any overflow means the data is too long
+        let (output, too_long) = match self.expected_len {
+            Some(expected) if output.len() >= expected + overhead => (
+                &mut output[..expected + overhead],
+                Error::WrongLength {
+                    expected,
+                    found: expected + 1,
+                },
+            ),
+            _ => (output, Error::BufferTooSmall),
+        };
+        let offset = |err: Error| match err {
+            Error::BufferTooSmall => too_long,
+            // Right-to-left the last byte of a non-ASCII character is read first
+            Error::NonAsciiCharacter { index } if self.rtl => Error::NonAsciiCharacter {
+                index: char_start(input, index),
+            }
+            .offset_index(self.text_prefix_len()),
+            err => err.offset_index(self.text_prefix_len() + padding),
+        };
+        let decoded_len = match unfold {
+            None if self.rtl => decode_into(input.iter().enumerate().rev(), output, alpha, record)
+                .map_err(offset)?,
+            None => decode_into(input.iter().enumerate(), output, alpha, record).map_err(offset)?,
+            Some(table) => {
+                let unfolded = input
+                    .iter()
+                    .map(|c| table.get(usize::from(*c)).unwrap_or(c));
+                let decoded = if self.rtl {
+                    decode_into(unfolded.enumerate().rev(), output, alpha, record)
+                } else {
+                    decode_into(unfolded.enumerate(), output, alpha, record)
+                };
+                // Ambiguous characters are unfolded to a non-ASCII byte, so rejected as one
+                decoded
+                    .map_err(|err| match err {
+                        Error::NonAsciiCharacter { index } if input[index].is_ascii() => {
+                            Error::AmbiguousFold {
+                                character: input[index] as char,
+                                index,
+                            }
+                        }
+                        err => err,
+                    })
+                    .map_err(offset)?
+            }
+        };
+        let decoded_len = match self.ordered {
+            Some(max_bytes) => unpad_ordered(output, decoded_len, max_bytes)?,
+            None => decoded_len,
+        };
+        if let Some(expected) = self.expected_len {
+            if decoded_len != expected + overhead {
+                return Err(Error::WrongLength {
+                    expected,
+                    found: decoded_len.saturating_sub(overhead),
+                });
+            }
+        }
+        check_canonical(self.canonical, &self.check, &output[..decoded_len])?;
+        let len = verify(&self.check, self.aad(), &mut output[..decoded_len])?;
+        if let Some(allowed) = self
+            .allowed_lengths
+            .filter(|allowed| !allowed.contains(&len))
+        {
+            return Err(Error::DisallowedLength {
+                allowed,
+                found: len,
+            });
+        }
+        if self.nonempty && len == 0 {
+            return Err(Error::EmptyPayload);
+        }
+        if self.nonzero && output[..len].iter().all(|&b| b == 0) {
+            return Err(Error::ZeroValue);
+        }
+        let len = self.remove_salt(&mut output[..len])?;
+        if let Some(map) = self.map {
+            output[..len].iter_mut().for_each(|b| *b = map(*b));
+        }
+        Ok(len)
+    }
+
+    /// Decode into a temporary buffer and pass the decoded bytes to `f`.
+    ///
+    /// The buffer is on the stack when the decoded data must fit in 128 bytes, otherwise it is
+    /// allocated, or without `alloc` [`Error::BufferTooSmall`] is returned.
+    fn with_scratch<R>(self, f: impl FnOnce(&[u8]) -> Result<R>) -> Result<R> {
+        let max_len = self.max_decoded_len();
+
+        let decode = |scratch: &mut [u8]| {
+            let len = self.onto(&mut *scratch)?;
+            f(&scratch[..len])
+        };
+
+        if max_len <= SCRATCH_STACK_LEN {
+            decode(&mut [0; SCRATCH_STACK_LEN][..max_len])
+        } else {
+            #[cfg(feature = "alloc")]
+            return decode(&mut alloc::vec![0; max_len]);
+            #[cfg(not(feature = "alloc"))]
+            return Err(Error::BufferTooSmall);
+        }
+    }
+
+    /// An upper bound on the decoded length, including any checksum.
+    fn max_decoded_len(&self) -> usize {
+        let len = match self.expected_len {
+            Some(expected) => expected + check_overhead(&self.check, expected),
+            None => self.max_input_decoded_len(self.input.as_ref()),
+        };
+        len.max(self.ordered.unwrap_or_default())
+    }
+
+    /// An upper bound on the length `input` decodes to with any of the accepted alphabets.
+    fn max_input_decoded_len(&self, input: &[u8]) -> usize {
+        // The estimate only accounts for leading zeros in left-to-right order
+        if self.rtl {
+            input.len()
+        } else {
+            let unfold = self.fold.as_ref();
+            let fallback = self.fallback.map_or(0, |alpha| {
+                estimate_decoded_len(input, alpha, unfold.map(|u| &u.fallback))
+            });
+            estimate_decoded_len(input, self.alpha, unfold.map(|u| &u.table)).max(fallback)
+        }
+    }
+
+    /// The input after the [text prefix](Self::with_text_prefix) or [HRP](Self::with_hrp), or
+    /// [`Error::WrongPrefix`] if it doesn't start with it.
+    fn unprefixed_input(&self) -> Result<&[u8]> {
+        self.strip_text_prefix(self.input.as_ref())
+            .ok_or(Error::WrongPrefix)
+    }
+
+    /// `input` after the text prefix and any HRP separator, if it starts with them.
+    fn strip_text_prefix<'i>(&self, input: &'i [u8]) -> Option<&'i [u8]> {
+        let rest = input.strip_prefix(self.prefix.as_bytes())?;
+        match self.hrp_separator {
+            Some(c) => rest.strip_prefix(c.encode_utf8(&mut [0; 4]).as_bytes()),
+            None => Some(rest),
+        }
+    }
+
+    /// The associated data the checksum is calculated over, see
+    /// [`with_check_aad`](Self::with_check_aad).
+    fn aad(&self) -> &'a [u8] {
+        #[cfg(feature = "check")]
+        return self.aad;
+        #[cfg(not(feature = "check"))]
+        return &[];
+    }
+
+    /// Check for and remove the [salt](Self::strip_salt) from the start of the decoded `output`,
+    /// or after the version byte if one is kept there, returning the remaining length.
+    fn remove_salt(&self, output: &mut [u8]) -> Result<usize> {
+        let start = match self.check {
+            #[cfg(feature = "check")]
+            Check::Enabled(Some(_)) => 1,
             #[cfg(feature = "cb58")]
-            Check::CB58(expected_ver) => output.decode_with(max_decoded_len, |output| {
-                decode_cb58_into(self.input.as_ref(), output, self.alpha, expected_ver)
+            Check::CB58(Some(_)) => 1,
+            _ => 0,
+        };
+        if !output[start..].starts_with(self.salt) {
+            return Err(Error::WrongSalt);
+        }
+        output.copy_within(start + self.salt.len().., start);
+        Ok(output.len() - self.salt.len())
+    }
+
+    /// The length of the text prefix and HRP separator before the encoded characters.
+    fn text_prefix_len(&self) -> usize {
+        self.prefix.len() + self.hrp_separator.map_or(0, char::len_utf8)
+    }
+
+    /// The space to reserve in the output for decoding.
+    fn max_output_len(&self) -> usize {
+        let len = if let Some(expected) = self.expected_len {
+            expected + check_overhead(&self.check, expected)
+        } else if self.reserve_exact {
+            self.max_input_decoded_len(self.input.as_ref())
+        } else {
+            self.input.as_ref().len()
+        };
+        len.max(self.ordered.unwrap_or_default())
+    }
+
+    /// Decode into an array, erroring with [`Error::WrongLength`] unless the decoded data is
+    /// exactly `N` bytes long.
+    #[cfg(feature = "uuid")]
+    pub(crate) fn into_exact_array<const N: usize>(self) -> Result<[u8; N]> {
+        let mut output = [0; N];
+        self.onto_exact(&mut output)?;
+        Ok(output)
+    }
+
+    /// Decode into `output`, erroring with [`Error::WrongLength`] unless the decoded data is
+    /// exactly as long as it.
+    #[cfg(any(feature = "uuid", feature = "alloc", feature = "generic-array"))]
+    fn onto_exact(self, output: &mut [u8]) -> Result<()> {
+        let expected = output.len();
+        match self.onto(output) {
+            Ok(len) if len == expected => Ok(()),
+            Ok(len) => Err(Error::WrongLength {
+                expected,
+                found: len,
             }),
+            Err(Error::BufferTooSmall) => Err(Error::WrongLength {
+                expected,
+                found: expected + 1,
+            }),
+            Err(err) => Err(err),
+        }
+    }
+}
+
+impl<'b> Delimited<'static, 'b> {
+    /// Setup decoder for each field of the input using the default prepared alphabet.
+    pub(crate) fn from_input(input: &'b [u8], delimiter: &'b [u8]) -> Self {
+        Delimited {
+            input,
+            delimiter,
+            alpha: Alphabet::DEFAULT,
+            check: Check::Disabled,
+            prefix: "",
+            reject_empty: false,
+            field: 0,
+            start: Some(0),
+        }
+    }
+}
+
+impl<'a, 'b> Delimited<'a, 'b> {
+    /// Change the alphabet that will be used for decoding each field.
+    pub fn with_alphabet(self, alpha: &'a Alphabet) -> Self {
+        Delimited { alpha, ..self }
+    }
+
+    /// Expect and check checksum using the [Base58Check][] algorithm on each field, see
+    /// [`DecodeBuilder::with_check`].
+    ///
+    /// [Base58Check]: https://en.bitcoin.it/wiki/Base58Check_encoding
+    #[cfg(feature = "check")]
+    pub fn with_check(self, expected_ver: Option<u8>) -> Self {
+        let check = Check::Enabled(expected_ver);
+        Delimited { check, ..self }
+    }
+
+    /// Expect and check checksum using the [CB58][] algorithm on each field, see
+    /// [`DecodeBuilder::as_cb58`].
+    ///
+    /// [CB58]: https://support.avax.network/en/articles/4587395-what-is-cb58
+    #[cfg(feature = "cb58")]
+    pub fn as_cb58(self, expected_ver: Option<u8>) -> Self {
+        let check = Check::CB58(expected_ver);
+        Delimited { check, ..self }
+    }
+
+    /// Error with [`Error::EmptyField`] for empty fields, rather than decoding them as empty byte
+    /// strings.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let mut fields = bs58::decode_delimited("he11owor1d,", b',').reject_empty();
+    /// assert!(fields.next().unwrap().is_ok());
+    /// assert_eq!(
+    ///     bs58::decode::Error::EmptyField,
+    ///     fields.next().unwrap().unwrap_err().error);
+    /// assert!(fields.next().is_none());
+    /// ```
+    pub fn reject_empty(self) -> Self {
+        Delimited {
+            reject_empty: true,
+            ..self
+        }
+    }
+
+    /// Decode the next field into the given buffer, returning the length written into it or
+    /// `None` if every field has been decoded.
+    ///
+    /// This behaves the same as [`DecodeBuilder::onto`] for each field, so can be used to decode
+    /// without allocating.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let mut fields = bs58::decode_delimited("EUYUqQf:he11owor1d", b':');
+    /// let mut output = [0; 8];
+    /// assert_eq!(Some(Ok(5)), fields.next_onto(&mut output));
+    /// assert_eq!(b"world", &output[..5]);
+    /// assert_eq!(Some(Ok(8)), fields.next_onto(&mut output));
+    /// assert_eq!(None, fields.next_onto(&mut output));
+    /// ```
+    pub fn next_onto(
+        &mut self,
+        output: impl DecodeTarget,
+    ) -> Option<core::result::Result<usize, DelimitedError>> {
+        let start = self.start?;
+        let rest = &self.input[start..];
+        let (field_input, next) = match find(rest, self.delimiter) {
+            Some(len) => (&rest[..len], Some(start + len + self.delimiter.len())),
+            None => (rest, None),
+        };
+        let field = self.field;
+        self.field += 1;
+        self.start = next;
+
+        let result = if self.reject_empty && field_input.is_empty() {
+            Err(Error::EmptyField)
+        } else {
+            DecodeBuilder {
+                check: self.check,
+                prefix: self.prefix,
+                ..DecodeBuilder::new(field_input, self.alpha)
+            }
+            .onto(output)
+        };
+        Some(result.map_err(|error| DelimitedError {
+            field,
+            field_start: start,
+            error: error.offset_index(start),
+        }))
+    }
+}
+
+/// The index of the first occurrence of `needle` in `haystack`.
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    match needle {
+        [byte] => haystack.iter().position(|c| c == byte),
+        needle => haystack.windows(needle.len()).position(|w| w == needle),
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Iterator for Delimited<'_, '_> {
+    type Item = core::result::Result<Vec<u8>, DelimitedError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut output = Vec::new();
+        Some(self.next_onto(&mut output)?.map(|_| output))
+    }
+}
+
+impl Commit<'_> {
+    /// The decoded bytes that will be written.
+    pub fn as_bytes(&self) -> &[u8] {
+        self.decoded.as_bytes()
+    }
+
+    /// The number of bytes that will be written to the start of the target.
+    pub fn len(&self) -> usize {
+        self.as_bytes().len()
+    }
+
+    /// Whether the decoded data is empty, so committing writes nothing.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Write the decoded bytes to the start of the target in a single copy, returning the
+    /// length written.
+    pub fn commit(self) -> usize {
+        let decoded = self.decoded.as_bytes();
+        self.target[..decoded.len()].copy_from_slice(decoded);
+        decoded.len()
+    }
+}
+
+impl Deferred<'_> {
+    fn as_bytes(&self) -> &[u8] {
+        match self {
+            Deferred::Stack(scratch, len) => &scratch[..*len],
+            Deferred::Scratch(decoded) => decoded,
+            #[cfg(feature = "alloc")]
+            Deferred::Heap(decoded) => decoded,
+        }
+    }
+}
+
+impl<I: AsRef<[u8]>> FixedPoint<'_, I> {
+    /// Decode the fixed-point number, returning its value scaled to an integer along with the
+    /// number of fractional Base58 digits, such that the number is `scaled / 58^scale`.
+    ///
+    /// The input is decoded as usual, including any checksum or other options of the builder,
+    /// and the decoded bytes read as a big-endian unsigned integer `scaled`. As Base58 is
+    /// positional, the characters `d[0] d[1] ... d[n-1]` encode `scaled = Σ d[i] * 58^(n-1-i)`,
+    /// so dividing by `58^scale` moves the radix point `scale` digits from the end, giving the
+    /// fractional part `Σ d[i] * 58^(n-1-i-scale)` for the last `scale` digits. If there are fewer
+    /// than `scale` characters the missing ones are leading zeros, e.g. `"2"` with a scale of 2 is
+    /// `1/58^2`.
+    ///
+    /// `58^scale` overflows an `i128` for a scale over 21, converting to another representation
+    /// is left to the caller.
+    ///
+    /// # Errors
+    ///
+    /// Any error decoding the input, or [`Error::Overflow`] if the value is larger than
+    /// [`i128::MAX`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// // 1 + 0/58
+    /// assert_eq!((58, 1), bs58::decode("21").fixed_point(1).into_fixed()?);
+    /// // 1/58^2
+    /// assert_eq!((1, 2), bs58::decode("2").fixed_point(2).into_fixed()?);
+    ///
+    /// // 4 + 16/58 + 57/58^2
+    /// let (scaled, scale) = bs58::decode("5Hz").fixed_point(2).into_fixed()?;
+    /// assert_eq!((4 * 58 * 58 + 16 * 58 + 57, 2), (scaled, scale));
+    /// assert!((scaled as f64 / 58f64.powi(scale as i32) - 4.2928).abs() < 0.0001);
+    /// # Ok::<(), bs58::decode::Error>(())
+    /// ```
+    pub fn into_fixed(self) -> Result<(i128, u32)> {
+        let frac_digits = self.frac_digits;
+        self.builder.with_scratch(|decoded| {
+            let start = decoded
+                .iter()
+                .position(|&b| b != 0)
+                .unwrap_or(decoded.len());
+            let bytes = &decoded[start..];
+            if bytes.len() > 16 || (bytes.len() == 16 && bytes[0] > 0x7f) {
+                return Err(Error::Overflow);
+            }
+            let scaled = bytes
+                .iter()
+                .fold(0i128, |value, &byte| value << 8 | i128::from(byte));
+            Ok((scaled, frac_digits))
+        })
+    }
+}
+
+impl DelimitedError {
+    /// The error decoding the field, with any character index relative to the start of the
+    /// field.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let error = bs58::decode_delimited("he11owor1d,he0", b',')
+    ///     .nth(1)
+    ///     .unwrap()
+    ///     .unwrap_err();
+    /// assert_eq!(
+    ///     bs58::decode::Error::InvalidCharacter { character: '0', index: 13 },
+    ///     error.error);
+    /// assert_eq!(
+    ///     bs58::decode::Error::InvalidCharacter { character: '0', index: 2 },
+    ///     error.error_in_field());
+    /// ```
+    pub fn error_in_field(&self) -> Error {
+        match self.error {
+            Error::InvalidCharacter { character, index } => Error::InvalidCharacter {
+                character,
+                index: index - self.field_start,
+            },
+            Error::NonAsciiCharacter { index } => Error::NonAsciiCharacter {
+                index: index - self.field_start,
+            },
+            error => error,
+        }
+    }
+}
+
+/// Decode a [`Uuid`](uuid::Uuid) from its 16 big-endian bytes using the
+/// [default alphabet](Alphabet::DEFAULT).
+///
+/// Leading zero bytes of the UUID are encoded as leading zero characters, so the input must decode
+/// to exactly 16 bytes, otherwise [`Error::WrongLength`] is returned.
+///
+/// # Examples
+///
+/// ```rust
+/// assert_eq!(
+///     uuid::Uuid::parse_str("67e55044-10b1-426f-9247-bb680e5fe0c8").unwrap(),
+///     bs58::decode::to_uuid("Dq7QdGPZBdz9vwjm3jLQSB")?);
+/// # Ok::<(), bs58::decode::Error>(())
+/// ```
+#[cfg(feature = "uuid")]
+pub fn to_uuid<I: AsRef<[u8]>>(input: I) -> Result<uuid::Uuid> {
+    let bytes = crate::decode(input).into_exact_array()?;
+    Ok(uuid::Uuid::from_bytes(bytes))
+}
+
+/// Decode a 128-bit value as its high and low 64-bit halves, for storing it in two 64-bit
+/// columns, using the [default alphabet](Alphabet::DEFAULT).
+///
+/// The decoded bytes are read as a big-endian integer, so may be shorter than 16 bytes. If they
+/// are longer than 16 bytes [`Error::Overflow`] is returned.
+///
+/// # Examples
+///
+/// ```rust
+/// assert_eq!(
+///     (0x0123_4567_89ab_cdef, 0xfedc_ba98_7654_3210),
+///     bs58::decode::to_u64_pair("99dn6s7bZpEGBoKc7YnPh")?);
+/// assert_eq!((0, 1), bs58::decode::to_u64_pair("2")?);
+/// assert_eq!(
+///     Err(bs58::decode::Error::Overflow),
+///     bs58::decode::to_u64_pair("YcVfxkQb6JRzqk5kF2tNLw"));
+/// # Ok::<(), bs58::decode::Error>(())
+/// ```
+pub fn to_u64_pair<I: AsRef<[u8]>>(input: I) -> Result<(u64, u64)> {
+    let mut bytes = [0; 16];
+    let len = crate::decode(input)
+        .onto(&mut bytes)
+        .map_err(|err| match err {
+            Error::BufferTooSmall => Error::Overflow,
+            err => err,
+        })?;
+    let value = bytes[..len]
+        .iter()
+        .fold(0u128, |value, &byte| value << 8 | u128::from(byte));
+    Ok(((value >> 64) as u64, value as u64))
+}
+
+/// Decode a timestamp or duration in milliseconds, as encoded by
+/// [`encode::from_timestamp_millis`](crate::encode::from_timestamp_millis), using the
+/// [default alphabet](Alphabet::DEFAULT).
+///
+/// The decoded bytes are read as a big-endian integer, leading zero bytes are accepted and an
+/// empty input is 0. If they are longer than 8 bytes [`Error::Overflow`] is returned.
+///
+/// # Examples
+///
+/// ```rust
+/// assert_eq!(Ok(1_700_000_000_000), bs58::decode::to_timestamp_millis("mf4BMtb"));
+/// assert_eq!(Ok(0), bs58::decode::to_timestamp_millis(""));
+/// assert_eq!(
+///     Err(bs58::decode::Error::Overflow),
+///     bs58::decode::to_timestamp_millis("jpXCZedGfVR"));
+/// ```
+pub fn to_timestamp_millis<I: AsRef<[u8]>>(input: I) -> Result<u64> {
+    let mut bytes = [0; 8];
+    let len = crate::decode(input)
+        .onto(&mut bytes)
+        .map_err(|err| match err {
+            Error::BufferTooSmall => Error::Overflow,
+            err => err,
+        })?;
+    Ok(bytes[..len]
+        .iter()
+        .fold(0, |value, &byte| value << 8 | u64::from(byte)))
+}
+
+/// Decode a set of `count` flags packed 8 to a byte, as encoded by
+/// [`encode::from_bits`](crate::encode::from_bits), using the
+/// [default alphabet](Alphabet::DEFAULT).
+///
+/// The first flag is the most significant bit of the first byte. The input must decode to
+/// exactly `count.div_ceil(8)` bytes, otherwise [`Error::WrongLength`] is returned; the padding
+/// bits after the last flag are ignored.
+///
+/// # Examples
+///
+/// ```rust
+/// assert_eq!(vec![true, false, true, false, false, false, false, false, true],
+///     bs58::decode::to_bits("DDR", 9)?);
+/// assert_eq!(
+///     Err(bs58::decode::Error::WrongLength { expected: 1, found: 2 }),
+///     bs58::decode::to_bits("DDR", 8));
+/// # Ok::<(), bs58::decode::Error>(())
+/// ```
+#[cfg(feature = "alloc")]
+pub fn to_bits<I: AsRef<[u8]>>(input: I, count: usize) -> Result<Vec<bool>> {
+    let bytes = crate::decode(input)
+        .with_expected_len(count.div_ceil(8))
+        .into_vec()?;
+    Ok((0..count)
+        .map(|i| bytes[i / 8] & 0x80 >> (i % 8) != 0)
+        .collect())
+}
+
+/// The number of significant bits in the value `s` decodes to with `alphabet`, i.e. the
+/// position of its highest set bit, for checking the magnitude of an encoded number.
+///
+/// Leading zero characters, each of which decodes to a zero byte, don't add to the value, so
+/// are ignored; a value of zero, including empty input, has a bit length of 0. The value is
+/// decoded into a temporary buffer, on the stack for values up to 1024 bits and otherwise
+/// allocated, or without `alloc` [`Error::BufferTooSmall`] is returned.
+///
+/// # Examples
+///
+/// ```rust
+/// use bs58::Alphabet;
+///
+/// assert_eq!(Ok(0), bs58::decode::bit_length("111", Alphabet::BITCOIN));
+/// assert_eq!(Ok(1), bs58::decode::bit_length("2", Alphabet::BITCOIN));
+/// assert_eq!(Ok(59), bs58::decode::bit_length("1he11owor1d", Alphabet::BITCOIN));
+///
+/// let encoded = bs58::encode([0xff; 32]).into_string();
+/// assert_eq!(Ok(256), bs58::decode::bit_length(&encoded, Alphabet::BITCOIN));
+/// ```
+pub fn bit_length(s: &str, alphabet: &Alphabet) -> Result<u64> {
+    let digits = s.trim_start_matches(|c: char| c.is_ascii() && alphabet.is_leading_zero(c as u8));
+    let offset = s.len() - digits.len();
+    DecodeBuilder::new(digits, alphabet)
+        .with_scratch(|decoded| {
+            // Without the leading zeros the first byte, if any, is non-zero
+            Ok(decoded.first().map_or(0, |&top| {
+                decoded.len() as u64 * 8 - u64::from(top.leading_zeros())
+            }))
+        })
+        .map_err(|err| err.offset_index(offset))
+}
+
+/// Decode a [Base58Check][] address, returning its version byte and payload, using the
+/// [default alphabet](Alphabet::DEFAULT).
+///
+/// This verifies the checksum as with `bs58::decode(s).with_check(None)`, then splits the
+/// version off; input with only a checksum and no version byte returns [`Error::NoChecksum`].
+/// See [`encode::address`](crate::encode::address) for the inverse.
+///
+/// [Base58Check]: https://en.bitcoin.it/wiki/Base58Check_encoding
+///
+/// # Examples
+///
+/// ```rust
+/// let (version, hash) = bs58::decode::address("1F9v11cupBVMpz3CrVfCppv9Rw2xEtU1c6")?;
+/// assert_eq!(0x00, version);
+/// assert_eq!(20, hash.len());
+///
+/// assert!(matches!(
+///     bs58::decode::address("1F9v11cupBVMpz3CrVfCppv9Rw2xEtU1c7"),
+///     Err(bs58::decode::Error::InvalidChecksum { .. })));
+/// # Ok::<(), bs58::decode::Error>(())
+/// ```
+#[cfg(all(feature = "check", feature = "alloc"))]
+pub fn address(s: &str) -> Result<(u8, Vec<u8>)> {
+    let mut decoded = crate::decode(s).with_check(None).into_vec()?;
+    if decoded.is_empty() {
+        return Err(Error::NoChecksum);
+    }
+    let version = decoded.remove(0);
+    Ok((version, decoded))
+}
+
+/// Decode a self-describing record, returning its payload, using the
+/// [default alphabet](Alphabet::DEFAULT).
+///
+/// This verifies the checksum as with `bs58::decode(s).with_check(None)`, then reads the varint
+/// length header described in [`encode::record`](crate::encode::record). A header that is
+/// missing, ends part way through, is not minimally encoded or does not fit in a `usize` returns
+/// [`Error::ParseFailed`], and a header that does not match the length of the payload after it
+/// returns [`Error::WrongLength`].
+///
+/// # Examples
+///
+/// ```rust
+/// use bs58::decode::Error;
+///
+/// let encoded = bs58::encode::record(b"hello");
+/// assert_eq!(b"hello".to_vec(), bs58::decode::record(&encoded)?);
+///
+/// let encoded = bs58::encode(b"\x06hello").with_check().into_string();
+/// assert_eq!(
+///     Err(Error::WrongLength { expected: 6, found: 5 }),
+///     bs58::decode::record(&encoded));
+/// # Ok::<(), bs58::decode::Error>(())
+/// ```
+#[cfg(all(feature = "check", feature = "alloc"))]
+pub fn record(s: &str) -> Result<Vec<u8>> {
+    let mut decoded = crate::decode(s).with_check(None).into_vec()?;
+    let mut len = 0usize;
+    let mut header_len = 0;
+    loop {
+        let &byte = decoded.get(header_len).ok_or(Error::ParseFailed)?;
+        let shift = 7 * header_len as u32;
+        let bits = usize::from(byte & 0x7f);
+        // The last byte must not be zero unless it is the only one, and no bits may be lost
+        if shift >= usize::BITS || (bits << shift) >> shift != bits || (byte == 0 && shift > 0) {
+            return Err(Error::ParseFailed);
+        }
+        len |= bits << shift;
+        header_len += 1;
+        if byte & 0x80 == 0 {
+            break;
+        }
+    }
+    decoded.drain(..header_len);
+    if decoded.len() != len {
+        return Err(Error::WrongLength {
+            expected: len,
+            found: decoded.len(),
+        });
+    }
+    Ok(decoded)
+}
+
+/// Decode an IPFS [CIDv0][], returning the sha2-256 [multihash][] it consists of.
+///
+/// A CIDv0 is the Base58 encoding, with the [default alphabet](Alphabet::DEFAULT) and no multibase
+/// prefix, of a 34 byte multihash: the header `0x12 0x20` followed by a 32 byte sha2-256 digest,
+/// so it is always 46 characters starting with `Qm`. Input that doesn't start with `Qm`, or
+/// decodes to a different multihash header, returns [`Error::WrongPrefix`], and input that doesn't
+/// decode to 34 bytes returns [`Error::WrongLength`].
+///
+/// [CIDv0]: https://docs.ipfs.tech/concepts/content-addressing/#version-0-v0
+/// [multihash]: https://multiformats.io/multihash/
+///
+/// # Examples
+///
+/// ```rust
+/// let multihash = bs58::decode::cidv0("QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG")?;
+/// assert_eq!([0x12, 0x20], multihash[..2]);
+/// assert_eq!(34, multihash.len());
+/// # Ok::<(), bs58::decode::Error>(())
+/// ```
+#[cfg(feature = "alloc")]
+pub fn cidv0(s: &str) -> Result<Vec<u8>> {
+    if !s.starts_with("Qm") {
+        return Err(Error::WrongPrefix);
+    }
+    let multihash = crate::decode(s)
+        .with_expected_len(crate::CIDV0_LEN)
+        .into_vec()?;
+    if !multihash.starts_with(&crate::CIDV0_HEADER) {
+        return Err(Error::WrongPrefix);
+    }
+    Ok(multihash)
+}
+
+/// Check whether `s` is a valid [Base58Check][] string with the
+/// [default alphabet](Alphabet::DEFAULT) and any version, for validation that only needs a yes or
+/// no answer.
+///
+/// Invalid characters, input too short to have a checksum, and mismatched checksums all give
+/// `false`. The input is decoded into a temporary buffer, which is on the stack for up to 128
+/// decoded bytes (so every common address and key size), otherwise allocated and freed before
+/// returning. Without `alloc` longer input gives `false`.
+///
+/// [Base58Check]: https://en.bitcoin.it/wiki/Base58Check_encoding
+///
+/// # Examples
+///
+/// ```rust
+/// assert!(bs58::decode::is_valid_check("QuT57JNzzWTu7mW"));
+/// assert!(!bs58::decode::is_valid_check("QuT57JNzzWTu7mX"));
+/// assert!(!bs58::decode::is_valid_check("he11owor1d"));
+/// ```
+#[cfg(feature = "check")]
+pub fn is_valid_check(s: &str) -> bool {
+    is_valid_check_with_alphabet(s, Alphabet::DEFAULT)
+}
+
+/// Check whether `s` is a valid [Base58Check][] string with `alpha` and any version, see
+/// [`is_valid_check`].
+///
+/// [Base58Check]: https://en.bitcoin.it/wiki/Base58Check_encoding
+///
+/// # Examples
+///
+/// ```rust
+/// let s = bs58::encode([0x01, 0x02])
+///     .with_alphabet(bs58::Alphabet::RIPPLE)
+///     .with_check()
+///     .into_string();
+/// assert!(bs58::decode::is_valid_check_with_alphabet(&s, bs58::Alphabet::RIPPLE));
+/// assert!(!bs58::decode::is_valid_check_with_alphabet(&s, bs58::Alphabet::BITCOIN));
+/// ```
+#[cfg(feature = "check")]
+pub fn is_valid_check_with_alphabet(s: &str, alpha: &Alphabet) -> bool {
+    DecodeBuilder::new(s, alpha)
+        .with_check(None)
+        .with_scratch(|_| Ok(()))
+        .is_ok()
+}
+
+/// Find the first substring of `haystack` that is a valid [Base58Check][] string with the
+/// [default alphabet](Alphabet::DEFAULT) and a payload (including the version byte) of exactly
+/// `payload_len` bytes, returning its byte offset and the decoded payload.
+///
+/// This is for extracting addresses from free-form text, where they may be surrounded by
+/// punctuation, whitespace or other words.
+///
+/// # Matching strategy
+///
+/// Only runs of characters from the alphabet are considered, and within them each start position
+/// from left to right, trying the possible string lengths for `payload_len` from shortest to
+/// longest. A string that decodes to `payload_len` plus the 4 checksum bytes is at least that many
+/// characters (if every byte is zero) and at most [`max_encoded_len`](crate::max_encoded_len) of
+/// it, so the number of candidates at each position is bounded by `payload_len` rather than the
+/// length of the haystack. Decoding a candidate stops as soon as it is too long for the
+/// payload, so the work is linear in the length of the haystack for a given `payload_len`.
+///
+/// The first candidate with a matching checksum is returned, even if it is part of a longer run of
+/// alphabet characters. A random candidate passes the checksum with a chance of 1 in 2³², so false
+/// matches are very unlikely but not impossible in large amounts of text.
+///
+/// [Base58Check]: https://en.bitcoin.it/wiki/Base58Check_encoding
+///
+/// # Examples
+///
+/// ```rust
+/// let email = "Hi, please send it to 1F9v11cupBVMpz3CrVfCppv9Rw2xEtU1c6, thanks!";
+/// let (offset, payload) = bs58::decode::find_first_check(email, 21).unwrap();
+/// assert_eq!(22, offset);
+/// assert_eq!([0x00, 0x9b], payload[..2]);
+/// assert_eq!(None, bs58::decode::find_first_check(email, 20));
+/// ```
+#[cfg(all(feature = "check", feature = "alloc"))]
+pub fn find_first_check(haystack: &str, payload_len: usize) -> Option<(usize, Vec<u8>)> {
+    let alpha = Alphabet::DEFAULT;
+    let decoded_len = payload_len + CHECKSUM_LEN;
+    let max_len = crate::max_encoded_len(decoded_len);
+    let haystack = haystack.as_bytes();
+    let mut output = alloc::vec![0; decoded_len];
+
+    let mut run_end = 0;
+    for start in 0..haystack.len() {
+        if start >= run_end {
+            run_end = start
+                + haystack[start..]
+                    .iter()
+                    .take_while(|&&c| alpha.would_conflict_with(c))
+                    .count();
+        }
+        for end in (start + decoded_len)..=(start + max_len).min(run_end) {
+            let decoded = DecodeBuilder::new(&haystack[start..end], alpha)
+                .with_check(None)
+                .onto(&mut output[..]);
+            if decoded == Ok(payload_len) {
+                output.truncate(payload_len);
+                return Some((start, output));
+            }
         }
     }
+    None
+}
+
+/// Decode `input` with the alphabet and checksum mode named by `scheme`, for configuring decoding
+/// from a string such as a config file entry.
+///
+/// The scheme is an alphabet name, one of `bitcoin`, `monero`, `ripple` or `flickr`, optionally
+/// followed by `+` and a checksum mode: `check` for [Base58Check][] with any version (with the
+/// `check` feature) or `cb58` for [CB58][] with any version (with the `cb58` feature). Names are
+/// case sensitive. Any other scheme, including a mode whose feature is disabled, returns
+/// [`Error::UnknownScheme`] without trying to decode the input.
+///
+/// [Base58Check]: https://en.bitcoin.it/wiki/Base58Check_encoding
+/// [CB58]: https://support.avax.network/en/articles/4587395-what-is-cb58
+///
+/// # Examples
+///
+/// ```rust
+/// assert_eq!(
+///     vec![0x60, 0x65, 0xe7, 0x9b, 0xba, 0x2f, 0x78],
+///     bs58::decode::from_scheme("ripple", "he11owor1d")?);
+/// assert_eq!(
+///     Err(bs58::decode::Error::UnknownScheme),
+///     bs58::decode::from_scheme("base64", "he11owor1d"));
+/// # Ok::<(), bs58::decode::Error>(())
+/// ```
+#[cfg(feature = "alloc")]
+pub fn from_scheme(scheme: &str, input: &str) -> Result<Vec<u8>> {
+    let (name, mode) = match scheme.split_once('+') {
+        Some((name, mode)) => (name, Some(mode)),
+        None => (scheme, None),
+    };
+    let alpha = match name {
+        "bitcoin" => Alphabet::BITCOIN,
+        "monero" => Alphabet::MONERO,
+        "ripple" => Alphabet::RIPPLE,
+        "flickr" => Alphabet::FLICKR,
+        _ => return Err(Error::UnknownScheme),
+    };
+    let builder = DecodeBuilder::new(input, alpha);
+    let builder = match mode {
+        None => builder,
+        #[cfg(feature = "check")]
+        Some("check") => builder.with_check(None),
+        #[cfg(feature = "cb58")]
+        Some("cb58") => builder.as_cb58(None),
+        Some(_) => return Err(Error::UnknownScheme),
+    };
+    builder.into_vec()
+}
+
+/// Decode the fields of `buf`, each a length prefix of the form given by `prefix` followed by
+/// that many Base58 characters, using the [default alphabet](Alphabet::DEFAULT).
+///
+/// Fields are read until the end of `buf`, which must be at the end of a field: if it ends part
+/// way through a length prefix or the characters after it [`Error::Truncated`] is returned with
+/// the index of the prefix. A field may have no characters, and decodes to no bytes. Character
+/// errors give the index of the character in `buf`.
+///
+/// # Examples
+///
+/// ```rust
+/// use bs58::decode::{Error, LenPrefix};
+///
+/// let buf = b"\x0ahe11owor1d\x00\x07EUYUqQf";
+/// assert_eq!(
+///     vec![vec![0x04, 0x30, 0x5e, 0x2b, 0x24, 0x73, 0xf0, 0x58], vec![], b"world".to_vec()],
+///     bs58::decode::read_all_length_prefixed(buf, LenPrefix::U8)?);
+///
+/// assert_eq!(
+///     Err(Error::Truncated { index: 12 }),
+///     bs58::decode::read_all_length_prefixed(&buf[..15], LenPrefix::U8));
+/// # Ok::<(), bs58::decode::Error>(())
+/// ```
+#[cfg(feature = "alloc")]
+pub fn read_all_length_prefixed(buf: &[u8], prefix: LenPrefix) -> Result<Vec<Vec<u8>>> {
+    let mut fields = Vec::new();
+    let mut start = 0;
+    while start < buf.len() {
+        let truncated = Error::Truncated { index: start };
+        let chars_start = start + prefix.width();
+        let len = prefix.read(buf.get(start..chars_start).ok_or(truncated)?);
+        let chars = buf[chars_start..].get(..len).ok_or(truncated)?;
+        let field = crate::decode(chars)
+            .into_vec()
+            .map_err(|err| err.offset_index(chars_start))?;
+        fields.push(field);
+        start = chars_start + len;
+    }
+    Ok(fields)
 }
 
 /// For `const` compatibility we are restricted to using a concrete input and output type, as
@@ -377,6 +3681,26 @@ impl<'a, 'b> DecodeBuilder<'a, &'b [u8]> {
             matches!(self.check, Check::Disabled),
             "checksums in const aren't supported (why are you using this API at runtime)",
         );
+        assert!(
+            self.prefix.is_empty() && self.hrp_separator.is_none(),
+            "text prefixes in const aren't supported (why are you using this API at runtime)",
+        );
+        assert!(
+            self.canonical.is_none(),
+            "canonical checks in const aren't supported (why are you using this API at runtime)",
+        );
+        assert!(
+            self.expected_len.is_none(),
+            "expected lengths in const aren't supported (why are you using this API at runtime)",
+        );
+        assert!(
+            self.map.is_none(),
+            "byte transforms in const aren't supported (why are you using this API at runtime)",
+        );
+        assert!(
+            self.fallback.is_none(),
+            "secondary alphabets in const aren't supported (why are you using this API at runtime)",
+        );
         get_decoded_len(self.input, self.alpha)
     }
 
@@ -438,6 +3762,26 @@ impl<'a, 'b> DecodeBuilder<'a, &'b [u8]> {
             matches!(self.check, Check::Disabled),
             "checksums in const aren't supported (why are you using this API at runtime)",
         );
+        assert!(
+            self.prefix.is_empty() && self.hrp_separator.is_none(),
+            "text prefixes in const aren't supported (why are you using this API at runtime)",
+        );
+        assert!(
+            self.canonical.is_none(),
+            "canonical checks in const aren't supported (why are you using this API at runtime)",
+        );
+        assert!(
+            self.expected_len.is_none(),
+            "expected lengths in const aren't supported (why are you using this API at runtime)",
+        );
+        assert!(
+            self.map.is_none(),
+            "byte transforms in const aren't supported (why are you using this API at runtime)",
+        );
+        assert!(
+            self.fallback.is_none(),
+            "secondary alphabets in const aren't supported (why are you using this API at runtime)",
+        );
         decode_into_const(self.input, self.alpha)
     }
 
@@ -472,57 +3816,795 @@ impl<'a, 'b> DecodeBuilder<'a, &'b [u8]> {
     }
 }
 
-fn decode_into(input: &[u8], output: &mut [u8], alpha: &Alphabet) -> Result<usize> {
-    let mut index = 0;
-    let zero = alpha.encode[0];
+impl<'a, 'b> DecodeBuilder<'a, &'b [u8]> {
+    /// Decode the longest leading part of the input consisting entirely of characters in the
+    /// alphabet into a new vector, returning it along with the rest of the input, for parsing a
+    /// Base58 field followed by other (possibly binary) data.
+    ///
+    /// The remainder starts at the first byte that is not in the alphabet, or is empty if every
+    /// byte is. If a [text prefix](Self::with_text_prefix) is set the input must start with it,
+    /// otherwise [`Error::WrongPrefix`] is returned. The other options of the builder apply to
+    /// the decoded part as usual, e.g. an empty leading part decodes to no bytes but fails a
+    /// checksum.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let input = b"he11owor1d\0\x01\xff";
+    /// let (decoded, rest) = bs58::decode(&input[..]).into_vec_with_remainder()?;
+    /// assert_eq!(vec![0x04, 0x30, 0x5e, 0x2b, 0x24, 0x73, 0xf0, 0x58], decoded);
+    /// assert_eq!(b"\0\x01\xff", rest);
+    /// # Ok::<(), bs58::decode::Error>(())
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn into_vec_with_remainder(self) -> Result<(Vec<u8>, &'b [u8])> {
+        let rest = self.unprefixed_input()?;
+        let len = valid_len(rest, self.alpha);
+        let (input, remainder) = self.input.split_at(self.text_prefix_len() + len);
+        let decoded = DecodeBuilder { input, ..self }.into_vec()?;
+        Ok((decoded, remainder))
+    }
+}
+
+impl<'a, 'b> DecodeBuilder<'a, &'b str> {
+    /// Check the input decodes with all the options of the builder, such as a
+    /// [checksum](Self::with_check) or [non-zero requirement](Self::require_nonzero), returning
+    /// the original input, for storing or forwarding the encoded form once it is known to be
+    /// valid.
+    ///
+    /// The returned string is the input itself, not a copy. The decoded bytes are discarded: they
+    /// are decoded into a buffer on the stack when they fit in 128 bytes, otherwise into an
+    /// allocated buffer, or without `alloc` [`Error::BufferTooSmall`] is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let input = String::from("he11owor1d");
+    /// let validated = bs58::decode(input.as_str()).validated()?;
+    /// assert!(std::ptr::eq(input.as_str(), validated));
+    ///
+    /// assert_eq!(
+    ///     Err(bs58::decode::Error::InvalidCharacter { character: '0', index: 5 }),
+    ///     bs58::decode("he11o0").validated());
+    /// # Ok::<(), bs58::decode::Error>(())
+    /// ```
+    pub fn validated(self) -> Result<&'b str> {
+        let input = self.input;
+        self.with_scratch(|_| Ok(()))?;
+        Ok(input)
+    }
+
+    /// Get the longest leading part of the input consisting entirely of characters in the
+    /// alphabet, without decoding it, for splitting the Base58 token off mixed text before
+    /// deciding whether to decode it.
+    ///
+    /// If a [text prefix](Self::with_text_prefix) is set the input must start with it for any of
+    /// it to be valid, and it is included in the result. If the input is empty or starts with a
+    /// character not in the alphabet this returns an empty string, if every character is in the
+    /// alphabet it returns the whole input.
+    ///
+    /// This only checks characters: the result may still fail to decode with other options of
+    /// the builder, such as a checksum or expected length.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// assert_eq!("he11owor1d", bs58::decode("he11owor1d, more text").valid_prefix());
+    /// assert_eq!("he11owor1d", bs58::decode("he11owor1d").valid_prefix());
+    /// assert_eq!("", bs58::decode("0xabc").valid_prefix());
+    /// assert_eq!("", bs58::decode("").valid_prefix());
+    ///
+    /// assert_eq!(
+    ///     "z:he11o",
+    ///     bs58::decode("z:he11o-world").with_text_prefix("z:").valid_prefix());
+    /// assert_eq!("", bs58::decode("he11o").with_text_prefix("z:").valid_prefix());
+    /// ```
+    pub fn valid_prefix(&self) -> &'b str {
+        let Some(rest) = self.strip_text_prefix(self.input.as_bytes()) else {
+            return "";
+        };
+        let len = valid_len(rest, self.alpha);
+        // All alphabet characters are ASCII, so this is always a character boundary
+        &self.input[..self.text_prefix_len() + len]
+    }
+}
+
+/// Decode the input with every built-in alphabet, returning each alphabet the input is valid for
+/// paired with the bytes it decodes to.
+///
+/// The built-in alphabets are all permutations of the same characters, so an input that is valid
+/// under one of them will decode under all of them; this returns all of those structurally valid
+/// interpretations, it does not try to guess which one was intended. Results are ordered
+/// [`BITCOIN`](Alphabet::BITCOIN), [`RIPPLE`](Alphabet::RIPPLE), [`FLICKR`](Alphabet::FLICKR);
+/// [`MONERO`](Alphabet::MONERO) has the same characters as `BITCOIN` so is not reported
+/// separately.
+///
+/// This is intended as a diagnostics aid for data of unknown origin, see [`detect_check`] for a
+/// stricter filter when the data is expected to carry a checksum.
+///
+/// # Examples
+///
+/// ```rust
+/// let detected = bs58::decode::detect("he11owor1d");
+/// assert_eq!(3, detected.len());
+/// assert_eq!(vec![0x04, 0x30, 0x5e, 0x2b, 0x24, 0x73, 0xf0, 0x58], detected[0].1);
+/// assert_eq!(vec![0x60, 0x65, 0xe7, 0x9b, 0xba, 0x2f, 0x78], detected[1].1);
+///
+/// // `0` is not in any of the built-in alphabets
+/// assert!(bs58::decode::detect("he11o0").is_empty());
+/// ```
+#[cfg(feature = "alloc")]
+pub fn detect<I: AsRef<[u8]>>(input: I) -> Vec<(&'static Alphabet, Vec<u8>)> {
+    let input = input.as_ref();
+    Alphabet::BUILTIN
+        .iter()
+        .filter_map(|&(_, alpha)| {
+            let decoded = DecodeBuilder::new(input, alpha).into_vec().ok()?;
+            Some((alpha, decoded))
+        })
+        .collect()
+}
+
+/// Same as [`detect`], but only returns the interpretations that pass [Base58Check][]
+/// verification, the returned bytes have the checksum removed.
+///
+/// Optional parameter for version byte. If provided, the version byte will
+/// be used in verification.
+///
+/// [Base58Check]: https://en.bitcoin.it/wiki/Base58Check_encoding
+///
+/// # Examples
+///
+/// ```rust
+/// let detected = bs58::decode::detect_check("PWEu9GGN", None);
+/// assert_eq!(1, detected.len());
+/// assert_eq!(vec![0x2d, 0x31], detected[0].1);
+/// ```
+#[cfg(all(feature = "alloc", feature = "check"))]
+pub fn detect_check<I: AsRef<[u8]>>(
+    input: I,
+    expected_ver: Option<u8>,
+) -> Vec<(&'static Alphabet, Vec<u8>)> {
+    let input = input.as_ref();
+    Alphabet::BUILTIN
+        .iter()
+        .filter_map(|&(_, alpha)| {
+            let decoded = DecodeBuilder::new(input, alpha)
+                .with_check(expected_ver)
+                .into_vec()
+                .ok()?;
+            Some((alpha, decoded))
+        })
+        .collect()
+}
+
+/// A push-based decoder, for input that arrives in pieces, such as from an [`io::Read`][].
+///
+/// Every base58 character can change every byte of the decoded value, so no decoded byte is final
+/// until the whole input has been seen. The pushed characters are therefore buffered and decoded
+/// (and with [`with_check`](Stream::with_check) verified) in one go by
+/// [`finish`](Stream::finish), which is the only place decoding errors are reported. Errors from
+/// the source of the input, like an [`io::Error`][] from [`std::io::copy`] into the
+/// [`io::Write`][] implementation, are kept separate from those.
+///
+/// [`io::Read`]: std::io::Read
+/// [`io::Error`]: std::io::Error
+/// [`io::Write`]: std::io::Write
+///
+/// # Examples
+///
+/// ```rust
+/// let mut stream = bs58::decode::Stream::new(bs58::Alphabet::DEFAULT);
+/// stream.push(b"he11o");
+/// stream.push(b"wor1d");
+/// assert_eq!(
+///     vec![0x04, 0x30, 0x5e, 0x2b, 0x24, 0x73, 0xf0, 0x58],
+///     stream.finish().unwrap());
+/// ```
+#[cfg(feature = "alloc")]
+#[allow(missing_debug_implementations)]
+pub struct Stream<'a> {
+    input: Vec<u8>,
+    alpha: &'a Alphabet,
+    check: Check,
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> Stream<'a> {
+    /// Setup a decoder for input in the given alphabet.
+    pub fn new(alpha: &'a Alphabet) -> Stream<'a> {
+        Stream {
+            input: Vec::new(),
+            alpha,
+            check: Check::Disabled,
+        }
+    }
+
+    /// Expect and check checksum using the [Base58Check][] algorithm when
+    /// finishing the stream, the returned bytes have the checksum removed.
+    ///
+    /// Optional parameter for version byte. If provided, the version byte will
+    /// be used in verification.
+    ///
+    /// [Base58Check]: https://en.bitcoin.it/wiki/Base58Check_encoding
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let mut stream = bs58::decode::Stream::new(bs58::Alphabet::DEFAULT).with_check(None);
+    /// for c in b"PWEu9GGN" {
+    ///     stream.push(&[*c]);
+    /// }
+    /// assert_eq!(vec![0x2d, 0x31], stream.finish().unwrap());
+    /// ```
+    #[cfg(feature = "check")]
+    pub fn with_check(self, expected_ver: Option<u8>) -> Stream<'a> {
+        let check = Check::Enabled(expected_ver);
+        Stream { check, ..self }
+    }
+
+    /// Add the next piece of the input.
+    pub fn push(&mut self, input: &[u8]) {
+        self.input.extend_from_slice(input);
+    }
+
+    /// Decode all the input pushed so far.
+    ///
+    /// See the documentation for [`DecodeBuilder::into_vec`] for the errors this can return, an
+    /// invalid character is reported at its index in the whole input. A bad checksum is reported
+    /// as [`Error::InvalidChecksum`].
+    pub fn finish(self) -> Result<Vec<u8>> {
+        DecodeBuilder {
+            check: self.check,
+            ..DecodeBuilder::new(&self.input[..], self.alpha)
+        }
+        .into_vec()
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::io::Write for Stream<'_> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.push(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Whether `a` and `b` decode to the same bytes under any of the `alphabets`, either the same
+/// alphabet or two different ones.
+///
+/// Under a single alphabet different strings always decode to different bytes, so this is only
+/// true for distinct strings when one alphabet reads `a` as the same data another reads `b` as.
+/// This is intended as a diagnostics aid for assessing the ambiguity of accepting input in more
+/// than one alphabet; strings that are not valid under an alphabet are skipped for it.
+///
+/// # Examples
+///
+/// ```rust
+/// use bs58::Alphabet;
+///
+/// let alphabets = [Alphabet::BITCOIN, Alphabet::FLICKR];
+/// assert!(bs58::decode::collides_across("ab", "AB", &alphabets));
+/// assert!(!bs58::decode::collides_across("ab", "AB", &alphabets[..1]));
+/// ```
+#[cfg(feature = "alloc")]
+pub fn collides_across(a: &str, b: &str, alphabets: &[&Alphabet]) -> bool {
+    let decode = |input| {
+        alphabets
+            .iter()
+            .filter_map(move |&alpha| DecodeBuilder::new(input, alpha).into_vec().ok())
+    };
+    let decoded_b: Vec<_> = decode(b).collect();
+    decode(a).any(|decoded| decoded_b.contains(&decoded))
+}
+
+/// Re-encode the Base58 `input` from the alphabet `from` to the alphabet `to`, as
+/// `bs58::decode(input).with_alphabet(from)` then encoding with `to` would.
+///
+/// Every alphabet is a permutation of the same 58 digits, and a leading zero byte is the zero
+/// digit in each, so the encoded digits are the same whatever the alphabet. Transcoding is
+/// therefore always a substitution of each character for the one with the same digit in `to`,
+/// with leading zero characters becoming the [lead character](Alphabet::lead_char) of `to`,
+/// taking time linear in the input rather than the quadratic time of decoding and re-encoding.
+/// Errors are the same as decoding with `from`, for the first character that isn't part of it.
+///
+/// # Examples
+///
+/// ```rust
+/// use bs58::Alphabet;
+///
+/// assert_eq!(
+///     Ok("6erroAoird".to_string()),
+///     bs58::decode::transcode("he11owor1d", Alphabet::BITCOIN, Alphabet::RIPPLE));
+///
+/// assert_eq!(
+///     Err(bs58::decode::Error::InvalidCharacter { character: '0', index: 5 }),
+///     bs58::decode::transcode("he11o0", Alphabet::BITCOIN, Alphabet::RIPPLE));
+/// ```
+#[cfg(feature = "alloc")]
+pub fn transcode<I: AsRef<[u8]>>(
+    input: I,
+    from: &Alphabet,
+    to: &Alphabet,
+) -> Result<alloc::string::String> {
+    let output = input
+        .as_ref()
+        .iter()
+        .enumerate()
+        .scan(true, |leading, (index, &c)| {
+            *leading &= from.is_leading_zero(c);
+            Some(match *leading {
+                true => Ok(to.lead_char()),
+                false => digit_value(index, c, from).map(|digit| raw::character(to, digit)),
+            })
+        })
+        .collect::<Result<Vec<u8>>>()?;
+    // The characters of an alphabet are all ASCII
+    Ok(alloc::string::String::from_utf8(output).unwrap())
+}
+
+/// Decode each of the inputs with the [default alphabet](Alphabet::DEFAULT) in parallel using
+/// [`rayon`], returning the result for each input in the same order.
+///
+/// Each input is decoded independently, so an error in one does not affect the others.
+///
+/// # Examples
+///
+/// ```rust
+/// let decoded = bs58::decode::par_batch(&["he11owor1d", "he11o0"]);
+/// assert_eq!(Ok(vec![0x04, 0x30, 0x5e, 0x2b, 0x24, 0x73, 0xf0, 0x58]), decoded[0]);
+/// assert_eq!(
+///     Err(bs58::decode::Error::InvalidCharacter { character: '0', index: 5 }),
+///     decoded[1]);
+/// ```
+#[cfg(feature = "rayon")]
+pub fn par_batch<I: AsRef<[u8]> + Sync>(inputs: &[I]) -> Vec<Result<Vec<u8>>> {
+    use rayon::prelude::*;
+
+    inputs
+        .par_iter()
+        .map(|input| crate::decode(input).into_vec())
+        .collect()
+}
+
+/// Decode each line read from `r` with the [default alphabet](Alphabet::DEFAULT), yielding the
+/// result for each line as it is read, for processing large files of one value per line without
+/// loading them whole.
+///
+/// Lines end at `\n` or `\r\n`, which is not part of the decoded line, and the last line need
+/// not end with either; an empty line decodes to no bytes. Lines are read as the results are
+/// needed, so only the current line is held in memory beyond the reader's own buffer.
+///
+/// The outer [`Result`](std::io::Result) is an error reading from `r`, the inner one the result
+/// of decoding the line as with [`into_vec`](DecodeBuilder::into_vec), with error indices into
+/// the line. As with [`BufRead::lines`](std::io::BufRead::lines) reading continues after an I/O
+/// error, with any part of a line read before it lost, so stop at the first one unless the reader
+/// is known to recover.
+///
+/// # Examples
+///
+/// ```rust
+/// let input = std::io::Cursor::new("he11owor1d\r\n\nhe11o0\n");
+/// let results = bs58::decode::from_bufread(input).collect::<std::io::Result<Vec<_>>>()?;
+/// assert_eq!(
+///     vec![
+///         Ok(vec![0x04, 0x30, 0x5e, 0x2b, 0x24, 0x73, 0xf0, 0x58]),
+///         Ok(vec![]),
+///         Err(bs58::decode::Error::InvalidCharacter { character: '0', index: 5 }),
+///     ],
+///     results);
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[cfg(feature = "std")]
+pub fn from_bufread<R: std::io::BufRead>(
+    r: R,
+) -> impl Iterator<Item = std::io::Result<Result<Vec<u8>>>> {
+    r.split(b'\n').map(|line| {
+        let mut line = line?;
+        if line.last() == Some(&b'\r') {
+            line.pop();
+        }
+        Ok(crate::decode(line).into_vec())
+    })
+}
+
+/// A decode that can be run a bounded amount at a time, for decoding large untrusted inputs
+/// without blocking a cooperative scheduler (such as a single-threaded async executor, or the
+/// main thread in WASM) for long.
+///
+/// Each call to [`run`](Job::run) does at most the given number of work units before returning,
+/// keeping the value decoded so far in the job so the next call continues where it left off. A
+/// work unit is either starting a character or visiting one byte of the value decoded so far
+/// while adding it, the same operations counted by [`estimated_ops`], so a character can be
+/// paused part way through. Once all the characters are added, writing the leading zeros and
+/// putting the bytes in order takes time linear in the decoded length, which isn't counted.
+///
+/// The result and errors, including the index of an invalid character, are the same as decoding
+/// in one go with [`DecodeBuilder::into_vec`].
+///
+/// # Examples
+///
+/// ```rust
+/// use bs58::decode::{Job, Status};
+///
+/// let mut job = Job::new("he11owor1d", bs58::Alphabet::DEFAULT);
+/// let decoded = loop {
+///     match job.run(8) {
+///         Status::Done(decoded) => break decoded,
+///         Status::Pending => continue, // yield to other tasks here
+///         Status::Failed(err) => panic!("{err}"),
+///     }
+/// };
+/// assert_eq!(vec![0x04, 0x30, 0x5e, 0x2b, 0x24, 0x73, 0xf0, 0x58], decoded);
+/// ```
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug)]
+pub struct Job<'a, I: AsRef<[u8]>> {
+    input: I,
+    alpha: &'a Alphabet,
+    /// The value decoded so far in its first `len` bytes, with room for the longest possible
+    /// result.
+    output: Vec<u8>,
+    len: usize,
+    /// The index of the next character to add.
+    position: usize,
+    /// The number of leading zero characters, which only add zero bytes.
+    zeros: usize,
+    /// The index of the next byte of the value to multiply, while adding a character.
+    byte: usize,
+    /// What remains to be added from the current character, or `None` between characters.
+    carry: Option<usize>,
+    finished: bool,
+}
+
+/// The progress of a [`Job`] after [running](Job::run) it.
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Status {
+    /// The input was completely decoded to these bytes.
+    Done(Vec<u8>),
+    /// The work limit was reached, call [`Job::run`] again to continue.
+    Pending,
+    /// Decoding failed.
+    Failed(Error),
+}
 
-    for (i, c) in input.iter().enumerate() {
-        if *c > 127 {
-            return Err(Error::NonAsciiCharacter { index: i });
+#[cfg(feature = "alloc")]
+impl<'a, I: AsRef<[u8]>> Job<'a, I> {
+    /// Setup decoding `input` with the given alphabet, without doing any work yet.
+    ///
+    /// This allocates a buffer the length of the input, enough for the longest result.
+    pub fn new(input: I, alpha: &'a Alphabet) -> Self {
+        let output = alloc::vec![0; input.as_ref().len()];
+        Job {
+            input,
+            alpha,
+            output,
+            len: 0,
+            position: 0,
+            zeros: 0,
+            byte: 0,
+            carry: None,
+            finished: false,
         }
+    }
 
-        let mut val = alpha.decode[*c as usize] as usize;
-        if val == 0xFF {
-            return Err(Error::InvalidCharacter {
-                character: *c as char,
-                index: i,
-            });
+    /// Continue decoding, doing at most `max_work_units` units of work (see [`Job`]).
+    ///
+    /// # Panics
+    ///
+    /// If called again after returning [`Status::Done`] or [`Status::Failed`].
+    pub fn run(&mut self, max_work_units: usize) -> Status {
+        assert!(!self.finished, "decode job already finished");
+        match self.advance(max_work_units) {
+            Ok(true) => {
+                self.finished = true;
+                let mut output = core::mem::take(&mut self.output);
+                match finish_decode(self.len, self.zeros, &mut output) {
+                    Ok(len) => {
+                        output.truncate(len);
+                        Status::Done(output)
+                    }
+                    Err(err) => Status::Failed(err),
+                }
+            }
+            Ok(false) => Status::Pending,
+            Err(err) => {
+                self.finished = true;
+                Status::Failed(err)
+            }
         }
+    }
 
-        for byte in &mut output[..index] {
-            val += (*byte as usize) * 58;
-            *byte = (val & 0xFF) as u8;
-            val >>= 8;
-        }
+    /// Add characters until they are all added, returning `true`, or `budget` work units are
+    /// done.
+    fn advance(&mut self, mut budget: usize) -> Result<bool> {
+        let input = self.input.as_ref();
+        let output = &mut self.output[..];
 
-        while val > 0 {
-            let byte = output.get_mut(index).ok_or(Error::BufferTooSmall)?;
-            *byte = (val & 0xFF) as u8;
-            index += 1;
-            val >>= 8
+        while let Some(&c) = input.get(self.position) {
+            let mut val = match self.carry {
+                Some(carry) => carry,
+                None if budget == 0 => return Ok(false),
+                None => {
+                    budget -= 1;
+                    if self.zeros == self.position && self.alpha.is_leading_zero(c) {
+                        self.zeros += 1;
+                        0
+                    } else {
+                        digit_value(self.position, c, self.alpha)? as usize
+                    }
+                }
+            };
+
+            let end = self.len.min(self.byte.saturating_add(budget));
+            val = raw::mul_add(&mut output[self.byte..end], val);
+            budget -= end - self.byte;
+            self.byte = end;
+            self.carry = Some(val);
+            if self.byte < self.len {
+                return Ok(false);
+            }
+
+            while val > 0 {
+                if budget == 0 {
+                    self.carry = Some(val);
+                    return Ok(false);
+                }
+                let byte = output.get_mut(self.len).ok_or(Error::BufferTooSmall)?;
+                *byte = (val & 0xFF) as u8;
+                self.len += 1;
+                self.byte += 1;
+                budget -= 1;
+                val >>= 8;
+            }
+
+            self.byte = 0;
+            self.carry = None;
+            self.position += 1;
         }
+
+        Ok(true)
     }
+}
 
-    for _ in input.iter().take_while(|c| **c == zero) {
-        let byte = output.get_mut(index).ok_or(Error::BufferTooSmall)?;
-        *byte = 0;
-        index += 1;
+/// Return an upper bound on the number of bignum operations decoding an input with the given
+/// number of characters takes, for scheduling or rejecting expensive decodes before starting them.
+///
+/// Decoding multiplies the value decoded so far by 58 and adds each character in turn, visiting
+/// every byte of the value so far, so the cost grows with the square of the input length. This
+/// counts one operation per byte visited for each character, bounding the value by the longest
+/// data the input could decode to, so the actual number is usually somewhat lower (by about half
+/// for random data). Verifying a checksum adds a cost linear in the length and isn't counted.
+///
+/// # Examples
+///
+/// ```rust
+/// assert_eq!(44 * 33, bs58::decode::estimated_ops(44));
+/// assert!(bs58::decode::estimated_ops(2000) > 3 * bs58::decode::estimated_ops(1000));
+/// ```
+pub fn estimated_ops(encoded_len: usize) -> u64 {
+    let max_len = encoded_len / 1000 * 733 + (encoded_len % 1000 * 733).div_ceil(1000);
+    (encoded_len as u64).saturating_mul(max_len as u64)
+}
+
+/// Return the expected ratio of decoded length to input length for an encoding of random data
+/// with the given number of characters, for capacity planning.
+///
+/// This is a statistical estimate based on each character carrying log<sub>256</sub>(58) ≈ 0.73
+/// bytes of information, rounded down to a whole number of bytes. It is not exact for any given
+/// input: an input may decode one byte longer, and each leading zero character decodes to a single
+/// byte. Use [`max_decoded_len`](crate::max_decoded_len) for sizing buffers.
+///
+/// # Examples
+///
+/// ```rust
+/// assert_eq!(32.0 / 44.0, bs58::decode::contraction_estimate(44));
+/// assert_eq!(32, bs58::decode("JEKNVnkbo3jma5nREBBJCDoXFVeKkD56V3xKrvRmWxFG").into_vec()?.len());
+/// # Ok::<(), bs58::decode::Error>(())
+/// ```
+pub fn contraction_estimate(input_len: usize) -> f64 {
+    // log(58) / log(256)
+    const RATIO: f64 = 0.732_247_624_390_946_5;
+    if input_len == 0 {
+        return RATIO;
     }
+    let bytes = (input_len as f64 * RATIO) as usize;
+    bytes as f64 / input_len as f64
+}
 
-    output[..index].reverse();
-    Ok(index)
+/// Return an upper bound on the decoded length of `input`, based only on the number of characters
+/// and leading zeros present, reading it through the `unfold` table of a display fold if there is
+/// one.
+fn estimate_decoded_len(input: &[u8], alpha: &Alphabet, unfold: Option<&[u8; 128]>) -> usize {
+    let zeros = leading_zeros(input, alpha, unfold);
+    let digits = input.len() - zeros;
+    // log_256(58) ≈ 0.7322, round up slightly so this is always an upper bound
+    zeros + digits / 1000 * 733 + (digits % 1000 * 733).div_ceil(1000)
 }
 
-#[cfg(feature = "check")]
-fn decode_check_into(
-    input: &[u8],
+/// The number of leading zero characters of `input`, reading it through the `unfold` table of a
+/// display fold if there is one.
+fn leading_zeros(input: &[u8], alpha: &Alphabet, unfold: Option<&[u8; 128]>) -> usize {
+    input
+        .iter()
+        .map(|&c| {
+            unfold
+                .and_then(|table| table.get(usize::from(c)).copied())
+                .unwrap_or(c)
+        })
+        .take_while(|&c| alpha.is_leading_zero(c))
+        .count()
+}
+
+/// Decode the input characters, given along with their index in the original input, into the
+/// output buffer returning the decoded length.
+fn decode_into<'i>(
+    input: impl Iterator<Item = (usize, &'i u8)> + Clone,
     output: &mut [u8],
     alpha: &Alphabet,
-    expected_ver: Option<u8>,
+    record: &mut impl Record,
 ) -> Result<usize> {
+    let mut index = 0;
+    let mut leading = true;
+
+    for (i, c) in input.clone() {
+        leading &= alpha.is_leading_zero(*c);
+        let digit = if leading {
+            0
+        } else {
+            digit_value(i, *c, alpha)?
+        };
+        record.iterations(index);
+        index = raw::push_digit(output, index, digit).ok_or(Error::BufferTooSmall)?;
+    }
+
+    let zeros = input
+        .take_while(|(_, c)| alpha.is_leading_zero(**c))
+        .count();
+    finish_decode(index, zeros, output)
+}
+
+/// The length of the longest leading part of `input` made of characters of `alpha`, including
+/// lead characters in its leading run of zero characters.
+fn valid_len(input: &[u8], alpha: &Alphabet) -> usize {
+    let zeros = input
+        .iter()
+        .take_while(|&&c| alpha.is_leading_zero(c))
+        .count();
+    input[zeros..]
+        .iter()
+        .position(|&c| alpha.digit(c) == Alphabet::INVALID)
+        .map_or(input.len(), |len| zeros + len)
+}
+
+/// The digit of the character `c` at `index` in the original input.
+#[inline(always)]
+const fn digit_value(index: usize, c: u8, alpha: &Alphabet) -> Result<u8> {
+    if c > 127 {
+        return Err(Error::NonAsciiCharacter { index });
+    }
+
+    match raw::digit(alpha, c) {
+        Some(digit) => Ok(digit),
+        None => Err(Error::InvalidCharacter {
+            character: c as char,
+            index,
+        }),
+    }
+}
+
+/// Replace the leading zeros of the `len` decoded bytes at the start of `output` with enough to
+/// pad the value to `max_bytes`, see [`DecodeBuilder::order_preserving`].
+fn unpad_ordered(output: &mut [u8], len: usize, max_bytes: usize) -> Result<usize> {
+    let zeros = output[..len].iter().take_while(|&&b| b == 0).count();
+    let digits = len - zeros;
+    if digits > max_bytes {
+        return Err(Error::Overflow);
+    }
+    if output.len() < max_bytes {
+        return Err(Error::BufferTooSmall);
+    }
+    let pad = max_bytes - digits;
+    output.copy_within(zeros..len, pad);
+    output[..pad].fill(0);
+    Ok(max_bytes)
+}
+
+/// Append `zeros` zero bytes for the leading zero characters to the `len` little-endian bytes at
+/// the start of `output` and put them in big-endian order, returning the total length.
+fn finish_decode(len: usize, zeros: usize, output: &mut [u8]) -> Result<usize> {
+    let total = len + zeros;
+    output
+        .get_mut(len..total)
+        .ok_or(Error::BufferTooSmall)?
+        .fill(0);
+    output[..total].reverse();
+    Ok(total)
+}
+
+/// The number of decoded bytes that are removed from the output when verifying the checksum (if
+/// any) of a payload of `payload_len` bytes.
+#[cfg_attr(not(feature = "check"), allow(unused_variables))]
+fn check_overhead(check: &Check, payload_len: usize) -> usize {
+    match *check {
+        Check::Disabled => 0,
+        #[cfg(feature = "check")]
+        Check::Enabled(_) => CHECKSUM_LEN,
+        #[cfg(feature = "check")]
+        Check::Versioned(version) => version.len() + CHECKSUM_LEN,
+        #[cfg(feature = "check")]
+        Check::Blocks(block_size) => payload_len.div_ceil(block_size) * CHECKSUM_LEN,
+        #[cfg(feature = "cb58")]
+        Check::CB58(_) => CHECKSUM_LEN,
+    }
+}
+
+/// Check the decoded bytes are the canonical encoding of a payload of `expected_len` bytes (if
+/// given), see [`DecodeBuilder::canonical`].
+fn check_canonical(expected_len: Option<usize>, check: &Check, output: &[u8]) -> Result<()> {
+    let Some(expected_len) = expected_len else {
+        return Ok(());
+    };
+
+    let expected_len = expected_len + check_overhead(check, expected_len);
+
+    // Each leading zero character decodes to exactly one leading zero byte
+    let zeros = output.iter().take_while(|&&b| b == 0).count();
+    let rule = if output.len() - zeros > expected_len {
+        CanonicalRule::Length
+    } else if output.len() != expected_len {
+        CanonicalRule::LeadingZeros
+    } else {
+        return Ok(());
+    };
+
+    Err(Error::NonCanonical {
+        rule,
+        expected_len,
+        found_len: output.len(),
+    })
+}
+
+/// Verify the checksum (if any) of the decoded bytes, a Base58Check checksum also covering `aad`
+/// before them, returning the length of the payload. If the version is being removed the payload
+/// is moved to the start of the output.
+#[cfg_attr(not(feature = "check"), allow(unused_variables))]
+fn verify(check: &Check, aad: &[u8], output: &mut [u8]) -> Result<usize> {
+    match *check {
+        Check::Disabled => Ok(output.len()),
+        #[cfg(feature = "check")]
+        Check::Enabled(expected_ver) => verify_check(aad, output, expected_ver),
+        #[cfg(feature = "check")]
+        Check::Versioned(version) => {
+            let checksum_index = verify_check(aad, output, None)?;
+            if checksum_index < version.len() {
+                return Err(Error::NoChecksum);
+            }
+            for (&ver, &expected_ver) in output.iter().zip(version) {
+                if ver != expected_ver {
+                    return Err(Error::InvalidVersion { ver, expected_ver });
+                }
+            }
+            output.copy_within(version.len()..checksum_index, 0);
+            Ok(checksum_index - version.len())
+        }
+        #[cfg(feature = "check")]
+        Check::Blocks(block_size) => verify_blocks(output, block_size),
+        #[cfg(feature = "cb58")]
+        Check::CB58(expected_ver) => verify_cb58(output, expected_ver),
+    }
+}
+
+#[cfg(feature = "check")]
+pub(crate) fn verify_check(aad: &[u8], output: &[u8], expected_ver: Option<u8>) -> Result<usize> {
     use sha2::{Digest, Sha256};
 
-    let decoded_len = decode_into(input, output, alpha)?;
+    let decoded_len = output.len();
     if decoded_len < CHECKSUM_LEN {
         return Err(Error::NoChecksum);
     }
@@ -530,12 +4612,19 @@ fn decode_check_into(
 
     let expected_checksum = &output[checksum_index..decoded_len];
 
-    let first_hash = Sha256::digest(&output[0..checksum_index]);
+    let first_hash = Sha256::new()
+        .chain_update(aad)
+        .chain_update(&output[0..checksum_index])
+        .finalize();
     let second_hash = Sha256::digest(first_hash);
     let (checksum, _) = second_hash.split_at(CHECKSUM_LEN);
 
     if checksum == expected_checksum {
         if let Some(ver) = expected_ver {
+            if checksum_index == 0 {
+                // Only a checksum, there is no version byte to compare
+                return Err(Error::NoChecksum);
+            }
             if output[0] == ver {
                 Ok(checksum_index)
             } else {
@@ -559,16 +4648,39 @@ fn decode_check_into(
     }
 }
 
+/// Verify and remove the checksum after each block, see [`DecodeBuilder::with_block_checksums`].
+#[cfg(feature = "check")]
+fn verify_blocks(output: &mut [u8], block_size: usize) -> Result<usize> {
+    use sha2::{Digest, Sha256};
+
+    let mut len = 0;
+    for (block_index, start) in (0..output.len())
+        .step_by(block_size + CHECKSUM_LEN)
+        .enumerate()
+    {
+        let end = output.len().min(start + block_size + CHECKSUM_LEN);
+        if end - start <= CHECKSUM_LEN {
+            return Err(Error::NoChecksum);
+        }
+        let checksum_index = end - CHECKSUM_LEN;
+
+        let first_hash = Sha256::digest(&output[start..checksum_index]);
+        let second_hash = Sha256::digest(first_hash);
+        if second_hash[..CHECKSUM_LEN] != output[checksum_index..end] {
+            return Err(Error::BlockChecksumFailed { block_index });
+        }
+
+        output.copy_within(start..checksum_index, len);
+        len += checksum_index - start;
+    }
+    Ok(len)
+}
+
 #[cfg(feature = "cb58")]
-fn decode_cb58_into(
-    input: &[u8],
-    output: &mut [u8],
-    alpha: &Alphabet,
-    expected_ver: Option<u8>,
-) -> Result<usize> {
+fn verify_cb58(output: &[u8], expected_ver: Option<u8>) -> Result<usize> {
     use sha2::{Digest, Sha256};
 
-    let decoded_len = decode_into(input, output, alpha)?;
+    let decoded_len = output.len();
     if decoded_len < CHECKSUM_LEN {
         return Err(Error::NoChecksum);
     }
@@ -581,6 +4693,10 @@ fn decode_cb58_into(
 
     if checksum == expected_checksum {
         if let Some(ver) = expected_ver {
+            if checksum_index == 0 {
+                // Only a checksum, there is no version byte to compare
+                return Err(Error::NoChecksum);
+            }
             if output[0] == ver {
                 Ok(checksum_index)
             } else {
@@ -604,14 +4720,108 @@ fn decode_cb58_into(
     }
 }
 
-const fn get_decoded_len(input: &[u8], alpha: &Alphabet) -> Result<usize> {
-    let zero = alpha.encode[0];
+/// The table of the CRC-32 of each byte value, for [`crc32`] to process a byte at a time.
+#[cfg(feature = "crc")]
+const CRC32_TABLE: [u32; 256] = {
+    let mut table = [0; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 == 1 {
+                crc >> 1 ^ 0xedb8_8320
+            } else {
+                crc >> 1
+            };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+};
+
+/// The CRC-32 of `bytes`, see [`DecodeBuilder::into_vec_with_crc32`].
+#[cfg(feature = "crc")]
+fn crc32(bytes: &[u8]) -> u32 {
+    !bytes.iter().fold(!0, |crc, &byte| {
+        CRC32_TABLE[usize::from(crc as u8 ^ byte)] ^ crc >> 8
+    })
+}
+
+/// The index of the first byte of the UTF-8 character containing the byte at `index`, or `index`
+/// itself if it isn't part of a valid UTF-8 character.
+fn char_start(input: &[u8], index: usize) -> usize {
+    let is_continuation = |byte: u8| byte & 0xc0 == 0x80;
+    (index.saturating_sub(3)..index)
+        .rev()
+        .take_while(|&i| is_continuation(input[i + 1]))
+        .find(|&i| core::str::from_utf8(&input[i..=index]).is_ok())
+        .unwrap_or(index)
+}
+
+/// The index just after the UTF-8 character starting at `index`, or `index + 1` if there isn't a
+/// valid UTF-8 character there.
+#[cfg(feature = "alloc")]
+fn char_end(input: &[u8], index: usize) -> usize {
+    (index + 1..input.len().min(index + 4) + 1)
+        .find(|&end| core::str::from_utf8(&input[index..end]).is_ok())
+        .unwrap_or(index + 1)
+}
+
+/// Check every character of `input` is in the alphabet, without branching on or indexing by the
+/// characters, returning the error for the first invalid character in the order of `input`, see
+/// [`DecodeBuilder::into_vec_constant_time`]. `offset` is the index of `input` in the original
+/// input.
+#[cfg(feature = "subtle")]
+fn validate_constant_time<'i>(
+    input: impl Iterator<Item = (usize, &'i u8)>,
+    offset: usize,
+    alpha: &Alphabet,
+) -> Result<()> {
+    use subtle::{ConditionallySelectable, ConstantTimeEq, ConstantTimeGreater};
+
+    let mut failed = subtle::Choice::from(0);
+    let mut character = 0u8;
+    let mut index = 0u64;
+    let mut leading = subtle::Choice::from(1);
+    for (i, &c) in input {
+        let found = alpha
+            .encode
+            .iter()
+            .fold(subtle::Choice::from(0), |found, digit| {
+                found | c.ct_eq(digit)
+            });
+        // The lead character is only valid in the leading run of zero characters
+        leading &= c.ct_eq(&alpha.encode[0]) | c.ct_eq(&alpha.lead_char());
+        let found = found | leading;
+        let first = !found & !failed;
+        character.conditional_assign(&c, first);
+        index.conditional_assign(&(i as u64), first);
+        failed |= !found;
+    }
+
+    if !bool::from(failed) {
+        return Ok(());
+    }
+    let index = offset + index as usize;
+    if bool::from(character.ct_gt(&127)) {
+        Err(Error::NonAsciiCharacter { index })
+    } else {
+        Err(Error::InvalidCharacter {
+            character: character as char,
+            index,
+        })
+    }
+}
 
+const fn get_decoded_len(input: &[u8], alpha: &Alphabet) -> Result<usize> {
     let mut index = 0;
     let mut val = 0;
     let mut i = 0;
 
-    while i < input.len() && input[i] == zero {
+    while i < input.len() && alpha.is_leading_zero(input[i]) {
         index += 1;
         i += 1;
     }
@@ -622,8 +4832,8 @@ const fn get_decoded_len(input: &[u8], alpha: &Alphabet) -> Result<usize> {
             return Err(Error::NonAsciiCharacter { index: i });
         }
 
-        let byte = alpha.decode[c as usize] as usize;
-        if byte == 0xFF {
+        let byte = alpha.digit(c) as usize;
+        if byte == Alphabet::INVALID as usize {
             return Err(Error::InvalidCharacter {
                 character: c as char,
                 index: i,
@@ -649,45 +4859,27 @@ const fn get_decoded_len(input: &[u8], alpha: &Alphabet) -> Result<usize> {
 const fn decode_into_const<const N: usize>(input: &[u8], alpha: &Alphabet) -> Result<[u8; N]> {
     let mut output = [0u8; N];
     let mut index = 0;
-    let zero = alpha.encode[0];
 
     let mut i = 0;
-    while i < input.len() {
-        let c = input[i];
-        if c > 127 {
-            return Err(Error::NonAsciiCharacter { index: i });
-        }
-
-        let mut val = alpha.decode[c as usize] as usize;
-        if val == 0xFF {
-            return Err(Error::InvalidCharacter {
-                character: c as char,
-                index: i,
-            });
-        }
-
-        let mut j = 0;
-        while j < index {
-            let byte = output[j];
-            val += (byte as usize) * 58;
-            output[j] = (val & 0xFF) as u8;
-            val >>= 8;
-            j += 1;
-        }
+    while i < input.len() && alpha.is_leading_zero(input[i]) {
+        i += 1;
+    }
+    let zeros = i;
 
-        while val > 0 {
-            if index >= output.len() {
-                return Err(Error::BufferTooSmall);
-            }
-            output[index] = (val & 0xFF) as u8;
-            index += 1;
-            val >>= 8
-        }
+    while i < input.len() {
+        let digit = match digit_value(i, input[i], alpha) {
+            Ok(digit) => digit,
+            Err(err) => return Err(err),
+        };
+        index = match raw::push_digit(&mut output, index, digit) {
+            Some(index) => index,
+            None => return Err(Error::BufferTooSmall),
+        };
         i += 1;
     }
 
     let mut i = 0;
-    while i < input.len() && input[i] == zero {
+    while i < zeros {
         if index >= output.len() {
             return Err(Error::BufferTooSmall);
         }
@@ -712,45 +4904,437 @@ const fn decode_into_const<const N: usize>(input: &[u8], alpha: &Alphabet) -> Re
 #[cfg(feature = "std")]
 impl std::error::Error for Error {}
 
-impl fmt::Display for Error {
+#[cfg(feature = "std")]
+impl std::error::Error for DelimitedError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+/// Converts into an error of kind [`InvalidData`](std::io::ErrorKind::InvalidData), or for
+/// [`BufferTooSmall`](Error::BufferTooSmall) [`WriteZero`](std::io::ErrorKind::WriteZero) and for
+/// [`AllocationFailed`](Error::AllocationFailed) [`OutOfMemory`](std::io::ErrorKind::OutOfMemory),
+/// with this error as the source so it can be recovered with
+/// [`get_ref`](std::io::Error::get_ref).
+#[cfg(feature = "std")]
+impl From<Error> for std::io::Error {
+    fn from(err: Error) -> Self {
+        let kind = match err {
+            Error::BufferTooSmall => std::io::ErrorKind::WriteZero,
+            Error::AllocationFailed => std::io::ErrorKind::OutOfMemory,
+            _ => std::io::ErrorKind::InvalidData,
+        };
+        std::io::Error::new(kind, err)
+    }
+}
+
+/// Converts into an error of the same kind as converting the [field's error](DelimitedError::error).
+#[cfg(feature = "std")]
+impl From<DelimitedError> for std::io::Error {
+    fn from(err: DelimitedError) -> Self {
+        std::io::Error::new(std::io::Error::from(err.error).kind(), err)
+    }
+}
+
+impl fmt::Display for CanonicalRule {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match *self {
-            Error::BufferTooSmall => write!(
+            CanonicalRule::Length => write!(f, "value too long"),
+            CanonicalRule::LeadingZeros => write!(f, "wrong number of leading zeros"),
+        }
+    }
+}
+
+impl fmt::Display for DelimitedError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "failed to decode field {}: {}", self.field, self.error)
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.report().fmt(f)
+    }
+}
+
+impl fmt::Display for ErrorReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let index = self.index.unwrap_or_default();
+        let expected = self.expected.unwrap_or_default();
+        let found = self.found.unwrap_or_default();
+        match self.code {
+            ErrorCode::BufferTooSmall => write!(
                 f,
                 "buffer provided to decode base58 encoded string into was too small"
             ),
-            Error::InvalidCharacter { character, index } => write!(
+            ErrorCode::InvalidCharacter => write!(
                 f,
-                "provided string contained invalid character {:?} at byte {}",
-                character, index
+                "provided string contained invalid character {} at byte {}",
+                Printable(self.character.unwrap_or_default()),
+                index
             ),
-            Error::NonAsciiCharacter { index } => write!(
+            ErrorCode::NonAsciiCharacter => write!(
                 f,
                 "provided string contained non-ascii character starting at byte {}",
                 index
             ),
-            #[cfg(any(feature = "check", feature = "cb58"))]
-            Error::InvalidChecksum {
-                checksum,
-                expected_checksum,
-            } => write!(
+            ErrorCode::WrongLength => write!(
+                f,
+                "decoded data was the wrong length, expected {} bytes but found {}",
+                expected, found
+            ),
+            ErrorCode::AllocationFailed => {
+                write!(f, "failed to allocate space for the decoded data")
+            }
+            ErrorCode::ZeroValue => write!(f, "decoded value was zero"),
+            ErrorCode::ParseFailed => write!(f, "decoded data could not be parsed"),
+            ErrorCode::EmptyField => write!(f, "delimited field was empty"),
+            ErrorCode::WrongPrefix => write!(f, "input did not start with the expected prefix"),
+            ErrorCode::Overflow => write!(f, "decoded value was too large"),
+            ErrorCode::NonCanonicalLength | ErrorCode::NonCanonicalLeadingZeros => {
+                let rule = match self.code {
+                    ErrorCode::NonCanonicalLength => CanonicalRule::Length,
+                    _ => CanonicalRule::LeadingZeros,
+                };
+                write!(
+                    f,
+                    "input was not canonical ({rule}), decoded to {found} bytes, expected {expected} bytes"
+                )
+            }
+            ErrorCode::InvalidChecksum => write!(
                 f,
                 "invalid checksum, calculated checksum: '{:?}', expected checksum: {:?}",
-                checksum, expected_checksum
+                (expected as u32).to_be_bytes(),
+                (found as u32).to_be_bytes()
             ),
-            #[cfg(any(feature = "check", feature = "cb58"))]
-            Error::InvalidVersion { ver, expected_ver } => write!(
+            ErrorCode::InvalidVersion => write!(
                 f,
                 "invalid version, payload version: '{:?}', expected version: {:?}",
-                ver, expected_ver
+                found, expected
             ),
-            #[cfg(any(feature = "check", feature = "cb58"))]
-            Error::NoChecksum => write!(f, "provided string is too small to contain a checksum"),
+            ErrorCode::NoChecksum => {
+                write!(f, "provided string is too small to contain a checksum")
+            }
+            ErrorCode::Unclassified => write!(
+                f,
+                "no expected kind has version {:#04x} and a {} byte payload",
+                self.version.unwrap_or_default(),
+                found
+            ),
+            ErrorCode::BlockChecksumFailed => {
+                write!(f, "invalid checksum for block {found}")
+            }
+            ErrorCode::AmbiguousFold => write!(
+                f,
+                "provided string contained character {} at byte {} which is the display form of \
+                 more than one character of the alphabet",
+                Printable(self.character.unwrap_or_default()),
+                index
+            ),
+            ErrorCode::PatternMismatch => {
+                write!(f, "decoded byte {found} did not match the pattern")
+            }
+            ErrorCode::UnknownScheme => write!(f, "unknown alphabet or mode in scheme"),
+            ErrorCode::EmptyPayload => write!(f, "decoded payload was empty"),
+            ErrorCode::Truncated => write!(
+                f,
+                "provided string ended part way through the field at byte {index}"
+            ),
+            ErrorCode::DisallowedLength => write!(
+                f,
+                "decoded length {found} was not one of the allowed lengths {:?}",
+                self.allowed.unwrap_or_default()
+            ),
+            ErrorCode::WrongSalt => write!(f, "decoded data did not start with the expected salt"),
+        }
+    }
+}
+
+/// The names of the ASCII control characters below space.
+const CONTROL_NAMES: [&str; 32] = [
+    "NUL", "SOH", "STX", "ETX", "EOT", "ENQ", "ACK", "BEL", "BS", "HT", "LF", "VT", "FF", "CR",
+    "SO", "SI", "DLE", "DC1", "DC2", "DC3", "DC4", "NAK", "SYN", "ETB", "CAN", "EM", "SUB", "ESC",
+    "FS", "GS", "RS", "US",
+];
+
+/// Displays a character readably in error messages: quoted, with tab, newline and carriage
+/// return escaped and other control characters by name and value, e.g. `NUL (0x00)`.
+struct Printable(char);
+
+impl fmt::Display for Printable {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.0 {
+            '\t' | '\n' | '\r' => write!(f, "{:?}", self.0),
+            '\x7f' => write!(f, "DEL (0x7f)"),
+            c if c < ' ' => write!(f, "{} ({:#04x})", CONTROL_NAMES[c as usize], c as u32),
+            c => write!(f, "{c:?}"),
         }
     }
 }
 
+#[cfg(feature = "serde")]
+impl serde::Serialize for ErrorReport {
+    fn serialize<S: serde::Serializer>(
+        &self,
+        serializer: S,
+    ) -> core::result::Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        let mut report = serializer.serialize_struct("ErrorReport", 7)?;
+        report.serialize_field("code", &self.code)?;
+        report.serialize_field("index", &self.index)?;
+        report.serialize_field("character", &self.character)?;
+        report.serialize_field("expected", &self.expected)?;
+        report.serialize_field("found", &self.found)?;
+        report.serialize_field("version", &self.version)?;
+        report.serialize_field("allowed", &self.allowed)?;
+        report.end()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for ErrorCode {
+    fn serialize<S: serde::Serializer>(
+        &self,
+        serializer: S,
+    ) -> core::result::Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+/// Characters commonly mistaken for others, along with the characters they may have been intended
+/// as. See [`Error::suggest_correction`].
+#[cfg(feature = "alloc")]
+const CONFUSABLES: [(char, &[char]); 4] = [
+    ('0', &['o']),
+    ('O', &['o']),
+    ('I', &['1', 'i']),
+    ('l', &['1']),
+];
+
 impl Error {
+    /// A structured view of this error with a stable shape, see [`ErrorReport`].
+    pub fn report(&self) -> ErrorReport {
+        let report = |code| ErrorReport {
+            code,
+            index: None,
+            character: None,
+            expected: None,
+            found: None,
+            version: None,
+            allowed: None,
+        };
+        let lengths = |code, expected: usize, found: usize| ErrorReport {
+            expected: Some(expected as u64),
+            found: Some(found as u64),
+            ..report(code)
+        };
+        match *self {
+            Error::BufferTooSmall => report(ErrorCode::BufferTooSmall),
+            Error::InvalidCharacter { character, index } => ErrorReport {
+                index: Some(index),
+                character: Some(character),
+                ..report(ErrorCode::InvalidCharacter)
+            },
+            Error::NonAsciiCharacter { index } => ErrorReport {
+                index: Some(index),
+                ..report(ErrorCode::NonAsciiCharacter)
+            },
+            Error::WrongLength { expected, found } => {
+                lengths(ErrorCode::WrongLength, expected, found)
+            }
+            Error::AllocationFailed => report(ErrorCode::AllocationFailed),
+            Error::ZeroValue => report(ErrorCode::ZeroValue),
+            Error::ParseFailed => report(ErrorCode::ParseFailed),
+            Error::EmptyField => report(ErrorCode::EmptyField),
+            Error::WrongPrefix => report(ErrorCode::WrongPrefix),
+            Error::Overflow => report(ErrorCode::Overflow),
+            Error::NonCanonical {
+                rule,
+                expected_len,
+                found_len,
+            } => {
+                let code = match rule {
+                    CanonicalRule::Length => ErrorCode::NonCanonicalLength,
+                    CanonicalRule::LeadingZeros => ErrorCode::NonCanonicalLeadingZeros,
+                };
+                lengths(code, expected_len, found_len)
+            }
+            Error::AmbiguousFold { character, index } => ErrorReport {
+                index: Some(index),
+                character: Some(character),
+                ..report(ErrorCode::AmbiguousFold)
+            },
+            Error::PatternMismatch { index } => ErrorReport {
+                found: Some(index as u64),
+                ..report(ErrorCode::PatternMismatch)
+            },
+            Error::UnknownScheme => report(ErrorCode::UnknownScheme),
+            Error::EmptyPayload => report(ErrorCode::EmptyPayload),
+            Error::Truncated { index } => ErrorReport {
+                index: Some(index),
+                ..report(ErrorCode::Truncated)
+            },
+            Error::DisallowedLength { allowed, found } => ErrorReport {
+                found: Some(found as u64),
+                allowed: Some(allowed),
+                ..report(ErrorCode::DisallowedLength)
+            },
+            Error::WrongSalt => report(ErrorCode::WrongSalt),
+            #[cfg(any(feature = "check", feature = "cb58"))]
+            Error::InvalidChecksum {
+                checksum,
+                expected_checksum,
+            } => ErrorReport {
+                expected: Some(u32::from_be_bytes(checksum).into()),
+                found: Some(u32::from_be_bytes(expected_checksum).into()),
+                ..report(ErrorCode::InvalidChecksum)
+            },
+            #[cfg(any(feature = "check", feature = "cb58"))]
+            Error::InvalidVersion { ver, expected_ver } => ErrorReport {
+                expected: Some(expected_ver.into()),
+                found: Some(ver.into()),
+                ..report(ErrorCode::InvalidVersion)
+            },
+            #[cfg(any(feature = "check", feature = "cb58"))]
+            Error::NoChecksum => report(ErrorCode::NoChecksum),
+            #[cfg(any(feature = "check", feature = "cb58"))]
+            Error::Unclassified {
+                version,
+                payload_len,
+            } => ErrorReport {
+                found: Some(payload_len as u64),
+                version: Some(version),
+                ..report(ErrorCode::Unclassified)
+            },
+            #[cfg(feature = "check")]
+            Error::BlockChecksumFailed { block_index } => ErrorReport {
+                found: Some(block_index as u64),
+                ..report(ErrorCode::BlockChecksumFailed)
+            },
+        }
+    }
+
+    /// This error with any character index moved `offset` bytes later, for errors decoding part
+    /// of a larger input.
+    fn offset_index(self, offset: usize) -> Error {
+        match self {
+            Error::InvalidCharacter { character, index } => Error::InvalidCharacter {
+                character,
+                index: index + offset,
+            },
+            Error::NonAsciiCharacter { index } => Error::NonAsciiCharacter {
+                index: index + offset,
+            },
+            Error::AmbiguousFold { character, index } => Error::AmbiguousFold {
+                character,
+                index: index + offset,
+            },
+            error => error,
+        }
+    }
+
+    /// For an [`InvalidCharacter`](Error::InvalidCharacter) error caused by a commonly confused
+    /// character, return `input` with that character corrected, for "did you mean?" messages.
+    ///
+    /// The corrections considered are:
+    ///
+    ///  Character | Intended as
+    /// -----------|-------------
+    ///  `0`       | `o`
+    ///  `O`       | `o`
+    ///  `I`       | `1` or `i`
+    ///  `l`       | `1`
+    ///
+    /// A correction is only suggested when exactly one of the intended characters makes the whole
+    /// input valid for the [default alphabet](Alphabet::DEFAULT), so this returns `None` if there
+    /// are other invalid characters, or if the correction is ambiguous (as `I` is, both `1` and `i`
+    /// are in the default alphabet). Checksums are not verified, so a suggestion is not necessarily
+    /// the intended value.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let input = "he11owor1d0";
+    /// let err = bs58::decode(input).into_vec().unwrap_err();
+    /// assert_eq!(Some("he11owor1do".to_owned()), err.suggest_correction(input));
+    ///
+    /// let input = "he11owor1dI";
+    /// let err = bs58::decode(input).into_vec().unwrap_err();
+    /// assert_eq!(None, err.suggest_correction(input));
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn suggest_correction(&self, input: &str) -> Option<alloc::string::String> {
+        let Error::InvalidCharacter { character, index } = *self else {
+            return None;
+        };
+        // The error may not have come from this input
+        if !input.get(index..)?.starts_with(character) {
+            return None;
+        }
+
+        let (_, intended) = CONFUSABLES.iter().find(|(c, _)| *c == character)?;
+        let mut corrections = intended.iter().filter_map(|&c| {
+            let mut corrected = alloc::string::String::with_capacity(input.len());
+            corrected.push_str(&input[..index]);
+            corrected.push(c);
+            corrected.push_str(&input[index + character.len_utf8()..]);
+            crate::decode(&corrected).into_vec().ok()?;
+            Some(corrected)
+        });
+
+        let correction = corrections.next()?;
+        if corrections.next().is_some() {
+            return None;
+        }
+        Some(correction)
+    }
+
+    /// For an error at a character of `input`, return a snippet of up to 10 characters of
+    /// `input` around it, for logging where decoding failed without logging all of a possibly
+    /// long input.
+    ///
+    /// The snippet is the character the error occurred at, up to 5 characters before it and up to
+    /// 4 characters after it, with control characters, quotes and backslashes escaped as by
+    /// [`str::escape_debug`] so it can be logged on one line. This returns `None` for errors
+    /// that are not at a character, or if `index` is not a character of `input`. The error itself
+    /// only holds the index so that it stays [`Copy`], the input it came from must be passed in.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let input = "2NEpo7TZRRrLZSi2U0xyz3m3FLi";
+    /// let err = bs58::decode(input).into_vec().unwrap_err();
+    /// assert_eq!(Some("ZSi2U0xyz3".to_owned()), err.context(input));
+    ///
+    /// let input = "he\n11o";
+    /// let err = bs58::decode(input).into_vec().unwrap_err();
+    /// assert_eq!(Some(r"he\n11o".to_owned()), err.context(input));
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn context(&self, input: &str) -> Option<alloc::string::String> {
+        let index = match *self {
+            Error::InvalidCharacter { index, .. }
+            | Error::NonAsciiCharacter { index }
+            | Error::AmbiguousFold { index, .. } => index,
+            _ => return None,
+        };
+        if index >= input.len() || !input.is_char_boundary(index) {
+            return None;
+        }
+
+        let start = input[..index]
+            .char_indices()
+            .rev()
+            .nth(4)
+            .map_or(0, |(i, _)| i);
+        let end = input[index..]
+            .char_indices()
+            .nth(5)
+            .map_or(input.len(), |(i, _)| index + i);
+        Some(input[start..end].escape_debug().collect())
+    }
+
     /// Panic with an error message based on this error. This cannot include any of the dynamic
     /// content because formatting in `const` is not yet possible.
     pub const fn unwrap_const(self) -> ! {
@@ -762,12 +5346,35 @@ impl Error {
             Error::NonAsciiCharacter { .. } => {
                 panic!("provided string contained non-ascii character")
             }
+            Error::WrongLength { .. } => panic!("decoded data was the wrong length"),
+            Error::AllocationFailed => panic!("failed to allocate space for the decoded data"),
+            Error::ZeroValue => panic!("decoded value was zero"),
+            Error::ParseFailed => panic!("decoded data could not be parsed"),
+            Error::EmptyField => panic!("delimited field was empty"),
+            Error::WrongPrefix => panic!("input did not start with the expected prefix"),
+            Error::Overflow => panic!("decoded value was too large"),
+            Error::NonCanonical { .. } => panic!("input was not canonical"),
+            Error::AmbiguousFold { .. } => {
+                panic!("provided string contained an ambiguous display character")
+            }
+            Error::PatternMismatch { .. } => panic!("decoded data did not match the pattern"),
+            Error::UnknownScheme => panic!("unknown alphabet or mode in scheme"),
+            Error::EmptyPayload => panic!("decoded payload was empty"),
+            Error::Truncated { .. } => panic!("provided string ended part way through a field"),
+            Error::DisallowedLength { .. } => {
+                panic!("decoded length was not one of the allowed lengths")
+            }
+            Error::WrongSalt => panic!("decoded data did not start with the expected salt"),
             #[cfg(any(feature = "check", feature = "cb58"))]
             Error::InvalidChecksum { .. } => panic!("invalid checksum"),
             #[cfg(any(feature = "check", feature = "cb58"))]
             Error::InvalidVersion { .. } => panic!("invalid version"),
             #[cfg(any(feature = "check", feature = "cb58"))]
             Error::NoChecksum => panic!("provided string is too small to contain a checksum"),
+            #[cfg(any(feature = "check", feature = "cb58"))]
+            Error::Unclassified { .. } => panic!("decoded data matched no expected kind"),
+            #[cfg(feature = "check")]
+            Error::BlockChecksumFailed { .. } => panic!("invalid block checksum"),
         }
     }
 }
@@ -1,15 +1,20 @@
 //! Functions for encoding into Base58 encoded strings.
 
-use core::fmt;
+use core::{fmt, mem::MaybeUninit};
 
 #[cfg(feature = "alloc")]
 use alloc::{string::String, vec::Vec};
 
-use crate::Check;
 #[cfg(any(feature = "check", feature = "cb58"))]
 use crate::CHECKSUM_LEN;
+use crate::{raw, Check, Record};
 
-use crate::Alphabet;
+#[cfg(feature = "metrics")]
+use crate::metrics::Stats;
+use crate::{
+    alphabet::{Fold, StaticAlphabet, StaticAlphabetExt},
+    max_encoded_len, Alphabet,
+};
 
 /// A builder for setting up the alphabet and output of a base58 encode.
 #[allow(missing_debug_implementations)]
@@ -17,6 +22,99 @@ pub struct EncodeBuilder<'a, I: AsRef<[u8]>> {
     input: I,
     alpha: &'a Alphabet,
     check: Check,
+    #[cfg(feature = "check")]
+    aad: &'a [u8],
+    prefix: &'a str,
+    hrp_separator: Option<char>,
+    max_input: Option<usize>,
+    ordered: bool,
+    fold: Option<Fold>,
+    #[cfg(feature = "unicode-normalization")]
+    nfc_prefix: bool,
+    #[cfg(feature = "metrics")]
+    stats: Option<&'a core::cell::Cell<Stats>>,
+}
+
+/// A reusable configuration of the alphabet, checksum and prefix for base58 encodes, independent
+/// of the input.
+///
+/// This is cheap to copy and can be built in a `const` or `static`, then used to setup an
+/// [`EncodeBuilder`] for each input with [`with_input`](Self::with_input).
+///
+/// # Examples
+///
+/// ```rust
+/// use bs58::{encode::EncodeConfig, Alphabet};
+///
+/// static ENCODER: EncodeConfig = EncodeConfig::new(Alphabet::RIPPLE).with_text_prefix("r:");
+///
+/// let input = [0x60, 0x65, 0xe7, 0x9b, 0xba, 0x2f, 0x78];
+/// assert_eq!("r:he11owor1d", ENCODER.with_input(input).into_string());
+/// ```
+#[derive(Clone, Copy)]
+#[allow(missing_debug_implementations)]
+pub struct EncodeConfig<'a> {
+    alpha: &'a Alphabet,
+    check: Check,
+    prefix: &'a str,
+}
+
+/// A builder for setting up the alphabet and output of a base58 encode of bytes that are produced
+/// on demand, see [`from_fn`].
+#[allow(missing_debug_implementations)]
+pub struct FnEncodeBuilder<'a, F: Fn(usize) -> u8> {
+    len: usize,
+    f: F,
+    alpha: &'a Alphabet,
+}
+
+/// A reusable buffer for encoding many inputs one after another, see
+/// [`EncodeBuilder::with_scratch`].
+///
+/// The buffer grows to fit the longest output encoded so far and is kept between encodes, so once
+/// it has reached that size encoding doesn't allocate.
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug, Default)]
+pub struct Scratch {
+    buffer: String,
+}
+
+/// Assembles the bytes of a payload from its fields, then encodes them once, see
+/// [`finish`](Self::finish).
+///
+/// # Examples
+///
+/// ```rust
+/// use bs58::encode::PayloadBuilder;
+///
+/// let encoded = PayloadBuilder::new()
+///     .push_u8(0x04)
+///     .push_bytes(&[0x30, 0x5e])
+///     .push_u32_be(0x2b24_73f0)
+///     .push_u8(0x58)
+///     .finish()
+///     .into_string();
+/// assert_eq!("he11owor1d", encoded);
+/// ```
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug, Default)]
+pub struct PayloadBuilder {
+    payload: Vec<u8>,
+}
+
+/// Statistics about how an input encodes, see [`EncodeBuilder::analyze`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[non_exhaustive]
+pub struct EncodeStats {
+    /// The length of the input in bytes.
+    pub input_len: usize,
+    /// The length of the encoded string in bytes, including any text prefix and checksum.
+    pub output_len: usize,
+    /// The number of zero bytes at the start of the input, each of which encodes to a zero
+    /// character.
+    pub leading_zero_bytes: usize,
+    /// The output length divided by the input length, or `0.0` for empty input.
+    pub expansion_ratio: f64,
 }
 
 /// A specialized [`Result`](core::result::Result) type for [`bs58::encode`](module@crate::encode)
@@ -28,8 +126,26 @@ pub type Result<T> = core::result::Result<T, Error>;
 pub enum Error {
     /// The output buffer was too small to contain the entire input.
     BufferTooSmall,
+
+    /// Allocating space for the output failed, see [`EncodeBuilder::try_into_vec`].
+    ///
+    /// The `TryReserveError` from the allocation isn't included, as this error type is `Copy`
+    /// and that one isn't.
+    AllocationFailed,
+
+    /// The input was longer than the limit set with [`EncodeBuilder::with_max_input`].
+    InputTooLarge {
+        /// The maximum input length allowed.
+        limit: usize,
+        /// The length of the input given.
+        actual: usize,
+    },
 }
 
+/// The size of the stack buffer used by [`EncodeBuilder::starts_with`],
+/// [`EncodeBuilder::into_segments`] and [`EncodeBuilder::analyze`].
+const SCRATCH_STACK_LEN: usize = 128;
+
 /// Represents a buffer that can be encoded into. See [`EncodeBuilder::onto`] and the provided
 /// implementations for more details.
 pub trait EncodeTarget {
@@ -68,6 +184,24 @@ impl EncodeTarget for Vec<u8> {
     }
 }
 
+#[cfg(feature = "allocator-api2")]
+impl<A: allocator_api2::alloc::Allocator> EncodeTarget for allocator_api2::vec::Vec<u8, A> {
+    /// Encodes data into an [`allocator_api2::vec::Vec`], reserving exactly the space requested so only
+    /// memory from its allocator is used.
+    fn encode_with(
+        &mut self,
+        max_len: usize,
+        f: impl for<'a> FnOnce(&'a mut [u8]) -> Result<usize>,
+    ) -> Result<usize> {
+        let original = self.len();
+        self.reserve_exact(max_len);
+        self.resize(original + max_len, 0);
+        let len = f(&mut self[original..])?;
+        self.truncate(original + len);
+        Ok(len)
+    }
+}
+
 #[cfg(feature = "smallvec")]
 impl<A: smallvec::Array<Item = u8>> EncodeTarget for smallvec::SmallVec<A> {
     /// Encodes data into a [`smallvec::SmallVec`].
@@ -203,25 +337,259 @@ impl EncodeTarget for str {
     }
 }
 
+#[cfg(feature = "alloc")]
+impl Scratch {
+    /// An empty scratch buffer, which doesn't allocate until it is first used.
+    pub const fn new() -> Self {
+        Self {
+            buffer: String::new(),
+        }
+    }
+
+    /// The number of bytes that can be encoded into this before it needs to grow.
+    pub fn capacity(&self) -> usize {
+        self.buffer.capacity()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl PayloadBuilder {
+    /// An empty payload, which doesn't allocate until the first field is pushed.
+    pub const fn new() -> Self {
+        Self {
+            payload: Vec::new(),
+        }
+    }
+
+    /// Append a single byte.
+    pub fn push_u8(mut self, value: u8) -> Self {
+        self.payload.push(value);
+        self
+    }
+
+    /// Append `bytes` as they are, without a length.
+    pub fn push_bytes(mut self, bytes: &[u8]) -> Self {
+        self.payload.extend_from_slice(bytes);
+        self
+    }
+
+    /// Append the 2 big-endian bytes of `value`.
+    pub fn push_u16_be(self, value: u16) -> Self {
+        self.push_bytes(&value.to_be_bytes())
+    }
+
+    /// Append the 4 big-endian bytes of `value`.
+    pub fn push_u32_be(self, value: u32) -> Self {
+        self.push_bytes(&value.to_be_bytes())
+    }
+
+    /// Append the 8 big-endian bytes of `value`.
+    pub fn push_u64_be(self, value: u64) -> Self {
+        self.push_bytes(&value.to_be_bytes())
+    }
+
+    /// The bytes assembled so far.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.payload
+    }
+
+    /// Setup an encoder for the assembled payload using the
+    /// [default alphabet](Alphabet::DEFAULT), which can then be configured as any other, such as
+    /// with a different alphabet or a checksum.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let payload = bs58::encode::PayloadBuilder::new()
+    ///     .push_bytes(&[0x60, 0x65, 0xe7])
+    ///     .push_u32_be(0x9bba_2f78);
+    /// assert_eq!(
+    ///     "he11owor1d",
+    ///     payload.finish().with_alphabet(bs58::Alphabet::RIPPLE).into_string());
+    /// ```
+    pub fn finish(self) -> EncodeBuilder<'static, Vec<u8>> {
+        EncodeBuilder::from_input(self.payload)
+    }
+}
+
+impl<'a> EncodeConfig<'a> {
+    /// Setup a configuration using the given alphabet, without a checksum or prefix.
+    pub const fn new(alpha: &'a Alphabet) -> EncodeConfig<'a> {
+        EncodeConfig {
+            alpha,
+            check: Check::Disabled,
+            prefix: "",
+        }
+    }
+
+    /// Change the alphabet that will be used for encoding, see
+    /// [`EncodeBuilder::with_alphabet`].
+    pub const fn with_alphabet(self, alpha: &'a Alphabet) -> EncodeConfig<'a> {
+        EncodeConfig { alpha, ..self }
+    }
+
+    /// Include a [Base58Check][] checksum, see [`EncodeBuilder::with_check`].
+    ///
+    /// [Base58Check]: https://en.bitcoin.it/wiki/Base58Check_encoding
+    #[cfg(feature = "check")]
+    pub const fn with_check(self) -> EncodeConfig<'a> {
+        let check = Check::Enabled(None);
+        EncodeConfig { check, ..self }
+    }
+
+    /// Include a [Base58Check][] checksum and version, see
+    /// [`EncodeBuilder::with_check_version`].
+    ///
+    /// [Base58Check]: https://en.bitcoin.it/wiki/Base58Check_encoding
+    #[cfg(feature = "check")]
+    pub const fn with_check_version(self, expected_ver: u8) -> EncodeConfig<'a> {
+        let check = Check::Enabled(Some(expected_ver));
+        EncodeConfig { check, ..self }
+    }
+
+    /// Include a [Base58Check][] checksum and the version bytes of `V`, see
+    /// [`EncodeBuilder::with_check_version_typed`].
+    ///
+    /// [Base58Check]: https://en.bitcoin.it/wiki/Base58Check_encoding
+    #[cfg(feature = "check")]
+    pub const fn with_check_version_typed<V: crate::check::Version>(self) -> EncodeConfig<'a> {
+        let check = Check::Versioned(V::BYTES);
+        EncodeConfig { check, ..self }
+    }
+
+    /// Include a [CB58][] checksum and version (if specified), see [`EncodeBuilder::as_cb58`].
+    ///
+    /// [CB58]: https://support.avax.network/en/articles/4587395-what-is-cb58
+    #[cfg(feature = "cb58")]
+    pub const fn as_cb58(self, expected_ver: Option<u8>) -> EncodeConfig<'a> {
+        let check = Check::CB58(expected_ver);
+        EncodeConfig { check, ..self }
+    }
+
+    /// Prepend the literal text `prefix` to the encoded output, see
+    /// [`EncodeBuilder::with_text_prefix`].
+    pub const fn with_text_prefix(self, prefix: &'a str) -> EncodeConfig<'a> {
+        EncodeConfig { prefix, ..self }
+    }
+
+    /// Setup an encoder for `input` using this configuration.
+    pub const fn with_input<I: AsRef<[u8]>>(self, input: I) -> EncodeBuilder<'a, I> {
+        EncodeBuilder {
+            input,
+            alpha: self.alpha,
+            check: self.check,
+            #[cfg(feature = "check")]
+            aad: &[],
+            prefix: self.prefix,
+            hrp_separator: None,
+            max_input: None,
+            ordered: false,
+            fold: None,
+            #[cfg(feature = "unicode-normalization")]
+            nfc_prefix: false,
+            #[cfg(feature = "metrics")]
+            stats: None,
+        }
+    }
+
+    /// Encode `input` into a new owned string using this configuration.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bs58::{encode::EncodeConfig, Alphabet};
+    ///
+    /// const ENCODER: EncodeConfig = EncodeConfig::new(Alphabet::DEFAULT);
+    ///
+    /// let input = [0x04, 0x30, 0x5e, 0x2b, 0x24, 0x73, 0xf0, 0x58];
+    /// assert_eq!("he11owor1d", ENCODER.encode(input));
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn encode<I: AsRef<[u8]>>(self, input: I) -> String {
+        self.with_input(input).into_string()
+    }
+
+    /// Encode each of `items` into one string using this configuration, with `separator`
+    /// between them, see [`bs58::encode_join`](crate::encode_join()).
+    ///
+    /// # Panics
+    ///
+    /// If `separator` is empty, contains a character of the alphabet, or appears in the text
+    /// prefix.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bs58::{encode::EncodeConfig, Alphabet};
+    ///
+    /// const ENCODER: EncodeConfig = EncodeConfig::new(Alphabet::RIPPLE).with_text_prefix("r:");
+    ///
+    /// let input = [0x60, 0x65, 0xe7, 0x9b, 0xba, 0x2f, 0x78];
+    /// assert_eq!("r:he11owor1d, r:", ENCODER.join([&input[..], &[]], ", "));
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[track_caller]
+    pub fn join<I, T>(self, items: I, separator: &str) -> String
+    where
+        I: IntoIterator<Item = T>,
+        T: AsRef<[u8]>,
+    {
+        let mut output = String::new();
+        self.join_to(items, separator, &mut output).unwrap();
+        output
+    }
+
+    /// Encode each of `items` into `writer` using this configuration, with `separator` between
+    /// them, see [`bs58::encode_join`](crate::encode_join()).
+    ///
+    /// Each item is encoded into a scratch buffer which is reused for the next item, so this
+    /// only allocates once for the longest item.
+    ///
+    /// # Errors
+    ///
+    /// If writing to `writer` fails.
+    ///
+    /// # Panics
+    ///
+    /// If `separator` is empty, contains a character of the alphabet, or appears in the text
+    /// prefix.
+    #[cfg(feature = "alloc")]
+    #[track_caller]
+    pub fn join_to<I, T>(
+        self,
+        items: I,
+        separator: &str,
+        writer: &mut impl core::fmt::Write,
+    ) -> core::fmt::Result
+    where
+        I: IntoIterator<Item = T>,
+        T: AsRef<[u8]>,
+    {
+        crate::assert_separator(separator, self.alpha, self.prefix);
+        let mut scratch = Vec::new();
+        for (i, item) in items.into_iter().enumerate() {
+            if i > 0 {
+                writer.write_str(separator)?;
+            }
+            scratch.clear();
+            self.with_input(item).onto(&mut scratch).unwrap();
+            writer.write_str(core::str::from_utf8(&scratch).unwrap())?;
+        }
+        Ok(())
+    }
+}
+
 impl<'a, I: AsRef<[u8]>> EncodeBuilder<'a, I> {
     /// Setup encoder for the given string using the given alphabet.
     /// Preferably use [`bs58::encode`](crate::encode()) instead of this
     /// directly.
     pub fn new(input: I, alpha: &'a Alphabet) -> EncodeBuilder<'a, I> {
-        EncodeBuilder {
-            input,
-            alpha,
-            check: Check::Disabled,
-        }
+        EncodeConfig::new(alpha).with_input(input)
     }
 
     /// Setup encoder for the given string using default prepared alphabet.
     pub(crate) fn from_input(input: I) -> EncodeBuilder<'static, I> {
-        EncodeBuilder {
-            input,
-            alpha: Alphabet::DEFAULT,
-            check: Check::Disabled,
-        }
+        EncodeConfig::new(Alphabet::DEFAULT).with_input(input)
     }
 
     /// Change the alphabet that will be used for encoding.
@@ -240,6 +608,25 @@ impl<'a, I: AsRef<[u8]>> EncodeBuilder<'a, I> {
         EncodeBuilder { alpha, ..self }
     }
 
+    /// Change the alphabet that will be used for encoding to a compile time constant, see
+    /// [`StaticAlphabet`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// bs58::static_alphabet!(Ripple = b"rpshnaf39wBUDNEGHJKLM4PQRST7VWXYZ2bcdeCg65jkm8oFqi1tuvAxyz");
+    ///
+    /// let input = [0x60, 0x65, 0xe7, 0x9b, 0xba, 0x2f, 0x78];
+    /// assert_eq!(
+    ///     "he11owor1d",
+    ///     bs58::encode(input)
+    ///         .with_static_alphabet::<Ripple>()
+    ///         .into_string());
+    /// ```
+    pub fn with_static_alphabet<A: StaticAlphabet>(self) -> EncodeBuilder<'a, I> {
+        self.with_alphabet(A::ALPHABET)
+    }
+
     /// Include checksum calculated using the [Base58Check][] algorithm when
     /// encoding.
     ///
@@ -282,6 +669,110 @@ impl<'a, I: AsRef<[u8]>> EncodeBuilder<'a, I> {
         EncodeBuilder { check, ..self }
     }
 
+    /// Include a [Base58Check][] checksum calculated over the associated data `aad` followed by
+    /// the encoded bytes, binding the encoded value to a context without including it.
+    ///
+    /// The checksum is the first 4 bytes of `SHA256(SHA256(aad || version || input))`, where
+    /// `version` is any version set with [`with_check_version`](Self::with_check_version) or
+    /// [`with_check_version_typed`](Self::with_check_version_typed), which are kept; otherwise
+    /// this is the same as [`with_check`](Self::with_check). Only the version, input and checksum
+    /// are encoded, the same `aad` must be given to
+    /// [`DecodeBuilder::with_check_aad`](crate::decode::DecodeBuilder::with_check_aad) to verify
+    /// it. The associated data is not length-prefixed, so use context of a fixed length or that
+    /// is self-delimiting, otherwise bytes could move between it and the input unnoticed.
+    ///
+    /// [Base58Check]: https://en.bitcoin.it/wiki/Base58Check_encoding
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let input = [0x60, 0x65, 0xe7, 0x9b, 0xba, 0x2f, 0x78];
+    /// let encoded = bs58::encode(input).with_check_aad(b"session:1").into_string();
+    /// assert_ne!(bs58::encode(input).with_check().into_string(), encoded);
+    /// assert_eq!(
+    ///     input.to_vec(),
+    ///     bs58::decode(&encoded).with_check_aad(b"session:1").into_vec()?);
+    /// # Ok::<(), bs58::decode::Error>(())
+    /// ```
+    #[cfg(feature = "check")]
+    pub fn with_check_aad(self, aad: &'a [u8]) -> EncodeBuilder<'a, I> {
+        let check = match self.check {
+            check @ (Check::Enabled(_) | Check::Versioned(_)) => check,
+            _ => Check::Enabled(None),
+        };
+        EncodeBuilder { check, aad, ..self }
+    }
+
+    /// Include checksum calculated using the [Base58Check][] algorithm and the version bytes of
+    /// `V` when encoding.
+    ///
+    /// This is the same as [`with_check_version`](Self::with_check_version) but with the version
+    /// given as a type, see [`check::Version`](crate::check::Version) for more details.
+    ///
+    /// [Base58Check]: https://en.bitcoin.it/wiki/Base58Check_encoding
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bs58::check::BitcoinMainnetP2pkh;
+    ///
+    /// let input = [
+    ///     0x9b, 0x41, 0x54, 0xbb, 0xf2, 0x03, 0xe4, 0x13, 0x0c, 0x4b,
+    ///     0x86, 0x25, 0x93, 0x18, 0xa4, 0x98, 0x75, 0xdd, 0x04, 0x56,
+    /// ];
+    /// assert_eq!(
+    ///     "1F9v11cupBVMpz3CrVfCppv9Rw2xEtU1c6",
+    ///     bs58::encode(input)
+    ///         .with_check_version_typed::<BitcoinMainnetP2pkh>()
+    ///         .into_string());
+    /// ```
+    #[cfg(feature = "check")]
+    pub fn with_check_version_typed<V: crate::check::Version>(self) -> EncodeBuilder<'a, I> {
+        let check = Check::Versioned(V::BYTES);
+        EncodeBuilder { check, ..self }
+    }
+
+    /// Include a [Base58Check][] checksum after every `block_size` bytes when encoding, so
+    /// corruption can be localized to a block when decoding with
+    /// [`DecodeBuilder::with_block_checksums`](crate::decode::DecodeBuilder::with_block_checksums).
+    ///
+    /// The input is split into blocks of `block_size` bytes, with the last block holding what
+    /// remains (between 1 and `block_size` bytes). Each block is followed by the first 4 bytes of
+    /// the double SHA-256 hash of that block alone, and the concatenation of all the blocks and
+    /// checksums is encoded as a single Base58 value. An empty input has no blocks and encodes as
+    /// an empty string. There is no version byte, and as each checksum only covers its own block
+    /// swapping or removing whole blocks is not detected.
+    ///
+    /// [Base58Check]: https://en.bitcoin.it/wiki/Base58Check_encoding
+    ///
+    /// # Panics
+    ///
+    /// If `block_size` is 0.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let input = [0x04, 0x30, 0x5e, 0x2b, 0x24, 0x73, 0xf0, 0x58, 0x01];
+    ///
+    /// // Each block followed by its checksum, as with `with_check`
+    /// let mut framed = Vec::new();
+    /// for block in input.chunks(4) {
+    ///     let checked = bs58::encode(block).with_check().into_string();
+    ///     framed.extend(bs58::decode(checked).into_vec()?);
+    /// }
+    /// assert_eq!(input.len() + 3 * 4, framed.len());
+    /// assert_eq!(
+    ///     bs58::encode(framed).into_string(),
+    ///     bs58::encode(input).with_block_checksums(4).into_string());
+    /// # Ok::<(), bs58::decode::Error>(())
+    /// ```
+    #[cfg(feature = "check")]
+    pub fn with_block_checksums(self, block_size: usize) -> EncodeBuilder<'a, I> {
+        assert!(block_size > 0, "block size must be greater than 0");
+        let check = Check::Blocks(block_size);
+        EncodeBuilder { check, ..self }
+    }
+
     /// Include checksum calculated using the [CB58][] algorithm and
     /// version (if specified) when encoding.
     ///
@@ -303,53 +794,774 @@ impl<'a, I: AsRef<[u8]>> EncodeBuilder<'a, I> {
         EncodeBuilder { check, ..self }
     }
 
-    /// Encode into a new owned string.
+    /// Prepend the literal text `prefix` to the encoded output, as a namespace tag for the data.
+    ///
+    /// The prefix is not part of the encoded value and is not included in any checksum, it must
+    /// be removed before decoding, e.g. with
+    /// [`DecodeBuilder::with_text_prefix`](crate::decode::DecodeBuilder::with_text_prefix).
     ///
     /// # Examples
     ///
     /// ```rust
     /// let input = [0x04, 0x30, 0x5e, 0x2b, 0x24, 0x73, 0xf0, 0x58];
-    /// assert_eq!("he11owor1d", bs58::encode(input).into_string());
+    /// assert_eq!(
+    ///     "acct_he11owor1d",
+    ///     bs58::encode(input)
+    ///         .with_text_prefix("acct_")
+    ///         .into_string());
     /// ```
-    #[cfg(feature = "alloc")]
-    pub fn into_string(self) -> String {
-        let mut output = String::new();
-        self.onto(&mut output).unwrap();
-        output
+    pub fn with_text_prefix(self, prefix: &'a str) -> EncodeBuilder<'a, I> {
+        EncodeBuilder {
+            prefix,
+            hrp_separator: None,
+            ..self
+        }
     }
 
-    /// Encode into a new owned vector.
+    /// Prepend a human-readable part `hrp` and `separator` to the encoded output, as used by
+    /// address formats such as `xrb_...`, replacing any [text prefix](Self::with_text_prefix).
+    ///
+    /// As with a text prefix neither is part of the encoded value or included in any checksum,
+    /// they must be removed before decoding, e.g. with
+    /// [`DecodeBuilder::with_hrp`](crate::decode::DecodeBuilder::with_hrp).
     ///
     /// # Examples
     ///
     /// ```rust
     /// let input = [0x04, 0x30, 0x5e, 0x2b, 0x24, 0x73, 0xf0, 0x58];
-    /// assert_eq!(b"he11owor1d", &*bs58::encode(input).into_vec());
+    /// assert_eq!("xrb_he11owor1d", bs58::encode(input).with_hrp("xrb", '_').into_string());
     /// ```
-    #[cfg(feature = "alloc")]
-    pub fn into_vec(self) -> Vec<u8> {
-        let mut output = Vec::new();
-        self.onto(&mut output).unwrap();
-        output
+    pub fn with_hrp(self, hrp: &'a str, separator: char) -> EncodeBuilder<'a, I> {
+        EncodeBuilder {
+            prefix: hrp,
+            hrp_separator: Some(separator),
+            ..self
+        }
     }
 
-    /// Encode onto the given buffer.
-    ///
-    /// Returns the length written onto the buffer.
-    ///
-    /// If the buffer is resizeable it will be extended and the new data will be written to the end
-    /// of it, otherwise the data will be overwritten from the start.
-    ///
-    /// If the buffer is not resizeable bytes after the final character will be left alone, except
-    /// up to 3 null bytes may be written to an `&mut str` to overwrite remaining characters of a
-    /// partially overwritten multi-byte character.
+    /// Normalize the [text prefix](Self::with_text_prefix) or [HRP](Self::with_hrp) to Unicode
+    /// Normalization Form C before prepending it, so prefixes that look the same are the same
+    /// bytes however they were typed or stored.
     ///
-    /// See the documentation for [`bs58::encode`](crate::encode()) for an
-    /// explanation of the errors that may occur.
+    /// Only the prefix is normalized, the HRP separator and the encoded characters are written as
+    /// they are. When decoding
+    /// [`DecodeBuilder::with_text_prefix`](crate::decode::DecodeBuilder::with_text_prefix)
+    /// compares the prefix byte for byte, so give it the normalized prefix.
     ///
     /// # Examples
     ///
-    /// ## `Vec<u8>`
+    /// ```rust
+    /// let input = [0x04, 0x30, 0x5e, 0x2b, 0x24, 0x73, 0xf0, 0x58];
+    /// // "e" followed by a combining acute accent
+    /// let decomposed = "caf\u{65}\u{301}:";
+    /// assert_eq!(
+    ///     "caf\u{e9}:he11owor1d",
+    ///     bs58::encode(input)
+    ///         .with_text_prefix(decomposed)
+    ///         .normalize_prefix_nfc()
+    ///         .into_string());
+    /// ```
+    #[cfg(feature = "unicode-normalization")]
+    pub fn normalize_prefix_nfc(self) -> EncodeBuilder<'a, I> {
+        EncodeBuilder {
+            nfc_prefix: true,
+            ..self
+        }
+    }
+
+    /// Prepend the `salt` bytes to the input before encoding, so the same input gives a
+    /// different string for each salt, e.g. to namespace identifiers per tenant.
+    ///
+    /// Unlike a [text prefix](Self::with_text_prefix) the salt is part of the encoded value, and
+    /// of any checksum, coming after any version byte. It counts towards the
+    /// [maximum input length](Self::with_max_input). Decode with
+    /// [`DecodeBuilder::strip_salt`](crate::decode::DecodeBuilder::strip_salt) to check and
+    /// remove it.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let input = [0x2b, 0x24, 0x73, 0xf0, 0x58];
+    /// assert_eq!(
+    ///     "he11owor1d",
+    ///     bs58::encode(input).with_salt(&[0x04, 0x30, 0x5e]).into_string());
+    /// assert_ne!(
+    ///     bs58::encode(input).with_salt(b"tenant-a").into_string(),
+    ///     bs58::encode(input).with_salt(b"tenant-b").into_string());
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn with_salt(self, salt: &[u8]) -> EncodeBuilder<'a, Vec<u8>> {
+        let mut input = Vec::with_capacity(salt.len() + self.input.as_ref().len());
+        input.extend_from_slice(salt);
+        input.extend_from_slice(self.input.as_ref());
+        #[cfg(feature = "metrics")]
+        if let Some(stats) = self.stats {
+            stats.set(Stats {
+                input_len: input.len(),
+                ..stats.get()
+            });
+        }
+        EncodeBuilder {
+            input,
+            alpha: self.alpha,
+            check: self.check,
+            #[cfg(feature = "check")]
+            aad: self.aad,
+            prefix: self.prefix,
+            hrp_separator: self.hrp_separator,
+            max_input: self.max_input,
+            ordered: self.ordered,
+            fold: self.fold,
+            #[cfg(feature = "unicode-normalization")]
+            nfc_prefix: self.nfc_prefix,
+            #[cfg(feature = "metrics")]
+            stats: self.stats,
+        }
+    }
+
+    /// Reject inputs longer than `limit` bytes with [`Error::InputTooLarge`].
+    ///
+    /// Encoding takes time quadratic in the input length, so when encoding untrusted data this
+    /// bounds the work done. The length is checked before anything is allocated or converted.
+    /// Only the input itself counts towards the limit, not any version or checksum bytes added
+    /// by a [checksum mode](Self::with_check).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let input = [0x04, 0x30, 0x5e, 0x2b, 0x24, 0x73, 0xf0, 0x58];
+    /// assert_eq!("he11owor1d", bs58::encode(input).with_max_input(8).try_into_string()?);
+    /// assert_eq!(
+    ///     Err(bs58::encode::Error::InputTooLarge { limit: 4, actual: 8 }),
+    ///     bs58::encode(input).with_max_input(4).try_into_string());
+    /// # Ok::<(), bs58::encode::Error>(())
+    /// ```
+    pub fn with_max_input(self, limit: usize) -> EncodeBuilder<'a, I> {
+        let max_input = Some(limit);
+        EncodeBuilder { max_input, ..self }
+    }
+
+    /// Encode the input as a big-endian number padded with zero characters to the same width for
+    /// every input of up to `max_bytes` bytes, so that comparing encodings as strings gives the
+    /// same order as comparing their values, e.g. for keys in a sorted key-value store.
+    ///
+    /// This sets the [maximum input length](Self::with_max_input) to `max_bytes`, which the width
+    /// is based on: inputs longer than it can't be encoded in this mode and give
+    /// [`Error::InputTooLarge`], even if their value would fit. Leading zero bytes of the input
+    /// are only part of its value, so inputs with the same value encode the same whatever their
+    /// length. Decode with
+    /// [`DecodeBuilder::order_preserving`](crate::decode::DecodeBuilder::order_preserving) and the
+    /// same `max_bytes` to get the value back as `max_bytes` bytes.
+    ///
+    /// The order is only preserved for alphabets with their characters in ASCII order, such as
+    /// the [default alphabet](Alphabet::DEFAULT) but not [`RIPPLE`](Alphabet::RIPPLE) or
+    /// [`FLICKR`](Alphabet::FLICKR), and with the same [text prefix](Self::with_text_prefix) for
+    /// all the keys. It only applies to encoding without a checksum, setting a
+    /// [checksum mode](Self::with_check) encodes as usual.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let encode = |value: u32| {
+    ///     bs58::encode(value.to_be_bytes()).order_preserving(4).into_string()
+    /// };
+    /// assert_eq!("111112", encode(1));
+    /// assert_eq!("11112z", encode(115));
+    /// assert_eq!("11115R", encode(256));
+    /// assert!(encode(115) < encode(256));
+    ///
+    /// let decoded = bs58::decode(encode(256)).order_preserving(4).into_vec()?;
+    /// assert_eq!(256u32.to_be_bytes().to_vec(), decoded);
+    ///
+    /// assert_eq!(
+    ///     Err(bs58::encode::Error::InputTooLarge { limit: 2, actual: 4 }),
+    ///     bs58::encode(1u32.to_be_bytes()).order_preserving(2).try_into_string());
+    /// # Ok::<(), bs58::decode::Error>(())
+    /// ```
+    pub fn order_preserving(self, max_bytes: usize) -> EncodeBuilder<'a, I> {
+        let max_input = Some(max_bytes);
+        EncodeBuilder {
+            max_input,
+            ordered: true,
+            ..self
+        }
+    }
+
+    /// Apply `fold` to the encoded characters to give a form for display, such as all uppercase
+    /// for fonts where some lowercase characters are hard to tell apart.
+    ///
+    /// Any [text prefix](Self::with_text_prefix) is left as it is. The display form is not a
+    /// Base58 encoding, it can only be decoded with
+    /// [`DecodeBuilder::display_fold`](crate::decode::DecodeBuilder::display_fold), and only
+    /// when no character of it is the display form of more than one character of the alphabet,
+    /// see [`Fold`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bs58::alphabet::Fold;
+    ///
+    /// let input = [0x04, 0x30, 0x5e, 0x2b, 0x24, 0x73, 0xf0, 0x58];
+    /// assert_eq!(
+    ///     "ID:HE11OWOR1D",
+    ///     bs58::encode(input)
+    ///         .with_text_prefix("ID:")
+    ///         .display_fold(Fold::Upper)
+    ///         .into_string());
+    /// ```
+    pub fn display_fold(self, fold: Fold) -> EncodeBuilder<'a, I> {
+        let fold = Some(fold);
+        EncodeBuilder { fold, ..self }
+    }
+
+    /// Reset `stats` and fill them in with what the encode does, see [`Stats`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bs58::metrics::Stats;
+    ///
+    /// let mut stats = Stats::default();
+    /// bs58::encode([0; 4]).with_stats(&mut stats).into_string();
+    /// assert_eq!((4, 4), (stats.input_len, stats.output_len));
+    /// // Zero bytes are encoded without the conversion loop
+    /// assert_eq!((0, true), (stats.iterations, stats.fast_path));
+    /// ```
+    #[cfg(feature = "metrics")]
+    pub fn with_stats(self, stats: &'a mut Stats) -> EncodeBuilder<'a, I> {
+        *stats = Stats {
+            input_len: self.input.as_ref().len(),
+            ..Stats::default()
+        };
+        let stats = Some(core::cell::Cell::from_mut(stats));
+        EncodeBuilder { stats, ..self }
+    }
+
+    /// Encode into a new owned string.
+    ///
+    /// # Panics
+    ///
+    /// If the input is longer than the [maximum input length](Self::with_max_input), use
+    /// [`try_into_string`](Self::try_into_string) to get an error instead.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let input = [0x04, 0x30, 0x5e, 0x2b, 0x24, 0x73, 0xf0, 0x58];
+    /// assert_eq!("he11owor1d", bs58::encode(input).into_string());
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn into_string(self) -> String {
+        let mut output = String::new();
+        self.onto(&mut output).unwrap();
+        output
+    }
+
+    /// Encode into a new owned string with space for at least `extra` more bytes after the encoded
+    /// characters, so appending up to that much doesn't reallocate.
+    ///
+    /// The string is allocated once with the capacity for the longest possible encoding of the
+    /// input plus `extra`, so its [`capacity`](String::capacity) is always at least its length
+    /// plus `extra`.
+    ///
+    /// # Panics
+    ///
+    /// If the input is longer than the [maximum input length](Self::with_max_input).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let input = [0x04, 0x30, 0x5e, 0x2b, 0x24, 0x73, 0xf0, 0x58];
+    /// let mut output = bs58::encode(input).into_string_with_capacity(6);
+    /// assert!(output.capacity() >= 16);
+    /// output.push_str(".jpeg");
+    /// assert_eq!("he11owor1d.jpeg", output);
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn into_string_with_capacity(self, extra: usize) -> String {
+        let mut output = String::with_capacity(self.max_output_len() + extra);
+        self.onto(&mut output).unwrap();
+        output
+    }
+
+    /// Encode into `scratch`, returning the encoded string borrowed from it.
+    ///
+    /// Base58 is converted in place in the output, so reusing the same [`Scratch`] for each encode
+    /// reuses that working space too instead of allocating a new string each time as
+    /// [`into_string`](Self::into_string) does. Whatever was previously encoded into `scratch` is
+    /// overwritten.
+    ///
+    /// # Panics
+    ///
+    /// If the input is longer than the [maximum input length](Self::with_max_input).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let mut scratch = bs58::encode::Scratch::new();
+    /// let input = [0x04, 0x30, 0x5e, 0x2b, 0x24, 0x73, 0xf0, 0x58];
+    /// assert_eq!("he11owor1d", bs58::encode(input).with_scratch(&mut scratch));
+    /// assert_eq!("11111111", bs58::encode([0; 8]).with_scratch(&mut scratch));
+    /// assert!(scratch.capacity() >= 10);
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn with_scratch(self, scratch: &mut Scratch) -> &str {
+        scratch.buffer.clear();
+        self.onto(&mut scratch.buffer).unwrap();
+        &scratch.buffer
+    }
+
+    /// Encode into a new owned string with the characters in reverse order.
+    ///
+    /// This is only a display convenience for contexts that render right-to-left and whose
+    /// protocol expects the characters in reversed display order; the result must be decoded with
+    /// [`DecodeBuilder::rtl`](crate::decode::DecodeBuilder::rtl). A
+    /// [text prefix](Self::with_text_prefix) is not reversed and stays at the start.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let input = [0x04, 0x30, 0x5e, 0x2b, 0x24, 0x73, 0xf0, 0x58];
+    /// assert_eq!("d1rowo11eh", bs58::encode(input).into_string_rtl());
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn into_string_rtl(self) -> String {
+        let prefix_len = self.text_prefix_len();
+        let mut output = self.into_vec();
+        output[prefix_len..].reverse();
+        String::from_utf8(output).unwrap()
+    }
+
+    /// Encode into a new owned string, along with the SHA-256 hash of the string's bytes
+    /// (including any [text prefix](Self::with_text_prefix)), for content addressing.
+    ///
+    /// Base58 characters are produced least significant first, so none of the string is known
+    /// until the whole input has been converted. The string is hashed as soon as it is complete,
+    /// while it is still in cache, rather than needing a separate pass by the caller.
+    ///
+    /// # Panics
+    ///
+    /// If the input is longer than the [maximum input length](Self::with_max_input).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let input = [0x04, 0x30, 0x5e, 0x2b, 0x24, 0x73, 0xf0, 0x58];
+    /// let (encoded, hash) = bs58::encode(input).into_string_with_hash();
+    /// assert_eq!("he11owor1d", encoded);
+    /// assert_eq!([0xdd, 0x6f, 0x60, 0x8b], hash[..4]);
+    /// ```
+    #[cfg(all(feature = "sha2", feature = "alloc"))]
+    pub fn into_string_with_hash(self) -> (String, [u8; 32]) {
+        use sha2::{Digest, Sha256};
+
+        let encoded = self.into_string();
+        let hash = Sha256::digest(encoded.as_bytes()).into();
+        (encoded, hash)
+    }
+
+    /// Encode into a new owned string with the checksum as a separate group after `sep`, so it
+    /// is visually distinct from the payload.
+    ///
+    /// The version (if any) and payload are encoded as one Base58 group and the checksum as
+    /// another, this is not the same string as [`into_string`](Self::into_string) gives and must
+    /// be decoded with
+    /// [`DecodeBuilder::into_vec_with_checksum_separator`](crate::decode::DecodeBuilder::into_vec_with_checksum_separator).
+    /// If no checksum has been configured a [Base58Check][] checksum without a version is used,
+    /// as with [`with_check`](Self::with_check).
+    ///
+    /// [Base58Check]: https://en.bitcoin.it/wiki/Base58Check_encoding
+    ///
+    /// # Panics
+    ///
+    /// If `sep` is a character of the alphabet, as the groups could not be told apart, if the input
+    /// is longer than the [maximum input length](Self::with_max_input), or if
+    /// [block checksums](Self::with_block_checksums) are used.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let input = [0x60, 0x65, 0xe7, 0x9b, 0xba, 0x2f, 0x78];
+    /// assert_eq!(
+    ///     "8678ThZWZ7M-4NnGCJ",
+    ///     bs58::encode(input)
+    ///         .with_check_version(42)
+    ///         .into_string_with_checksum_separator('-'));
+    /// ```
+    #[cfg(all(feature = "check", feature = "alloc"))]
+    pub fn into_string_with_checksum_separator(self, sep: char) -> String {
+        assert!(
+            !self.alpha.contains(sep),
+            "checksum separator {sep:?} is part of the alphabet"
+        );
+        self.check_input_len().unwrap();
+
+        let input = self.input.as_ref();
+        let (version, checksum) = match &self.check {
+            Check::Disabled => (&[][..], check_checksum(self.aad, &[], input)),
+            Check::Enabled(version) => (
+                version.as_slice(),
+                check_checksum(self.aad, version.as_slice(), input),
+            ),
+            Check::Versioned(version) => (*version, check_checksum(self.aad, version, input)),
+            #[cfg(feature = "check")]
+            Check::Blocks(_) => panic!("block checksums can't be put after a separator"),
+            #[cfg(feature = "cb58")]
+            Check::CB58(version) => (version.as_slice(), cb58_checksum(*version, input)),
+        };
+
+        let fold = |output: &mut [u8], len| {
+            if let Some(fold) = self.fold {
+                apply_fold(fold, &mut output[..len]);
+            }
+            Ok(len)
+        };
+        let mut output: String = self.text_prefix_chars().collect();
+        output
+            .encode_with(max_encoded_len(version.len() + input.len()), |output| {
+                let len = encode_into(
+                    version.iter().chain(input).copied(),
+                    output,
+                    self.alpha,
+                    &mut (),
+                )?;
+                fold(output, len)
+            })
+            .unwrap();
+        output.push(sep);
+        output
+            .encode_with(max_encoded_len(CHECKSUM_LEN), |output| {
+                let len = encode_into(checksum.iter().copied(), output, self.alpha, &mut ())?;
+                fold(output, len)
+            })
+            .unwrap();
+        output
+    }
+
+    /// Encode into a new owned string, returning [`Error::AllocationFailed`] instead of aborting if
+    /// the space for it cannot be allocated.
+    ///
+    /// This is the only allocation made while encoding, including when calculating a checksum.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let input = [0x04, 0x30, 0x5e, 0x2b, 0x24, 0x73, 0xf0, 0x58];
+    /// assert_eq!("he11owor1d", bs58::encode(input).try_into_string()?);
+    /// # Ok::<(), bs58::encode::Error>(())
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn try_into_string(self) -> Result<String> {
+        self.check_input_len()?;
+        let mut output = String::new();
+        output
+            .try_reserve_exact(self.max_output_len())
+            .map_err(|_| Error::AllocationFailed)?;
+        self.onto(&mut output)?;
+        Ok(output)
+    }
+
+    /// Encode into a new owned vector.
+    ///
+    /// # Panics
+    ///
+    /// If the input is longer than the [maximum input length](Self::with_max_input), use
+    /// [`try_into_vec`](Self::try_into_vec) to get an error instead.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let input = [0x04, 0x30, 0x5e, 0x2b, 0x24, 0x73, 0xf0, 0x58];
+    /// assert_eq!(b"he11owor1d", &*bs58::encode(input).into_vec());
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn into_vec(self) -> Vec<u8> {
+        let mut output = Vec::new();
+        self.onto(&mut output).unwrap();
+        output
+    }
+
+    /// Encode into a new owned vector, returning [`Error::AllocationFailed`] instead of aborting if
+    /// the space for it cannot be allocated.
+    ///
+    /// This is the only allocation made while encoding, including when calculating a checksum.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let input = [0x04, 0x30, 0x5e, 0x2b, 0x24, 0x73, 0xf0, 0x58];
+    /// assert_eq!(b"he11owor1d", &*bs58::encode(input).try_into_vec()?);
+    /// # Ok::<(), bs58::encode::Error>(())
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn try_into_vec(self) -> Result<Vec<u8>> {
+        self.check_input_len()?;
+        let mut output = Vec::new();
+        output
+            .try_reserve_exact(self.max_output_len())
+            .map_err(|_| Error::AllocationFailed)?;
+        self.onto(&mut output)?;
+        Ok(output)
+    }
+
+    /// Encode into a new [`allocator_api2::vec::Vec`] using the given allocator.
+    ///
+    /// Space is reserved exactly for the [maximum encoded length](crate::max_encoded_len) from
+    /// `alloc` and nothing is allocated from the global allocator. There is no allocator aware
+    /// `String` type, the output is always ASCII so can be viewed with [`core::str::from_utf8`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use allocator_api2::alloc::Global;
+    ///
+    /// let input = [0x04, 0x30, 0x5e, 0x2b, 0x24, 0x73, 0xf0, 0x58];
+    /// let encoded = bs58::encode(input).into_vec_in(Global);
+    /// assert_eq!(Ok("he11owor1d"), core::str::from_utf8(&encoded));
+    /// ```
+    #[cfg(feature = "allocator-api2")]
+    pub fn into_vec_in<A: allocator_api2::alloc::Allocator>(
+        self,
+        alloc: A,
+    ) -> allocator_api2::vec::Vec<u8, A> {
+        let mut output = allocator_api2::vec::Vec::new_in(alloc);
+        self.onto(&mut output).unwrap();
+        output
+    }
+
+    /// Whether the encoded string would start with `prefix`, without allocating the string, for
+    /// filtering many candidates such as in a vanity address search.
+    ///
+    /// Base58 digits are produced least significant first, so in general the leading characters
+    /// are only known once the whole input has been converted. This skips that conversion when the
+    /// answer is already known: when `prefix` has characters that can't appear in the output, is
+    /// longer than the output can be, or (without a checksum) its leading
+    /// [zero characters](Alphabet::encode_table) don't match the input's leading zero bytes.
+    /// Otherwise the input is encoded into a temporary buffer, which is on the stack for outputs
+    /// of up to 128 characters and allocated for longer ones, or without `alloc`
+    /// [`Error::BufferTooSmall`] is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let input = [0x04, 0x30, 0x5e, 0x2b, 0x24, 0x73, 0xf0, 0x58];
+    /// assert!(bs58::encode(input).starts_with("he11o")?);
+    /// assert!(!bs58::encode(input).starts_with("hello")?);
+    /// assert!(!bs58::encode(input).starts_with("1")?);
+    /// # Ok::<(), bs58::encode::Error>(())
+    /// ```
+    pub fn starts_with(self, prefix: &str) -> Result<bool> {
+        self.check_input_len()?;
+        let max_len = self.max_output_len();
+        let prefix = prefix.as_bytes();
+
+        let text_len = self.text_prefix_len().min(prefix.len());
+        let (text, digits) = prefix.split_at(text_len);
+        if !text
+            .iter()
+            .copied()
+            .eq(self.text_prefix_bytes().take(text_len))
+            || prefix.len() > max_len
+            || digits
+                .iter()
+                .any(|&c| self.alpha.digit(c) == Alphabet::INVALID && c != self.alpha.lead_char())
+        {
+            return Ok(false);
+        }
+
+        if matches!(self.check, Check::Disabled) && !self.ordered {
+            // Each leading zero byte encodes to a lead character, followed by at least one other
+            // character unless the input is all zeros
+            let input = self.input.as_ref();
+            let zero = self.alpha.lead_char();
+            let zeros = input.iter().take_while(|&&b| b == 0).count();
+            let prefix_zeros = digits.iter().take_while(|&&c| c == zero).count();
+            if zeros == input.len() || prefix_zeros > zeros || prefix_zeros == digits.len() {
+                return Ok(prefix_zeros == digits.len() && prefix_zeros <= zeros);
+            }
+            if prefix_zeros < zeros {
+                return Ok(false);
+            }
+        }
+
+        self.with_encoded(|encoded| Ok(encoded.starts_with(prefix)))
+    }
+
+    /// Report the lengths the input encodes to, for capacity planning across a batch of inputs
+    /// before allocating any of the output.
+    ///
+    /// There's no shortcut to the exact encoded length, Base58 characters are produced least
+    /// significant first so the length is only known once the whole input has been converted,
+    /// so this encodes into a temporary buffer which is then discarded. The buffer is on the
+    /// stack for up to 128 characters, otherwise it is allocated and freed before returning.
+    /// Use [`max_encoded_len`] for a bound without the conversion.
+    ///
+    /// # Panics
+    ///
+    /// If the input is longer than the [maximum input length](Self::with_max_input).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let stats = bs58::encode([0x00, 0x00, 0x28, 0x7f, 0xb4, 0xcd]).analyze();
+    /// assert_eq!(6, stats.input_len);
+    /// assert_eq!(8, stats.output_len);
+    /// assert_eq!(2, stats.leading_zero_bytes);
+    /// assert_eq!(8.0 / 6.0, stats.expansion_ratio);
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn analyze(&self) -> EncodeStats {
+        self.check_input_len().unwrap();
+        let input = self.input.as_ref();
+
+        let max_len = self.max_output_len();
+        let mut stack = [0; SCRATCH_STACK_LEN];
+        let mut heap;
+        let scratch = if max_len <= SCRATCH_STACK_LEN {
+            &mut stack[..max_len]
+        } else {
+            heap = alloc::vec![0; max_len];
+            &mut heap[..]
+        };
+        let output_len = self.encode_prefixed(scratch, &mut ()).unwrap();
+
+        EncodeStats {
+            input_len: input.len(),
+            output_len,
+            leading_zero_bytes: input.iter().take_while(|&&b| b == 0).count(),
+            expansion_ratio: if input.is_empty() {
+                0.0
+            } else {
+                output_len as f64 / input.len() as f64
+            },
+        }
+    }
+
+    /// Encode across `segments` in order, returning the total length written.
+    ///
+    /// The encoded characters fill each segment before moving on to the next, so a segment
+    /// boundary can fall anywhere in the output. Bytes after the encoded length are left
+    /// untouched, and if the segments can't hold the whole output [`Error::BufferTooSmall`] is
+    /// returned without writing anything.
+    ///
+    /// The conversion needs contiguous space, so this encodes into a temporary buffer then
+    /// copies it into the segments. The buffer is on the stack for outputs of up to 128
+    /// characters and allocated for longer ones, or without `alloc` [`Error::BufferTooSmall`] is
+    /// returned.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let input = [0x04, 0x30, 0x5e, 0x2b, 0x24, 0x73, 0xf0, 0x58];
+    /// let (mut first, mut second) = ([b'.'; 4], [b'.'; 8]);
+    /// assert_eq!(10, bs58::encode(input).into_segments(&mut [&mut first, &mut second])?);
+    /// assert_eq!(b"he11", &first);
+    /// assert_eq!(b"owor1d..", &second);
+    /// # Ok::<(), bs58::encode::Error>(())
+    /// ```
+    pub fn into_segments(self, segments: &mut [&mut [u8]]) -> Result<usize> {
+        self.with_encoded(|encoded| {
+            if crate::scatter(encoded, segments) {
+                Ok(encoded.len())
+            } else {
+                Err(Error::BufferTooSmall)
+            }
+        })
+    }
+
+    /// Encode into `buf` starting at `offset`, returning the number of bytes written.
+    ///
+    /// Before encoding this checks there is space after `offset` for the
+    /// [maximum encoded length](crate::max_encoded_len), returning [`Error::BufferTooSmall`] if
+    /// there isn't. Only the bytes from `offset` up to the returned length are written, everything
+    /// around them is left untouched.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let input = [0x04, 0x30, 0x5e, 0x2b, 0x24, 0x73, 0xf0, 0x58];
+    /// let mut frame = *b"<<............>>";
+    /// assert_eq!(10, bs58::encode(input).into_at(&mut frame, 2)?);
+    /// assert_eq!(b"<<he11owor1d..>>", &frame);
+    ///
+    /// assert_eq!(
+    ///     Err(bs58::encode::Error::BufferTooSmall),
+    ///     bs58::encode(input).into_at(&mut frame, 6));
+    /// # Ok::<(), bs58::encode::Error>(())
+    /// ```
+    pub fn into_at(self, buf: &mut [u8], offset: usize) -> Result<usize> {
+        self.check_input_len()?;
+        let max_len = self.max_output_len();
+        let output = buf
+            .get_mut(offset..)
+            .filter(|output| output.len() >= max_len)
+            .ok_or(Error::BufferTooSmall)?;
+        self.onto(output)
+    }
+
+    /// Encode into the uninitialized `buf`, returning the initialized part of it holding the
+    /// encoded characters.
+    ///
+    /// `buf` is only ever written, never read, so it doesn't need to be initialized first. The
+    /// characters are encoded into a temporary buffer, on the stack when the output must fit in
+    /// 128 characters and otherwise allocated, then copied to the start of `buf`. When `buf` is
+    /// too short for them [`Error::BufferTooSmall`] is returned without touching it, as are all
+    /// other errors. Only the returned slice is initialized, the rest of `buf` is left as it was.
+    ///
+    /// Without `alloc` outputs longer than 128 characters give [`Error::BufferTooSmall`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use core::mem::MaybeUninit;
+    ///
+    /// let input = [0x04, 0x30, 0x5e, 0x2b, 0x24, 0x73, 0xf0, 0x58];
+    /// let mut buf = [MaybeUninit::uninit(); 16];
+    /// assert_eq!(b"he11owor1d", bs58::encode(input).into_uninit(&mut buf)?);
+    ///
+    /// assert_eq!(
+    ///     Err(bs58::encode::Error::BufferTooSmall),
+    ///     bs58::encode(input).into_uninit(&mut buf[..9]));
+    /// # Ok::<(), bs58::encode::Error>(())
+    /// ```
+    pub fn into_uninit(self, buf: &mut [MaybeUninit<u8>]) -> Result<&mut [u8]> {
+        self.check_input_len()?;
+        self.with_encoded(|encoded| {
+            let buf = buf.get_mut(..encoded.len()).ok_or(Error::BufferTooSmall)?;
+            for (byte, &c) in buf.iter_mut().zip(encoded) {
+                byte.write(c);
+            }
+            #[allow(unsafe_code)]
+            // SAFETY: every byte of `buf` was initialized above, and `MaybeUninit<u8>` has the
+            // same layout as `u8`
+            Ok(unsafe { &mut *(core::ptr::from_mut(buf) as *mut [u8]) })
+        })
+    }
+
+    /// Encode onto the given buffer.
+    ///
+    /// Returns the length written onto the buffer.
+    ///
+    /// If the buffer is resizeable it will be extended and the new data will be written to the end
+    /// of it, otherwise the data will be overwritten from the start.
+    ///
+    /// If the buffer is not resizeable bytes after the final character will be left alone, except
+    /// up to 3 null bytes may be written to an `&mut str` to overwrite remaining characters of a
+    /// partially overwritten multi-byte character.
+    ///
+    /// The input is read in place and never copied, and the conversion is done in place in the
+    /// buffer, so the memory used beyond the output is independent of the input size, which
+    /// suits large borrowed inputs such as memory-mapped files (see also
+    /// [`from_slice_streaming`]). Encoding into a buffer that is not resizeable never allocates, and with
+    /// optimizations enabled uses under 1 KiB of stack, including the SHA-256 state when
+    /// calculating a checksum (unoptimized builds use several times more).
+    ///
+    /// See the documentation for [`bs58::encode`](crate::encode()) for an
+    /// explanation of the errors that may occur.
+    ///
+    /// # Examples
+    ///
+    /// ## `Vec<u8>`
     ///
     /// ```rust
     /// let input = [0x04, 0x30, 0x5e, 0x2b, 0x24, 0x73, 0xf0, 0x58];
@@ -399,61 +1611,628 @@ impl<'a, I: AsRef<[u8]>> EncodeBuilder<'a, I> {
     /// # Ok::<(), bs58::encode::Error>(())
     /// ```
     pub fn onto(self, mut output: impl EncodeTarget) -> Result<usize> {
+        self.check_input_len()?;
+        output.encode_with(self.max_output_len(), |output| {
+            #[cfg(feature = "metrics")]
+            if let Some(stats) = self.stats {
+                let mut record = stats.get();
+                let result = self.encode_prefixed(output, &mut record);
+                record.output_len = *result.as_ref().unwrap_or(&0);
+                stats.set(record);
+                return result;
+            }
+            self.encode_prefixed(output, &mut ())
+        })
+    }
+
+    /// Write the text prefix, HRP separator and encoded input to the start of `output`,
+    /// returning the length written.
+    fn encode_prefixed(&self, output: &mut [u8], record: &mut impl Record) -> Result<usize> {
         let input = self.input.as_ref();
-        match self.check {
-            Check::Disabled => output.encode_with(max_encoded_len(input.len()), |output| {
-                encode_into(input, output, self.alpha)
-            }),
+        let prefix_len = self.text_prefix_len();
+        if output.len() < prefix_len {
+            return Err(Error::BufferTooSmall);
+        }
+        let (output_prefix, output) = output.split_at_mut(prefix_len);
+        for (out, byte) in output_prefix.iter_mut().zip(self.text_prefix_bytes()) {
+            *out = byte;
+        }
+        let len = match self.check {
+            Check::Disabled if self.ordered => {
+                encode_ordered(input, output, self.alpha, self.ordered_width(), record)
+            }
+            Check::Disabled if input.iter().all(|&b| b == 0) => {
+                record.fast_path();
+                encode_zeros(input.len(), output, self.alpha)
+            }
+            Check::Disabled => encode_into(input.iter().copied(), output, self.alpha, record),
+            #[cfg(feature = "check")]
+            Check::Enabled(version) => encode_check_into(
+                input,
+                output,
+                self.alpha,
+                self.aad,
+                version.as_slice(),
+                record,
+            ),
             #[cfg(feature = "check")]
-            Check::Enabled(version) => {
-                let input_len = input.len() + CHECKSUM_LEN + version.map_or(0, |_| 1);
-                output.encode_with(max_encoded_len(input_len), |output| {
-                    encode_check_into(self.input.as_ref(), output, self.alpha, version)
-                })
+            Check::Versioned(version) => {
+                encode_check_into(input, output, self.alpha, self.aad, version, record)
+            }
+            #[cfg(feature = "check")]
+            Check::Blocks(block_size) => {
+                encode_blocks_into(input, output, self.alpha, block_size, record)
             }
             #[cfg(feature = "cb58")]
-            Check::CB58(version) => {
-                let input_len = input.len() + CHECKSUM_LEN + version.map_or(0, |_| 1);
-                output.encode_with(max_encoded_len(input_len), |output| {
-                    encode_cb58_into(self.input.as_ref(), output, self.alpha, version)
-                })
+            Check::CB58(version) => encode_cb58_into(input, output, self.alpha, version, record),
+        }?;
+        if let Some(fold) = self.fold {
+            apply_fold(fold, &mut output[..len]);
+        }
+        Ok(prefix_len + len)
+    }
+
+    /// The length of the text prefix and HRP separator before the encoded characters.
+    fn text_prefix_len(&self) -> usize {
+        #[cfg(feature = "unicode-normalization")]
+        if self.nfc_prefix {
+            return self.text_prefix_chars().map(char::len_utf8).sum();
+        }
+        self.prefix.len() + self.hrp_separator.map_or(0, char::len_utf8)
+    }
+
+    /// The characters of the text prefix, normalized if
+    /// [`normalize_prefix_nfc`](Self::normalize_prefix_nfc) was set, followed by any HRP
+    /// separator.
+    fn text_prefix_chars(&self) -> impl Iterator<Item = char> + '_ {
+        #[cfg(feature = "unicode-normalization")]
+        let text = {
+            use unicode_normalization::UnicodeNormalization;
+            let nfc = self.nfc_prefix.then(|| self.prefix.nfc());
+            let raw = (!self.nfc_prefix).then(|| self.prefix.chars());
+            nfc.into_iter().flatten().chain(raw.into_iter().flatten())
+        };
+        #[cfg(not(feature = "unicode-normalization"))]
+        let text = self.prefix.chars();
+        text.chain(self.hrp_separator)
+    }
+
+    /// The UTF-8 bytes of [`text_prefix_chars`](Self::text_prefix_chars).
+    fn text_prefix_bytes(&self) -> impl Iterator<Item = u8> + '_ {
+        self.text_prefix_chars().flat_map(|c| {
+            let mut bytes = [0; 4];
+            let len = c.encode_utf8(&mut bytes).len();
+            bytes.into_iter().take(len)
+        })
+    }
+
+    /// Encode into a temporary buffer and pass the encoded characters to `f`.
+    ///
+    /// The buffer is on the stack when the output must fit in 128 characters, otherwise it is
+    /// allocated, or without `alloc` [`Error::BufferTooSmall`] is returned.
+    fn with_encoded<R>(self, f: impl FnOnce(&[u8]) -> Result<R>) -> Result<R> {
+        let max_len = self.max_output_len();
+
+        let encode = |scratch: &mut [u8]| {
+            let len = self.onto(&mut *scratch)?;
+            f(&scratch[..len])
+        };
+
+        if max_len <= SCRATCH_STACK_LEN {
+            encode(&mut [0; SCRATCH_STACK_LEN][..max_len])
+        } else {
+            #[cfg(feature = "alloc")]
+            return encode(&mut alloc::vec![0; max_len]);
+            #[cfg(not(feature = "alloc"))]
+            return Err(Error::BufferTooSmall);
+        }
+    }
+
+    fn check_input_len(&self) -> Result<()> {
+        let actual = self.input.as_ref().len();
+        match self.max_input {
+            Some(limit) if actual > limit => Err(Error::InputTooLarge { limit, actual }),
+            _ => Ok(()),
+        }
+    }
+
+    /// The space to reserve in the output for encoding.
+    fn max_output_len(&self) -> usize {
+        let extra = match self.check {
+            Check::Disabled => 0,
+            #[cfg(feature = "check")]
+            Check::Enabled(version) => CHECKSUM_LEN + version.map_or(0, |_| 1),
+            #[cfg(feature = "check")]
+            Check::Versioned(version) => CHECKSUM_LEN + version.len(),
+            #[cfg(feature = "check")]
+            Check::Blocks(block_size) => {
+                CHECKSUM_LEN * self.input.as_ref().len().div_ceil(block_size)
             }
+            #[cfg(feature = "cb58")]
+            Check::CB58(version) => CHECKSUM_LEN + version.map_or(0, |_| 1),
+        };
+        let len = match self.check {
+            Check::Disabled if self.ordered => self.ordered_width(),
+            _ => 0,
+        };
+        self.text_prefix_len() + len.max(max_encoded_len(self.input.as_ref().len() + extra))
+    }
+
+    /// The width of encodings in [order-preserving](Self::order_preserving) mode.
+    fn ordered_width(&self) -> usize {
+        ordered_width(self.max_input.unwrap_or(self.input.as_ref().len()))
+    }
+}
+
+/// Setup encoder for a [`Uuid`](uuid::Uuid), encoding its 16 big-endian bytes using the
+/// [default alphabet](Alphabet::DEFAULT).
+///
+/// # Examples
+///
+/// ```rust
+/// let uuid = uuid::Uuid::parse_str("67e55044-10b1-426f-9247-bb680e5fe0c8").unwrap();
+/// assert_eq!("Dq7QdGPZBdz9vwjm3jLQSB", bs58::encode::from_uuid(uuid).into_string());
+/// ```
+#[cfg(feature = "uuid")]
+pub fn from_uuid(uuid: uuid::Uuid) -> EncodeBuilder<'static, [u8; 16]> {
+    EncodeBuilder::from_input(uuid.into_bytes())
+}
+
+/// Setup encoder for a set of flags, packing them 8 to a byte using the
+/// [default alphabet](Alphabet::DEFAULT).
+///
+/// The first flag is the most significant bit of the first byte. If the number of flags is not a
+/// multiple of 8 the last byte is padded with zero bits, so `bits.len().div_ceil(8)` bytes are
+/// encoded. Every byte is kept including leading zeros, so decoding with
+/// [`decode::to_bits`](crate::decode::to_bits) and the same number of flags gives them back.
+///
+/// # Examples
+///
+/// ```rust
+/// // 0b1010_0000, 0b1000_0000
+/// let flags = [true, false, true, false, false, false, false, false, true];
+/// assert_eq!("DDR", bs58::encode::from_bits(&flags).into_string());
+/// assert_eq!("1", bs58::encode::from_bits(&[false; 3]).into_string());
+/// ```
+#[cfg(feature = "alloc")]
+pub fn from_bits(bits: &[bool]) -> EncodeBuilder<'static, Vec<u8>> {
+    let bytes = bits
+        .chunks(8)
+        .map(|chunk| {
+            chunk
+                .iter()
+                .enumerate()
+                .fold(0, |byte, (i, &bit)| byte | u8::from(bit) << (7 - i))
+        })
+        .collect();
+    EncodeBuilder::from_input(bytes)
+}
+
+/// Encode `values` as their big-endian (network order) bytes, for data that is semantically an
+/// array of 16-bit integers.
+///
+/// Every byte is kept including leading zeros, so decoding with
+/// [`DecodeBuilder::into_u16_be_vec`](crate::decode::DecodeBuilder::into_u16_be_vec) gives the
+/// values back on any platform.
+///
+/// # Examples
+///
+/// ```rust
+/// let values = [0x0430, 0x5e2b, 0x2473, 0xf058];
+/// assert_eq!("he11owor1d", bs58::encode::from_u16_be(&values).into_string());
+/// ```
+#[cfg(feature = "alloc")]
+pub fn from_u16_be(values: &[u16]) -> EncodeBuilder<'static, Vec<u8>> {
+    EncodeBuilder::from_input(values.iter().flat_map(|v| v.to_be_bytes()).collect())
+}
+
+/// Encode `values` as their big-endian (network order) bytes, for data that is semantically an
+/// array of 32-bit integers.
+///
+/// Every byte is kept including leading zeros, so decoding with
+/// [`DecodeBuilder::into_u32_be_vec`](crate::decode::DecodeBuilder::into_u32_be_vec) gives the
+/// values back on any platform.
+///
+/// # Examples
+///
+/// ```rust
+/// let values = [0x0430_5e2b, 0x2473_f058];
+/// assert_eq!("he11owor1d", bs58::encode::from_u32_be(&values).into_string());
+/// ```
+#[cfg(feature = "alloc")]
+pub fn from_u32_be(values: &[u32]) -> EncodeBuilder<'static, Vec<u8>> {
+    EncodeBuilder::from_input(values.iter().flat_map(|v| v.to_be_bytes()).collect())
+}
+
+/// Encode a timestamp or duration in milliseconds as the big-endian bytes of its value without
+/// leading zero bytes, for compact time-based IDs, using the
+/// [default alphabet](Alphabet::DEFAULT).
+///
+/// Unix timestamps from 1971 until late 2039 encode to 7 characters, and 0 to an empty string.
+/// Decode them with [`decode::to_timestamp_millis`](crate::decode::to_timestamp_millis).
+///
+/// Base58 is not order-preserving, so sorting the encodings as strings doesn't in general sort
+/// them by time: a value with fewer digits gives a shorter string that can sort after a longer
+/// one, and other alphabets (such as [`RIPPLE`](Alphabet::RIPPLE)) don't have their characters
+/// in ASCII order. Encodings of the same length with the default alphabet do sort by value, as
+/// its characters are in ASCII order.
+///
+/// # Examples
+///
+/// ```rust
+/// let encoded = bs58::encode::from_timestamp_millis(1_700_000_000_000).into_string();
+/// assert_eq!("mf4BMtb", encoded);
+/// assert_eq!(Ok(1_700_000_000_000), bs58::decode::to_timestamp_millis(&encoded));
+///
+/// // 57 and 58 milliseconds sort the wrong way round
+/// let before = bs58::encode::from_timestamp_millis(57).into_string();
+/// let after = bs58::encode::from_timestamp_millis(58).into_string();
+/// assert_eq!(("z", "21"), (before.as_str(), after.as_str()));
+/// assert!(before > after);
+/// ```
+#[cfg(feature = "alloc")]
+pub fn from_timestamp_millis(ms: u64) -> EncodeBuilder<'static, Vec<u8>> {
+    let bytes = ms.to_be_bytes();
+    let zeros = ms.leading_zeros() as usize / 8;
+    EncodeBuilder::from_input(bytes[zeros..].to_vec())
+}
+
+/// Encode a [Base58Check][] address: the `version` byte followed by `payload` and the checksum,
+/// using the [default alphabet](Alphabet::DEFAULT).
+///
+/// This is the same as `bs58::encode(payload).with_check_version(version).into_string()`, see
+/// [`decode::address`](crate::decode::address) for the inverse.
+///
+/// [Base58Check]: https://en.bitcoin.it/wiki/Base58Check_encoding
+///
+/// # Examples
+///
+/// ```rust
+/// // The genesis block coinbase address
+/// let hash = [
+///     0x62, 0xe9, 0x07, 0xb1, 0x5c, 0xbf, 0x27, 0xd5, 0x42, 0x53, 0x99, 0xeb, 0xf6, 0xf0, 0xfb,
+///     0x50, 0xeb, 0xb8, 0x8f, 0x18,
+/// ];
+/// assert_eq!("1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNa", bs58::encode::address(0x00, &hash));
+/// ```
+#[cfg(all(feature = "check", feature = "alloc"))]
+pub fn address(version: u8, payload: &[u8]) -> String {
+    EncodeBuilder::from_input(payload)
+        .with_check_version(version)
+        .into_string()
+}
+
+/// Encode a self-describing record: a length header followed by `payload` and a
+/// [Base58Check][] checksum, using the [default alphabet](Alphabet::DEFAULT).
+///
+/// The header is the length of `payload` as an unsigned [LEB128][] varint: seven bits per byte,
+/// least significant first, with the high bit set on every byte but the last, so payloads
+/// shorter than 128 bytes have a one byte header. The checksum covers the header and payload.
+/// See [`decode::record`](crate::decode::record) for the inverse.
+///
+/// [Base58Check]: https://en.bitcoin.it/wiki/Base58Check_encoding
+/// [LEB128]: https://en.wikipedia.org/wiki/LEB128
+///
+/// # Examples
+///
+/// ```rust
+/// let encoded = bs58::encode::record(b"hello");
+/// assert_eq!(bs58::encode(b"\x05hello").with_check().into_string(), encoded);
+/// assert_eq!(b"hello".to_vec(), bs58::decode::record(&encoded)?);
+/// # Ok::<(), bs58::decode::Error>(())
+/// ```
+#[cfg(all(feature = "check", feature = "alloc"))]
+pub fn record(payload: &[u8]) -> String {
+    let mut input = Vec::with_capacity(payload.len() + 10);
+    let mut len = payload.len();
+    while len >= 0x80 {
+        input.push(len as u8 | 0x80);
+        len >>= 7;
+    }
+    input.push(len as u8);
+    input.extend_from_slice(payload);
+    EncodeBuilder::from_input(input).with_check().into_string()
+}
+
+/// Encode a sha2-256 [multihash][] as an IPFS [CIDv0][].
+///
+/// A CIDv0 is the Base58 encoding, with the [default alphabet](Alphabet::DEFAULT) and no multibase
+/// prefix, of a 34 byte multihash: the header `0x12 0x20` followed by a 32 byte sha2-256 digest,
+/// so it is always 46 characters starting with `Qm`.
+///
+/// [CIDv0]: https://docs.ipfs.tech/concepts/content-addressing/#version-0-v0
+/// [multihash]: https://multiformats.io/multihash/
+///
+/// # Panics
+///
+/// If `multihash` is not 34 bytes starting with the sha2-256 header `0x12 0x20`, no other
+/// multihash can be represented as a CIDv0.
+///
+/// # Examples
+///
+/// ```rust
+/// let mut multihash = [0; 34];
+/// multihash[..2].copy_from_slice(&[0x12, 0x20]);
+/// assert_eq!("QmNLei78zWmzUdbeRB3CiUfAizWUrbeeZh5K1rhAQKCh51", bs58::encode::cidv0(&multihash));
+/// ```
+#[cfg(feature = "alloc")]
+pub fn cidv0(multihash: &[u8]) -> String {
+    assert!(
+        multihash.len() == crate::CIDV0_LEN && multihash.starts_with(&crate::CIDV0_HEADER),
+        "CIDv0 must be a 34 byte sha2-256 multihash"
+    );
+    crate::encode(multihash).into_string()
+}
+
+/// Setup encoder for `len` bytes produced on demand by `f` using the
+/// [default alphabet](Alphabet::DEFAULT), so the input does not need to be stored in a buffer.
+///
+/// `f` is called with each index in `0..len` in order, then again with the indexes of any leading
+/// zero bytes (stopping at the first non-zero byte), so it must return the same byte each time it
+/// is called with an index.
+///
+/// # Examples
+///
+/// ```rust
+/// let input = [0x04, 0x30, 0x5e, 0x2b, 0x24, 0x73, 0xf0, 0x58];
+/// assert_eq!("he11owor1d", bs58::encode::from_fn(input.len(), |i| input[i]).into_string());
+/// ```
+pub fn from_fn<F: Fn(usize) -> u8>(len: usize, f: F) -> FnEncodeBuilder<'static, F> {
+    FnEncodeBuilder {
+        len,
+        f,
+        alpha: Alphabet::DEFAULT,
+    }
+}
+
+/// The longest chunk passed to the callback of [`from_slice_streaming`].
+#[cfg(feature = "alloc")]
+const STREAM_CHUNK_LEN: usize = 64 * 1024;
+
+/// Encode `data` using the [default alphabet](Alphabet::DEFAULT), passing the encoded string to
+/// `chunk_callback` in consecutive chunks of at most 64 KiB, e.g. to write it out without
+/// keeping a [`String`] of it.
+///
+/// This is intended for large borrowed inputs such as a memory-mapped file. The input is read in
+/// place and never copied, but Base58 characters are produced least significant first so none of
+/// the output is known until all of it has been converted: the only allocation is one buffer of
+/// the [maximum encoded length](crate::max_encoded_len) of `data`, which is freed before
+/// returning. No other memory proportional to the input is used, on the heap or the stack.
+///
+/// Base58 conversion takes time quadratic in the input length, so this is only practical for
+/// inputs up to a few hundred KiB.
+///
+/// # Examples
+///
+/// ```rust
+/// let input = [0x04, 0x30, 0x5e, 0x2b, 0x24, 0x73, 0xf0, 0x58];
+/// let mut output = String::new();
+/// bs58::encode::from_slice_streaming(&input, |chunk| output.push_str(chunk));
+/// assert_eq!("he11owor1d", output);
+/// ```
+#[cfg(feature = "alloc")]
+pub fn from_slice_streaming(data: &[u8], mut chunk_callback: impl FnMut(&str)) {
+    let mut output = Vec::new();
+    crate::encode(data).onto(&mut output).unwrap();
+    for chunk in output.chunks(STREAM_CHUNK_LEN) {
+        chunk_callback(core::str::from_utf8(chunk).unwrap());
+    }
+}
+
+/// Encode `input` using the [default alphabet](Alphabet::DEFAULT) and write the encoded string
+/// to the async writer `w`, for use in async servers.
+///
+/// None of the output is known until all of the input has been converted, so this encodes into
+/// memory as [`into_string`](EncodeBuilder::into_string) does then writes it all out, only the
+/// write is asynchronous. Base58 conversion takes time quadratic in the input length, for large
+/// inputs consider encoding on a blocking thread instead.
+///
+/// # Examples
+///
+/// ```rust
+/// # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+/// let mut output = Vec::new();
+/// bs58::encode::write_async([0x04, 0x30, 0x5e, 0x2b, 0x24, 0x73, 0xf0, 0x58], &mut output)
+///     .await?;
+/// assert_eq!(b"he11owor1d", output.as_slice());
+/// # Ok::<(), std::io::Error>(())
+/// # }).unwrap();
+/// ```
+#[cfg(feature = "tokio")]
+pub async fn write_async<W: tokio::io::AsyncWrite + Unpin>(
+    input: impl AsRef<[u8]>,
+    w: &mut W,
+) -> std::io::Result<()> {
+    use tokio::io::AsyncWriteExt;
+
+    let output = crate::encode(input).into_vec();
+    w.write_all(&output).await
+}
+
+impl<'a, F: Fn(usize) -> u8> FnEncodeBuilder<'a, F> {
+    /// Change the alphabet that will be used for encoding.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let input = [0x60, 0x65, 0xe7, 0x9b, 0xba, 0x2f, 0x78];
+    /// assert_eq!(
+    ///     "he11owor1d",
+    ///     bs58::encode::from_fn(input.len(), |i| input[i])
+    ///         .with_alphabet(bs58::Alphabet::RIPPLE)
+    ///         .into_string());
+    /// ```
+    pub fn with_alphabet<'b>(self, alpha: &'b Alphabet) -> FnEncodeBuilder<'b, F> {
+        FnEncodeBuilder {
+            len: self.len,
+            f: self.f,
+            alpha,
         }
     }
+
+    /// Encode into a new owned string.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// assert_eq!("1111", bs58::encode::from_fn(4, |_| 0).into_string());
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn into_string(self) -> String {
+        let mut output = String::new();
+        self.onto(&mut output).unwrap();
+        output
+    }
+
+    /// Encode into a new owned vector.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// assert_eq!(b"1111", &*bs58::encode::from_fn(4, |_| 0).into_vec());
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn into_vec(self) -> Vec<u8> {
+        let mut output = Vec::new();
+        self.onto(&mut output).unwrap();
+        output
+    }
+
+    /// Encode onto the given buffer, see [`EncodeBuilder::onto`] for details.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let input = [0x04, 0x30, 0x5e, 0x2b, 0x24, 0x73, 0xf0, 0x58];
+    /// let mut output = [0; 10];
+    /// assert_eq!(10, bs58::encode::from_fn(input.len(), |i| input[i]).onto(&mut output[..])?);
+    /// assert_eq!(b"he11owor1d", &output);
+    /// # Ok::<(), bs58::encode::Error>(())
+    /// ```
+    pub fn onto(self, mut output: impl EncodeTarget) -> Result<usize> {
+        let input = (0..self.len).map(&self.f);
+        output.encode_with(max_encoded_len(self.len), |output| {
+            encode_into(input, output, self.alpha, &mut ())
+        })
+    }
+}
+
+/// Return the expected ratio of encoded length to input length for random data of the given
+/// length, for capacity planning.
+///
+/// This is a statistical estimate based on each byte carrying log<sub>58</sub>(256) ≈ 1.37
+/// characters of information, rounded up to a whole number of characters. It is not exact for any
+/// given input: an input may encode one character shorter, and each leading zero byte encodes to
+/// a single character. Use [`max_encoded_len`] for sizing buffers.
+///
+/// # Examples
+///
+/// ```rust
+/// assert_eq!(1.375, bs58::encode::expansion_estimate(32));
+/// assert_eq!(44, bs58::encode([0xff; 32]).into_string().len());
+/// ```
+pub fn expansion_estimate(input_len: usize) -> f64 {
+    // log(256) / log(58)
+    const RATIO: f64 = 1.365_658_237_309_761;
+    if input_len == 0 {
+        return RATIO;
+    }
+    let chars = input_len as f64 * RATIO;
+    let whole = chars as usize as f64;
+    let chars = if whole < chars { whole + 1.0 } else { whole };
+    chars / input_len as f64
 }
 
-/// Return maximum possible encoded length of a buffer with given length.
+/// Report which characters of `alphabet` appear in the encoding of `input`, indexed by their
+/// position in the alphabet, e.g. to see how densely an encoding uses the alphabet.
+///
+/// This is usage over the encoded output, so leading zero bytes each count as a use of the zero
+/// character and an empty input uses none. The input is encoded into a temporary buffer, on the
+/// stack for up to 128 characters and allocated otherwise.
 ///
-/// Assumes that the `len` already includes version and checksum bytes if those
-/// are
-fn max_encoded_len(len: usize) -> usize {
-    // log_2(256) / log_2(58) ≈ 1.37.  Assume 1.5 for easier calculation.
-    len + (len + 1) / 2
+/// # Examples
+///
+/// ```rust
+/// let input = [0x04, 0x30, 0x5e, 0x2b, 0x24, 0x73, 0xf0, 0x58];
+/// let usage = bs58::encode::charset_usage(&input, bs58::Alphabet::BITCOIN);
+/// // "he11owor1d" uses `1`, `d`, `e`, `h`, `o`, `r` and `w`
+/// assert_eq!(7, usage.iter().filter(|&&used| used).count());
+/// assert!(usage[0] && !usage[1]);
+/// ```
+#[cfg(feature = "alloc")]
+pub fn charset_usage(input: &[u8], alphabet: &Alphabet) -> [bool; 58] {
+    let mut usage = [false; 58];
+    EncodeBuilder::new(input, alphabet)
+        .with_encoded(|encoded| {
+            for &c in encoded {
+                // A separate lead character is still a zero digit
+                let digit = match alphabet.digit(c) {
+                    Alphabet::INVALID => 0,
+                    digit => digit,
+                };
+                usage[usize::from(digit)] = true;
+            }
+            Ok(())
+        })
+        .unwrap();
+    usage
+}
+
+/// Convert the encoded characters in `output` to their display form.
+fn apply_fold(fold: Fold, output: &mut [u8]) {
+    output.iter_mut().for_each(|c| *c = fold.apply(*c));
+}
+
+/// The number of characters needed for any value of up to `max_bytes` bytes, the smallest width
+/// with `58^width >= 256^max_bytes`.
+fn ordered_width(max_bytes: usize) -> usize {
+    // log_58(256) as a 64.64 fixed point number, rounded up so this never underestimates
+    const LOG_58_256: u128 = 0x1_5d9b_c73a_c228_8343;
+    let scaled = (max_bytes as u128).saturating_mul(LOG_58_256);
+    usize::try_from(scaled.div_ceil(1 << 64)).unwrap_or(usize::MAX)
+}
+
+/// Encode the value of `input` padded with zero characters to `width`, see
+/// [`EncodeBuilder::order_preserving`].
+fn encode_ordered(
+    input: &[u8],
+    output: &mut [u8],
+    alpha: &Alphabet,
+    width: usize,
+    record: &mut impl Record,
+) -> Result<usize> {
+    let zeros = input.iter().take_while(|&&b| b == 0).count();
+    let output = output.get_mut(..width).ok_or(Error::BufferTooSmall)?;
+    let len = encode_into(input[zeros..].iter().copied(), output, alpha, record)?;
+    output.copy_within(..len, width - len);
+    output[..width - len].fill(alpha.zero_char());
+    Ok(width)
+}
+
+/// Encode `len` zero bytes, which is just a lead character for each of them.
+fn encode_zeros(len: usize, output: &mut [u8], alpha: &Alphabet) -> Result<usize> {
+    let output = output.get_mut(..len).ok_or(Error::BufferTooSmall)?;
+    output.fill(alpha.lead_char());
+    Ok(len)
 }
 
-fn encode_into<'a, I>(input: I, output: &mut [u8], alpha: &Alphabet) -> Result<usize>
+fn encode_into<I>(
+    input: I,
+    output: &mut [u8],
+    alpha: &Alphabet,
+    record: &mut impl Record,
+) -> Result<usize>
 where
-    I: Clone + IntoIterator<Item = &'a u8>,
+    I: Clone + IntoIterator<Item = u8>,
 {
     let mut index = 0;
-    for &val in input.clone() {
-        let mut carry = val as usize;
-        for byte in &mut output[..index] {
-            carry += (*byte as usize) << 8;
-            *byte = (carry % 58) as u8;
-            carry /= 58;
-        }
-        while carry > 0 {
-            if index == output.len() {
-                return Err(Error::BufferTooSmall);
-            }
-            output[index] = (carry % 58) as u8;
-            index += 1;
-            carry /= 58;
-        }
+    for byte in input.clone() {
+        record.iterations(index);
+        index = raw::push_byte(output, index, byte).ok_or(Error::BufferTooSmall)?;
     }
 
-    for _ in input.into_iter().take_while(|v| **v == 0) {
+    let digits = index;
+    for _ in input.into_iter().take_while(|v| *v == 0) {
         if index == output.len() {
             return Err(Error::BufferTooSmall);
         }
@@ -461,46 +2240,63 @@ where
         index += 1;
     }
 
-    for val in &mut output[..index] {
-        *val = alpha.encode[*val as usize];
+    for digit in &mut output[..digits] {
+        *digit = raw::character(alpha, *digit);
     }
+    output[digits..index].fill(alpha.lead_char());
 
     output[..index].reverse();
     Ok(index)
 }
 
+#[cfg(feature = "check")]
+fn check_checksum(aad: &[u8], version: &[u8], input: &[u8]) -> [u8; CHECKSUM_LEN] {
+    let mut checksummer = crate::check::Checksummer::new();
+    checksummer.update(aad);
+    checksummer.update(version);
+    checksummer.update(input);
+    checksummer.finalize()
+}
+
 #[cfg(feature = "check")]
 fn encode_check_into(
     input: &[u8],
     output: &mut [u8],
     alpha: &Alphabet,
-    version: Option<u8>,
+    aad: &[u8],
+    version: &[u8],
+    record: &mut impl Record,
 ) -> Result<usize> {
-    use sha2::{Digest, Sha256};
-
-    let mut first_hash = Sha256::new();
-    if let Some(version) = version {
-        first_hash.update([version; 1]);
-    }
-    let first_hash = first_hash.chain_update(input).finalize();
-    let second_hash = Sha256::digest(first_hash);
-
-    let checksum = &second_hash[0..CHECKSUM_LEN];
+    let checksum = check_checksum(aad, version, input);
 
     encode_into(
-        version.iter().chain(input.iter()).chain(checksum.iter()),
+        version.iter().chain(input).chain(&checksum).copied(),
         output,
         alpha,
+        record,
     )
 }
 
-#[cfg(feature = "cb58")]
-fn encode_cb58_into(
+#[cfg(feature = "check")]
+fn encode_blocks_into(
     input: &[u8],
     output: &mut [u8],
     alpha: &Alphabet,
-    version: Option<u8>,
+    block_size: usize,
+    record: &mut impl Record,
 ) -> Result<usize> {
+    encode_into(
+        input
+            .chunks(block_size)
+            .flat_map(|block| block.iter().copied().chain(check_checksum(&[], &[], block))),
+        output,
+        alpha,
+        record,
+    )
+}
+
+#[cfg(feature = "cb58")]
+fn cb58_checksum(version: Option<u8>, input: &[u8]) -> [u8; CHECKSUM_LEN] {
     use sha2::{Digest, Sha256};
 
     let mut hash = Sha256::new();
@@ -509,18 +2305,49 @@ fn encode_cb58_into(
     }
     let hash = hash.chain_update(input).finalize();
 
-    let checksum = &hash[hash.len() - CHECKSUM_LEN..];
+    let mut checksum = [0; CHECKSUM_LEN];
+    checksum.copy_from_slice(&hash[hash.len() - CHECKSUM_LEN..]);
+    checksum
+}
+
+#[cfg(feature = "cb58")]
+fn encode_cb58_into(
+    input: &[u8],
+    output: &mut [u8],
+    alpha: &Alphabet,
+    version: Option<u8>,
+    record: &mut impl Record,
+) -> Result<usize> {
+    let checksum = cb58_checksum(version, input);
 
     encode_into(
-        version.iter().chain(input.iter()).chain(checksum.iter()),
+        version.iter().chain(input).chain(&checksum).copied(),
         output,
         alpha,
+        record,
     )
 }
 
 #[cfg(feature = "std")]
 impl std::error::Error for Error {}
 
+/// Converts into an error of kind [`WriteZero`](std::io::ErrorKind::WriteZero) for
+/// [`BufferTooSmall`](Error::BufferTooSmall), [`OutOfMemory`](std::io::ErrorKind::OutOfMemory) for
+/// [`AllocationFailed`](Error::AllocationFailed), or
+/// [`InvalidInput`](std::io::ErrorKind::InvalidInput) for
+/// [`InputTooLarge`](Error::InputTooLarge), with this error as the source.
+#[cfg(feature = "std")]
+impl From<Error> for std::io::Error {
+    fn from(err: Error) -> Self {
+        let kind = match err {
+            Error::BufferTooSmall => std::io::ErrorKind::WriteZero,
+            Error::AllocationFailed => std::io::ErrorKind::OutOfMemory,
+            Error::InputTooLarge { .. } => std::io::ErrorKind::InvalidInput,
+        };
+        std::io::Error::new(kind, err)
+    }
+}
+
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match *self {
@@ -528,6 +2355,11 @@ impl fmt::Display for Error {
                 f,
                 "buffer provided to encode base58 string into was too small"
             ),
+            Error::AllocationFailed => write!(f, "failed to allocate space for the encoded string"),
+            Error::InputTooLarge { limit, actual } => write!(
+                f,
+                "input to encode was {actual} bytes, more than the limit of {limit} bytes"
+            ),
         }
     }
 }
@@ -0,0 +1,97 @@
+//! Generation of valid Base58 strings for test fixtures, enabled by the `rand` feature.
+//!
+//! The strings are produced by encoding generated bytes with this crate's own encoder, so they
+//! always decode back to exactly the requested length with the same alphabet.
+//!
+//! In the 2024 edition `gen` is a keyword, so this module must be referred to as `bs58::r#gen`.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use rand::SeedableRng;
+//!
+//! let mut rng = rand::rngs::SmallRng::seed_from_u64(0);
+//! for _ in 0..100 {
+//!     let s = bs58::gen::encoded_with_len(&mut rng, 32, bs58::Alphabet::DEFAULT);
+//!     assert_eq!(32, bs58::decode(s).into_vec()?.len());
+//! }
+//! # Ok::<(), bs58::decode::Error>(())
+//! ```
+
+use alloc::{string::String, vec};
+
+use rand_core::RngCore;
+
+use crate::Alphabet;
+
+/// Generate a string that decodes with `alphabet` to `decoded_len` random bytes.
+///
+/// Uniformly random bytes almost never start with a zero byte, so a quarter of the strings have
+/// a random number of leading zero bytes, to cover the leading zero characters these encode to.
+pub fn encoded_with_len(rng: &mut impl RngCore, decoded_len: usize, alphabet: &Alphabet) -> String {
+    let mut bytes = vec![0; decoded_len];
+    rng.fill_bytes(&mut bytes);
+    if decoded_len > 0 && rng.next_u32() & 3 == 0 {
+        let zeros = (rng.next_u64() % (decoded_len as u64 + 1)) as usize;
+        bytes[..zeros].fill(0);
+    }
+    crate::encode(bytes).with_alphabet(alphabet).into_string()
+}
+
+/// Generate a string of exactly `len` characters, each chosen uniformly from the 58 characters of
+/// `alphabet`.
+///
+/// Unlike [`encoded_with_len`] this controls the number of characters rather than the number of
+/// decoded bytes, e.g. for placeholder IDs that must have a fixed width. Every such string is
+/// valid, decoding to a number of bytes that depends on its value and leading zero characters.
+///
+/// # Examples
+///
+/// ```rust
+/// use rand::SeedableRng;
+///
+/// let mut rng = rand::rngs::SmallRng::seed_from_u64(0);
+/// let s = bs58::gen::chars_with_len(&mut rng, 22, bs58::Alphabet::DEFAULT);
+/// assert_eq!(22, s.len());
+/// assert!(bs58::decode(s).into_vec().is_ok());
+/// ```
+pub fn chars_with_len(rng: &mut impl RngCore, len: usize, alphabet: &Alphabet) -> String {
+    (0..len)
+        .map(|_| loop {
+            // Rejecting the values above 57 keeps every character equally likely
+            let digit = (rng.next_u32() >> 26) as usize;
+            if let Some(&c) = alphabet.encode_table().get(digit) {
+                break char::from(c);
+            }
+        })
+        .collect()
+}
+
+/// Generate the strings decoding with `alphabet` to each of the deliberately awkward values of
+/// `decoded_len` bytes: all zero bytes (all leading zero characters), all `0xFF` bytes (the
+/// maximum value), then each value with a single bit set, from the lowest bit up.
+///
+/// For a length of zero this only yields the empty string, otherwise it yields
+/// `2 + 8 * decoded_len` strings.
+///
+/// # Examples
+///
+/// ```rust
+/// let cases: Vec<String> = bs58::gen::edge_cases(2, bs58::Alphabet::DEFAULT).collect();
+/// assert_eq!(18, cases.len());
+/// assert_eq!(["11", "LUv", "12", "13"], cases[..4]);
+/// ```
+pub fn edge_cases(decoded_len: usize, alphabet: &Alphabet) -> impl Iterator<Item = String> + '_ {
+    let zeros = vec![0; decoded_len];
+    let max = (decoded_len > 0).then(|| vec![0xFF; decoded_len]);
+    let single_bits = (0..decoded_len * 8).map(move |bit| {
+        let mut bytes = vec![0; decoded_len];
+        bytes[decoded_len - 1 - bit / 8] = 1 << (bit % 8);
+        bytes
+    });
+
+    core::iter::once(zeros)
+        .chain(max)
+        .chain(single_bits)
+        .map(move |bytes| crate::encode(bytes).with_alphabet(alphabet).into_string())
+}
@@ -0,0 +1,105 @@
+//! Summarizing every way an input could be Base58, for "smart paste" fields.
+
+use crate::{decode, Alphabet};
+
+/// A summary of how an input decodes with each built-in alphabet and whether it carries a
+/// checksum, see [`inspect`](crate::inspect()).
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Inspection {
+    /// How the input decodes with each distinct built-in alphabet, in the order
+    /// [`BITCOIN`](Alphabet::BITCOIN), [`RIPPLE`](Alphabet::RIPPLE), [`FLICKR`](Alphabet::FLICKR).
+    pub alphabets: [AlphabetInspection; 3],
+    /// The first error decoding the input the default way, as [Base58Check][] with any version
+    /// and the [default alphabet](Alphabet::DEFAULT), or `None` if it decodes.
+    ///
+    /// [Base58Check]: https://en.bitcoin.it/wiki/Base58Check_encoding
+    pub default_error: Option<decode::Error>,
+}
+
+/// How an input decodes with one alphabet, see [`Inspection`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct AlphabetInspection {
+    /// The lowercase name of the alphabet, e.g. `"bitcoin"`.
+    pub name: &'static str,
+    /// The alphabet.
+    pub alphabet: &'static Alphabet,
+    /// The decoded length in bytes (including any checksum), or `None` if not every character of
+    /// the input is in the alphabet.
+    pub decoded_len: Option<usize>,
+    /// Whether the last 4 decoded bytes are a valid [Base58Check][] checksum of the bytes before
+    /// them.
+    ///
+    /// [Base58Check]: https://en.bitcoin.it/wiki/Base58Check_encoding
+    pub valid_checksum: bool,
+    /// The version byte, the first decoded byte, when the checksum is valid and there is at least
+    /// one byte before it.
+    pub version: Option<u8>,
+}
+
+impl AlphabetInspection {
+    /// Whether every character of the input is in the alphabet.
+    pub fn charset_valid(&self) -> bool {
+        self.decoded_len.is_some()
+    }
+}
+
+/// Decode `input` with each built-in alphabet, checking for a [Base58Check][] checksum, and
+/// summarize what it could be, e.g. to tell a user what they pasted into an address field.
+///
+/// The input is decoded once per alphabet into one reused buffer, and the checksum is verified
+/// from the decoded bytes without decoding again. Any input can be inspected without panicking,
+/// though as with all Base58 decoding the time taken grows quadratically with its length, so
+/// untrusted input should be limited to a reasonable length first.
+///
+/// [Base58Check]: https://en.bitcoin.it/wiki/Base58Check_encoding
+///
+/// # Examples
+///
+/// ```rust
+/// let inspection = bs58::inspect("1F9v11cupBVMpz3CrVfCppv9Rw2xEtU1c6");
+/// let bitcoin = &inspection.alphabets[0];
+/// assert_eq!("bitcoin", bitcoin.name);
+/// assert_eq!(Some(25), bitcoin.decoded_len);
+/// assert!(bitcoin.valid_checksum);
+/// assert_eq!(Some(0x00), bitcoin.version);
+/// assert_eq!(None, inspection.default_error);
+///
+/// let inspection = bs58::inspect("he11o0");
+/// assert!(!inspection.alphabets[0].charset_valid());
+/// assert_eq!(
+///     Some(bs58::decode::Error::InvalidCharacter { character: '0', index: 5 }),
+///     inspection.default_error);
+/// ```
+pub fn inspect(input: &str) -> Inspection {
+    let mut buffer = alloc::vec::Vec::with_capacity(input.len());
+    let mut default_error = None;
+
+    let alphabets = Alphabet::BUILTIN.map(|(name, alphabet)| {
+        buffer.clear();
+        let decoded = crate::decode(input)
+            .with_alphabet(alphabet)
+            .onto(&mut buffer);
+        let checked = decoded.and_then(|_| decode::verify_check(&[], &buffer, None));
+        if alphabet == Alphabet::DEFAULT {
+            default_error = checked.err();
+        }
+
+        AlphabetInspection {
+            name,
+            alphabet,
+            decoded_len: decoded.ok(),
+            valid_checksum: checked.is_ok(),
+            version: match checked {
+                Ok(payload_len) if payload_len > 0 => Some(buffer[0]),
+                _ => None,
+            },
+        }
+    });
+
+    Inspection {
+        alphabets,
+        default_error,
+    }
+}
@@ -8,8 +8,8 @@
 #![warn(unused_extern_crates)]
 #![warn(unused_import_braces)]
 #![warn(variant_size_differences)]
-// This would be forbid, except unsafe is necessary to work with `&mut str`,
-// nowhere else should use it
+// This would be forbid, except unsafe is necessary to work with `&mut str` and uninitialized
+// buffers, nowhere else should use it
 #![deny(unsafe_code)]
 #![doc(test(attr(deny(warnings))))]
 
@@ -39,6 +39,24 @@
 //!  `alloc` | implied by `std`   | Support encoding/decoding to [`Vec`](alloc::vec::Vec) and [`String`](alloc::string::String) as appropriate
 //!  `check` | **off**-by-default | Integrated support for [Base58Check][]
 //!  `cb58`  | **off**-by-default | Integrated support for [CB58][]
+//!  `sha2`  | **off**-by-default | Hash while encoding with [`EncodeBuilder::into_string_with_hash`](encode::EncodeBuilder::into_string_with_hash), implied by `check` and `cb58`
+//!  `uuid`  | **off**-by-default | Encoding/decoding of [`uuid::Uuid`] values
+//!  `serde` | **off**-by-default | Implement `Serialize` and `Deserialize` for validated types
+//!  `allocator-api2` | **off**-by-default | Support encoding/decoding into an [`allocator_api2::vec::Vec`] with a custom allocator
+//!  `bytemuck` | **off**-by-default | Encoding/decoding of plain-old-data values with [`encode_pod`] and [`DecodeBuilder::into_pod`](decode::DecodeBuilder::into_pod)
+//!  `generic-array` | **off**-by-default | Decoding into a [`GenericArray`](generic_array::GenericArray) with [`DecodeBuilder::into_generic_array`](decode::DecodeBuilder::into_generic_array)
+//!  `ss58` | **off**-by-default | Encoding/decoding of Substrate [`ss58`] addresses, implies `alloc`
+//!  `rayon` | **off**-by-default | Parallel batch decoding with [`decode::par_batch`], implies `std`
+//!  `test-vectors` | **off**-by-default | Expose the conformance test [`vectors`]
+//!  `rand` | **off**-by-default | Generate valid strings for test fixtures in [`gen`], implies `alloc`
+//!  `test-util` | **off**-by-default | Assertions for testing code using Base58 in [`testing`], implies `alloc`
+//!  `compact-tables` | **off**-by-default | Smaller but slower decode tables, see [`Alphabet`]
+//!  `crc` | **off**-by-default | Compute a CRC-32 of decoded data with [`DecodeBuilder::into_vec_with_crc32`](decode::DecodeBuilder::into_vec_with_crc32), implies `alloc`
+//!  `subtle` | **off**-by-default | Validating secret inputs without timing leaks with [`DecodeBuilder::into_vec_constant_time`](decode::DecodeBuilder::into_vec_constant_time), implies `alloc`
+//!  `tokio` | **off**-by-default | Writing encoded strings to a [`tokio::io::AsyncWrite`] with [`encode::write_async`], implies `std`
+//!  `unicode-normalization` | **off**-by-default | NFC normalization of text prefixes with [`EncodeBuilder::normalize_prefix_nfc`](encode::EncodeBuilder::normalize_prefix_nfc)
+//!  `digest` | **off**-by-default | Hash while decoding with [`DecodeBuilder::decode_to_digest`](decode::DecodeBuilder::decode_to_digest)
+//!  `metrics` | **off**-by-default | Counters of the work done by an encode or decode in [`metrics`]
 //!
 //! [Base58Check]: https://en.bitcoin.it/wiki/Base58Check_encoding
 //! [CB58]: https://support.avax.network/en/articles/4587395-what-is-cb58
@@ -87,21 +105,297 @@ pub mod alphabet;
 #[doc(inline)]
 pub use alphabet::Alphabet;
 
+mod buffer;
+pub use buffer::Buffer;
+
+#[cfg(all(feature = "check", feature = "alloc"))]
+mod inspect;
+#[cfg(all(feature = "check", feature = "alloc"))]
+pub use inspect::{inspect, AlphabetInspection, Inspection};
+
+mod self_test;
+pub use self_test::{self_test, SelfTestError, SelfTestStage};
+
+#[cfg(feature = "check")]
+pub mod check;
 pub mod decode;
 pub mod encode;
+#[cfg(feature = "rand")]
+pub mod gen;
+#[cfg(feature = "metrics")]
+pub mod metrics;
+pub mod raw;
+#[cfg(feature = "ss58")]
+pub mod ss58;
+#[cfg(feature = "test-util")]
+pub mod testing;
+#[cfg(feature = "test-vectors")]
+pub mod vectors;
 
 #[cfg(any(feature = "check", feature = "cb58"))]
 const CHECKSUM_LEN: usize = 4;
 
+/// The [multihash][] header of a sha2-256 digest, which every IPFS [CIDv0][] consists of.
+///
+/// [multihash]: https://multiformats.io/multihash/
+/// [CIDv0]: https://docs.ipfs.tech/concepts/content-addressing/#version-0-v0
+#[cfg(feature = "alloc")]
+const CIDV0_HEADER: [u8; 2] = [0x12, 0x20];
+
+/// The length of a sha2-256 multihash, the header followed by the 32 byte digest.
+#[cfg(feature = "alloc")]
+const CIDV0_LEN: usize = CIDV0_HEADER.len() + 32;
+
+/// Receives counts of the work done while converting, for [`metrics::Stats`], or ignores them
+/// so that counting costs nothing when it isn't wanted.
+pub(crate) trait Record {
+    /// The inner conversion loop is about to run `n` iterations.
+    fn iterations(&mut self, n: usize);
+
+    /// A fast path is being taken instead of the conversion loop.
+    fn fast_path(&mut self);
+}
+
+impl Record for () {
+    #[inline(always)]
+    fn iterations(&mut self, _: usize) {}
+
+    #[inline(always)]
+    fn fast_path(&mut self) {}
+}
+
 /// Possible check variants.
+#[allow(variant_size_differences)]
+#[derive(Clone, Copy)]
 enum Check {
     Disabled,
     #[cfg(feature = "check")]
     Enabled(Option<u8>),
+    #[cfg(feature = "check")]
+    Versioned(&'static [u8]),
+    #[cfg(feature = "check")]
+    Blocks(usize),
     #[cfg(feature = "cb58")]
     CB58(Option<u8>),
 }
 
+/// Return the maximum possible encoded length of an input with the given length.
+///
+/// When encoding with a checksum the `len` must include the version and checksum bytes that will
+/// be added.
+///
+/// # Examples
+///
+/// ```rust
+/// const BUFFER_LEN: usize = bs58::max_encoded_len(32);
+/// let mut output = [0; BUFFER_LEN];
+/// assert_eq!(44, bs58::encode([0xff; 32]).onto(&mut output[..])?);
+/// # Ok::<(), bs58::encode::Error>(())
+/// ```
+pub const fn max_encoded_len(len: usize) -> usize {
+    // log_2(256) / log_2(58) ≈ 1.37.  Assume 1.5 for easier calculation.
+    len + len.div_ceil(2)
+}
+
+/// Return the maximum possible decoded length of an input with the given length.
+///
+/// Every leading zero character decodes to a zero byte, so this is the same as the input length.
+/// When decoding with a checksum this bounds the length before the checksum is removed.
+///
+/// # Examples
+///
+/// ```rust
+/// const BUFFER_LEN: usize = bs58::max_decoded_len(10);
+/// let mut output = [0; BUFFER_LEN];
+/// assert_eq!(8, bs58::decode("he11owor1d").onto(&mut output)?);
+/// # Ok::<(), bs58::decode::Error>(())
+/// ```
+pub const fn max_decoded_len(len: usize) -> usize {
+    len
+}
+
+/// Lookup table of the characters in [`Alphabet::DEFAULT`], indexed by byte.
+const DEFAULT_CHARS: [bool; 256] = {
+    let mut table = [false; 256];
+    let mut i = 0;
+    while i < Alphabet::DEFAULT.encode.len() {
+        table[Alphabet::DEFAULT.encode[i] as usize] = true;
+        i += 1;
+    }
+    table
+};
+
+/// Return whether the byte is a character in the [default alphabet](Alphabet::DEFAULT).
+///
+/// # Examples
+///
+/// ```rust
+/// assert!(bs58::is_default_char(b'1'));
+/// assert!(bs58::is_default_char(b'z'));
+/// assert!(!bs58::is_default_char(b'0'));
+/// assert!(!bs58::is_default_char(b'l'));
+///
+/// const _: () = assert!(bs58::is_default_char(b'A'));
+/// ```
+pub const fn is_default_char(byte: u8) -> bool {
+    DEFAULT_CHARS[byte as usize]
+}
+
+/// Payloads up to this length are compared by [`eq`] using only stack space.
+const EQ_STACK_LEN: usize = 128;
+
+/// Check whether `encoded` is the Base58 encoding of `bytes` using the given alphabet, without
+/// allocating.
+///
+/// Inputs that can't possibly match because of their length are rejected without doing any
+/// decoding work, otherwise `encoded` is decoded into a scratch buffer only as large as `bytes`,
+/// so the work done is bounded by the length of `bytes` regardless of how long `encoded` is.
+///
+/// The scratch buffer lives on the stack for payloads up to 128 bytes. Longer payloads are instead
+/// compared by their remainders modulo a series of 32-bit primes, each computed in a single pass
+/// over `bytes` and `encoded`, so they are still compared exactly and still without allocating,
+/// in time quadratic in the length as for decoding.
+///
+/// # Examples
+///
+/// ```rust
+/// let key = [0x04, 0x30, 0x5e, 0x2b, 0x24, 0x73, 0xf0, 0x58];
+/// assert!(bs58::eq(&key, "he11owor1d", bs58::Alphabet::DEFAULT));
+/// assert!(!bs58::eq(&key, "he11owor1e", bs58::Alphabet::DEFAULT));
+/// assert!(!bs58::eq(&key, "1he11owor1d", bs58::Alphabet::DEFAULT));
+/// assert!(!bs58::eq(&key, "he11o wor1d", bs58::Alphabet::DEFAULT));
+/// ```
+pub fn eq(bytes: &[u8], encoded: impl AsRef<[u8]>, alphabet: &Alphabet) -> bool {
+    eq_with(bytes, &[], encoded.as_ref(), alphabet)
+}
+
+/// Check whether `encoded` is the [Base58Check][] encoding of `bytes` (including any version
+/// byte) using the given alphabet, without allocating.
+///
+/// See [`eq`] for details of the scratch space used.
+///
+/// [Base58Check]: https://en.bitcoin.it/wiki/Base58Check_encoding
+///
+/// # Examples
+///
+/// ```rust
+/// assert!(bs58::eq_check(&[0x2d, 0x31], "PWEu9GGN", bs58::Alphabet::DEFAULT));
+/// assert!(!bs58::eq_check(&[0x2d, 0x31], "PWEu9GGM", bs58::Alphabet::DEFAULT));
+/// ```
+#[cfg(feature = "check")]
+pub fn eq_check(bytes: &[u8], encoded: impl AsRef<[u8]>, alphabet: &Alphabet) -> bool {
+    use sha2::{Digest, Sha256};
+
+    let hash = Sha256::digest(Sha256::digest(bytes));
+    eq_with(bytes, &hash[..CHECKSUM_LEN], encoded.as_ref(), alphabet)
+}
+
+/// Copy `data` across `segments` in order, returning `false` without writing anything if they
+/// can't hold all of it.
+fn scatter(mut data: &[u8], segments: &mut [&mut [u8]]) -> bool {
+    if segments.iter().map(|segment| segment.len()).sum::<usize>() < data.len() {
+        return false;
+    }
+    for segment in segments {
+        let len = segment.len().min(data.len());
+        let (head, rest) = data.split_at(len);
+        segment[..len].copy_from_slice(head);
+        data = rest;
+    }
+    true
+}
+
+/// Compare the decoding of `encoded` with `bytes` followed by `checksum`, either by decoding into
+/// a scratch buffer of exactly the expected length, or for long payloads with [`eq_residues`].
+fn eq_with(bytes: &[u8], checksum: &[u8], encoded: &[u8], alphabet: &Alphabet) -> bool {
+    let decoded_len = bytes.len() + checksum.len();
+    if encoded.len() < decoded_len || encoded.len() > max_encoded_len(decoded_len) {
+        return false;
+    }
+    if decoded_len > EQ_STACK_LEN {
+        return eq_residues(bytes, checksum, encoded, alphabet);
+    }
+
+    let scratch = &mut [0; EQ_STACK_LEN][..decoded_len];
+    let decoder = decode::DecodeBuilder::new(encoded, alphabet);
+    if decoder.onto(&mut *scratch) != Ok(decoded_len) {
+        return false;
+    }
+    let (head, tail) = scratch.split_at(bytes.len());
+    head == bytes && tail == checksum
+}
+
+/// Compare the decoding of `encoded` with `bytes` followed by `checksum` without a buffer for
+/// either, by checking the leading zeros match and the remaining values are equal modulo enough
+/// distinct primes that their product is larger than both values, so by the Chinese remainder
+/// theorem the values are equal too.
+fn eq_residues(bytes: &[u8], checksum: &[u8], encoded: &[u8], alphabet: &Alphabet) -> bool {
+    let zeros = encoded
+        .iter()
+        .take_while(|&&c| alphabet.is_leading_zero(c))
+        .count();
+    let digits = &encoded[zeros..];
+    if digits
+        .iter()
+        .any(|&c| alphabet.digit(c) == Alphabet::INVALID)
+    {
+        return false;
+    }
+    let value = || bytes.iter().chain(checksum);
+    if value().take_while(|&&byte| byte == 0).count() != zeros {
+        return false;
+    }
+
+    // Each base58 digit adds less than 6 bits to the value, and each prime at least 31
+    let bits = (8 * (bytes.len() + checksum.len())).max(6 * digits.len());
+    let mut covered = 0;
+    let mut prime = u64::from(u32::MAX);
+    while covered < bits {
+        prime = prev_prime(prime);
+        let decoded = value().fold(0, |rem, &byte| (rem * 256 + u64::from(byte)) % prime);
+        let encoded = digits.iter().fold(0, |rem, &c| {
+            (rem * 58 + u64::from(alphabet.digit(c))) % prime
+        });
+        if decoded != encoded {
+            return false;
+        }
+        covered += 31;
+    }
+    true
+}
+
+/// The largest prime smaller than `n`, for `n` above 2³¹ and below 2³².
+fn prev_prime(n: u64) -> u64 {
+    let mut candidate = (n - 2) | 1;
+    while !is_prime(candidate) {
+        candidate -= 2;
+    }
+    candidate
+}
+
+/// Deterministic Miller-Rabin test for odd `n` below 2³², the bases 2, 7 and 61 suffice for all
+/// such `n`.
+fn is_prime(n: u64) -> bool {
+    let s = (n - 1).trailing_zeros();
+    let d = (n - 1) >> s;
+    [2, 7, 61].iter().all(|&base| {
+        let (mut x, mut power, mut exp) = (1, base, d);
+        while exp > 0 {
+            if exp & 1 == 1 {
+                x = x * power % n;
+            }
+            power = power * power % n;
+            exp >>= 1;
+        }
+        x == 1
+            || (0..s).any(|_| {
+                let found = x == n - 1;
+                x = x * x % n;
+                found
+            })
+    })
+}
+
 /// Setup decoder for the given string using the [default alphabet][Alphabet::DEFAULT].
 ///
 /// # Examples
@@ -171,6 +465,88 @@ pub const fn decode<I: AsRef<[u8]>>(input: I) -> decode::DecodeBuilder<'static,
     decode::DecodeBuilder::from_input(input)
 }
 
+/// Setup decoder for each field of `input` separated by `delimiter`, using the
+/// [default alphabet][Alphabet::DEFAULT].
+///
+/// Each field is decoded like [`decode()`], the returned [`Delimited`](decode::Delimited) iterator
+/// yields the decoded bytes of each field in turn, or when the `alloc` feature is not enabled
+/// they can be decoded into a buffer with [`next_onto`](decode::Delimited::next_onto). Errors
+/// identify the field, and report character indices relative to the whole input. As with
+/// splitting a string, input ending in the delimiter has an empty final field, empty fields
+/// decode as no bytes unless [`reject_empty`](decode::Delimited::reject_empty) is used.
+///
+/// # Examples
+///
+/// ```rust
+/// let fields = bs58::decode_delimited("he11owor1d.EUYUqQf", b'.')
+///     .collect::<Result<Vec<_>, _>>()?;
+/// assert_eq!(
+///     vec![vec![0x04, 0x30, 0x5e, 0x2b, 0x24, 0x73, 0xf0, 0x58], b"world".to_vec()],
+///     fields);
+/// # Ok::<(), bs58::decode::DelimitedError>(())
+/// ```
+pub fn decode_delimited<I: AsRef<[u8]> + ?Sized>(
+    input: &I,
+    delimiter: u8,
+) -> decode::Delimited<'static, '_> {
+    static BYTES: [u8; 256] = {
+        let mut bytes = [0; 256];
+        let mut i = 0;
+        while i < 256 {
+            bytes[i] = i as u8;
+            i += 1;
+        }
+        bytes
+    };
+    let delimiter = core::slice::from_ref(&BYTES[delimiter as usize]);
+    decode::Delimited::from_input(input.as_ref(), delimiter)
+}
+
+/// Setup decoder for each field of `input` separated by `separator`, using the
+/// [default alphabet][Alphabet::DEFAULT], reversing [`encode_join`].
+///
+/// This behaves like [`decode_delimited`] with a multi-character separator, which is checked in
+/// the same way as for [`encode_join`]. Use [`DecodeConfig::split`](decode::DecodeConfig::split)
+/// to decode with another alphabet, checksum or prefix.
+///
+/// # Panics
+///
+/// If `separator` is empty or contains a character of the alphabet.
+///
+/// # Examples
+///
+/// ```rust
+/// let fields = bs58::decode_split("he11owor1d, EUYUqQf", ", ")
+///     .collect::<Result<Vec<_>, _>>()?;
+/// assert_eq!(
+///     vec![vec![0x04, 0x30, 0x5e, 0x2b, 0x24, 0x73, 0xf0, 0x58], b"world".to_vec()],
+///     fields);
+/// # Ok::<(), bs58::decode::DelimitedError>(())
+/// ```
+#[track_caller]
+pub fn decode_split<'b, I: AsRef<[u8]> + ?Sized>(
+    input: &'b I,
+    separator: &'b str,
+) -> decode::Delimited<'static, 'b> {
+    decode::DecodeConfig::new(Alphabet::DEFAULT).split(input, separator)
+}
+
+/// Panic unless `separator` can separate encodings with `alpha` and `prefix` such that splitting
+/// on it gives back each encoding: it must be non-empty, have no characters of the alphabet, and
+/// not appear in the prefix.
+#[track_caller]
+fn assert_separator(separator: &str, alpha: &Alphabet, prefix: &str) {
+    assert!(!separator.is_empty(), "separator must not be empty");
+    assert!(
+        separator.bytes().all(|c| !alpha.would_conflict_with(c)),
+        "separator {separator:?} must not contain characters of the alphabet"
+    );
+    assert!(
+        !prefix.contains(separator),
+        "separator {separator:?} must not appear in the text prefix {prefix:?}"
+    );
+}
+
 /// Setup encoder for the given bytes using the [default alphabet][Alphabet::DEFAULT].
 ///
 /// # Examples
@@ -219,3 +595,99 @@ pub const fn decode<I: AsRef<[u8]>>(input: I) -> decode::DecodeBuilder<'static,
 pub fn encode<I: AsRef<[u8]>>(input: I) -> encode::EncodeBuilder<'static, I> {
     encode::EncodeBuilder::from_input(input)
 }
+
+/// Encode each of `items` using the [default alphabet][Alphabet::DEFAULT] into one string, with
+/// `separator` between them.
+///
+/// No separators are written for no items or a single item. Use
+/// [`EncodeConfig::join`](encode::EncodeConfig::join) to encode with another alphabet, checksum
+/// or prefix, and [`decode_split`] to decode the items again.
+///
+/// # Panics
+///
+/// If `separator` is empty or contains a character of the alphabet, as then it could not be
+/// told apart from the encodings.
+///
+/// # Examples
+///
+/// ```rust
+/// let keys = [&[0x04, 0x30, 0x5e, 0x2b, 0x24, 0x73, 0xf0, 0x58][..], b"world"];
+/// assert_eq!("he11owor1d,EUYUqQf", bs58::encode_join(keys, ","));
+/// assert_eq!("", bs58::encode_join::<_, &[u8]>([], ","));
+/// ```
+#[cfg(feature = "alloc")]
+#[track_caller]
+pub fn encode_join<I, T>(items: I, separator: &str) -> alloc::string::String
+where
+    I: IntoIterator<Item = T>,
+    T: AsRef<[u8]>,
+{
+    encode::EncodeConfig::new(Alphabet::DEFAULT).join(items, separator)
+}
+
+/// Encode each of `items` using the [default alphabet][Alphabet::DEFAULT] into `writer`, with
+/// `separator` between them, see [`encode_join`].
+///
+/// # Errors
+///
+/// If writing to `writer` fails.
+///
+/// # Panics
+///
+/// If `separator` is empty or contains a character of the alphabet.
+///
+/// # Examples
+///
+/// ```rust
+/// let mut line = String::from("keys: ");
+/// bs58::encode_join_to([b"hello", b"world"], " ", &mut line)?;
+/// assert_eq!("keys: Cn8eVZg EUYUqQf", line);
+/// # Ok::<(), std::fmt::Error>(())
+/// ```
+#[cfg(feature = "alloc")]
+#[track_caller]
+pub fn encode_join_to<I, T>(
+    items: I,
+    separator: &str,
+    writer: &mut impl core::fmt::Write,
+) -> core::fmt::Result
+where
+    I: IntoIterator<Item = T>,
+    T: AsRef<[u8]>,
+{
+    encode::EncodeConfig::new(Alphabet::DEFAULT).join_to(items, separator, writer)
+}
+
+/// Setup encoder for the bytes of a plain-old-data value using the
+/// [default alphabet][Alphabet::DEFAULT].
+///
+/// The bytes are in memory order, so multi-byte integers are encoded in the platform's native
+/// endianness. Use [`DecodeBuilder::into_pod`](decode::DecodeBuilder::into_pod) to decode the value
+/// again.
+///
+/// # Examples
+///
+/// ```rust
+/// let commitment: [u64; 4] = [1, 2, 3, 4];
+/// let encoded = bs58::encode_pod(&commitment).into_string();
+/// assert_eq!(commitment, bs58::decode(encoded).into_pod::<[u64; 4]>()?);
+/// # Ok::<(), bs58::decode::Error>(())
+/// ```
+///
+/// Types containing padding bytes cannot be encoded, as the padding could contain uninitialized
+/// memory:
+///
+/// ```compile_fail
+/// #[derive(Clone, Copy, bytemuck::NoUninit)]
+/// #[repr(C)]
+/// struct Padded {
+///     a: u8,
+///     b: u32,
+/// }
+///
+/// bs58::encode_pod(&Padded { a: 1, b: 2 });
+/// ```
+#[cfg(feature = "bytemuck")]
+pub fn encode_pod<T: bytemuck::NoUninit>(value: &T) -> encode::EncodeBuilder<'static, &[u8]> {
+    encode(bytemuck::bytes_of(value))
+}
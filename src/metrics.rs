@@ -0,0 +1,63 @@
+//! Counters of the work done by a single encode or decode, enabled by the `metrics` feature.
+//!
+//! Pass a [`Stats`] to [`EncodeBuilder::with_stats`](crate::encode::EncodeBuilder::with_stats)
+//! or [`DecodeBuilder::with_stats`](crate::decode::DecodeBuilder::with_stats) and it is filled in
+//! during the operation. There is no global state, and nothing is counted for builders without
+//! stats attached, so this costs nothing when unused.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use bs58::metrics::Stats;
+//!
+//! let mut stats = Stats::default();
+//! let decoded = bs58::decode("he11owor1d").with_stats(&mut stats).into_vec()?;
+//! assert_eq!(10, stats.input_len);
+//! assert_eq!(decoded.len(), stats.output_len);
+//! assert!(stats.iterations > 0);
+//! # Ok::<(), bs58::decode::Error>(())
+//! ```
+
+/// What a single encode or decode did.
+///
+/// Attaching stats to a builder resets them, then the operation fills them in as it runs, so if
+/// it fails they describe the work done up to the failure. Methods that run several separate
+/// conversions, such as the `*_with_checksum_separator` methods, leave them at their reset
+/// values.
+///
+/// # Stability
+///
+/// The meaning of each counter won't change in a semver compatible release, but the
+/// [`iterations`](Self::iterations) and [`fast_path`](Self::fast_path) recorded for a given input
+/// depend on the implementation and may change in any release, e.g. when a faster algorithm is
+/// added. They are for tuning and monitoring, not for behaviour to depend on. New counters may be
+/// added, so create stats with [`Stats::default`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct Stats {
+    /// The length of the input in bytes, including any text prefix.
+    pub input_len: usize,
+    /// The length of the output in bytes: the decoded data without any version or checksum that
+    /// was removed, or the encoded string including any text prefix. Zero if the operation
+    /// failed.
+    pub output_len: usize,
+    /// The number of iterations of the inner conversion loop. Each input character (or byte when
+    /// encoding) takes one iteration for each byte (or digit) of the value converted so far, so
+    /// this grows quadratically with the input length.
+    pub iterations: u64,
+    /// Whether a fast path was taken instead of the conversion loop, currently only for encoding
+    /// input that is all zero bytes.
+    pub fast_path: bool,
+}
+
+impl crate::Record for Stats {
+    #[inline]
+    fn iterations(&mut self, n: usize) {
+        self.iterations += n as u64;
+    }
+
+    #[inline]
+    fn fast_path(&mut self) {
+        self.fast_path = true;
+    }
+}
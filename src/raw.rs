@@ -0,0 +1,147 @@
+//! The innermost steps of Base58 conversion, for code generators (such as JIT compiled decoders)
+//! and other implementations that need exactly the same results as this crate.
+//!
+//! **This module is exempt from semver.** Any of it may change or be removed in any release,
+//! including patch releases, so depend on an exact version of this crate (`bs58 = "=0.5.1"`) when
+//! using it.
+//!
+//! The crate's own encoding and decoding are built from these functions, so they cannot drift
+//! from what it does. Both directions accumulate the converted value least significant digit
+//! first in the start of a buffer, then the caller appends one zero for each leading zero of the
+//! input and reverses the buffer to get the result most significant first.
+//!
+//! # Examples
+//!
+//! Decoding with only these functions:
+//!
+//! ```rust
+//! use bs58::{raw, Alphabet};
+//!
+//! let input = b"1he11owor1d";
+//! let mut buf = [0; 16];
+//! let mut len = 0;
+//! for &c in input {
+//!     let digit = raw::digit(Alphabet::DEFAULT, c).expect("invalid character");
+//!     len = raw::push_digit(&mut buf, len, digit).expect("buffer too small");
+//! }
+//! let zeros = input.iter().take_while(|&&c| c == b'1').count();
+//! buf[len..len + zeros].fill(0);
+//! buf[..len + zeros].reverse();
+//! assert_eq!([0x00, 0x04, 0x30, 0x5e, 0x2b, 0x24, 0x73, 0xf0, 0x58], buf[..len + zeros]);
+//! ```
+//!
+//! Encoding with only these functions:
+//!
+//! ```rust
+//! use bs58::{raw, Alphabet};
+//!
+//! let input = [0x00, 0x04, 0x30, 0x5e, 0x2b, 0x24, 0x73, 0xf0, 0x58];
+//! let mut buf = [0; 16];
+//! let mut len = 0;
+//! for &byte in &input {
+//!     len = raw::push_byte(&mut buf, len, byte).expect("buffer too small");
+//! }
+//! let zeros = input.iter().take_while(|&&byte| byte == 0).count();
+//! buf[len..len + zeros].fill(0);
+//! for digit in &mut buf[..len + zeros] {
+//!     *digit = raw::character(Alphabet::DEFAULT, *digit);
+//! }
+//! buf[..len + zeros].reverse();
+//! assert_eq!(b"1he11owor1d", &buf[..len + zeros]);
+//! ```
+
+use crate::Alphabet;
+
+/// The digit for the input byte `c` with `alphabet`, or `None` if it is not one of its
+/// characters (including every byte of 128 and above).
+#[inline]
+pub const fn digit(alphabet: &Alphabet, c: u8) -> Option<u8> {
+    match alphabet.digit(c) {
+        Alphabet::INVALID => None,
+        digit => Some(digit),
+    }
+}
+
+/// The character for `digit` with `alphabet`.
+///
+/// # Panics
+///
+/// If `digit` is not less than 58.
+#[inline]
+pub const fn character(alphabet: &Alphabet, digit: u8) -> u8 {
+    alphabet.encode[digit as usize]
+}
+
+/// Add a Base58 digit to a value being decoded: multiply the value in the first `len` bytes of
+/// `buf`, least significant byte first, by 58 and add `digit`, returning the new length.
+///
+/// Each step adds at most one byte. Returns `None` if the new value does not fit in `buf`, in
+/// which case the first `len` bytes have been changed and no longer hold a meaningful value.
+///
+/// `digit` must be less than 58 (as returned by [`digit`]) for the value to be the decoding of
+/// the digits added.
+///
+/// # Panics
+///
+/// If `len` is greater than the length of `buf`.
+#[inline]
+pub const fn push_digit(buf: &mut [u8], len: usize, digit: u8) -> Option<usize> {
+    let (value, rest) = buf.split_at_mut(len);
+    // The carry is never more than 57, so fits in the one next byte
+    match mul_add(value, digit as usize) {
+        0 => Some(len),
+        carry => match rest.first_mut() {
+            Some(byte) => {
+                *byte = carry as u8;
+                Some(len + 1)
+            }
+            None => None,
+        },
+    }
+}
+
+/// Add a byte to a value being encoded: multiply the value in the first `len` Base58 digits of
+/// `buf`, least significant digit first, by 256 and add `byte`, returning the new length.
+///
+/// Each step adds at most two digits. Returns `None` if the new value does not fit in `buf`, in
+/// which case the first `len` digits have been changed and no longer hold a meaningful value.
+///
+/// Every one of the first `len` bytes of `buf` must be less than 58, as they are when they were
+/// all produced by this function.
+///
+/// # Panics
+///
+/// If `len` is greater than the length of `buf`.
+#[inline]
+pub fn push_byte(buf: &mut [u8], len: usize, byte: u8) -> Option<usize> {
+    let mut carry = byte as usize;
+    for digit in &mut buf[..len] {
+        carry += (*digit as usize) << 8;
+        *digit = (carry % 58) as u8;
+        carry /= 58;
+    }
+
+    let mut len = len;
+    while carry > 0 {
+        *buf.get_mut(len)? = (carry % 58) as u8;
+        len += 1;
+        carry /= 58;
+    }
+    Some(len)
+}
+
+/// Multiply the little-endian bytes of `value` by 58 adding `carry`, returning what carries out of
+/// the last byte.
+///
+/// This can be applied to consecutive parts of a value in turn, passing the carry along.
+#[inline(always)]
+pub(crate) const fn mul_add(value: &mut [u8], mut carry: usize) -> usize {
+    let mut i = 0;
+    while i < value.len() {
+        carry += (value[i] as usize) * 58;
+        value[i] = carry as u8;
+        carry >>= 8;
+        i += 1;
+    }
+    carry
+}
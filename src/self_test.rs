@@ -0,0 +1,183 @@
+use core::fmt;
+
+use crate::Alphabet;
+
+/// The built-in alphabets along with their names, and the expected encodings of [`VECTORS`] and
+/// [`CHECK_VECTOR`].
+const ALPHABETS: [(&str, &Alphabet, [&str; 3], &str); 4] = [
+    (
+        "bitcoin",
+        Alphabet::BITCOIN,
+        ["112", "he11owor1d", "FPBt6CHo3fovdL"],
+        "CNubrgU3g",
+    ),
+    (
+        "monero",
+        Alphabet::MONERO,
+        ["112", "he11owor1d", "FPBt6CHo3fovdL"],
+        "CNubrgU3g",
+    ),
+    (
+        "ripple",
+        Alphabet::RIPPLE,
+        ["rrp", "6erroAoird", "EPBtaUHosCovdL"],
+        "U4ubig7sg",
+    ),
+    (
+        "flickr",
+        Alphabet::FLICKR,
+        ["112", "GD11NWNR1C", "fobT6chN3ENVCk"],
+        "cnUARFt3F",
+    ),
+];
+
+/// Inputs covering leading zeros, a typical value and a long carry chain.
+const VECTORS: [&[u8]; 3] = [
+    &[0x00, 0x00, 0x01],
+    &[0x04, 0x30, 0x5e, 0x2b, 0x24, 0x73, 0xf0, 0x58],
+    &[0xff; 10],
+];
+
+/// A version and payload for [Base58Check](https://en.bitcoin.it/wiki/Base58Check_encoding).
+#[cfg(feature = "check")]
+const CHECK_VECTOR: (u8, &[u8]) = (0x05, &[0x2d, 0x31]);
+
+/// The stage of [`self_test`] that failed.
+#[non_exhaustive]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum SelfTestStage {
+    /// The alphabet's lookup tables were inconsistent.
+    Alphabet,
+    /// Encoding did not give the expected string.
+    Encode,
+    /// Decoding did not give the expected bytes.
+    Decode,
+    /// Encoding or decoding with a [Base58Check][] checksum did not give the expected result.
+    ///
+    /// [Base58Check]: https://en.bitcoin.it/wiki/Base58Check_encoding
+    #[cfg(feature = "check")]
+    Check,
+}
+
+/// Error returned by [`self_test`], identifying the first stage and alphabet that failed.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct SelfTestError {
+    /// The stage that failed.
+    pub stage: SelfTestStage,
+    /// The name of the built-in alphabet that failed, e.g. `"bitcoin"`.
+    pub alphabet: &'static str,
+}
+
+/// Verify that encoding and decoding work correctly, for startup checks in safety-critical
+/// environments.
+///
+/// This checks the lookup tables of each built-in alphabet are consistent, then runs a small set
+/// of embedded vectors through encoding and decoding (and [Base58Check][] when the `check`
+/// feature is enabled) with each alphabet, comparing against expected constants. Only stack
+/// buffers are used so this works without `alloc`. Every stage is always run, even after a
+/// failure, so the execution time does not depend on the result.
+///
+/// [Base58Check]: https://en.bitcoin.it/wiki/Base58Check_encoding
+///
+/// # Examples
+///
+/// ```rust
+/// assert_eq!(Ok(()), bs58::self_test());
+/// ```
+#[inline(never)]
+pub fn self_test() -> Result<(), SelfTestError> {
+    let mut result = Ok(());
+    let mut fail = |stage, alphabet| {
+        if result.is_ok() {
+            result = Err(SelfTestError { stage, alphabet });
+        }
+    };
+
+    for (name, alpha, encoded, check_encoded) in ALPHABETS {
+        if !alphabet_consistent(alpha) {
+            fail(SelfTestStage::Alphabet, name);
+        }
+
+        for (input, expected) in VECTORS.iter().zip(encoded) {
+            let mut output = [0; 16];
+            let len = crate::encode(input)
+                .with_alphabet(alpha)
+                .onto(&mut output[..]);
+            if len.map(|len| &output[..len]) != Ok(expected.as_bytes()) {
+                fail(SelfTestStage::Encode, name);
+            }
+
+            let mut output = [0; 16];
+            let len = crate::decode(expected)
+                .with_alphabet(alpha)
+                .onto(&mut output);
+            if len.map(|len| &output[..len]) != Ok(*input) {
+                fail(SelfTestStage::Decode, name);
+            }
+        }
+
+        #[cfg(not(feature = "check"))]
+        let _ = check_encoded;
+
+        #[cfg(feature = "check")]
+        {
+            let (version, payload) = CHECK_VECTOR;
+
+            let mut output = [0; 16];
+            let len = crate::encode(payload)
+                .with_alphabet(alpha)
+                .with_check_version(version)
+                .onto(&mut output[..]);
+            if len.map(|len| &output[..len]) != Ok(check_encoded.as_bytes()) {
+                fail(SelfTestStage::Check, name);
+            }
+
+            let mut output = [0; 16];
+            let len = crate::decode(check_encoded)
+                .with_alphabet(alpha)
+                .with_check(Some(version))
+                .onto(&mut output);
+            if len.map(|len| output.get(1..len)) != Ok(Some(payload)) {
+                fail(SelfTestStage::Check, name);
+            }
+        }
+    }
+
+    result
+}
+
+/// Check that the alphabet's decode table is exactly the inverse of its encode table.
+fn alphabet_consistent(alpha: &Alphabet) -> bool {
+    let decode = alpha.decode_table();
+    let mut consistent = true;
+    for (i, &c) in alpha.encode.iter().enumerate() {
+        consistent &= decode.get(c as usize) == Some(&(i as u8));
+    }
+    let valid = decode.iter().filter(|&&v| v != Alphabet::INVALID).count();
+    consistent && valid == alpha.encode.len()
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for SelfTestError {}
+
+impl fmt::Display for SelfTestStage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            SelfTestStage::Alphabet => write!(f, "alphabet"),
+            SelfTestStage::Encode => write!(f, "encode"),
+            SelfTestStage::Decode => write!(f, "decode"),
+            #[cfg(feature = "check")]
+            SelfTestStage::Check => write!(f, "check"),
+        }
+    }
+}
+
+impl fmt::Display for SelfTestError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "self test failed at the {} stage with the {} alphabet",
+            self.stage, self.alphabet
+        )
+    }
+}
@@ -0,0 +1,208 @@
+//! Support for [SS58][] encoded Substrate account addresses.
+//!
+//! An SS58 address is the Base58 encoding of a network identifier prefix, a 32 byte account id
+//! and the first two bytes of a blake2b-512 checksum over all of those.
+//!
+//! [SS58]: https://docs.substrate.io/reference/address-formats/
+
+use alloc::string::String;
+use core::fmt;
+
+use blake2::{Blake2b512, Digest};
+
+/// The length of the account id carried by an address.
+const ACCOUNT_LEN: usize = 32;
+
+/// The length of the truncated checksum appended to an address.
+const CHECKSUM_LEN: usize = 2;
+
+/// Network identifiers must be less than this to be representable in a prefix.
+const MAX_NETWORK: u16 = 1 << 14;
+
+/// Errors that could occur when decoding an SS58 address.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum Error {
+    /// The input was not valid Base58.
+    Decode(crate::decode::Error),
+
+    /// The first byte of the decoded data was not a valid network identifier prefix.
+    InvalidPrefix {
+        /// The prefix byte that was found.
+        prefix: u8,
+    },
+
+    /// The decoded data was not the length of a prefix, account id and checksum.
+    WrongLength {
+        /// The expected decoded length for the prefix that was found.
+        expected: usize,
+        /// The decoded length that was found.
+        found: usize,
+    },
+
+    /// The checksum did not match the prefix and account id.
+    InvalidChecksum {
+        /// The given checksum.
+        checksum: [u8; CHECKSUM_LEN],
+        /// The checksum calculated for the prefix and account id.
+        expected_checksum: [u8; CHECKSUM_LEN],
+    },
+}
+
+/// Encode a 32 byte account id as an SS58 address for the given network.
+///
+/// # Panics
+///
+/// If `network` is 16384 or greater, the largest identifier SS58 can represent is 16383.
+///
+/// # Examples
+///
+/// ```rust
+/// let alice = [
+///     0xd4, 0x35, 0x93, 0xc7, 0x15, 0xfd, 0xd3, 0x1c, 0x61, 0x14, 0x1a, 0xbd, 0x04, 0xa9, 0x9f,
+///     0xd6, 0x82, 0x2c, 0x85, 0x58, 0x85, 0x4c, 0xcd, 0xe3, 0x9a, 0x56, 0x84, 0xe7, 0xa5, 0x6d,
+///     0xa2, 0x7d,
+/// ];
+/// assert_eq!(
+///     "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY",
+///     bs58::ss58::encode(42, &alice),
+/// );
+/// ```
+pub fn encode(network: u16, account: &[u8; ACCOUNT_LEN]) -> String {
+    assert!(
+        network < MAX_NETWORK,
+        "SS58 network identifier {network} is out of range"
+    );
+
+    let mut data = [0; 2 + ACCOUNT_LEN + CHECKSUM_LEN];
+    let prefix_len = encode_prefix(network, &mut data);
+    data[prefix_len..][..ACCOUNT_LEN].copy_from_slice(account);
+    let body_len = prefix_len + ACCOUNT_LEN;
+    let checksum = checksum(&data[..body_len]);
+    data[body_len..][..CHECKSUM_LEN].copy_from_slice(&checksum);
+
+    crate::encode(&data[..body_len + CHECKSUM_LEN]).into_string()
+}
+
+/// Decode an SS58 address into its network identifier and 32 byte account id.
+///
+/// # Examples
+///
+/// ```rust
+/// let (network, account) =
+///     bs58::ss58::decode("15oF4uVJwmo4TdGW7VfQxNLavjCXviqxT9S1MgbjMNHr6Sp5")?;
+/// assert_eq!(0, network);
+/// assert_eq!(bs58::ss58::encode(42, &account), "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY");
+/// # Ok::<(), bs58::ss58::Error>(())
+/// ```
+pub fn decode(input: &str) -> Result<(u16, [u8; ACCOUNT_LEN]), Error> {
+    // One byte more than the longest address so overlong input is reported as the wrong length.
+    let mut data = [0; 2 + ACCOUNT_LEN + CHECKSUM_LEN + 1];
+    let len = match crate::decode(input).onto(&mut data[..]) {
+        Ok(len) => len,
+        Err(crate::decode::Error::BufferTooSmall) => data.len(),
+        Err(err) => return Err(Error::Decode(err)),
+    };
+    let data = &data[..len];
+
+    let (network, prefix_len) = decode_prefix(data)?;
+    let expected = prefix_len + ACCOUNT_LEN + CHECKSUM_LEN;
+    if len != expected {
+        return Err(Error::WrongLength {
+            expected,
+            found: len,
+        });
+    }
+
+    let (body, checksum) = data.split_at(prefix_len + ACCOUNT_LEN);
+    let expected_checksum = self::checksum(body);
+    if checksum != expected_checksum {
+        return Err(Error::InvalidChecksum {
+            checksum: [checksum[0], checksum[1]],
+            expected_checksum,
+        });
+    }
+
+    let mut account = [0; ACCOUNT_LEN];
+    account.copy_from_slice(&body[prefix_len..]);
+    Ok((network, account))
+}
+
+/// Write the prefix for `network` to the start of `output`, returning its length.
+fn encode_prefix(network: u16, output: &mut [u8]) -> usize {
+    if network < 64 {
+        output[0] = network as u8;
+        1
+    } else {
+        output[0] = (((network & 0xfc) >> 2) | 0x40) as u8;
+        output[1] = ((network >> 8) | ((network & 0x03) << 6)) as u8;
+        2
+    }
+}
+
+/// Read the network identifier from the start of `data`, returning it and the prefix length.
+fn decode_prefix(data: &[u8]) -> Result<(u16, usize), Error> {
+    match *data {
+        [] => Err(Error::WrongLength {
+            expected: 1 + ACCOUNT_LEN + CHECKSUM_LEN,
+            found: 0,
+        }),
+        [first @ 0..=63, ..] => Ok((first.into(), 1)),
+        [first @ 64..=127, second, ..] => {
+            let lower = (first << 2) | (second >> 6);
+            let upper = second & 0x3f;
+            Ok((u16::from(lower) | (u16::from(upper) << 8), 2))
+        }
+        [64..=127] => Err(Error::WrongLength {
+            expected: 2 + ACCOUNT_LEN + CHECKSUM_LEN,
+            found: 1,
+        }),
+        [prefix, ..] => Err(Error::InvalidPrefix { prefix }),
+    }
+}
+
+fn checksum(body: &[u8]) -> [u8; CHECKSUM_LEN] {
+    let hash = Blake2b512::new()
+        .chain_update(b"SS58PRE")
+        .chain_update(body)
+        .finalize();
+    [hash[0], hash[1]]
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Decode(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<crate::decode::Error> for Error {
+    fn from(err: crate::decode::Error) -> Self {
+        Error::Decode(err)
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Error::Decode(err) => write!(f, "invalid base58: {err}"),
+            Error::InvalidPrefix { prefix } => {
+                write!(f, "invalid SS58 network identifier prefix {prefix:#04x}")
+            }
+            Error::WrongLength { expected, found } => write!(
+                f,
+                "SS58 address decoded to {found} bytes, expected {expected} bytes"
+            ),
+            Error::InvalidChecksum {
+                checksum,
+                expected_checksum,
+            } => write!(
+                f,
+                "invalid SS58 checksum, calculated checksum: '{expected_checksum:?}', expected checksum: {checksum:?}"
+            ),
+        }
+    }
+}
@@ -0,0 +1,153 @@
+//! Assertions for testing code that uses Base58, enabled by the `test-util` feature.
+//!
+//! Each assertion panics with a message showing the bytes and encodings involved, and where they
+//! first differ, so failures in tests and fuzz targets can be understood without re-running them.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use bs58::testing::{assert_decode_matches, assert_roundtrip};
+//!
+//! assert_decode_matches("he11owor1d", &[0x04, 0x30, 0x5e, 0x2b, 0x24, 0x73, 0xf0, 0x58]);
+//! assert_roundtrip(&[0x00, 0x01, 0x02], bs58::Alphabet::RIPPLE);
+//! ```
+
+use core::fmt;
+
+use crate::Alphabet;
+
+/// Assert that `bytes` encodes with `alphabet` to a string that decodes back to exactly `bytes`,
+/// and re-encodes to exactly the same string.
+///
+/// # Panics
+///
+/// If either step doesn't round trip, showing the bytes and strings and where they differ.
+#[track_caller]
+pub fn assert_roundtrip(bytes: &[u8], alphabet: &Alphabet) {
+    let encoded = crate::encode(bytes).with_alphabet(alphabet).into_string();
+    let decoded = match crate::decode(&encoded).with_alphabet(alphabet).into_vec() {
+        Ok(decoded) => decoded,
+        Err(err) => panic!(
+            "encoding of bytes failed to decode\n    bytes: {}\n  encoded: {encoded:?}\n    error: {err}",
+            Hex(bytes)
+        ),
+    };
+    if decoded != bytes {
+        panic!(
+            "decoding did not round trip, first difference at byte {}\n    bytes: {}\n  decoded: {}\n  encoded: {encoded:?}",
+            first_difference(bytes, &decoded),
+            Hex(bytes),
+            Hex(&decoded)
+        );
+    }
+
+    let reencoded = crate::encode(&decoded)
+        .with_alphabet(alphabet)
+        .into_string();
+    if reencoded != encoded {
+        panic!(
+            "encoding did not round trip, first difference at byte {}\n    bytes: {}\n  encoded: {encoded:?}\nreencoded: {reencoded:?}",
+            first_difference(encoded.as_bytes(), reencoded.as_bytes()),
+            Hex(bytes)
+        );
+    }
+}
+
+/// Assert that `s` decodes with the [default alphabet](Alphabet::DEFAULT) to exactly `expected`,
+/// and that those bytes encode back to exactly `s`.
+///
+/// # Panics
+///
+/// If `s` doesn't decode, or the bytes or strings differ, showing both sides and where they
+/// differ.
+#[track_caller]
+pub fn assert_decode_matches(s: &str, expected: &[u8]) {
+    let expected_encoding = crate::encode(expected).into_string();
+    let decoded = match crate::decode(s).into_vec() {
+        Ok(decoded) => decoded,
+        Err(err) => panic!(
+            "string failed to decode\n    string: {s:?}\n     error: {err}\n  expected: {}\n            (encodes to {expected_encoding:?})",
+            Hex(expected)
+        ),
+    };
+    if decoded != expected {
+        panic!(
+            "string decoded to the wrong bytes, first difference at byte {}\n    string: {s:?}\n   decoded: {}\n  expected: {}\n            (encodes to {expected_encoding:?})",
+            first_difference(expected, &decoded),
+            Hex(&decoded),
+            Hex(expected)
+        );
+    }
+
+    let reencoded = crate::encode(&decoded).into_string();
+    if reencoded != s {
+        panic!(
+            "decoded bytes did not encode back to the string, first difference at byte {}\n     string: {s:?}\n  reencoded: {reencoded:?}\n      bytes: {}",
+            first_difference(s.as_bytes(), reencoded.as_bytes()),
+            Hex(&decoded)
+        );
+    }
+}
+
+/// Assert that `s` is a valid [Base58Check][] string with the
+/// [default alphabet](Alphabet::DEFAULT), and that replacing any one of its characters with any
+/// other character of the alphabet makes decoding it with a checksum fail.
+///
+/// This decodes the string 57 times for each character, so is best kept to short strings.
+///
+/// [Base58Check]: https://en.bitcoin.it/wiki/Base58Check_encoding
+///
+/// # Panics
+///
+/// If `s` doesn't decode, or a corrupted string does, showing the index and characters of the
+/// corruption and what it decoded to.
+#[cfg(feature = "check")]
+#[track_caller]
+pub fn assert_check_rejects_single_char_corruption(s: &str) {
+    if let Err(err) = crate::decode(s).with_check(None).into_vec() {
+        panic!("string failed to decode with a checksum\n  string: {s:?}\n   error: {err}");
+    }
+
+    let mut corrupted = s.as_bytes().to_vec();
+    for index in 0..corrupted.len() {
+        let original = corrupted[index];
+        for &replacement in Alphabet::DEFAULT.encode.iter().filter(|&&c| c != original) {
+            corrupted[index] = replacement;
+            if let Ok(decoded) = crate::decode(&corrupted).with_check(None).into_vec() {
+                panic!(
+                    "corrupting byte {index} from {:?} to {:?} still decoded with a checksum\n     string: {s:?}\n  corrupted: {:?}\n    decoded: {}",
+                    original as char,
+                    replacement as char,
+                    core::str::from_utf8(&corrupted).unwrap_or_default(),
+                    Hex(&decoded)
+                );
+            }
+        }
+        corrupted[index] = original;
+    }
+}
+
+/// The index of the first difference between `a` and `b`, or the length of the shorter one if
+/// it is a prefix of the other.
+fn first_difference(a: &[u8], b: &[u8]) -> usize {
+    a.iter()
+        .zip(b)
+        .position(|(a, b)| a != b)
+        .unwrap_or(a.len().min(b.len()))
+}
+
+/// Displays bytes as space separated hex.
+struct Hex<'a>(&'a [u8]);
+
+impl fmt::Display for Hex<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("[")?;
+        for (i, byte) in self.0.iter().enumerate() {
+            if i > 0 {
+                f.write_str(" ")?;
+            }
+            write!(f, "{byte:02x}")?;
+        }
+        f.write_str("]")
+    }
+}
@@ -0,0 +1,147 @@
+#![cfg(feature = "allocator-api2")]
+
+mod cases;
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::{Cell, UnsafeCell},
+    ptr::NonNull,
+};
+
+use allocator_api2::alloc::{AllocError, Allocator};
+
+/// Wraps the system allocator, counting allocations made from the current thread.
+struct CountingAlloc;
+
+thread_local! {
+    static GLOBAL_ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let _ = GLOBAL_ALLOCATIONS.try_with(|count| count.set(count.get() + 1));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAlloc = CountingAlloc;
+
+fn global_allocations() -> usize {
+    GLOBAL_ALLOCATIONS.with(|count| count.get())
+}
+
+/// A minimal bump allocator over a fixed arena that never frees.
+struct Bump {
+    arena: UnsafeCell<[u8; 4096]>,
+    used: Cell<usize>,
+    allocations: Cell<usize>,
+}
+
+impl Bump {
+    fn new() -> Self {
+        Bump {
+            arena: UnsafeCell::new([0; 4096]),
+            used: Cell::new(0),
+            allocations: Cell::new(0),
+        }
+    }
+}
+
+unsafe impl Allocator for &Bump {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        let base = self.arena.get() as *mut u8;
+        let start =
+            (base as usize + self.used.get()).next_multiple_of(layout.align()) - base as usize;
+        let end = start.checked_add(layout.size()).ok_or(AllocError)?;
+        if end > 4096 {
+            return Err(AllocError);
+        }
+        self.used.set(end);
+        self.allocations.set(self.allocations.get() + 1);
+        let ptr = unsafe { NonNull::new_unchecked(base.add(start)) };
+        Ok(NonNull::slice_from_raw_parts(ptr, layout.size()))
+    }
+
+    unsafe fn deallocate(&self, _ptr: NonNull<u8>, _layout: Layout) {}
+}
+
+#[test]
+fn test_allocator_api2() {
+    for &(val, s) in cases::TEST_CASES.iter() {
+        let bump = Bump::new();
+
+        let before = global_allocations();
+        let decoded = bs58::decode(s).into_vec_in(&bump).unwrap();
+        let encoded = bs58::encode(val).into_vec_in(&bump);
+        let after = global_allocations();
+
+        assert_eq!(val, &*decoded);
+        assert_eq!(s.as_bytes(), &*encoded);
+        assert_eq!(before, after);
+        // Each output needs at most a single allocation
+        assert!(bump.allocations.get() <= 2);
+    }
+
+    // Sanity check that global allocations are being counted
+    let before = global_allocations();
+    let _ = bs58::decode("he11owor1d").into_vec().unwrap();
+    assert!(global_allocations() > before);
+
+    let bump = Bump::new();
+    let err = bs58::decode("he11o0").into_vec_in(&bump).unwrap_err();
+    assert_eq!(
+        bs58::decode::Error::InvalidCharacter {
+            character: '0',
+            index: 5
+        },
+        err
+    );
+}
+
+#[test]
+fn test_allocator_api2_expected_len() {
+    for &(val, s) in cases::TEST_CASES.iter() {
+        let bump = Bump::new();
+        let decoded = bs58::decode(s)
+            .with_expected_len(val.len())
+            .into_vec_in(&bump)
+            .unwrap();
+        assert_eq!(val, &*decoded);
+        assert_eq!(val.len(), decoded.capacity());
+        assert!(bump.allocations.get() <= 1);
+    }
+}
+
+#[test]
+fn test_decode_into_vec_reuse_allocations() {
+    let mut output = Vec::new();
+    bs58::decode("he11owor1d")
+        .into_vec_reuse(&mut output)
+        .unwrap();
+
+    let before = global_allocations();
+    for _ in 0..16 {
+        bs58::decode("he11owor1d")
+            .into_vec_reuse(&mut output)
+            .unwrap();
+    }
+    assert_eq!(before, global_allocations());
+}
+
+#[test]
+fn test_decode_into_vec_expected_len_allocations() {
+    for &(val, s) in cases::TEST_CASES.iter().filter(|(val, _)| !val.is_empty()) {
+        let before = global_allocations();
+        let decoded = bs58::decode(s)
+            .with_expected_len(val.len())
+            .into_vec()
+            .unwrap();
+        assert_eq!(1, global_allocations() - before);
+        assert_eq!(val, &*decoded);
+    }
+}
@@ -0,0 +1,349 @@
+//! These mostly only need to compile, pinning which alphabet APIs can be used in `const` and
+//! `static` items and in patterns.
+
+use bs58::{alphabet::Error, decode::Status, Alphabet};
+
+/// A downstream configuration table holding references to alphabets.
+struct Config {
+    name: &'static str,
+    alphabet: &'static Alphabet,
+}
+
+static CONFIGS: &[Config] = &[
+    Config {
+        name: "bitcoin",
+        alphabet: Alphabet::BITCOIN,
+    },
+    Config {
+        name: "ripple",
+        alphabet: Alphabet::RIPPLE,
+    },
+    Config {
+        name: "custom",
+        alphabet: &CUSTOM,
+    },
+    Config {
+        name: "shuffled",
+        alphabet: &Alphabet::shuffled(Alphabet::DEFAULT, 58),
+    },
+];
+
+const CUSTOM_CHARS: &[u8; 58] = b" !\"#$%&'()*+,-./0123456789:;<=>?@ABCDEFGHIJKLMNOPQRSTUVWXY";
+
+const CUSTOM: Alphabet = match Alphabet::new(CUSTOM_CHARS) {
+    Ok(alphabet) => alphabet,
+    Err(_) => panic!("invalid alphabet"),
+};
+
+static CUSTOM_UNWRAP: Alphabet = Alphabet::new_unwrap(CUSTOM_CHARS);
+
+const DUPLICATE: Result<Alphabet, Error> = Alphabet::new(&[b'a'; 58]);
+
+const ENCODE_TABLE: &[u8; 58] = Alphabet::FLICKR.encode_table();
+const DECODE_TABLE: [u8; 256] = Alphabet::FLICKR.decode_table();
+
+const _: () = {
+    assert!(ENCODE_TABLE[0] == b'1');
+    assert!(DECODE_TABLE[b'z' as usize] == 33);
+    assert!(DECODE_TABLE[b'l' as usize] == Alphabet::INVALID);
+    assert!(DECODE_TABLE[0x80] == Alphabet::INVALID);
+    assert!(Alphabet::RIPPLE.would_conflict_with(b'r'));
+    assert!(matches!(
+        DUPLICATE,
+        Err(Error::DuplicateCharacter {
+            character: 'a',
+            first: 0,
+            second: 1
+        })
+    ));
+    assert!(bs58::alphabet::count_unique(CUSTOM_CHARS) == bs58::alphabet::required_unique_chars());
+};
+
+fn builtin_name(alphabet: &Alphabet) -> Option<&'static str> {
+    // `MONERO` has the same characters as `BITCOIN`, so would be an unreachable pattern
+    match alphabet {
+        Alphabet::BITCOIN => Some("bitcoin"),
+        Alphabet::RIPPLE => Some("ripple"),
+        Alphabet::FLICKR => Some("flickr"),
+        _ => None,
+    }
+}
+
+#[test]
+fn test_alphabet_const() {
+    assert_eq!(CUSTOM, CUSTOM_UNWRAP);
+    assert_eq!(Ok(CUSTOM), Alphabet::new(CUSTOM_CHARS));
+    assert_eq!(
+        &Alphabet::new_unwrap(b"rpshnaf39wBUDNEGHJKLM4PQRST7VWXYZ2bcdeCg65jkm8oFqi1tuvAxyz"),
+        CONFIGS[1].alphabet
+    );
+
+    for config in CONFIGS {
+        let encoded = bs58::encode(b"hello")
+            .with_alphabet(config.alphabet)
+            .into_string();
+        assert_eq!(
+            Ok(b"hello".to_vec()),
+            bs58::decode(&encoded)
+                .with_alphabet(config.alphabet)
+                .into_vec(),
+            "{}",
+            config.name
+        );
+    }
+}
+
+#[test]
+fn test_alphabet_patterns() {
+    assert_eq!(Some("bitcoin"), builtin_name(Alphabet::DEFAULT));
+    assert_eq!(Some("bitcoin"), builtin_name(Alphabet::MONERO));
+    assert_eq!(Some("ripple"), builtin_name(CONFIGS[1].alphabet));
+    assert_eq!(
+        Some("flickr"),
+        builtin_name(&Alphabet::new_unwrap(ENCODE_TABLE))
+    );
+    assert_eq!(None, builtin_name(&CUSTOM));
+}
+
+#[test]
+fn test_alphabet_would_conflict_with() {
+    for alphabet in [
+        Alphabet::BITCOIN,
+        Alphabet::RIPPLE,
+        Alphabet::FLICKR,
+        &CUSTOM,
+    ] {
+        for c in 0..=255 {
+            assert_eq!(
+                alphabet.encode_table().contains(&c),
+                alphabet.would_conflict_with(c),
+                "{:?}",
+                c as char
+            );
+        }
+    }
+
+    for c in *b"0OIl,.:;-_ \t\n" {
+        assert!(!Alphabet::BITCOIN.would_conflict_with(c), "{:?}", c as char);
+    }
+    assert!(CUSTOM.would_conflict_with(b','));
+    assert!(!CUSTOM.would_conflict_with(b'Z'));
+}
+
+#[test]
+fn test_alphabet_zero_char() {
+    assert_eq!(b'1', Alphabet::DEFAULT.zero_char());
+    assert_eq!(b'1', Alphabet::BITCOIN.zero_char());
+    assert_eq!(b'1', Alphabet::MONERO.zero_char());
+    assert_eq!(b'r', Alphabet::RIPPLE.zero_char());
+    assert_eq!(b'1', Alphabet::FLICKR.zero_char());
+    assert_eq!(CUSTOM.encode_table()[0], CUSTOM.zero_char());
+
+    const ZERO: u8 = Alphabet::RIPPLE.zero_char();
+    for alphabet in [Alphabet::BITCOIN, Alphabet::RIPPLE, &CUSTOM] {
+        let encoded = bs58::encode([0, 0, 1]).with_alphabet(alphabet).into_vec();
+        assert_eq!([alphabet.zero_char(); 2], encoded[..2]);
+    }
+    assert_eq!(
+        b"rrr",
+        &*bs58::encode([0; 3])
+            .with_alphabet(Alphabet::RIPPLE)
+            .into_vec()
+    );
+    assert_eq!(b'r', ZERO);
+}
+
+#[test]
+fn test_alphabet_diff() {
+    let diff = Alphabet::BITCOIN.diff(Alphabet::RIPPLE);
+    let digits: Vec<usize> = diff.iter().map(|&(digit, _, _)| digit).collect();
+    assert_eq!(
+        vec![0, 1, 2, 3, 4, 5, 6, 7, 9, 11, 13, 14, 21, 27, 33, 38, 40, 41, 45, 47, 49, 50, 54],
+        digits
+    );
+    for &(digit, bitcoin, ripple) in &diff {
+        assert_eq!(Alphabet::BITCOIN.encode_table()[digit], bitcoin);
+        assert_eq!(Alphabet::RIPPLE.encode_table()[digit], ripple);
+        assert_ne!(bitcoin, ripple);
+    }
+    assert_eq!((0, b'1', b'r'), diff[0]);
+    assert_eq!((54, b'w', b'A'), diff[22]);
+
+    // Reversed the characters swap places
+    let reversed: Vec<_> = Alphabet::RIPPLE
+        .diff(Alphabet::BITCOIN)
+        .into_iter()
+        .map(|(digit, ripple, bitcoin)| (digit, bitcoin, ripple))
+        .collect();
+    assert_eq!(diff, reversed);
+
+    assert!(Alphabet::BITCOIN.diff(Alphabet::MONERO).is_empty());
+    assert!(CUSTOM.diff(&CUSTOM).is_empty());
+}
+
+const MACRO: Alphabet = bs58::alphabet!(CUSTOM_CHARS);
+static MACRO_REF: &Alphabet =
+    &bs58::alphabet!(b"rpshnaf39wBUDNEGHJKLM4PQRST7VWXYZ2bcdeCg65jkm8oFqi1tuvAxyz");
+
+#[test]
+fn test_alphabet_macro() {
+    assert_eq!(CUSTOM.encode_table(), MACRO.encode_table());
+    assert_eq!(CUSTOM.decode_table(), MACRO.decode_table());
+    assert_eq!(Alphabet::RIPPLE.encode_table(), MACRO_REF.encode_table());
+    assert_eq!(Alphabet::RIPPLE.decode_table(), MACRO_REF.decode_table());
+
+    // Also usable outside a `const` context
+    let local = bs58::alphabet!(b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz");
+    assert!(local.diff(Alphabet::BITCOIN).is_empty());
+
+    let input = [0x04, 0x30, 0x5e, 0x2b, 0x24, 0x73, 0xf0, 0x58];
+    let encoded = bs58::encode(input).with_alphabet(&MACRO).into_string();
+    assert_eq!(
+        bs58::encode(input).with_alphabet(&CUSTOM).into_string(),
+        encoded
+    );
+    assert_eq!(
+        Ok(input.to_vec()),
+        bs58::decode(&encoded).with_alphabet(&MACRO).into_vec()
+    );
+}
+
+const PADDED: Alphabet = match Alphabet::BITCOIN.with_lead_char(b'0') {
+    Ok(alphabet) => alphabet,
+    Err(_) => panic!("invalid lead character"),
+};
+
+#[test]
+fn test_alphabet_with_lead_char_roundtrip() {
+    let inputs: [&[u8]; 7] = [
+        &[],
+        &[0x00],
+        &[0x00, 0x00, 0x00],
+        &[0x04, 0x30, 0x5e, 0x2b, 0x24, 0x73, 0xf0, 0x58],
+        &[0x00, 0x00, 0x04, 0x30, 0x5e, 0x2b, 0x24, 0x73, 0xf0, 0x58],
+        // Zero digits in the body
+        &[0x00, 0x01, 0x00, 0x00, 0x00, 0x00],
+        &[0x00; 40],
+    ];
+    for input in inputs {
+        let plain = bs58::encode(input).into_string();
+        let zeros = input.iter().take_while(|&&b| b == 0).count();
+        let expected = "0".repeat(zeros) + &plain[zeros..];
+
+        let encoded = bs58::encode(input).with_alphabet(&PADDED).into_string();
+        assert_eq!(expected, encoded, "{input:?}");
+        let mut output = vec![0; encoded.len()];
+        let len = bs58::encode(input)
+            .with_alphabet(&PADDED)
+            .onto(&mut output[..])
+            .unwrap();
+        assert_eq!(encoded.as_bytes(), &output[..len], "{input:?}");
+
+        // Both the lead and the zero character decode as leading zeros
+        for encoded in [&encoded, &plain] {
+            assert_eq!(
+                Ok(input.to_vec()),
+                bs58::decode(encoded).with_alphabet(&PADDED).into_vec(),
+                "{encoded}"
+            );
+            let mut job = bs58::decode::Job::new(encoded.as_str(), &PADDED);
+            assert_eq!(Status::Done(input.to_vec()), job.run(1 << 20), "{encoded}");
+            #[cfg(feature = "subtle")]
+            assert_eq!(
+                Ok(input.to_vec()),
+                bs58::decode(encoded)
+                    .with_alphabet(&PADDED)
+                    .into_vec_constant_time(),
+                "{encoded}"
+            );
+            let decoded = bs58::decode(encoded.as_bytes())
+                .with_alphabet(&PADDED)
+                .into_array_const::<40>()
+                .unwrap();
+            assert_eq!(input, &decoded[..input.len()], "{encoded}");
+            assert_eq!(
+                bs58::decode(plain.as_bytes()).len_const(),
+                bs58::decode(encoded.as_bytes())
+                    .with_alphabet(&PADDED)
+                    .len_const(),
+                "{encoded}"
+            );
+            assert_eq!(
+                encoded.as_str(),
+                bs58::decode(encoded.as_str())
+                    .with_alphabet(&PADDED)
+                    .valid_prefix()
+            );
+        }
+
+        assert_eq!(
+            Ok(encoded.clone()),
+            bs58::decode::transcode(&plain, Alphabet::BITCOIN, &PADDED)
+        );
+        assert_eq!(
+            Ok(plain.clone()),
+            bs58::decode::transcode(&encoded, &PADDED, Alphabet::BITCOIN)
+        );
+    }
+}
+
+#[test]
+fn test_alphabet_with_lead_char_errors() {
+    use bs58::decode::Error as DecodeError;
+
+    #[cfg(feature = "subtle")]
+    assert_eq!(
+        Err(DecodeError::InvalidCharacter {
+            character: '0',
+            index: 3
+        }),
+        bs58::decode("00h0")
+            .with_alphabet(&PADDED)
+            .into_vec_constant_time()
+    );
+    // The lead character is only read as zero in leading position
+    assert_eq!(
+        Err(DecodeError::InvalidCharacter {
+            character: '0',
+            index: 3
+        }),
+        bs58::decode("00h0").with_alphabet(&PADDED).into_vec()
+    );
+    assert_eq!(
+        Err(DecodeError::InvalidCharacter {
+            character: '0',
+            index: 2
+        }),
+        bs58::decode("1h0".as_bytes())
+            .with_alphabet(&PADDED)
+            .len_const()
+    );
+    assert_eq!(
+        "01h",
+        bs58::decode("01h0e").with_alphabet(&PADDED).valid_prefix()
+    );
+    assert!(PADDED.would_conflict_with(b'0'));
+
+    assert_eq!(
+        Err(Error::InvalidLeadCharacter { lead: b'z' }),
+        Alphabet::BITCOIN.with_lead_char(b'z')
+    );
+    assert_eq!(
+        Err(Error::InvalidLeadCharacter { lead: 0xff }),
+        Alphabet::BITCOIN.with_lead_char(0xff)
+    );
+    assert_eq!(
+        "lead character 0x7a is non-ascii or another character of the alphabet",
+        Error::InvalidLeadCharacter { lead: b'z' }.to_string()
+    );
+
+    // Using the zero character removes the lead character
+    assert_eq!(Ok(*Alphabet::BITCOIN), PADDED.with_lead_char(b'1'));
+    assert_ne!(*Alphabet::BITCOIN, PADDED);
+    assert_eq!(b'1', PADDED.zero_char());
+    assert_eq!(b'0', PADDED.lead_char());
+    assert_eq!(
+        r#"Alphabet("123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz", '0')"#,
+        format!("{PADDED:?}")
+    );
+}
@@ -0,0 +1,1282 @@
+#![cfg(feature = "check")]
+
+mod cases;
+
+use bs58::check::CheckString;
+
+#[test]
+fn test_check_string() {
+    for &(val, s) in cases::CHECK_TEST_CASES[1..].iter() {
+        let parsed: CheckString = s.parse().unwrap();
+        assert_eq!(val[0], parsed.version());
+        assert_eq!(&val[1..], parsed.payload());
+        assert_eq!(s, parsed.as_str());
+
+        let encoded = CheckString::new(val[0], &val[1..]);
+        assert_eq!(parsed, encoded);
+        assert_eq!(s, encoded.to_string());
+
+        assert_eq!(
+            Ok(&parsed),
+            CheckString::parse_with_version(s, val[0]).as_ref()
+        );
+        assert_eq!(
+            Err(bs58::decode::Error::InvalidVersion {
+                ver: val[0],
+                expected_ver: !val[0],
+            }),
+            CheckString::parse_with_version(s, !val[0])
+        );
+    }
+
+    for &(val, _) in cases::TEST_CASES.iter() {
+        let encoded = CheckString::new(0x05, val);
+        let parsed: CheckString = encoded.as_str().parse().unwrap();
+        assert_eq!(0x05, parsed.version());
+        assert_eq!(val, parsed.payload());
+        assert_eq!(encoded, parsed);
+    }
+
+    // Just a checksum, no version byte
+    assert_eq!(
+        Err(bs58::decode::Error::NoChecksum),
+        "3QJmnh".parse::<CheckString>()
+    );
+}
+
+#[test]
+fn test_check_string_corrupted() {
+    for &(_, s) in cases::CHECK_TEST_CASES[1..].iter() {
+        let mut corrupted = s.to_owned().into_bytes();
+        let last = corrupted.last_mut().unwrap();
+        *last = if *last == b'2' { b'3' } else { b'2' };
+        let corrupted = String::from_utf8(corrupted).unwrap();
+        assert!(matches!(
+            corrupted.parse::<CheckString>(),
+            Err(bs58::decode::Error::InvalidChecksum { .. })
+        ));
+
+        #[cfg(feature = "test-util")]
+        bs58::testing::assert_check_rejects_single_char_corruption(s);
+    }
+}
+
+#[test]
+#[cfg(feature = "serde")]
+fn test_check_string_serde() {
+    let parsed: CheckString = "1F9v11cupBVMpz3CrVfCppv9Rw2xEtU1c6".parse().unwrap();
+    let json = serde_json::to_string(&parsed).unwrap();
+    assert_eq!(r#""1F9v11cupBVMpz3CrVfCppv9Rw2xEtU1c6""#, json);
+    assert_eq!(parsed, serde_json::from_str::<CheckString>(&json).unwrap());
+
+    assert!(
+        serde_json::from_str::<CheckString>(r#""1F9v11cupBVMpz3CrVfCppv9Rw2xEtU1c7""#).is_err()
+    );
+    assert!(serde_json::from_str::<CheckString>("12").is_err());
+}
+
+struct TwoByteVersion;
+
+impl bs58::check::Version for TwoByteVersion {
+    const BYTES: &'static [u8] = &[0x1c, 0xb8];
+}
+
+fn check_typed_matches_dynamic<V: bs58::check::Version>(payload: &[u8]) {
+    let encoded = bs58::encode(payload)
+        .with_check_version_typed::<V>()
+        .into_string();
+    let dynamic = bs58::encode([V::BYTES, payload].concat())
+        .with_check()
+        .into_string();
+    assert_eq!(dynamic, encoded);
+
+    assert_eq!(
+        payload,
+        bs58::decode(&encoded)
+            .with_check_version_typed::<V>()
+            .into_vec()
+            .unwrap()
+    );
+
+    let mut output = [0xff; 64];
+    let len = bs58::decode(&encoded)
+        .with_check_version_typed::<V>()
+        .onto(&mut output[..])
+        .unwrap();
+    assert_eq!(payload, &output[..len]);
+}
+
+#[test]
+fn test_check_version_typed() {
+    use bs58::check::{BitcoinMainnetP2pkh, BitcoinTestnetP2pkh, Wif};
+
+    for &(val, _) in cases::TEST_CASES.iter().filter(|(val, _)| val.len() < 40) {
+        check_typed_matches_dynamic::<BitcoinMainnetP2pkh>(val);
+        check_typed_matches_dynamic::<BitcoinTestnetP2pkh>(val);
+        check_typed_matches_dynamic::<Wif>(val);
+        check_typed_matches_dynamic::<TwoByteVersion>(val);
+    }
+
+    for &(val, s) in cases::CHECK_TEST_CASES[1..].iter() {
+        let dynamic = bs58::decode(s).with_check(Some(0x6f)).into_vec();
+        let typed = bs58::decode(s)
+            .with_check_version_typed::<BitcoinTestnetP2pkh>()
+            .into_vec();
+        if val[0] == 0x6f {
+            assert_eq!(&val[1..], typed.unwrap());
+        } else {
+            assert_eq!(dynamic, typed);
+        }
+    }
+
+    // Checksum is valid, but only has the first version byte
+    let encoded = bs58::encode([0x1c]).with_check().into_string();
+    assert_eq!(
+        Err(bs58::decode::Error::NoChecksum),
+        bs58::decode(&encoded)
+            .with_check_version_typed::<TwoByteVersion>()
+            .into_vec()
+    );
+
+    let encoded = bs58::encode([0x1c, 0xb9, 0x01]).with_check().into_string();
+    assert_eq!(
+        Err(bs58::decode::Error::InvalidVersion {
+            ver: 0xb9,
+            expected_ver: 0xb8,
+        }),
+        bs58::decode(&encoded)
+            .with_check_version_typed::<TwoByteVersion>()
+            .into_vec()
+    );
+}
+
+#[test]
+fn test_checked() {
+    use bs58::check::{BitcoinMainnetP2pkh, BitcoinTestnetP2pkh, Checked};
+
+    fn mainnet(
+        address: Checked<BitcoinMainnetP2pkh, [u8; 20]>,
+    ) -> Checked<BitcoinMainnetP2pkh, [u8; 20]> {
+        address
+    }
+
+    let address: Checked<BitcoinMainnetP2pkh, [u8; 20]> =
+        "1F9v11cupBVMpz3CrVfCppv9Rw2xEtU1c6".parse().unwrap();
+    let address = mainnet(address);
+    assert_eq!(&cases::CHECK_TEST_CASES[6].0[1..], address.payload());
+    assert_eq!("1F9v11cupBVMpz3CrVfCppv9Rw2xEtU1c6", address.to_string());
+    assert_eq!(address, Checked::new(*address.payload()),);
+
+    assert_eq!(
+        Err(bs58::decode::Error::InvalidVersion {
+            ver: 0x00,
+            expected_ver: 0x6f,
+        }),
+        "1F9v11cupBVMpz3CrVfCppv9Rw2xEtU1c6".parse::<Checked<BitcoinTestnetP2pkh, [u8; 20]>>()
+    );
+    assert_eq!(
+        Err(bs58::decode::Error::WrongLength {
+            expected: 32,
+            found: 20,
+        }),
+        "1F9v11cupBVMpz3CrVfCppv9Rw2xEtU1c6".parse::<Checked<BitcoinMainnetP2pkh, [u8; 32]>>()
+    );
+
+    let testnet = Checked::<BitcoinTestnetP2pkh, _>::new(address.into_payload());
+    assert_eq!(
+        testnet,
+        testnet
+            .to_string()
+            .parse::<Checked<BitcoinTestnetP2pkh, [u8; 20]>>()
+            .unwrap()
+    );
+}
+
+#[test]
+fn test_checksum_separator() {
+    for &(val, s) in cases::CHECK_TEST_CASES[1..].iter() {
+        let separated = bs58::encode(val).into_string_with_checksum_separator('-');
+        let (payload, checksum) = separated.rsplit_once('-').unwrap();
+        assert_eq!(bs58::encode(val).into_string(), payload);
+        assert_eq!(
+            Ok(&bs58::decode(s).into_vec().unwrap()[val.len()..]),
+            bs58::decode(checksum).into_vec().as_deref()
+        );
+
+        assert_eq!(
+            Ok(val.to_vec()),
+            bs58::decode(&separated).into_vec_with_checksum_separator('-')
+        );
+        assert_eq!(
+            Ok(val.to_vec()),
+            bs58::decode(&separated)
+                .with_check(Some(val[0]))
+                .into_vec_with_checksum_separator('-')
+        );
+
+        let separated = bs58::encode(&val[1..])
+            .with_check_version(val[0])
+            .into_string_with_checksum_separator('·');
+        assert_eq!(
+            Ok(val.to_vec()),
+            bs58::decode(&separated).into_vec_with_checksum_separator('·')
+        );
+    }
+
+    let separated = bs58::encode([0x9b; 20])
+        .with_check_version_typed::<bs58::check::BitcoinMainnetP2pkh>()
+        .into_string_with_checksum_separator(' ');
+    assert_eq!(
+        Ok(vec![0x9b; 20]),
+        bs58::decode(&separated)
+            .with_check_version_typed::<bs58::check::BitcoinMainnetP2pkh>()
+            .into_vec_with_checksum_separator(' ')
+    );
+}
+
+#[test]
+fn test_checksum_separator_errors() {
+    let separated = bs58::encode([0x2a, 0x60, 0x65]).into_string_with_checksum_separator('-');
+
+    assert_eq!(
+        Err(bs58::decode::Error::NoChecksum),
+        bs58::decode(separated.replace('-', "")).into_vec_with_checksum_separator('-')
+    );
+
+    let (payload, checksum) = separated.split_once('-').unwrap();
+    assert!(bs58::decode(format!("{checksum}-{payload}"))
+        .into_vec_with_checksum_separator('-')
+        .is_err());
+    assert_eq!(
+        Err(bs58::decode::Error::WrongLength {
+            expected: 4,
+            found: 5
+        }),
+        bs58::decode(format!("{payload}-{checksum}2")).into_vec_with_checksum_separator('-')
+    );
+    assert_eq!(
+        Err(bs58::decode::Error::InvalidCharacter {
+            character: '0',
+            index: payload.len() + 1,
+        }),
+        bs58::decode(format!("{payload}-0{checksum}")).into_vec_with_checksum_separator('-')
+    );
+    assert_eq!(
+        Err(bs58::decode::Error::InvalidVersion {
+            ver: 0x2a,
+            expected_ver: 0x2b
+        }),
+        bs58::decode(&separated)
+            .with_check(Some(0x2b))
+            .into_vec_with_checksum_separator('-')
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_checksum_separator_in_alphabet() {
+    bs58::encode([0x2a]).into_string_with_checksum_separator('z');
+}
+
+#[test]
+fn test_decode_delimited_check() {
+    let (val, s) = cases::CHECK_TEST_CASES[1];
+    let line = format!("{s},{},{s}", &s[1..]);
+    let fields: Vec<_> = bs58::decode_delimited(&line, b',')
+        .with_check(Some(val[0]))
+        .map(|field| field.map_err(|err| err.field))
+        .collect();
+    assert_eq!(vec![Ok(val.to_vec()), Err(1), Ok(val.to_vec())], fields);
+}
+
+#[test]
+fn test_check_text_prefix() {
+    for &(val, s) in cases::CHECK_TEST_CASES[1..].iter() {
+        let encoded = bs58::encode(&val[1..])
+            .with_check_version(val[0])
+            .with_text_prefix("addr:")
+            .into_string();
+        assert_eq!(format!("addr:{s}"), encoded);
+        assert_eq!(
+            Ok(val.to_vec()),
+            bs58::decode(&encoded)
+                .with_text_prefix("addr:")
+                .with_check(Some(val[0]))
+                .into_vec()
+        );
+
+        let separated = bs58::encode(val)
+            .with_text_prefix("addr:")
+            .into_string_with_checksum_separator('-');
+        assert!(separated.starts_with("addr:"));
+        assert_eq!(
+            Ok(val.to_vec()),
+            bs58::decode(&separated)
+                .with_text_prefix("addr:")
+                .into_vec_with_checksum_separator('-')
+        );
+    }
+}
+
+#[test]
+fn test_check_hrp() {
+    for &(val, s) in cases::CHECK_TEST_CASES[1..].iter() {
+        let encoded = bs58::encode(&val[1..])
+            .with_check_version(val[0])
+            .with_hrp("xrb", '_')
+            .into_string();
+        assert_eq!(format!("xrb_{s}"), encoded);
+        assert_eq!(
+            Ok(val.to_vec()),
+            bs58::decode(&encoded)
+                .with_hrp("xrb", '_')
+                .with_check(Some(val[0]))
+                .into_vec()
+        );
+
+        let separated = bs58::encode(val)
+            .with_hrp("xrb", '_')
+            .into_string_with_checksum_separator('-');
+        assert!(separated.starts_with("xrb_"));
+        assert_eq!(
+            Ok(val.to_vec()),
+            bs58::decode(&separated)
+                .with_hrp("xrb", '_')
+                .into_vec_with_checksum_separator('-')
+        );
+    }
+}
+
+#[test]
+fn test_check_canonical() {
+    use bs58::decode::{CanonicalRule, Error};
+
+    let payload = [0x00; 20];
+    let s = bs58::encode(payload).with_check_version(0x00).into_string();
+    assert_eq!(
+        Ok(vec![0x00; 21]),
+        bs58::decode(&s)
+            .with_check(Some(0x00))
+            .canonical(21)
+            .into_vec()
+    );
+    assert_eq!(
+        Err(Error::NonCanonical {
+            rule: CanonicalRule::LeadingZeros,
+            expected_len: 25,
+            found_len: 26,
+        }),
+        bs58::decode(format!("1{s}"))
+            .with_check(Some(0x00))
+            .canonical(21)
+            .into_vec()
+    );
+    assert_eq!(
+        Err(Error::NonCanonical {
+            rule: CanonicalRule::LeadingZeros,
+            expected_len: 25,
+            found_len: 24,
+        }),
+        bs58::decode(&s[1..])
+            .with_check(Some(0x00))
+            .canonical(21)
+            .into_vec()
+    );
+    assert_eq!(
+        Ok(vec![0x00; 20]),
+        bs58::decode(&s)
+            .with_check_version_typed::<bs58::check::BitcoinMainnetP2pkh>()
+            .canonical(20)
+            .into_vec()
+    );
+}
+
+#[test]
+fn test_check_expected_len() {
+    use bs58::decode::Error;
+
+    let s = bs58::encode([0x2a; 20])
+        .with_check_version(0x00)
+        .into_string();
+    let mut expected = vec![0x00];
+    expected.extend([0x2a; 20]);
+    assert_eq!(
+        Ok(expected),
+        bs58::decode(&s)
+            .with_check(Some(0x00))
+            .with_expected_len(21)
+            .into_vec()
+    );
+    assert_eq!(
+        Err(Error::WrongLength {
+            expected: 20,
+            found: 21
+        }),
+        bs58::decode(&s)
+            .with_check(Some(0x00))
+            .with_expected_len(20)
+            .into_vec()
+    );
+    assert_eq!(
+        Err(Error::WrongLength {
+            expected: 22,
+            found: 21
+        }),
+        bs58::decode(&s)
+            .with_check(Some(0x00))
+            .with_expected_len(22)
+            .into_vec()
+    );
+}
+
+#[test]
+fn test_check_map_bytes() {
+    let s = bs58::encode([0x2a; 20]).with_check().into_string();
+    assert_eq!(
+        Ok(vec![0xd5; 20]),
+        bs58::decode(&s)
+            .with_check(None)
+            .map_bytes(&|b| !b)
+            .into_vec()
+    );
+}
+
+#[test]
+fn test_check_into_at() {
+    use bs58::decode::Error;
+
+    let s = bs58::encode([0x2a; 20]).with_check().into_string();
+
+    let mut buf = [0xa5; 40];
+    assert_eq!(
+        Ok(20),
+        bs58::decode(&s).with_check(None).into_at(&mut buf, 4)
+    );
+    assert_eq!([0xa5; 4], buf[..4]);
+    assert_eq!([0x2a; 20], buf[4..24]);
+    assert_eq!([0xa5; 16], buf[24..]);
+
+    let mut buf = [0xa5; 40];
+    assert_matches::assert_matches!(
+        bs58::decode(&s).with_check(Some(1)).into_at(&mut buf, 4),
+        Err(Error::InvalidVersion { .. })
+    );
+    assert_eq!([0xa5; 40], buf);
+
+    let mut buf = [0xa5; 40];
+    let encoded = bs58::encode([0x2a; 20])
+        .with_check()
+        .into_at(&mut buf, 3)
+        .unwrap();
+    assert_eq!(s.as_bytes(), &buf[3..][..encoded]);
+    assert_eq!([0xa5; 3], buf[..3]);
+    assert!(buf[3 + encoded..].iter().all(|&b| b == 0xa5));
+}
+
+#[test]
+fn test_check_also_accept_alphabet() {
+    use bs58::Alphabet;
+
+    let payload = [0x04, 0x30, 0x5e, 0x2b, 0x24, 0x73, 0xf0, 0x58];
+    for alpha in [Alphabet::BITCOIN, Alphabet::FLICKR] {
+        let s = bs58::encode(payload)
+            .with_alphabet(alpha)
+            .with_check()
+            .into_string();
+        let mut output = Vec::new();
+        let (len, used) = bs58::decode(&s)
+            .with_alphabet(Alphabet::BITCOIN)
+            .also_accept_alphabet(Alphabet::FLICKR)
+            .with_check(None)
+            .onto_reporting_alphabet(&mut output)
+            .unwrap();
+        assert_eq!(payload, output[..len]);
+        assert_eq!(alpha, used);
+
+        let sep = bs58::encode(payload)
+            .with_alphabet(alpha)
+            .with_check()
+            .into_string_with_checksum_separator('-');
+        assert_eq!(
+            Ok(payload.to_vec()),
+            bs58::decode(&sep)
+                .with_alphabet(Alphabet::BITCOIN)
+                .also_accept_alphabet(Alphabet::FLICKR)
+                .with_check(None)
+                .into_vec_with_checksum_separator('-')
+        );
+    }
+}
+
+#[test]
+fn test_check_into_classified() {
+    use bs58::decode::Error;
+
+    #[derive(Copy, Clone, Debug, PartialEq)]
+    enum Kind {
+        Wif,
+        WifCompressed,
+        Hash,
+    }
+
+    let rules = [
+        (0x80, 32, Kind::Wif),
+        (0x80, 33, Kind::WifCompressed),
+        (0x80, 20, Kind::Hash),
+    ];
+
+    let key = [0x2a; 32];
+    let wif = bs58::encode(key).with_check_version(0x80).into_string();
+    assert_eq!(
+        Ok((Kind::Wif, key.to_vec())),
+        bs58::decode(&wif).into_classified(&rules)
+    );
+
+    let mut compressed = key.to_vec();
+    compressed.push(0x01);
+    let wif = bs58::encode(&compressed)
+        .with_check_version(0x80)
+        .into_string();
+    assert_eq!(
+        Ok((Kind::WifCompressed, compressed)),
+        bs58::decode(&wif).into_classified(&rules)
+    );
+
+    let hash = bs58::encode([0x2a; 20])
+        .with_check_version(0x80)
+        .into_string();
+    assert_eq!(
+        Ok((Kind::Hash, vec![0x2a; 20])),
+        bs58::decode(&hash).into_classified(&rules)
+    );
+
+    let unknown = bs58::encode([0x2a; 21])
+        .with_check_version(0x80)
+        .into_string();
+    assert_eq!(
+        Err(Error::Unclassified {
+            version: 0x80,
+            payload_len: 21
+        }),
+        bs58::decode(&unknown).into_classified(&rules)
+    );
+
+    let unknown = bs58::encode(key).with_check_version(0xef).into_string();
+    assert_eq!(
+        Err(Error::Unclassified {
+            version: 0xef,
+            payload_len: 32
+        }),
+        bs58::decode(&unknown).into_classified(&rules)
+    );
+
+    let mut corrupt = bs58::decode(&hash).into_vec().unwrap();
+    corrupt[3] ^= 1;
+    assert_matches::assert_matches!(
+        bs58::decode(bs58::encode(corrupt).into_string()).into_classified(&rules),
+        Err(Error::InvalidChecksum { .. })
+    );
+
+    assert_eq!(
+        Err(Error::NoChecksum),
+        bs58::decode(bs58::encode([]).with_check().into_string()).into_classified(&rules)
+    );
+}
+
+#[test]
+fn test_check_static_config() {
+    use bs58::{decode::DecodeConfig, encode::EncodeConfig, Alphabet};
+
+    static ENCODER: EncodeConfig = EncodeConfig::new(Alphabet::FLICKR).with_check_version(0x2a);
+    static DECODER: DecodeConfig = DecodeConfig::new(Alphabet::FLICKR).with_check(Some(0x2a));
+
+    std::thread::scope(|scope| {
+        for &(val, _) in cases::TEST_CASES.iter() {
+            scope.spawn(move || {
+                let encoded = ENCODER.encode(val);
+                assert_eq!(
+                    bs58::encode(val)
+                        .with_alphabet(Alphabet::FLICKR)
+                        .with_check_version(0x2a)
+                        .into_string(),
+                    encoded
+                );
+
+                let decoded = DECODER.decode(&encoded).unwrap();
+                assert_eq!(0x2a, decoded[0]);
+                assert_eq!(val, &decoded[1..]);
+                assert_eq!(
+                    Ok(decoded),
+                    DECODER
+                        .with_input(&encoded)
+                        .reserve_exact_output()
+                        .into_vec()
+                );
+            });
+        }
+    });
+}
+
+#[test]
+fn test_check_block_checksums() {
+    for &(val, _) in cases::TEST_CASES.iter() {
+        for block_size in [1, 3, 16] {
+            let encoded = bs58::encode(val)
+                .with_block_checksums(block_size)
+                .into_string();
+            assert_eq!(
+                Ok(val.to_vec()),
+                bs58::decode(&encoded)
+                    .with_block_checksums(block_size)
+                    .into_vec()
+            );
+            assert_eq!(
+                Ok(val.to_vec()),
+                bs58::decode(&encoded)
+                    .with_block_checksums(block_size)
+                    .with_expected_len(val.len())
+                    .into_vec()
+            );
+        }
+    }
+
+    // With a single block this is the same as a checksum without a version
+    let input = [0x04, 0x30, 0x5e, 0x2b, 0x24, 0x73, 0xf0, 0x58];
+    assert_eq!(
+        bs58::encode(input).with_check().into_string(),
+        bs58::encode(input).with_block_checksums(8).into_string()
+    );
+}
+
+#[test]
+fn test_check_block_checksums_corrupted() {
+    let input: Vec<u8> = (1..=40).collect();
+    let framed = bs58::decode(bs58::encode(&input).with_block_checksums(10).into_string())
+        .into_vec()
+        .unwrap();
+    assert_eq!(40 + 4 * 4, framed.len());
+
+    for block_index in 0..4 {
+        // Corrupt a payload byte and a checksum byte of the block
+        for offset in [block_index * 14 + 5, block_index * 14 + 12] {
+            let mut corrupted = framed.clone();
+            corrupted[offset] ^= 0x40;
+            assert_eq!(
+                Err(bs58::decode::Error::BlockChecksumFailed { block_index }),
+                bs58::decode(bs58::encode(&corrupted).into_string())
+                    .with_block_checksums(10)
+                    .into_vec()
+            );
+        }
+    }
+
+    // The last block is too short to hold both a payload and a checksum
+    let mut truncated = framed.clone();
+    truncated.truncate(3 * 14 + 3);
+    assert_eq!(
+        Err(bs58::decode::Error::NoChecksum),
+        bs58::decode(bs58::encode(&truncated).into_string())
+            .with_block_checksums(10)
+            .into_vec()
+    );
+}
+
+#[test]
+fn test_check_starts_with() {
+    for &(val, _) in cases::TEST_CASES.iter() {
+        let encoded = bs58::encode(val).with_check_version(0x2a).into_string();
+        for len in 0..=encoded.len() {
+            assert_eq!(
+                Ok(true),
+                bs58::encode(val)
+                    .with_check_version(0x2a)
+                    .starts_with(&encoded[..len])
+            );
+        }
+        assert_eq!(
+            Ok(false),
+            bs58::encode(val)
+                .with_check_version(0x2a)
+                .starts_with(&format!("{encoded}1"))
+        );
+    }
+}
+
+#[test]
+fn test_check_verify_check_only() {
+    for &(_, s) in cases::CHECK_TEST_CASES[1..].iter() {
+        assert_eq!(Ok(true), bs58::decode(s).verify_check_only());
+    }
+
+    // Large enough to need an allocated buffer
+    let payload = vec![0xa5; 4096];
+    let encoded = bs58::encode(&payload)
+        .with_check_version(0x2a)
+        .into_string();
+    assert_eq!(Ok(true), bs58::decode(&encoded).verify_check_only());
+    assert_eq!(
+        Ok(true),
+        bs58::decode(&encoded)
+            .with_check(Some(0x2a))
+            .verify_check_only()
+    );
+    assert_eq!(
+        Err(bs58::decode::Error::InvalidVersion {
+            ver: 0x2a,
+            expected_ver: 0x2b
+        }),
+        bs58::decode(&encoded)
+            .with_check(Some(0x2b))
+            .verify_check_only()
+    );
+
+    let mut corrupted = encoded.into_bytes();
+    corrupted[100] = if corrupted[100] == b'2' { b'3' } else { b'2' };
+    assert_eq!(Ok(false), bs58::decode(&corrupted).verify_check_only());
+
+    assert_matches::assert_matches!(
+        bs58::decode("0").verify_check_only(),
+        Err(bs58::decode::Error::InvalidCharacter { .. })
+    );
+}
+
+#[test]
+fn test_check_join_split() {
+    let items: Vec<&[u8]> = cases::CHECK_TEST_CASES
+        .iter()
+        .map(|&(val, _)| val)
+        .collect();
+    let encoder = bs58::encode::EncodeConfig::new(bs58::Alphabet::DEFAULT).with_check();
+    let decoder = bs58::decode::DecodeConfig::new(bs58::Alphabet::DEFAULT).with_check(None);
+    let joined = encoder.join(&items, ",");
+    let fields: Vec<_> = decoder
+        .split(&joined, ",")
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert_eq!(items, fields);
+}
+
+#[test]
+fn test_check_is_valid_check() {
+    for &(_, s) in cases::CHECK_TEST_CASES {
+        assert!(bs58::decode::is_valid_check(s), "{s:?}");
+
+        let mut tampered = s.as_bytes().to_vec();
+        let last = tampered.len() - 1;
+        tampered[last] = if tampered[last] == b'2' { b'3' } else { b'2' };
+        let tampered = String::from_utf8(tampered).unwrap();
+        assert!(!bs58::decode::is_valid_check(&tampered), "{tampered:?}");
+    }
+
+    // Longer than the stack buffer
+    let encoded = bs58::encode([0x5a; 200]).with_check().into_string();
+    assert!(bs58::decode::is_valid_check(&encoded));
+    assert!(!bs58::decode::is_valid_check(&encoded[1..]));
+
+    let ripple = bs58::encode([0x01, 0x02, 0x03])
+        .with_alphabet(bs58::Alphabet::RIPPLE)
+        .with_check_version(0x2b)
+        .into_string();
+    assert!(bs58::decode::is_valid_check_with_alphabet(
+        &ripple,
+        bs58::Alphabet::RIPPLE
+    ));
+    assert!(!bs58::decode::is_valid_check(&ripple));
+
+    for s in ["", "1", "he11o", "0QuT57JNzzWTu7mW", "QuT57JNzzWTu7mW\n"] {
+        assert!(!bs58::decode::is_valid_check(s), "{s:?}");
+    }
+}
+
+#[test]
+fn test_check_into_deferred_errors() {
+    use bs58::decode::Error;
+
+    let assert_untouched = |builder: bs58::decode::DecodeBuilder<'_, &str>| {
+        let mut target = [0xaa; 32];
+        let err = builder
+            .into_deferred(&mut target)
+            .map(|commit| commit.len());
+        assert_eq!([0xaa; 32], target);
+        err.unwrap_err()
+    };
+
+    assert_matches::assert_matches!(
+        assert_untouched(bs58::decode("QuT57JNzzWTu7mX").with_check(None)),
+        Error::InvalidChecksum { .. }
+    );
+    assert_matches::assert_matches!(
+        assert_untouched(bs58::decode("QuT57JNzzWTu7mW").with_check(Some(0x01))),
+        Error::InvalidVersion { .. }
+    );
+    assert_eq!(
+        Error::NoChecksum,
+        assert_untouched(bs58::decode("1").with_check(None))
+    );
+
+    let mut corrupted = bs58::encode([0x5a; 4])
+        .with_block_checksums(2)
+        .into_string()
+        .into_bytes();
+    corrupted[0] = if corrupted[0] == b'2' { b'3' } else { b'2' };
+    let corrupted = String::from_utf8(corrupted).unwrap();
+    assert_matches::assert_matches!(
+        assert_untouched(bs58::decode(corrupted.as_str()).with_block_checksums(2)),
+        Error::BlockChecksumFailed { .. }
+    );
+}
+
+#[test]
+fn test_check_inspect() {
+    use bs58::decode::Error;
+
+    let names = |inspection: &bs58::Inspection| inspection.alphabets.map(|alpha| alpha.name);
+
+    // P2PKH address
+    let inspection = bs58::inspect("1F9v11cupBVMpz3CrVfCppv9Rw2xEtU1c6");
+    assert_eq!(["bitcoin", "ripple", "flickr"], names(&inspection));
+    assert_eq!(None, inspection.default_error);
+    let [bitcoin, ripple, flickr] = inspection.alphabets;
+    assert_eq!(bs58::Alphabet::BITCOIN, bitcoin.alphabet);
+    assert!(bitcoin.charset_valid());
+    assert_eq!(
+        (Some(25), true, Some(0x00)),
+        (bitcoin.decoded_len, bitcoin.valid_checksum, bitcoin.version)
+    );
+    for other in [ripple, flickr] {
+        assert!(other.charset_valid());
+        assert_eq!((false, None), (other.valid_checksum, other.version));
+    }
+
+    // Ripple address
+    let inspection = bs58::inspect("rHb9CJAWyB4rj91VRWn96DkukG4bwdtyTh");
+    assert_matches::assert_matches!(
+        inspection.default_error,
+        Some(Error::InvalidChecksum { .. })
+    );
+    let [bitcoin, ripple, flickr] = inspection.alphabets;
+    assert_eq!(
+        (Some(25), true, Some(0x00)),
+        (ripple.decoded_len, ripple.valid_checksum, ripple.version)
+    );
+    assert!(!bitcoin.valid_checksum && !flickr.valid_checksum);
+
+    // Plain 32 byte key
+    let key = bs58::encode([0x5a; 32]).into_string();
+    let inspection = bs58::inspect(&key);
+    assert_matches::assert_matches!(
+        inspection.default_error,
+        Some(Error::InvalidChecksum { .. })
+    );
+    assert_eq!(Some(32), inspection.alphabets[0].decoded_len);
+    assert!(inspection
+        .alphabets
+        .iter()
+        .all(|alpha| !alpha.valid_checksum));
+
+    // Garbage
+    let inspection = bs58::inspect("hello world!");
+    assert_eq!(
+        Some(Error::InvalidCharacter {
+            character: 'l',
+            index: 2
+        }),
+        inspection.default_error
+    );
+    assert!(inspection
+        .alphabets
+        .iter()
+        .all(|alpha| !alpha.charset_valid() && !alpha.valid_checksum && alpha.version.is_none()));
+    for garbage in [
+        "",
+        "1",
+        "1111",
+        "\u{0}é\u{ffff}",
+        "11111111111111111111111111111111",
+    ] {
+        let inspection = bs58::inspect(garbage);
+        assert!(inspection.default_error.is_some(), "{garbage:?}");
+    }
+
+    // A checksum of nothing has no version
+    let empty = bs58::encode([]).with_check().into_string();
+    let inspection = bs58::inspect(&empty);
+    assert_eq!(None, inspection.default_error);
+    assert_eq!(
+        (true, None),
+        (
+            inspection.alphabets[0].valid_checksum,
+            inspection.alphabets[0].version
+        )
+    );
+}
+
+#[test]
+fn test_check_checksummer() {
+    use bs58::check::Checksummer;
+
+    for &(val, s) in cases::CHECK_TEST_CASES.iter() {
+        let decoded = bs58::decode(s).into_vec().unwrap();
+        let (payload, checksum) = decoded.split_at(decoded.len() - 4);
+        assert_eq!(val, payload);
+
+        let mut one_shot = Checksummer::new();
+        one_shot.update(payload);
+        assert_eq!(checksum, one_shot.finalize());
+
+        for split in 0..=payload.len() {
+            let mut incremental = Checksummer::new();
+            incremental.update(&payload[..split]);
+            incremental.update(&[]);
+            incremental.update(&payload[split..]);
+            assert_eq!(checksum, incremental.clone().finalize(), "{s:?} {split}");
+        }
+    }
+
+    let mut checksummer = Checksummer::new();
+    checksummer.update(&[0x00]);
+    checksummer.update(&[0x9b; 20]);
+    let encoded = bs58::encode([0x9b; 20]).with_check_version(0x00).into_vec();
+    let decoded = bs58::decode(&encoded).into_vec().unwrap();
+    assert_eq!(decoded[21..], checksummer.finalize());
+}
+
+#[test]
+fn test_check_find_first_check() {
+    use bs58::decode::find_first_check;
+
+    let address = "1F9v11cupBVMpz3CrVfCppv9Rw2xEtU1c6";
+    let payload = bs58::decode(address).with_check(None).into_vec().unwrap();
+    assert_eq!(21, payload.len());
+
+    for (text, offset) in [
+        (address.to_owned(), 0),
+        (format!("Send to {address}."), 8),
+        (format!("(address:{address})\nThanks"), 9),
+        // Glued to other alphabet characters
+        (format!("xyz{address}abc"), 3),
+        (format!("😀 <{address}>"), 6),
+    ] {
+        assert_eq!(
+            Some((offset, payload.clone())),
+            find_first_check(&text, 21),
+            "{text:?}"
+        );
+    }
+
+    // The first of several, with a different version and a partial address before
+    let other = bs58::encode([0x05; 21]).with_check().into_string();
+    let text = format!("{} {other} and {address}", &address[..20]);
+    let found = find_first_check(&text, 21).unwrap();
+    assert_eq!((21, vec![0x05; 21]), found);
+
+    // Wrong length, corrupted or absent
+    assert_eq!(None, find_first_check(address, 20));
+    assert_eq!(None, find_first_check(address, 22));
+    assert_eq!(None, find_first_check(&address.replace('F', "G"), 21));
+    assert_eq!(None, find_first_check("", 21));
+    assert_eq!(None, find_first_check("no addresses here", 0));
+
+    // Payloads of all zeros have the shortest encoding
+    let zeros = bs58::encode([0; 4]).with_check().into_string();
+    let text = format!("zeros: {zeros}");
+    assert_eq!(Some((7, vec![0; 4])), find_first_check(&text, 4));
+}
+
+#[test]
+fn test_check_address() {
+    const GENESIS_HASH: [u8; 20] = [
+        0x62, 0xe9, 0x07, 0xb1, 0x5c, 0xbf, 0x27, 0xd5, 0x42, 0x53, 0x99, 0xeb, 0xf6, 0xf0, 0xfb,
+        0x50, 0xeb, 0xb8, 0x8f, 0x18,
+    ];
+    let known: [(&str, u8, &[u8]); 4] = [
+        ("1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNa", 0x00, &GENESIS_HASH),
+        ("31h1vYVSYuKP6AhS86fbRdMw9XHieotbST", 0x05, &[0; 20]),
+        ("1111111111111111111114oLvT2", 0x00, &[0; 20]),
+        ("mpXwg4jMtRhuSpVq4xS3HFHmCmWp9NyGKt", 0x6f, &GENESIS_HASH),
+    ];
+    for (address, version, payload) in known {
+        assert_eq!(address, bs58::encode::address(version, payload));
+        assert_eq!(
+            Ok((version, payload.to_vec())),
+            bs58::decode::address(address)
+        );
+    }
+
+    for &(val, s) in cases::CHECK_TEST_CASES[1..].iter() {
+        assert_eq!(s, bs58::encode::address(val[0], &val[1..]));
+        assert_eq!(Ok((val[0], val[1..].to_vec())), bs58::decode::address(s));
+    }
+
+    assert_matches::assert_matches!(
+        bs58::decode::address("1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNb"),
+        Err(bs58::decode::Error::InvalidChecksum { .. })
+    );
+    assert_eq!(
+        Err(bs58::decode::Error::NoChecksum),
+        bs58::decode::address("3QJmnh")
+    );
+    assert_eq!(
+        Err(bs58::decode::Error::NoChecksum),
+        bs58::decode::address("")
+    );
+    assert_eq!(
+        Err(bs58::decode::Error::InvalidCharacter {
+            character: '0',
+            index: 1
+        }),
+        bs58::decode::address("10")
+    );
+}
+
+#[test]
+fn test_check_record() {
+    use bs58::decode::Error;
+
+    for len in [0, 1, 5, 127, 128, 300, 16384] {
+        let payload: Vec<u8> = (0..len).map(|i| i as u8).collect();
+        let encoded = bs58::encode::record(&payload);
+        assert_eq!(
+            Ok(&payload),
+            bs58::decode::record(&encoded).as_ref(),
+            "{len}"
+        );
+    }
+
+    // The header is a LEB128 varint, covered by the checksum along with the payload
+    let payload = [7; 300];
+    let mut framed = vec![0xac, 0x02];
+    framed.extend_from_slice(&payload);
+    assert_eq!(
+        bs58::encode(&framed).with_check().into_string(),
+        bs58::encode::record(&payload)
+    );
+
+    let record =
+        |bytes: &[u8]| bs58::decode::record(&bs58::encode(bytes).with_check().into_string());
+    assert_eq!(Ok(vec![]), record(&[0x00]));
+    assert_eq!(
+        Err(Error::WrongLength {
+            expected: 4,
+            found: 5
+        }),
+        record(b"\x04hello")
+    );
+    assert_eq!(
+        Err(Error::WrongLength {
+            expected: 128,
+            found: 0
+        }),
+        record(&[0x80, 0x01])
+    );
+    // Missing, unterminated, non-minimal and oversized headers
+    assert_eq!(Err(Error::ParseFailed), record(&[]));
+    assert_eq!(Err(Error::ParseFailed), record(&[0x85]));
+    assert_eq!(Err(Error::ParseFailed), record(&[0x80, 0x00]));
+    assert_eq!(Err(Error::ParseFailed), record(&[0xff; 11]));
+    assert_eq!(
+        Err(Error::ParseFailed),
+        record(&[0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x7f])
+    );
+
+    let mut encoded = bs58::encode::record(b"hello").into_bytes();
+    let last = encoded.last_mut().unwrap();
+    *last = if *last == b'2' { b'3' } else { b'2' };
+    assert_matches::assert_matches!(
+        bs58::decode::record(core::str::from_utf8(&encoded).unwrap()),
+        Err(Error::InvalidChecksum { .. })
+    );
+}
+
+#[test]
+fn test_check_empty_payload() {
+    use bs58::{check::BitcoinMainnetP2pkh, decode::Error};
+
+    // An empty payload encodes to only its checksum
+    let encoded = bs58::encode([]).with_check().into_string();
+    assert_eq!("3QJmnh", encoded);
+    assert_eq!(
+        Ok(vec![0x5d, 0xf6, 0xe0, 0xe2]),
+        bs58::decode(&encoded).into_vec()
+    );
+    assert_eq!(
+        Ok(vec![]),
+        bs58::decode(&encoded).with_check(None).into_vec()
+    );
+    assert_eq!(
+        Ok(0),
+        bs58::decode(&encoded).with_check(None).onto(&mut [0; 4])
+    );
+    assert_eq!(
+        Err(Error::EmptyPayload),
+        bs58::decode(&encoded)
+            .with_check(None)
+            .disallow_empty_payload()
+            .into_vec()
+    );
+
+    // There is no version byte to check, even one equal to the first byte of the checksum
+    for version in [0x00, 0x5d] {
+        assert_eq!(
+            Err(Error::NoChecksum),
+            bs58::decode(&encoded).with_check(Some(version)).into_vec(),
+            "{version}"
+        );
+    }
+    assert_eq!(
+        Err(Error::NoChecksum),
+        bs58::decode(&encoded)
+            .with_check_version_typed::<BitcoinMainnetP2pkh>()
+            .into_vec()
+    );
+
+    // Only a version byte, so the payload after removing it is empty
+    let encoded = bs58::encode([])
+        .with_check_version_typed::<BitcoinMainnetP2pkh>()
+        .into_string();
+    assert_eq!(
+        Ok(vec![0x00]),
+        bs58::decode(&encoded).with_check(Some(0)).into_vec()
+    );
+    let versioned = || bs58::decode(&encoded).with_check_version_typed::<BitcoinMainnetP2pkh>();
+    assert_eq!(Ok(vec![]), versioned().into_vec());
+    assert_eq!(
+        Err(Error::EmptyPayload),
+        versioned().disallow_empty_payload().into_vec()
+    );
+
+    // Too short for a checksum
+    for input in ["", "1", "111", "2g"] {
+        assert_eq!(
+            Err(Error::NoChecksum),
+            bs58::decode(input).with_check(None).into_vec(),
+            "{input:?}"
+        );
+        assert_eq!(
+            Err(Error::NoChecksum),
+            bs58::decode(input)
+                .with_check(None)
+                .disallow_empty_payload()
+                .into_vec(),
+            "{input:?}"
+        );
+    }
+
+    // An all-zero payload is not empty
+    for len in 1..4 {
+        let payload = vec![0; len];
+        let encoded = bs58::encode(&payload).with_check().into_string();
+        assert_eq!(
+            Ok(payload.clone()),
+            bs58::decode(&encoded)
+                .with_check(None)
+                .disallow_empty_payload()
+                .into_vec()
+        );
+        assert_eq!(
+            Err(Error::ZeroValue),
+            bs58::decode(&encoded)
+                .with_check(None)
+                .disallow_empty_payload()
+                .require_nonzero()
+                .into_vec()
+        );
+    }
+}
+
+#[test]
+fn test_check_aad() {
+    use bs58::check::{BitcoinMainnetP2pkh, Checksummer};
+    use bs58::decode::Error;
+
+    for &(val, _) in cases::TEST_CASES.iter() {
+        for aad in [&b""[..], b"ctx", &[0; 40]] {
+            let encoded = bs58::encode(val).with_check_aad(aad).into_string();
+            assert_eq!(
+                Ok(val),
+                bs58::decode(&encoded)
+                    .with_check_aad(aad)
+                    .into_vec()
+                    .as_deref(),
+                "{encoded}"
+            );
+        }
+    }
+
+    // Without associated data this is a normal checksum
+    let input = [0x60, 0x65, 0xe7, 0x9b, 0xba, 0x2f, 0x78];
+    assert_eq!(
+        bs58::encode(input).with_check().into_string(),
+        bs58::encode(input).with_check_aad(b"").into_string()
+    );
+
+    // The checksum is over the associated data, version and payload in that order
+    let mut checksummer = Checksummer::new();
+    checksummer.update(b"ctx");
+    checksummer.update(&[42]);
+    checksummer.update(&input);
+    let mut manual = vec![42];
+    manual.extend_from_slice(&input);
+    manual.extend_from_slice(&checksummer.finalize());
+    let encoded = bs58::encode(input)
+        .with_check_version(42)
+        .with_check_aad(b"ctx")
+        .into_string();
+    assert_eq!(bs58::encode(&manual).into_string(), encoded);
+
+    let mut versioned = vec![42];
+    versioned.extend_from_slice(&input);
+    assert_eq!(
+        Ok(&versioned),
+        bs58::decode(&encoded)
+            .with_check(Some(42))
+            .with_check_aad(b"ctx")
+            .into_vec()
+            .as_ref()
+    );
+    assert_eq!(
+        Err(Error::InvalidVersion {
+            ver: 42,
+            expected_ver: 43
+        }),
+        bs58::decode(&encoded)
+            .with_check_aad(b"ctx")
+            .with_check(Some(43))
+            .into_vec()
+    );
+
+    // A different context, or none, fails verification
+    for aad in [&b"ctx2"[..], b"ct", b""] {
+        assert_matches::assert_matches!(
+            bs58::decode(&encoded).with_check_aad(aad).into_vec(),
+            Err(Error::InvalidChecksum { .. })
+        );
+    }
+    assert_matches::assert_matches!(
+        bs58::decode(&encoded).with_check(None).into_vec(),
+        Err(Error::InvalidChecksum { .. })
+    );
+
+    let hash = [0x9b; 20];
+    let encoded = bs58::encode(hash)
+        .with_check_version_typed::<BitcoinMainnetP2pkh>()
+        .with_check_aad(b"ctx")
+        .into_string();
+    assert_eq!(
+        Ok(hash.to_vec()),
+        bs58::decode(&encoded)
+            .with_check_version_typed::<BitcoinMainnetP2pkh>()
+            .with_check_aad(b"ctx")
+            .into_vec()
+    );
+
+    let separated = bs58::encode(input)
+        .with_check_aad(b"ctx")
+        .into_string_with_checksum_separator('-');
+    assert_eq!(
+        Ok(input.to_vec()),
+        bs58::decode(&separated)
+            .with_check_aad(b"ctx")
+            .into_vec_with_checksum_separator('-')
+    );
+}
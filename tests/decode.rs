@@ -6,6 +6,9 @@ use assert_matches::assert_matches;
 #[test]
 fn test_decode() {
     for &(val, s) in cases::TEST_CASES.iter() {
+        #[cfg(feature = "test-util")]
+        bs58::testing::assert_decode_matches(s, val);
+        #[cfg(not(feature = "test-util"))]
         assert_eq!(val.to_vec(), bs58::decode(s).into_vec().unwrap());
 
         const PREFIX: &[u8] = &[0, 1, 2];
@@ -17,8 +20,26 @@ fn test_decode() {
         }
 
         {
-            let vec = bs58::decode(s.as_bytes()).into_array_const_unwrap::<128>();
-            let mut check = [0; 128];
+            let mut buffer = bs58::Buffer::<36>::new();
+            let res = bs58::decode(s).onto(&mut buffer);
+            if val.len() <= buffer.capacity() {
+                assert_eq!(Ok(val.len()), res);
+                assert_eq!(val, &*buffer);
+            } else {
+                assert_eq!(Err(bs58::decode::Error::BufferTooSmall), res);
+                assert!(buffer.is_empty());
+            }
+        }
+
+        {
+            let mut buffer = bs58::Buffer::<{ bs58::max_decoded_len(256) }>::new();
+            assert_eq!(Ok(val.len()), bs58::decode(s).onto(&mut buffer));
+            assert_eq!(val, &*buffer);
+        }
+
+        {
+            let vec = bs58::decode(s.as_bytes()).into_array_const_unwrap::<256>();
+            let mut check = [0; 256];
             check[..val.len()].copy_from_slice(val);
             assert_eq!(vec, check);
         }
@@ -141,3 +162,3348 @@ fn no_append() {
     bs58::decode("a").onto(buf.as_mut()).unwrap();
     assert_eq!(b"!ello world", buf.as_ref());
 }
+
+#[test]
+fn test_detect() {
+    for &(val, s) in cases::TEST_CASES.iter() {
+        let detected = bs58::decode::detect(s);
+        assert_eq!(bs58::Alphabet::BITCOIN, detected[0].0);
+        assert_eq!(val, detected[0].1.as_slice());
+        for (alpha, decoded) in detected {
+            assert_eq!(bs58::encode(decoded).with_alphabet(alpha).into_string(), s);
+        }
+    }
+
+    let detected = bs58::decode::detect("he11owor1d");
+    let alphabets: Vec<_> = detected.iter().map(|&(alpha, _)| alpha).collect();
+    assert_eq!(3, alphabets.len());
+    assert_eq!(bs58::Alphabet::RIPPLE, alphabets[1]);
+    assert_eq!(bs58::Alphabet::FLICKR, alphabets[2]);
+
+    assert!(bs58::decode::detect("hello").is_empty());
+    assert!(bs58::decode::detect("he11o0").is_empty());
+}
+
+#[test]
+#[cfg(feature = "check")]
+fn test_detect_check() {
+    for &(val, s) in cases::CHECK_TEST_CASES[1..].iter() {
+        let detected = bs58::decode::detect_check(s, Some(val[0]));
+        assert_eq!(bs58::Alphabet::BITCOIN, detected[0].0);
+        assert_eq!(val, detected[0].1.as_slice());
+    }
+
+    assert!(bs58::decode::detect_check("he11owor1d", None).is_empty());
+}
+
+#[test]
+fn test_collides_across() {
+    use bs58::{decode::collides_across, Alphabet};
+
+    let swap_case = |c: u8| if c.is_ascii_alphabetic() { c ^ 0x20 } else { c };
+
+    // The bitcoin alphabet with the case of every letter swapped
+    let swapped: Vec<u8> = Alphabet::BITCOIN
+        .encode_table()
+        .iter()
+        .map(|&c| swap_case(c))
+        .collect();
+    let swapped = Alphabet::new(swapped.as_slice().try_into().unwrap()).unwrap();
+    let alphabets = [Alphabet::BITCOIN, &swapped];
+
+    for &(_, s) in cases::TEST_CASES.iter() {
+        let other: Vec<u8> = s.bytes().map(swap_case).collect();
+        let other = String::from_utf8(other).unwrap();
+        assert!(collides_across(s, &other, &alphabets), "{s}");
+        assert!(collides_across(&other, s, &alphabets), "{s}");
+        assert!(collides_across(s, s, &alphabets[..1]), "{s}");
+        if other != s {
+            assert!(!collides_across(s, &other, &alphabets[..1]), "{s}");
+        }
+    }
+
+    assert!(!collides_across("he11owor1d", "he11owor1e", &alphabets));
+    // Strings that are invalid under every alphabet never collide, even with themselves
+    assert!(!collides_across("he11o0", "he11o0", &alphabets));
+    assert!(!collides_across("he11owor1d", "he11owor1d", &[]));
+}
+
+/// The built-in alphabets share a character set, so a wrong built-in alphabet can never be the
+/// cause of an `InvalidCharacter` error.
+#[test]
+fn test_builtin_alphabets_share_characters() {
+    let alphabets = [
+        bs58::Alphabet::BITCOIN,
+        bs58::Alphabet::MONERO,
+        bs58::Alphabet::RIPPLE,
+        bs58::Alphabet::FLICKR,
+    ];
+    for c in 0..128u8 {
+        let valid: Vec<_> = alphabets
+            .iter()
+            .map(|alpha| bs58::decode([c]).with_alphabet(alpha).into_vec().is_ok())
+            .collect();
+        assert!(valid.iter().all(|&v| v == valid[0]), "{:?}", c as char);
+    }
+}
+
+#[test]
+fn test_decode_reserve_exact_output() {
+    for &(val, s) in cases::TEST_CASES.iter() {
+        let decoded = bs58::decode(s).reserve_exact_output().into_vec().unwrap();
+        assert_eq!(val, decoded.as_slice());
+        // `Vec` always allocates at least 8 bytes
+        assert!(decoded.capacity() <= (val.len() + 1).max(8));
+
+        let mut output = [0; 256];
+        let len = bs58::decode(s).reserve_exact_output().onto(&mut output);
+        assert_eq!(Ok(val.len()), len);
+        assert_eq!(val, &output[..val.len()]);
+    }
+
+    let input = b"\xff".repeat(512);
+    for len in 0..=input.len() {
+        let s = bs58::encode(&input[..len]).into_string();
+        let decoded = bs58::decode(&s).reserve_exact_output().into_vec().unwrap();
+        assert_eq!(&input[..len], decoded.as_slice());
+        assert!(decoded.capacity() <= (len + 2).max(8));
+    }
+}
+
+#[test]
+#[cfg(feature = "check")]
+fn test_decode_stream_check() {
+    use std::io::Write;
+
+    fn stream(s: &str) -> Result<Vec<u8>, bs58::decode::Error> {
+        let mut stream = bs58::decode::Stream::new(bs58::Alphabet::DEFAULT).with_check(None);
+        for c in s.as_bytes() {
+            assert_eq!(1, stream.write(&[*c]).unwrap());
+        }
+        stream.finish()
+    }
+
+    for &(val, s) in cases::CHECK_TEST_CASES.iter() {
+        assert_eq!(Ok(val.to_vec()), stream(s));
+
+        let mut corrupted = s.to_owned();
+        let last = corrupted.pop().unwrap();
+        corrupted.push(if last == '1' { '2' } else { '1' });
+        assert_matches!(
+            stream(&corrupted),
+            Err(bs58::decode::Error::InvalidChecksum { .. })
+        );
+    }
+}
+
+#[test]
+fn test_decode_rtl() {
+    for &(val, s) in cases::TEST_CASES.iter() {
+        let reversed: String = s.chars().rev().collect();
+        assert_eq!(val, bs58::decode(&reversed).rtl().into_vec().unwrap());
+
+        let encoded = bs58::encode(val).into_string_rtl();
+        assert_eq!(reversed, encoded);
+        assert_eq!(val, bs58::decode(encoded).rtl().into_vec().unwrap());
+    }
+
+    assert_eq!(
+        bs58::decode("1a0").rtl().into_vec().unwrap_err(),
+        bs58::decode::Error::InvalidCharacter {
+            character: '0',
+            index: 2
+        }
+    );
+}
+
+#[test]
+#[cfg(feature = "check")]
+fn test_decode_check_rtl() {
+    for &(val, s) in cases::CHECK_TEST_CASES.iter() {
+        let encoded = bs58::encode(val).with_check().into_string_rtl();
+        assert_eq!(s.chars().rev().collect::<String>(), encoded);
+        assert_eq!(
+            val,
+            bs58::decode(encoded)
+                .rtl()
+                .with_check(None)
+                .into_vec()
+                .unwrap()
+        );
+    }
+}
+
+#[test]
+#[cfg(feature = "uuid")]
+fn test_decode_uuid() {
+    let uuid = uuid::Uuid::parse_str("67e55044-10b1-426f-9247-bb680e5fe0c8").unwrap();
+    assert_eq!(Ok(uuid), bs58::decode::to_uuid("Dq7QdGPZBdz9vwjm3jLQSB"));
+
+    let uuid = uuid::Uuid::parse_str("00e55044-10b1-426f-9247-bb680e5fe0c8").unwrap();
+    assert_eq!(Ok(uuid), bs58::decode::to_uuid("17R6gfUnt6jLeH7Bno7DMZ"));
+    assert_eq!(
+        Err(bs58::decode::Error::WrongLength {
+            expected: 16,
+            found: 15
+        }),
+        bs58::decode::to_uuid("7R6gfUnt6jLeH7Bno7DMZ")
+    );
+    assert_eq!(
+        Err(bs58::decode::Error::WrongLength {
+            expected: 16,
+            found: 17
+        }),
+        bs58::decode::to_uuid("117R6gfUnt6jLeH7Bno7DMZ")
+    );
+
+    assert_eq!(
+        Ok(uuid::Uuid::nil()),
+        bs58::decode::to_uuid("1111111111111111")
+    );
+}
+
+#[test]
+fn test_decode_best_effort() {
+    for &(val, s) in cases::TEST_CASES.iter() {
+        assert_eq!(Ok(val.to_vec()), bs58::decode(s).into_vec_best_effort());
+
+        let input = format!("{}0{}", s, s);
+        assert_eq!(
+            Err((
+                val.to_vec(),
+                bs58::decode::Error::InvalidCharacter {
+                    character: '0',
+                    index: s.len()
+                }
+            )),
+            bs58::decode(&input).into_vec_best_effort()
+        );
+        assert_eq!(
+            Err((
+                bs58::decode(s).rtl().into_vec().unwrap(),
+                bs58::decode::Error::InvalidCharacter {
+                    character: '0',
+                    index: s.len()
+                }
+            )),
+            bs58::decode(&input).rtl().into_vec_best_effort()
+        );
+
+        let input = format!("{}é", s);
+        assert_eq!(
+            Err((
+                val.to_vec(),
+                bs58::decode::Error::NonAsciiCharacter { index: s.len() }
+            )),
+            bs58::decode(&input).into_vec_best_effort()
+        );
+
+        // Right-to-left the character is still reported at its first byte
+        let input = format!("{}é{}", s, s);
+        assert_eq!(
+            Err((
+                bs58::decode(s).rtl().into_vec().unwrap(),
+                bs58::decode::Error::NonAsciiCharacter { index: s.len() }
+            )),
+            bs58::decode(&input).rtl().into_vec_best_effort()
+        );
+    }
+
+    assert_eq!(
+        Err((
+            vec![],
+            bs58::decode::Error::InvalidCharacter {
+                character: '!',
+                index: 0
+            }
+        )),
+        bs58::decode("!he11owor1d").into_vec_best_effort()
+    );
+
+    // The partial decode is of the input after the text prefix
+    for rtl in [false, true] {
+        let decoder = |input| {
+            let decoder = bs58::decode(input);
+            if rtl {
+                decoder.rtl()
+            } else {
+                decoder
+            }
+        };
+        let (expected, _) = decoder("d1r0w11eh").into_vec_best_effort().unwrap_err();
+        assert!(!expected.is_empty());
+        assert_eq!(
+            Err((
+                expected,
+                bs58::decode::Error::InvalidCharacter {
+                    character: '0',
+                    index: 5
+                }
+            )),
+            decoder("p_d1r0w11eh")
+                .with_text_prefix("p_")
+                .into_vec_best_effort()
+        );
+    }
+    assert_eq!(
+        Err((vec![], bs58::decode::Error::WrongPrefix)),
+        bs58::decode("d1r0w11eh")
+            .with_text_prefix("p_")
+            .into_vec_best_effort()
+    );
+}
+
+#[test]
+#[cfg(feature = "check")]
+fn test_decode_check_best_effort() {
+    let (partial, err) = bs58::decode("K5zqBMZZTzUbAZQgrt4")
+        .with_check(Some(0x01))
+        .into_vec_best_effort()
+        .unwrap_err();
+    assert_matches!(err, bs58::decode::Error::InvalidVersion { .. });
+    assert_eq!(bs58::decode("K5zqBMZZTzUbAZQgrt4").into_vec(), Ok(partial));
+}
+
+#[test]
+fn test_is_default_char() {
+    let alphabet = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+    for &c in alphabet {
+        assert!(bs58::is_default_char(c), "{}", c as char);
+    }
+    for c in [
+        b'0', b'O', b'I', b'l', b'+', b'/', b'=', b' ', b'\0', 0x7f, 0x80, 0xff,
+    ] {
+        assert!(!bs58::is_default_char(c), "{:#x}", c);
+    }
+    assert_eq!(58, (0..=255).filter(|&c| bs58::is_default_char(c)).count());
+}
+
+#[test]
+fn test_decode_alphabets() {
+    for (alpha, cases) in [
+        (bs58::Alphabet::RIPPLE, cases::RIPPLE_TEST_CASES),
+        (bs58::Alphabet::FLICKR, cases::FLICKR_TEST_CASES),
+    ] {
+        for &(val, s) in cases {
+            assert_eq!(
+                val,
+                bs58::decode(s).with_alphabet(alpha).into_vec().unwrap()
+            );
+        }
+    }
+}
+
+#[test]
+#[cfg(feature = "rayon")]
+fn test_decode_par_batch() {
+    let mut inputs: Vec<String> = cases::TEST_CASES
+        .iter()
+        .map(|&(_, s)| s.to_owned())
+        .collect();
+    inputs.push("he11o0".to_owned());
+    inputs.push("l".repeat(100));
+
+    let serial: Vec<_> = inputs.iter().map(|s| bs58::decode(s).into_vec()).collect();
+    assert_eq!(serial, bs58::decode::par_batch(&inputs));
+    assert!(bs58::decode::par_batch::<&str>(&[]).is_empty());
+}
+
+#[test]
+fn test_contraction_estimate() {
+    // Deterministic xorshift generator so the "random" inputs are reproducible
+    let mut state = 0x2545_f491_4f6c_dd1du64;
+    let mut next = move || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state
+    };
+
+    for len in 1..=256 {
+        for _ in 0..8 {
+            let mut input: Vec<u8> = (0..len).map(|_| next() as u8).collect();
+            input[0] |= 0x80;
+            let encoded = bs58::encode(&input).into_string();
+            let estimate =
+                (bs58::decode::contraction_estimate(encoded.len()) * encoded.len() as f64).round();
+            let decoded = bs58::decode(&encoded).into_vec().unwrap().len() as f64;
+            assert!(
+                decoded >= estimate && decoded <= estimate + 1.0,
+                "len {}: estimated {} but decoded to {}",
+                encoded.len(),
+                estimate,
+                decoded,
+            );
+        }
+    }
+
+    assert!((bs58::decode::contraction_estimate(0) - 0.73).abs() < 0.01);
+    assert!((bs58::decode::contraction_estimate(1_000_000) - 0.73).abs() < 0.01);
+}
+
+#[test]
+fn test_decode_into_sink() {
+    /// A fixed-capacity ring buffer that rejects pushes when full.
+    struct Ring {
+        bytes: [u8; 8],
+        start: usize,
+        len: usize,
+    }
+
+    impl bs58::decode::Sink for Ring {
+        fn push_byte(&mut self, b: u8) -> Result<(), ()> {
+            if self.len == self.bytes.len() {
+                return Err(());
+            }
+            self.bytes[(self.start + self.len) % self.bytes.len()] = b;
+            self.len += 1;
+            Ok(())
+        }
+    }
+
+    impl Ring {
+        fn drain(&mut self) -> Vec<u8> {
+            let drained = (0..self.len)
+                .map(|i| self.bytes[(self.start + i) % self.bytes.len()])
+                .collect();
+            self.start = (self.start + self.len) % self.bytes.len();
+            self.len = 0;
+            drained
+        }
+    }
+
+    let mut ring = Ring {
+        bytes: [0; 8],
+        start: 0,
+        len: 0,
+    };
+    for &(val, s) in cases::TEST_CASES.iter() {
+        let res = bs58::decode(s).into_sink(&mut ring);
+        if val.len() <= ring.bytes.len() {
+            assert_eq!(Ok(()), res);
+            assert_eq!(val, ring.drain());
+        } else {
+            assert_eq!(Err(bs58::decode::Error::BufferTooSmall), res);
+            assert_eq!(&val[..ring.bytes.len()], ring.drain());
+        }
+
+        let mut vec = vec![0xff];
+        assert_eq!(Ok(()), bs58::decode(s).into_sink(&mut vec));
+        assert_eq!((&[0xff][..], val), vec.split_at(1));
+
+        let mut output = [0; 256];
+        let mut sink = &mut output[..];
+        assert_eq!(Ok(()), bs58::decode(s).into_sink(&mut sink));
+        assert_eq!(256 - val.len(), sink.len());
+        assert_eq!(val, &output[..val.len()]);
+    }
+
+    assert_eq!(
+        Err(bs58::decode::Error::InvalidCharacter {
+            character: '0',
+            index: 5,
+        }),
+        bs58::decode("he11o0").into_sink(&mut ring)
+    );
+    assert!(ring.drain().is_empty());
+}
+
+#[test]
+fn test_decode_require_nonzero() {
+    for s in ["", "1", "11111111111111111111111111111111"] {
+        assert_eq!(
+            Err(bs58::decode::Error::ZeroValue),
+            bs58::decode(s).require_nonzero().into_vec()
+        );
+        let mut output = [0xff; 64];
+        assert_eq!(
+            Err(bs58::decode::Error::ZeroValue),
+            bs58::decode(s).require_nonzero().onto(&mut output)
+        );
+    }
+
+    for &(val, s) in cases::TEST_CASES.iter() {
+        let res = bs58::decode(s).require_nonzero().into_vec();
+        if val.iter().all(|&b| b == 0) {
+            assert_eq!(Err(bs58::decode::Error::ZeroValue), res);
+        } else {
+            assert_eq!(Ok(val), res.as_deref());
+        }
+    }
+
+    assert_eq!(
+        Ok(vec![0x00, 0x00, 0x01]),
+        bs58::decode("112").require_nonzero().into_vec()
+    );
+    // Invalid characters are reported before the value is checked
+    assert_eq!(
+        Err(bs58::decode::Error::InvalidCharacter {
+            character: '0',
+            index: 2,
+        }),
+        bs58::decode("110").require_nonzero().into_vec()
+    );
+}
+
+#[test]
+#[cfg(feature = "check")]
+fn test_decode_check_require_nonzero() {
+    let encoded = bs58::encode([0x00, 0x00]).with_check().into_string();
+    assert_eq!(
+        Err(bs58::decode::Error::ZeroValue),
+        bs58::decode(&encoded)
+            .with_check(None)
+            .require_nonzero()
+            .into_vec()
+    );
+
+    for &(val, s) in cases::CHECK_TEST_CASES[1..].iter() {
+        assert_eq!(
+            Ok(val),
+            bs58::decode(s)
+                .with_check(None)
+                .require_nonzero()
+                .into_vec()
+                .as_deref()
+        );
+    }
+}
+
+#[test]
+fn test_suggest_correction() {
+    for (input, expected) in [
+        ("0", "o"),
+        ("he11owor1d0", "he11owor1do"),
+        ("heO1owor1d", "heo1owor1d"),
+        ("hel1owor1d", "he11owor1d"),
+    ] {
+        let err = bs58::decode(input).into_vec().unwrap_err();
+        assert_eq!(Some(expected), err.suggest_correction(input).as_deref());
+        assert!(bs58::decode(expected).into_vec().is_ok());
+    }
+
+    for input in [
+        // Ambiguous between `1` and `i`
+        "he11Iwor1d",
+        // More than one invalid character
+        "he00owor1d",
+        "hel0owor1d",
+        // Not a confusable character
+        "he11owor1d+",
+    ] {
+        let err = bs58::decode(input).into_vec().unwrap_err();
+        assert_eq!(None, err.suggest_correction(input), "{}", input);
+    }
+
+    // Only invalid character errors have suggestions
+    let err = bs58::decode("he11ö").into_vec().unwrap_err();
+    assert_eq!(None, err.suggest_correction("he11ö"));
+    assert_eq!(
+        None,
+        bs58::decode::Error::BufferTooSmall.suggest_correction("he11owor1d")
+    );
+
+    // Errors from a different input
+    let err = bs58::decode("he11owor1d0").into_vec().unwrap_err();
+    assert_eq!(None, err.suggest_correction("he11"));
+    assert_eq!(None, err.suggest_correction("he11owor1dd"));
+}
+
+#[test]
+fn test_decode_extend_into() {
+    for &(val, s) in cases::TEST_CASES.iter() {
+        let mut vec = vec![0xff];
+        assert_eq!(Ok(val.len()), bs58::decode(s).extend_into(&mut vec));
+        assert_eq!((&[0xff][..], val), vec.split_at(1));
+
+        let mut deque = std::collections::VecDeque::from([0xff]);
+        assert_eq!(Ok(val.len()), bs58::decode(s).extend_into(&mut deque));
+        assert_eq!(Some(0xff), deque.pop_front());
+        assert_eq!(val, deque.make_contiguous());
+    }
+
+    let mut deque = std::collections::VecDeque::from([0xff]);
+    assert_eq!(
+        Err(bs58::decode::Error::InvalidCharacter {
+            character: '0',
+            index: 5,
+        }),
+        bs58::decode("he11o0").extend_into(&mut deque)
+    );
+    assert_eq!([0xff], *deque.make_contiguous());
+}
+
+#[test]
+fn test_decode_validated() {
+    use bs58::decode::Error;
+
+    let input = String::from("he11owor1d");
+    let validated = bs58::decode(input.as_str()).validated().unwrap();
+    assert!(std::ptr::eq(input.as_str(), validated));
+
+    // Longer than the stack buffer
+    let long = bs58::encode([0xff; 200]).into_string();
+    assert!(std::ptr::eq(
+        long.as_str(),
+        bs58::decode(long.as_str()).validated().unwrap()
+    ));
+    assert_eq!(Ok(""), bs58::decode("").validated());
+
+    assert_eq!(
+        Err(Error::InvalidCharacter {
+            character: '0',
+            index: 5
+        }),
+        bs58::decode("he11o0").validated()
+    );
+    assert_eq!(
+        Err(Error::NonAsciiCharacter { index: 2 }),
+        bs58::decode("he\u{e9}").validated()
+    );
+    assert_eq!(
+        Err(Error::ZeroValue),
+        bs58::decode("111").require_nonzero().validated()
+    );
+}
+
+#[test]
+#[cfg(feature = "check")]
+fn test_decode_validated_check() {
+    use bs58::decode::Error;
+
+    let encoded = bs58::encode([0x04, 0x30, 0x5e])
+        .with_check_version(0x11)
+        .into_string();
+    assert_eq!(
+        Ok(encoded.as_str()),
+        bs58::decode(encoded.as_str())
+            .with_check(Some(0x11))
+            .validated()
+    );
+    assert_matches!(
+        bs58::decode(encoded.as_str())
+            .with_check(Some(0x12))
+            .validated(),
+        Err(Error::InvalidVersion { .. })
+    );
+    assert_matches!(
+        bs58::decode(&encoded[1..]).with_check(None).validated(),
+        Err(Error::InvalidChecksum { .. })
+    );
+    assert_matches!(
+        bs58::decode("2g").with_check(None).validated(),
+        Err(Error::NoChecksum)
+    );
+}
+
+#[test]
+fn test_decode_into_struct() {
+    #[derive(Debug, PartialEq)]
+    struct Header {
+        version: u8,
+        flags: u8,
+        length: u16,
+    }
+
+    let parse = |bytes: &[u8]| match *bytes {
+        [version, flags, hi, lo] => Some(Header {
+            version,
+            flags,
+            length: u16::from_be_bytes([hi, lo]),
+        }),
+        _ => None,
+    };
+
+    let encoded = bs58::encode([0x01, 0x80, 0x12, 0x34]).into_string();
+    assert_eq!(
+        Ok(Header {
+            version: 0x01,
+            flags: 0x80,
+            length: 0x1234,
+        }),
+        bs58::decode(&encoded).into_struct(parse)
+    );
+
+    assert_eq!(
+        Err(bs58::decode::Error::ParseFailed),
+        bs58::decode("he11owor1d").into_struct(parse)
+    );
+    assert_eq!(
+        Err(bs58::decode::Error::InvalidCharacter {
+            character: '0',
+            index: 5,
+        }),
+        bs58::decode("he11o0").into_struct(parse)
+    );
+}
+
+#[test]
+fn test_decode_delimited() {
+    use bs58::decode::{DelimitedError, Error};
+
+    let mut fields = bs58::decode_delimited("he11owor1d:EUY0qQf:EUYUqQf", b':');
+    assert_eq!(
+        Some(Ok(vec![0x04, 0x30, 0x5e, 0x2b, 0x24, 0x73, 0xf0, 0x58])),
+        fields.next()
+    );
+    let error = fields.next().unwrap().unwrap_err();
+    assert_eq!(
+        DelimitedError {
+            field: 1,
+            field_start: 11,
+            error: Error::InvalidCharacter {
+                character: '0',
+                index: 14,
+            },
+        },
+        error
+    );
+    assert_eq!(
+        Error::InvalidCharacter {
+            character: '0',
+            index: 3,
+        },
+        error.error_in_field()
+    );
+    assert_eq!(Some(Ok(b"world".to_vec())), fields.next());
+    assert_eq!(None, fields.next());
+
+    let fields: Vec<_> = bs58::decode_delimited("EUYUqQf,,EUYUqQf,", b',').collect();
+    assert_eq!(
+        vec![
+            Ok(b"world".to_vec()),
+            Ok(vec![]),
+            Ok(b"world".to_vec()),
+            Ok(vec![])
+        ],
+        fields
+    );
+
+    let fields: Vec<_> = bs58::decode_delimited("EUYUqQf,,EUYUqQf,", b',')
+        .reject_empty()
+        .map(|field| field.map_err(|err| (err.field, err.error)))
+        .collect();
+    assert_eq!(
+        vec![
+            Ok(b"world".to_vec()),
+            Err((1, Error::EmptyField)),
+            Ok(b"world".to_vec()),
+            Err((3, Error::EmptyField))
+        ],
+        fields
+    );
+
+    for &(val, s) in cases::TEST_CASES.iter() {
+        let mut fields = bs58::decode_delimited(s, b'.');
+        assert_eq!(Some(Ok(val.to_vec())), fields.next());
+        assert_eq!(None, fields.next());
+
+        let mut fields = bs58::decode_delimited(s, b'.');
+        let mut output = [0xff; 256];
+        assert_eq!(Some(Ok(val.len())), fields.next_onto(&mut output));
+        assert_eq!(val, &output[..val.len()]);
+        assert_eq!(None, fields.next_onto(&mut output));
+    }
+}
+
+#[test]
+fn test_decode_text_prefix() {
+    for &(val, s) in cases::TEST_CASES.iter() {
+        let encoded = bs58::encode(val).with_text_prefix("cosmos1").into_string();
+        assert_eq!(format!("cosmos1{s}"), encoded);
+        assert_eq!(
+            val,
+            bs58::decode(&encoded)
+                .with_text_prefix("cosmos1")
+                .into_vec()
+                .unwrap()
+        );
+
+        let encoded = bs58::encode(val)
+            .with_text_prefix("cosmos1")
+            .into_string_rtl();
+        assert_eq!(
+            val,
+            bs58::decode(&encoded)
+                .with_text_prefix("cosmos1")
+                .rtl()
+                .into_vec()
+                .unwrap()
+        );
+
+        let mut output = [0u8; 512];
+        let len = bs58::encode(val)
+            .with_text_prefix("cosmos1")
+            .onto(&mut output[..])
+            .unwrap();
+        assert_eq!(encoded.len(), len);
+    }
+
+    assert_eq!(
+        Err(bs58::decode::Error::WrongPrefix),
+        bs58::decode("cosmo1he11owor1d")
+            .with_text_prefix("cosmos1")
+            .into_vec()
+    );
+    assert_eq!(
+        Err(bs58::decode::Error::InvalidCharacter {
+            character: '0',
+            index: 12,
+        }),
+        bs58::decode("cosmos1he11o0")
+            .with_text_prefix("cosmos1")
+            .into_vec()
+    );
+    assert_eq!(
+        Err(bs58::encode::Error::BufferTooSmall),
+        bs58::encode([0x00])
+            .with_text_prefix("cosmos1")
+            .onto(&mut [0; 4][..])
+    );
+}
+
+#[test]
+fn test_decode_table_matches_decode() {
+    for alpha in [
+        bs58::Alphabet::BITCOIN,
+        bs58::Alphabet::MONERO,
+        bs58::Alphabet::RIPPLE,
+        bs58::Alphabet::FLICKR,
+    ] {
+        let table = alpha.decode_table();
+        for byte in 0..=u8::MAX {
+            let digit =
+                Some(table[usize::from(byte)]).filter(|&digit| digit != bs58::Alphabet::INVALID);
+            let decoded = bs58::decode([byte]).with_alphabet(alpha).into_vec();
+            match digit {
+                Some(digit) => {
+                    assert_eq!(byte, alpha.encode_table()[usize::from(digit)]);
+                    assert_eq!(Ok(vec![digit]), decoded);
+                }
+                None => assert!(decoded.is_err()),
+            }
+        }
+    }
+}
+
+#[test]
+fn test_decode_canonical() {
+    use bs58::decode::{CanonicalRule, Error};
+
+    let val = [0x00, 0x00, 0x41, 0x42, 0x43];
+    let s = bs58::encode(val).into_string();
+    assert_eq!("11NvLz", s);
+    assert_eq!(Ok(val.to_vec()), bs58::decode(&s).canonical(5).into_vec());
+
+    assert_eq!(
+        Err(Error::NonCanonical {
+            rule: CanonicalRule::LeadingZeros,
+            expected_len: 5,
+            found_len: 6,
+        }),
+        bs58::decode(format!("1{s}")).canonical(5).into_vec()
+    );
+    assert_eq!(
+        Err(Error::NonCanonical {
+            rule: CanonicalRule::LeadingZeros,
+            expected_len: 5,
+            found_len: 4,
+        }),
+        bs58::decode(&s[1..]).canonical(5).into_vec()
+    );
+    assert_eq!(
+        Err(Error::NonCanonical {
+            rule: CanonicalRule::Length,
+            expected_len: 2,
+            found_len: 5,
+        }),
+        bs58::decode(&s).canonical(2).into_vec()
+    );
+
+    let mut output = [0; 5];
+    assert_eq!(Ok(5), bs58::decode(&s).canonical(5).onto(&mut output));
+    assert_eq!(val, output);
+}
+
+#[test]
+#[cfg(any(target_os = "linux", target_os = "macos", target_os = "windows"))]
+fn test_decode_small_stack() {
+    let input = bs58::encode(vec![0xa5; 4096]).into_string();
+    // Unoptimized builds use several times more stack, over 11 KiB within SHA-256 alone
+    let stack_size = if cfg!(debug_assertions) {
+        32 * 1024
+    } else {
+        8 * 1024
+    };
+    let handle = std::thread::Builder::new()
+        .stack_size(stack_size)
+        .spawn(move || {
+            let mut output = vec![0; input.len()];
+            let len = bs58::decode(&input).onto(&mut output[..]).unwrap();
+
+            let mut encoded = vec![0; input.len() + 16];
+            let encoded_len = bs58::encode(&output[..len]).onto(&mut encoded[..]).unwrap();
+            assert_eq!(input.as_bytes(), &encoded[..encoded_len]);
+
+            #[cfg(feature = "check")]
+            {
+                let encoded_len = bs58::encode(&output[..len])
+                    .with_check()
+                    .onto(&mut encoded[..])
+                    .unwrap();
+                let mut checked = vec![0; encoded_len];
+                assert_eq!(
+                    Ok(len),
+                    bs58::decode(&encoded[..encoded_len])
+                        .with_check(None)
+                        .onto(&mut checked[..])
+                );
+            }
+
+            len
+        })
+        .unwrap();
+    assert_eq!(4096, handle.join().unwrap());
+}
+
+#[test]
+fn test_decode_u64_pair() {
+    for (high, low) in [
+        (0, 0),
+        (0, 1),
+        (1, 0),
+        (0x0123_4567_89ab_cdef, 0xfedc_ba98_7654_3210),
+        (u64::MAX, u64::MAX),
+    ] {
+        let value = u128::from(high) << 64 | u128::from(low);
+        let encoded = bs58::encode(value.to_be_bytes()).into_string();
+        assert_eq!(Ok((high, low)), bs58::decode::to_u64_pair(&encoded));
+    }
+
+    assert_eq!(Ok((0, 0)), bs58::decode::to_u64_pair(""));
+    assert_eq!(
+        Ok((u64::MAX, u64::MAX)),
+        bs58::decode::to_u64_pair("YcVfxkQb6JRzqk5kF2tNLv")
+    );
+    assert_eq!(
+        Err(bs58::decode::Error::Overflow),
+        bs58::decode::to_u64_pair("YcVfxkQb6JRzqk5kF2tNLw")
+    );
+    assert_eq!(
+        Err(bs58::decode::Error::InvalidCharacter {
+            character: '0',
+            index: 1,
+        }),
+        bs58::decode::to_u64_pair("20")
+    );
+}
+
+#[test]
+fn test_decode_expected_len() {
+    use bs58::decode::Error;
+
+    for &(val, s) in cases::TEST_CASES.iter() {
+        assert_eq!(
+            Ok(val.to_vec()),
+            bs58::decode(s).with_expected_len(val.len()).into_vec()
+        );
+        assert_eq!(
+            Err(Error::WrongLength {
+                expected: val.len() + 1,
+                found: val.len()
+            }),
+            bs58::decode(s).with_expected_len(val.len() + 1).into_vec(),
+        );
+        if let Some(expected) = val.len().checked_sub(1) {
+            assert_eq!(
+                Err(Error::WrongLength {
+                    expected,
+                    found: val.len()
+                }),
+                bs58::decode(s).with_expected_len(expected).into_vec(),
+            );
+        }
+    }
+
+    let mut output = [0xff; 16];
+    assert_eq!(
+        Ok(8),
+        bs58::decode("he11owor1d")
+            .with_expected_len(8)
+            .onto(&mut output[..])
+    );
+    assert_eq!([0xff; 8], output[8..]);
+
+    // Input too short to decode to the expected length is rejected up front
+    assert_eq!(
+        Err(Error::WrongLength {
+            expected: 4,
+            found: 1
+        }),
+        bs58::decode("1").with_expected_len(4).into_vec()
+    );
+
+    // A caller buffer shorter than the expected length is still too small
+    assert_eq!(
+        Err(Error::BufferTooSmall),
+        bs58::decode("he11owor1d")
+            .with_expected_len(8)
+            .onto(&mut [0; 4][..])
+    );
+}
+
+#[test]
+fn test_decode_map_bytes() {
+    for &(val, s) in cases::TEST_CASES.iter() {
+        assert_eq!(val, bs58::decode(s).map_bytes(&|b| b).into_vec().unwrap());
+
+        let masked: Vec<u8> = val.iter().map(|b| b ^ 0xa5).collect();
+        assert_eq!(
+            masked,
+            bs58::decode(s).map_bytes(&|b| b ^ 0xa5).into_vec().unwrap()
+        );
+
+        let mut output = vec![0; val.len()];
+        assert_eq!(
+            Ok(val.len()),
+            bs58::decode(s)
+                .map_bytes(&|b| b ^ 0xa5)
+                .onto(&mut output[..])
+        );
+        assert_eq!(masked, output);
+    }
+}
+
+#[test]
+fn test_decode_into_at() {
+    use bs58::decode::Error;
+
+    for &(val, s) in cases::TEST_CASES.iter() {
+        let mut buf = [0xa5; 512];
+        assert_eq!(Ok(val.len()), bs58::decode(s).into_at(&mut buf, 100));
+        assert_eq!([0xa5; 100], buf[..100]);
+        assert_eq!(val, &buf[100..][..val.len()]);
+        assert!(buf[100 + val.len()..].iter().all(|&b| b == 0xa5));
+    }
+
+    let original = [0xa5; 16];
+
+    let mut buf = original;
+    assert_eq!(
+        Err(Error::InvalidCharacter {
+            character: '0',
+            index: 10
+        }),
+        bs58::decode("he11owor1d0").into_at(&mut buf, 2)
+    );
+    assert_eq!(original, buf);
+
+    let mut buf = original;
+    assert_eq!(
+        Err(Error::BufferTooSmall),
+        bs58::decode("he11owor1d").into_at(&mut buf, 9)
+    );
+    assert_eq!(original, buf);
+
+    let mut buf = original;
+    assert_eq!(
+        Err(Error::BufferTooSmall),
+        bs58::decode("he11owor1d").into_at(&mut buf, 17)
+    );
+    assert_eq!(original, buf);
+}
+
+#[test]
+fn test_decode_also_accept_alphabet() {
+    use bs58::{decode::Error, Alphabet};
+
+    let decode = |input| {
+        let mut output = Vec::new();
+        bs58::decode(input)
+            .with_alphabet(Alphabet::RIPPLE)
+            .also_accept_alphabet(Alphabet::BITCOIN)
+            .with_expected_len(4)
+            .onto_reporting_alphabet(&mut output)
+            .map(|(len, alpha)| (output[..len].to_vec(), alpha))
+    };
+
+    let (decoded, alpha) = decode("111z").unwrap();
+    assert_eq!(vec![0x00, 0x00, 0x00, 0x39], decoded);
+    assert_eq!(Alphabet::BITCOIN, alpha);
+
+    let (decoded, alpha) = decode("rrrz").unwrap();
+    assert_eq!(vec![0x00, 0x00, 0x00, 0x39], decoded);
+    assert_eq!(Alphabet::RIPPLE, alpha);
+
+    // The error from the primary alphabet is reported when neither succeeds
+    assert_eq!(
+        Err(Error::InvalidCharacter {
+            character: '0',
+            index: 3
+        }),
+        decode("1110")
+    );
+
+    // Without a secondary alphabet nothing is retried
+    assert_eq!(
+        Err(Error::WrongLength {
+            expected: 4,
+            found: 3
+        }),
+        bs58::decode("111z")
+            .with_alphabet(Alphabet::RIPPLE)
+            .with_expected_len(4)
+            .into_vec()
+    );
+}
+
+#[test]
+fn test_decode_estimated_ops() {
+    assert_eq!(0, bs58::decode::estimated_ops(0));
+    assert_eq!(1, bs58::decode::estimated_ops(1));
+
+    // Each character visits at most every byte of the final decoded value, leading zeros are
+    // only appended at the end
+    for &(val, s) in cases::TEST_CASES.iter() {
+        let value_len = val.iter().skip_while(|&&b| b == 0).count();
+        assert!(bs58::decode::estimated_ops(s.len()) >= (s.len() * value_len) as u64);
+    }
+
+    let small = bs58::decode::estimated_ops(1000);
+    let large = bs58::decode::estimated_ops(10_000);
+    assert!(large >= 99 * small && large <= 101 * small);
+
+    assert_eq!(u64::MAX, bs58::decode::estimated_ops(usize::MAX));
+}
+
+#[test]
+fn test_decode_cidv0() {
+    use bs58::decode::Error;
+
+    let cid = "QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG";
+    let multihash = [
+        0x12, 0x20, 0x9d, 0x6c, 0x2b, 0xe5, 0x0f, 0x70, 0x69, 0x53, 0x47, 0x9a, 0xb9, 0xdf, 0x2c,
+        0xe3, 0xed, 0xca, 0x90, 0xb6, 0x80, 0x53, 0xc0, 0x0b, 0x30, 0x04, 0xb7, 0xf0, 0xac, 0xcb,
+        0xe1, 0xe8, 0xee, 0xdf,
+    ];
+    assert_eq!(Ok(multihash.to_vec()), bs58::decode::cidv0(cid));
+    assert_eq!(cid, bs58::encode::cidv0(&multihash));
+
+    // A raw base58 string that is not a CIDv0
+    assert_eq!(Err(Error::WrongPrefix), bs58::decode::cidv0("he11owor1d"));
+    // Starts with `Qm` but the multihash header is not sha2-256
+    assert_eq!(
+        Err(Error::WrongPrefix),
+        bs58::decode::cidv0("QmNLei78zWmzUdbeRB3CiUfAizWUrbeeZh5K1rhAQKCh4z")
+    );
+    assert_eq!(
+        Err(Error::WrongLength {
+            expected: 34,
+            found: 33
+        }),
+        bs58::decode::cidv0(&cid[..45])
+    );
+}
+
+#[test]
+#[cfg(feature = "serde")]
+fn test_decode_error_report_json() {
+    use bs58::decode::{CanonicalRule, Error};
+
+    let json = |err: Error| serde_json::to_string(&err.report()).unwrap();
+
+    #[rustfmt::skip]
+    #[cfg_attr(not(feature = "check"), allow(unused_mut))]
+    let mut cases = vec![
+        (
+            Error::BufferTooSmall,
+            r#"{"code":"buffer_too_small","index":null,"character":null,"expected":null,"found":null,"version":null,"allowed":null}"#,
+        ),
+        (
+            Error::InvalidCharacter { character: '0', index: 5 },
+            r#"{"code":"invalid_character","index":5,"character":"0","expected":null,"found":null,"version":null,"allowed":null}"#,
+        ),
+        (
+            Error::NonAsciiCharacter { index: 3 },
+            r#"{"code":"non_ascii_character","index":3,"character":null,"expected":null,"found":null,"version":null,"allowed":null}"#,
+        ),
+        (
+            Error::WrongLength { expected: 32, found: 31 },
+            r#"{"code":"wrong_length","index":null,"character":null,"expected":32,"found":31,"version":null,"allowed":null}"#,
+        ),
+        (
+            Error::AllocationFailed,
+            r#"{"code":"allocation_failed","index":null,"character":null,"expected":null,"found":null,"version":null,"allowed":null}"#,
+        ),
+        (
+            Error::ZeroValue,
+            r#"{"code":"zero_value","index":null,"character":null,"expected":null,"found":null,"version":null,"allowed":null}"#,
+        ),
+        (
+            Error::ParseFailed,
+            r#"{"code":"parse_failed","index":null,"character":null,"expected":null,"found":null,"version":null,"allowed":null}"#,
+        ),
+        (
+            Error::EmptyField,
+            r#"{"code":"empty_field","index":null,"character":null,"expected":null,"found":null,"version":null,"allowed":null}"#,
+        ),
+        (
+            Error::WrongPrefix,
+            r#"{"code":"wrong_prefix","index":null,"character":null,"expected":null,"found":null,"version":null,"allowed":null}"#,
+        ),
+        (
+            Error::Overflow,
+            r#"{"code":"overflow","index":null,"character":null,"expected":null,"found":null,"version":null,"allowed":null}"#,
+        ),
+        (
+            Error::NonCanonical { rule: CanonicalRule::Length, expected_len: 4, found_len: 5 },
+            r#"{"code":"non_canonical_length","index":null,"character":null,"expected":4,"found":5,"version":null,"allowed":null}"#,
+        ),
+        (
+            Error::NonCanonical { rule: CanonicalRule::LeadingZeros, expected_len: 4, found_len: 3 },
+            r#"{"code":"non_canonical_leading_zeros","index":null,"character":null,"expected":4,"found":3,"version":null,"allowed":null}"#,
+        ),
+        (
+            Error::AmbiguousFold { character: 'H', index: 2 },
+            r#"{"code":"ambiguous_fold","index":2,"character":"H","expected":null,"found":null,"version":null,"allowed":null}"#,
+        ),
+        (
+            Error::PatternMismatch { index: 1 },
+            r#"{"code":"pattern_mismatch","index":null,"character":null,"expected":null,"found":1,"version":null,"allowed":null}"#,
+        ),
+        (
+            Error::UnknownScheme,
+            r#"{"code":"unknown_scheme","index":null,"character":null,"expected":null,"found":null,"version":null,"allowed":null}"#,
+        ),
+        (
+            Error::EmptyPayload,
+            r#"{"code":"empty_payload","index":null,"character":null,"expected":null,"found":null,"version":null,"allowed":null}"#,
+        ),
+        (
+            Error::Truncated { index: 7 },
+            r#"{"code":"truncated","index":7,"character":null,"expected":null,"found":null,"version":null,"allowed":null}"#,
+        ),
+        (
+            Error::DisallowedLength {
+                allowed: &[20, 32],
+                found: 8,
+            },
+            r#"{"code":"disallowed_length","index":null,"character":null,"expected":null,"found":8,"version":null,"allowed":[20,32]}"#,
+        ),
+        (
+            Error::WrongSalt,
+            r#"{"code":"wrong_salt","index":null,"character":null,"expected":null,"found":null,"version":null,"allowed":null}"#,
+        ),
+    ];
+
+    #[cfg(feature = "check")]
+    #[rustfmt::skip]
+    cases.extend([
+        (
+            Error::InvalidChecksum { checksum: [0x01, 0x02, 0x03, 0x04], expected_checksum: [0xff, 0x00, 0x00, 0x00] },
+            r#"{"code":"invalid_checksum","index":null,"character":null,"expected":16909060,"found":4278190080,"version":null,"allowed":null}"#,
+        ),
+        (
+            Error::InvalidVersion { ver: 0x01, expected_ver: 0x2a },
+            r#"{"code":"invalid_version","index":null,"character":null,"expected":42,"found":1,"version":null,"allowed":null}"#,
+        ),
+        (
+            Error::NoChecksum,
+            r#"{"code":"no_checksum","index":null,"character":null,"expected":null,"found":null,"version":null,"allowed":null}"#,
+        ),
+        (
+            Error::Unclassified { version: 0x80, payload_len: 21 },
+            r#"{"code":"unclassified","index":null,"character":null,"expected":null,"found":21,"version":128,"allowed":null}"#,
+        ),
+    ]);
+
+    for (err, expected) in cases {
+        assert_eq!(expected, json(err), "{err:?}");
+        assert_eq!(err.to_string(), err.report().to_string());
+    }
+}
+
+#[test]
+fn test_decode_error_display() {
+    use bs58::decode::Error;
+
+    assert_eq!(
+        "provided string contained invalid character '0' at byte 5",
+        Error::InvalidCharacter {
+            character: '0',
+            index: 5
+        }
+        .to_string()
+    );
+    assert_eq!(
+        "decoded data was the wrong length, expected 32 bytes but found 31",
+        Error::WrongLength {
+            expected: 32,
+            found: 31
+        }
+        .to_string()
+    );
+    #[cfg(feature = "check")]
+    assert_eq!(
+        "invalid checksum, calculated checksum: '[1, 2, 3, 4]', expected checksum: [255, 0, 0, 0]",
+        Error::InvalidChecksum {
+            checksum: [0x01, 0x02, 0x03, 0x04],
+            expected_checksum: [0xff, 0x00, 0x00, 0x00]
+        }
+        .to_string()
+    );
+}
+
+#[test]
+fn test_decode_into_vec_reuse() {
+    let mut output = vec![0xff; 4];
+    for &(val, s) in cases::TEST_CASES.iter() {
+        bs58::decode(s).into_vec_reuse(&mut output).unwrap();
+        assert_eq!(val, &*output);
+    }
+
+    let mut output = Vec::with_capacity(64);
+    let ptr = output.as_ptr();
+    bs58::decode("he11owor1d")
+        .into_vec_reuse(&mut output)
+        .unwrap();
+    assert_eq!(ptr, output.as_ptr());
+
+    assert_eq!(
+        Err(bs58::decode::Error::InvalidCharacter {
+            character: '0',
+            index: 4
+        }),
+        bs58::decode("he110").into_vec_reuse(&mut output)
+    );
+    assert!(output.is_empty());
+    assert_eq!(64, output.capacity());
+}
+
+#[test]
+fn test_decode_error_display_control_character() {
+    for (input, expected) in [
+        ("he\0llo", "invalid character NUL (0x00) at byte 2"),
+        ("he\tllo", "invalid character '\\t' at byte 2"),
+        ("he\nllo", "invalid character '\\n' at byte 2"),
+        ("he\x1bllo", "invalid character ESC (0x1b) at byte 2"),
+        ("he\x7fllo", "invalid character DEL (0x7f) at byte 2"),
+    ] {
+        let message = bs58::decode(input).into_vec().unwrap_err().to_string();
+        assert!(message.ends_with(expected), "{message:?}");
+    }
+}
+
+#[test]
+fn test_decode_into_segments() {
+    for &(val, s) in cases::TEST_CASES.iter() {
+        for segment_len in [1, 7, 64] {
+            let mut buffer = [0xa5; 512];
+            let mut segments: Vec<&mut [u8]> = buffer.chunks_mut(segment_len).collect();
+            assert_eq!(Ok(val.len()), bs58::decode(s).into_segments(&mut segments));
+            assert_eq!(val, &buffer[..val.len()]);
+            assert!(buffer[val.len()..].iter().all(|&b| b == 0xa5));
+
+            if !val.is_empty() {
+                let mut buffer = [0xa5; 512];
+                let mut segments: Vec<&mut [u8]> =
+                    buffer[..val.len() - 1].chunks_mut(segment_len).collect();
+                assert_eq!(
+                    Err(bs58::decode::Error::BufferTooSmall),
+                    bs58::decode(s).into_segments(&mut segments)
+                );
+                assert!(buffer.iter().all(|&b| b == 0xa5));
+            }
+        }
+    }
+}
+
+fn run_job(
+    mut job: bs58::decode::Job<'_, &[u8]>,
+    max_work_units: usize,
+) -> bs58::decode::Result<Vec<u8>> {
+    loop {
+        match job.run(max_work_units) {
+            bs58::decode::Status::Done(decoded) => return Ok(decoded),
+            bs58::decode::Status::Pending => {}
+            bs58::decode::Status::Failed(err) => return Err(err),
+        }
+    }
+}
+
+#[test]
+fn test_decode_job() {
+    for &(val, s) in cases::TEST_CASES.iter() {
+        let job = bs58::decode::Job::new(s.as_bytes(), bs58::Alphabet::DEFAULT);
+        assert_eq!(Ok(val.to_vec()), run_job(job, 1));
+    }
+
+    let mut data: Vec<u8> = (0..2000u32).map(|i| (i * 7919 % 251) as u8).collect();
+    data[..3].fill(0);
+    let encoded = bs58::encode(&data).into_string();
+    for max_work_units in [1, 7, 1000, usize::MAX] {
+        let job = bs58::decode::Job::new(encoded.as_bytes(), bs58::Alphabet::DEFAULT);
+        assert_eq!(Ok(data.clone()), run_job(job, max_work_units));
+    }
+
+    let mut job = bs58::decode::Job::new(encoded.as_bytes(), bs58::Alphabet::DEFAULT);
+    assert_eq!(bs58::decode::Status::Pending, job.run(0));
+    let mut calls = 1;
+    while job.run(10_000) == bs58::decode::Status::Pending {
+        calls += 1;
+    }
+    assert!(calls > bs58::decode::estimated_ops(encoded.len()) / 10_000 / 4);
+    assert!(calls <= bs58::decode::estimated_ops(encoded.len()) / 10_000 + 1);
+}
+
+#[test]
+fn test_decode_job_errors() {
+    let data: Vec<u8> = (0..500u32).map(|i| (i * 7919 % 251) as u8).collect();
+    let encoded = bs58::encode(&data).into_string();
+    for bad in ["0", "l", "é"] {
+        let mut invalid = encoded.clone();
+        invalid.insert_str(400, bad);
+        let expected = bs58::decode(&invalid).into_vec();
+        assert!(expected.is_err());
+        for max_work_units in [1, 13, usize::MAX] {
+            let job = bs58::decode::Job::new(invalid.as_bytes(), bs58::Alphabet::DEFAULT);
+            assert_eq!(expected, run_job(job, max_work_units));
+        }
+    }
+}
+
+#[test]
+#[should_panic(expected = "decode job already finished")]
+fn test_decode_job_finished() {
+    let mut job = bs58::decode::Job::new("he11owor1d", bs58::Alphabet::DEFAULT);
+    assert!(matches!(job.run(usize::MAX), bs58::decode::Status::Done(_)));
+    job.run(1);
+}
+
+#[test]
+fn test_decode_valid_prefix() {
+    for &(_, s) in cases::TEST_CASES.iter() {
+        assert_eq!(s, bs58::decode(s).valid_prefix());
+        for rest in [" ", "0", "I", "O", "l", "+abc", "é", "\n1"] {
+            let mixed = format!("{s}{rest}");
+            assert_eq!(s, bs58::decode(mixed.as_str()).valid_prefix());
+        }
+    }
+
+    let text = "send 1BvBMSEYstWetqTFn5Au4m4GFg7xJaNVN2 now";
+    let token = bs58::decode(&text[5..]).valid_prefix();
+    assert_eq!("1BvBMSEYstWetqTFn5Au4m4GFg7xJaNVN2", token);
+    assert_eq!(Ok(25), bs58::decode(token).into_vec().map(|v| v.len()));
+
+    assert_eq!("", bs58::decode(" he11o").valid_prefix());
+    assert_eq!("he11o", bs58::decode("he11oIOl").valid_prefix());
+    assert_eq!(
+        "he1",
+        bs58::decode("he1lo")
+            .with_alphabet(bs58::Alphabet::BITCOIN)
+            .valid_prefix()
+    );
+    assert_eq!(
+        "r:he11oWor",
+        bs58::decode("r:he11oWorld")
+            .with_alphabet(bs58::Alphabet::FLICKR)
+            .with_text_prefix("r:")
+            .valid_prefix()
+    );
+    assert_eq!("", bs58::decode("r").with_text_prefix("r:").valid_prefix());
+}
+
+#[test]
+fn test_decode_fixed_point() {
+    use bs58::decode::Error;
+
+    // (input, fractional digits, scaled value)
+    let cases: &[(&str, usize, i128)] = &[
+        ("", 0, 0),
+        ("1", 3, 0),
+        ("2", 0, 1),
+        ("2", 1, 1),
+        ("21", 1, 58),
+        ("z", 1, 57),
+        ("1z", 1, 57),
+        ("zz", 2, 58 * 58 - 1),
+        ("4z", 1, 3 * 58 + 57),
+        ("2111", 3, 58 * 58 * 58),
+    ];
+    for &(input, frac_digits, scaled) in cases {
+        assert_eq!(
+            Ok((scaled, frac_digits as u32)),
+            bs58::decode(input).fixed_point(frac_digits).into_fixed(),
+            "{input:?}"
+        );
+    }
+
+    // 1/2 is 29/58
+    let (scaled, scale) = bs58::decode("VW").fixed_point(1).into_fixed().unwrap();
+    assert_eq!(28 * 58 + 29, scaled);
+    assert_eq!(28.5, scaled as f64 / 58f64.powi(scale as i32));
+
+    let max = bs58::encode(i128::MAX.to_be_bytes()).into_string();
+    assert_eq!(
+        Ok((i128::MAX, 5)),
+        bs58::decode(&max).fixed_point(5).into_fixed()
+    );
+    let leading_zeros = format!("{}{max}", "1".repeat(20));
+    assert_eq!(
+        Ok((i128::MAX, 5)),
+        bs58::decode(&leading_zeros).fixed_point(5).into_fixed()
+    );
+    let too_large = bs58::encode((i128::MAX as u128 + 1).to_be_bytes()).into_string();
+    assert_eq!(
+        Err(Error::Overflow),
+        bs58::decode(&too_large).fixed_point(5).into_fixed()
+    );
+
+    assert_eq!(
+        Err(Error::InvalidCharacter {
+            character: '0',
+            index: 1
+        }),
+        bs58::decode("20").fixed_point(1).into_fixed()
+    );
+}
+
+#[test]
+fn test_decode_split() {
+    let items: Vec<&[u8]> = cases::TEST_CASES.iter().map(|&(val, _)| val).collect();
+    for separator in [",", ", ", "::", "\u{2014}"] {
+        let joined = bs58::encode_join(&items, separator);
+        let fields: Vec<_> = bs58::decode_split(&joined, separator)
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(items, fields);
+    }
+
+    let mut fields = bs58::decode_split("he11owor1d, EUY0qQf", ", ");
+    assert_eq!(
+        Some(Ok(vec![0x04, 0x30, 0x5e, 0x2b, 0x24, 0x73, 0xf0, 0x58])),
+        fields.next()
+    );
+    let err = fields.next().unwrap().unwrap_err();
+    assert_eq!((1, 12), (err.field, err.field_start));
+    assert_eq!(
+        bs58::decode::Error::InvalidCharacter {
+            character: '0',
+            index: 15
+        },
+        err.error
+    );
+    assert!(fields.next().is_none());
+
+    let decoder = bs58::decode::DecodeConfig::new(bs58::Alphabet::FLICKR).with_text_prefix("f:");
+    let encoder = bs58::encode::EncodeConfig::new(bs58::Alphabet::FLICKR).with_text_prefix("f:");
+    let joined = encoder.join(&items, " ");
+    let fields: Vec<_> = decoder
+        .split(&joined, " ")
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert_eq!(items, fields);
+    assert_eq!(
+        bs58::decode::Error::WrongPrefix,
+        decoder
+            .split("f:2 2", " ")
+            .nth(1)
+            .unwrap()
+            .unwrap_err()
+            .error
+    );
+}
+
+#[test]
+#[should_panic(expected = "separator \"1\" must not contain characters of the alphabet")]
+fn test_decode_split_separator_in_alphabet() {
+    bs58::decode_split("2121", "1");
+}
+
+#[test]
+fn test_decode_error_into_io_error() {
+    use std::io::ErrorKind;
+
+    fn decode(input: &str, output: &mut [u8]) -> std::io::Result<usize> {
+        Ok(bs58::decode(input).onto(output)?)
+    }
+
+    let err = decode("he11o0", &mut [0; 8]).unwrap_err();
+    assert_eq!(ErrorKind::InvalidData, err.kind());
+    assert_eq!(
+        "provided string contained invalid character '0' at byte 5",
+        err.to_string()
+    );
+    assert_eq!(
+        Some(&bs58::decode::Error::InvalidCharacter {
+            character: '0',
+            index: 5
+        }),
+        err.get_ref().and_then(|err| err.downcast_ref())
+    );
+
+    let err = decode("he11owor1d", &mut [0; 7]).unwrap_err();
+    assert_eq!(ErrorKind::WriteZero, err.kind());
+
+    let err = std::io::Error::from(bs58::decode::Error::AllocationFailed);
+    assert_eq!(ErrorKind::OutOfMemory, err.kind());
+
+    let err = bs58::decode_delimited("2,0", b',')
+        .nth(1)
+        .unwrap()
+        .unwrap_err();
+    assert_eq!(ErrorKind::InvalidData, std::io::Error::from(err).kind());
+}
+
+#[test]
+fn test_decode_into_boxed_array() {
+    use bs58::decode::Error;
+
+    let key: Vec<u8> = (0..64).map(|i| i * 3 + 1).collect();
+    let encoded = bs58::encode(&key).into_string();
+    let boxed: Box<[u8; 64]> = bs58::decode(&encoded).into_boxed_array().unwrap();
+    assert_eq!(key, boxed[..]);
+
+    let mut zeros = [0; 64];
+    zeros[63] = 1;
+    let encoded_zeros = bs58::encode(zeros).into_string();
+    assert_eq!(
+        Ok(Box::new(zeros)),
+        bs58::decode(&encoded_zeros).into_boxed_array()
+    );
+
+    assert_eq!(
+        Err(Error::WrongLength {
+            expected: 65,
+            found: 64
+        }),
+        bs58::decode(&encoded).into_boxed_array::<65>()
+    );
+    assert_eq!(
+        Err(Error::WrongLength {
+            expected: 63,
+            found: 64
+        }),
+        bs58::decode(&encoded).into_boxed_array::<63>()
+    );
+    assert_eq!(
+        Err(Error::WrongLength {
+            expected: 1,
+            found: 0
+        }),
+        bs58::decode("").into_boxed_array::<1>()
+    );
+    assert_eq!(Ok(Box::new([])), bs58::decode("").into_boxed_array::<0>());
+    assert_eq!(
+        Err(Error::InvalidCharacter {
+            character: '0',
+            index: 1
+        }),
+        bs58::decode("20").into_boxed_array::<64>()
+    );
+}
+
+#[test]
+fn test_decode_into_deferred() {
+    let mut target = [0xaa; 12];
+    let commit = bs58::decode("he11owor1d")
+        .into_deferred(&mut target)
+        .unwrap();
+    assert_eq!(8, commit.len());
+    assert_eq!(
+        [0x04, 0x30, 0x5e, 0x2b, 0x24, 0x73, 0xf0, 0x58],
+        commit.as_bytes()
+    );
+    drop(commit);
+    // Dropping without committing writes nothing
+    assert_eq!([0xaa; 12], target);
+
+    let commit = bs58::decode("he11owor1d")
+        .into_deferred(&mut target)
+        .unwrap();
+    assert_eq!(8, commit.commit());
+    assert_eq!(
+        [0x04, 0x30, 0x5e, 0x2b, 0x24, 0x73, 0xf0, 0x58, 0xaa, 0xaa, 0xaa, 0xaa],
+        target
+    );
+
+    // Longer than the buffer kept in the commit
+    let input = [0x5a; 200];
+    let encoded = bs58::encode(input).into_string();
+    let mut target = [0xaa; 256];
+    let commit = bs58::decode(&encoded).into_deferred(&mut target).unwrap();
+    assert_eq!(200, commit.len());
+    assert_eq!(200, commit.commit());
+    assert_eq!(input, target[..200]);
+    assert_eq!([0xaa; 56], target[200..]);
+
+    let (mut target, mut scratch) = ([0xaa; 8], [0; 8]);
+    let commit = bs58::decode("he11owor1d")
+        .into_deferred_with_scratch(&mut target, &mut scratch)
+        .unwrap();
+    assert!(!commit.is_empty());
+    assert_eq!(8, commit.commit());
+    assert_eq!([0x04, 0x30, 0x5e, 0x2b, 0x24, 0x73, 0xf0, 0x58], target);
+
+    let (mut target, mut scratch) = ([0xaa; 8], [0; 4]);
+    assert_eq!(
+        Err(bs58::decode::Error::BufferTooSmall),
+        bs58::decode("he11owor1d")
+            .into_deferred_with_scratch(&mut target, &mut scratch)
+            .map(|commit| commit.len())
+    );
+    assert_eq!([0xaa; 8], target);
+}
+
+#[test]
+fn test_decode_into_deferred_errors() {
+    use bs58::{
+        alphabet::Fold,
+        decode::{DecodeBuilder, Error},
+    };
+
+    #[track_caller]
+    fn assert_untouched(builder: DecodeBuilder<'_, &str>, expected: fn(Error) -> bool) {
+        let mut target = [0xaa; 8];
+        match builder.into_deferred(&mut target) {
+            Ok(commit) => panic!("decoded to {:?}", commit.as_bytes()),
+            Err(err) => assert!(expected(err), "{err:?}"),
+        }
+        assert_eq!([0xaa; 8], target);
+    }
+
+    assert_untouched(bs58::decode("he11owor1dhe11o"), |err| {
+        err == Error::BufferTooSmall
+    });
+    assert_untouched(bs58::decode("he11o0"), |err| {
+        matches!(
+            err,
+            Error::InvalidCharacter {
+                character: '0',
+                index: 5
+            }
+        )
+    });
+    assert_untouched(bs58::decode("he11é"), |err| {
+        matches!(err, Error::NonAsciiCharacter { index: 4 })
+    });
+    assert_untouched(bs58::decode("he11owor1d").with_expected_len(4), |err| {
+        matches!(err, Error::WrongLength { expected: 4, .. })
+    });
+    assert_untouched(bs58::decode("111").require_nonzero(), |err| {
+        err == Error::ZeroValue
+    });
+    assert_untouched(bs58::decode("he11owor1d").with_text_prefix("id:"), |err| {
+        err == Error::WrongPrefix
+    });
+    assert_untouched(bs58::decode("11Ldp").canonical(4), |err| {
+        matches!(err, Error::NonCanonical { .. })
+    });
+    assert_untouched(
+        bs58::decode("HE11OWOR1D").display_fold(Fold::Upper),
+        |err| {
+            matches!(
+                err,
+                Error::AmbiguousFold {
+                    character: 'H',
+                    index: 0
+                }
+            )
+        },
+    );
+}
+
+#[test]
+fn test_decode_into_decode_result() {
+    use bs58::decode::{DecodeResult, Error};
+
+    assert_eq!(
+        Ok(DecodeResult::Empty),
+        bs58::decode("").into_decode_result()
+    );
+    assert_eq!(
+        Ok(DecodeResult::Zero { leading_ones: 1 }),
+        bs58::decode("1").into_decode_result()
+    );
+    assert_eq!(
+        Ok(DecodeResult::Zero { leading_ones: 4 }),
+        bs58::decode("rrrr")
+            .with_alphabet(bs58::Alphabet::RIPPLE)
+            .into_decode_result()
+    );
+    assert_eq!(
+        Ok(DecodeResult::Value(vec![0x00, 0x00, 0x39])),
+        bs58::decode("11z").into_decode_result()
+    );
+    assert_eq!(
+        Ok(DecodeResult::Value(vec![
+            0x04, 0x30, 0x5e, 0x2b, 0x24, 0x73, 0xf0, 0x58
+        ])),
+        bs58::decode("he11owor1d").into_decode_result()
+    );
+    assert_eq!(
+        Err(Error::InvalidCharacter {
+            character: '0',
+            index: 1
+        }),
+        bs58::decode("10").into_decode_result()
+    );
+    assert_eq!(
+        Err(Error::ZeroValue),
+        bs58::decode("11").require_nonzero().into_decode_result()
+    );
+
+    // Longer than the stack buffer
+    let zeros = "1".repeat(200);
+    assert_eq!(
+        Ok(DecodeResult::Zero { leading_ones: 200 }),
+        bs58::decode(&zeros).into_decode_result()
+    );
+
+    // The payload is classified, not the checksum
+    #[cfg(feature = "check")]
+    {
+        let empty = bs58::encode([]).with_check().into_string();
+        assert_eq!(
+            Ok(DecodeResult::Empty),
+            bs58::decode(&empty).with_check(None).into_decode_result()
+        );
+        let zero = bs58::encode([0, 0]).with_check().into_string();
+        assert_eq!(
+            Ok(DecodeResult::Zero { leading_ones: 2 }),
+            bs58::decode(&zero).with_check(None).into_decode_result()
+        );
+    }
+}
+
+#[test]
+fn test_decode_into_vec_matching() {
+    use bs58::decode::Error;
+
+    let decoded = [0x04, 0x30, 0x5e, 0x2b, 0x24, 0x73, 0xf0, 0x58];
+    let magic = |len: usize| {
+        let mut pattern = vec![None; len];
+        pattern[..2].copy_from_slice(&[Some(0x04), Some(0x30)]);
+        pattern
+    };
+
+    assert_eq!(
+        Ok(decoded.to_vec()),
+        bs58::decode("he11owor1d").into_vec_matching(&magic(8))
+    );
+    assert_eq!(
+        Ok(decoded.to_vec()),
+        bs58::decode("he11owor1d").into_vec_matching(&decoded.map(Some))
+    );
+    assert_eq!(
+        Ok(decoded.to_vec()),
+        bs58::decode("he11owor1d").into_vec_matching(&[None; 8])
+    );
+    assert_eq!(Ok(vec![]), bs58::decode("").into_vec_matching(&[]));
+
+    // The first mismatching byte is reported
+    let mut pattern = decoded.map(Some);
+    pattern[5] = Some(0x00);
+    pattern[7] = Some(0x00);
+    assert_eq!(
+        Err(Error::PatternMismatch { index: 5 }),
+        bs58::decode("he11owor1d").into_vec_matching(&pattern)
+    );
+    assert_eq!(
+        Err(Error::PatternMismatch { index: 0 }),
+        bs58::decode("11").into_vec_matching(&[Some(0x01), Some(0x00)])
+    );
+
+    // The pattern must be the decoded length
+    assert_eq!(
+        Err(Error::WrongLength {
+            expected: 7,
+            found: 8
+        }),
+        bs58::decode("he11owor1d").into_vec_matching(&magic(7))
+    );
+    assert_matches::assert_matches!(
+        bs58::decode("he11owor1d").into_vec_matching(&magic(9)),
+        Err(Error::WrongLength { expected: 9, .. })
+    );
+    assert_matches::assert_matches!(
+        bs58::decode("he110").into_vec_matching(&magic(4)),
+        Err(Error::InvalidCharacter {
+            character: '0',
+            index: 4
+        })
+    );
+
+    let err = bs58::decode("he11owor1d")
+        .into_vec_matching(&pattern)
+        .unwrap_err();
+    assert_eq!("decoded byte 5 did not match the pattern", err.to_string());
+    assert_eq!("pattern_mismatch", err.report().code.as_str());
+}
+
+#[test]
+fn test_decode_to_bits() {
+    use bs58::decode::Error;
+
+    for count in [0, 1, 2, 7, 8, 9, 15, 16, 17, 31, 64, 100] {
+        for seed in 0..4 {
+            let bits: Vec<bool> = (0..count).map(|i| (i * 7 + seed) % 3 == 0).collect();
+            let encoded = bs58::encode::from_bits(&bits).into_string();
+            assert_eq!(
+                Ok(&bits),
+                bs58::decode::to_bits(&encoded, count).as_ref(),
+                "{count}"
+            );
+
+            // Any count needing the same number of bytes decodes a prefix or zero padding
+            let padded = count.div_ceil(8) * 8;
+            let decoded = bs58::decode::to_bits(&encoded, padded).unwrap();
+            assert_eq!(bits[..], decoded[..count], "{count}");
+            assert!(decoded[count..].iter().all(|&bit| !bit), "{count}");
+        }
+    }
+
+    // Leading zero bytes are kept
+    let bits = [[false; 8], [false; 8], [true; 8]].concat();
+    assert_eq!("115Q", bs58::encode::from_bits(&bits).into_string());
+    assert_eq!(Ok(bits), bs58::decode::to_bits("115Q", 24));
+
+    assert_eq!(
+        Err(Error::WrongLength {
+            expected: 4,
+            found: 3
+        }),
+        bs58::decode::to_bits("115Q", 25)
+    );
+    assert_eq!(
+        Err(Error::WrongLength {
+            expected: 2,
+            found: 3
+        }),
+        bs58::decode::to_bits("115Q", 16)
+    );
+    assert_eq!(
+        Err(Error::InvalidCharacter {
+            character: '0',
+            index: 2
+        }),
+        bs58::decode::to_bits("110Q", 24)
+    );
+}
+
+#[test]
+fn test_decode_into_vec_with_remainder() {
+    use bs58::decode::Error;
+
+    let decoded = vec![0x04, 0x30, 0x5e, 0x2b, 0x24, 0x73, 0xf0, 0x58];
+    for rest in [
+        &b""[..],
+        b"\0",
+        b"0",
+        b" he11o",
+        b"\xff\xfe",
+        "é".as_bytes(),
+        b"l1",
+    ] {
+        let input = [&b"he11owor1d"[..], rest].concat();
+        assert_eq!(
+            Ok((decoded.clone(), rest)),
+            bs58::decode(&input[..]).into_vec_with_remainder(),
+            "{rest:?}"
+        );
+    }
+
+    // The remainder starts at the first non-alphabet byte, even if it is the first
+    assert_eq!(
+        Ok((vec![], &b"\x00\x01"[..])),
+        bs58::decode(&b"\x00\x01"[..]).into_vec_with_remainder()
+    );
+    assert_eq!(
+        Ok((vec![], &b""[..])),
+        bs58::decode(&b""[..]).into_vec_with_remainder()
+    );
+
+    // Other options apply to the decoded part
+    assert_eq!(
+        Ok((decoded.clone(), &b"|tail"[..])),
+        bs58::decode(&b"id:he11owor1d|tail"[..])
+            .with_text_prefix("id:")
+            .into_vec_with_remainder()
+    );
+    assert_eq!(
+        Err(Error::WrongPrefix),
+        bs58::decode(&b"he11owor1d|tail"[..])
+            .with_text_prefix("id:")
+            .into_vec_with_remainder()
+    );
+    assert_eq!(
+        Err(Error::WrongLength {
+            expected: 7,
+            found: 8
+        }),
+        bs58::decode(&b"he11owor1d|tail"[..])
+            .with_expected_len(7)
+            .into_vec_with_remainder()
+    );
+    assert_eq!(
+        Ok((vec![0x60, 0x65, 0xe7, 0x9b, 0xba, 0x2f, 0x78], &b"0"[..])),
+        bs58::decode(&b"he11owor1d0"[..])
+            .with_alphabet(bs58::Alphabet::RIPPLE)
+            .into_vec_with_remainder()
+    );
+}
+
+#[test]
+fn test_decode_from_scheme() {
+    use bs58::decode::{from_scheme, Error};
+
+    let bitcoin = vec![0x04, 0x30, 0x5e, 0x2b, 0x24, 0x73, 0xf0, 0x58];
+    assert_eq!(Ok(&bitcoin), from_scheme("bitcoin", "he11owor1d").as_ref());
+    assert_eq!(Ok(&bitcoin), from_scheme("monero", "he11owor1d").as_ref());
+    assert_eq!(
+        bs58::decode("he11owor1d")
+            .with_alphabet(bs58::Alphabet::RIPPLE)
+            .into_vec(),
+        from_scheme("ripple", "he11owor1d")
+    );
+    assert_eq!(
+        bs58::decode("he11owor1d")
+            .with_alphabet(bs58::Alphabet::FLICKR)
+            .into_vec(),
+        from_scheme("flickr", "he11owor1d")
+    );
+    assert_eq!(
+        Err(Error::InvalidCharacter {
+            character: '0',
+            index: 5
+        }),
+        from_scheme("bitcoin", "he11o0")
+    );
+
+    for scheme in [
+        "",
+        "Bitcoin",
+        "base64",
+        "bitcoin+",
+        "bitcoin+crc",
+        "+check",
+        "a+check+check",
+    ] {
+        assert_eq!(
+            Err(Error::UnknownScheme),
+            from_scheme(scheme, "he11owor1d"),
+            "{scheme:?}"
+        );
+    }
+    let err = from_scheme("base64", "").unwrap_err();
+    assert_eq!("unknown alphabet or mode in scheme", err.to_string());
+
+    #[cfg(feature = "check")]
+    {
+        let address = "1F9v11cupBVMpz3CrVfCppv9Rw2xEtU1c6";
+        let decoded = from_scheme("bitcoin+check", address).unwrap();
+        assert_eq!((21, 0x00), (decoded.len(), decoded[0]));
+        assert_eq!(
+            Ok(25),
+            from_scheme("bitcoin", address).map(|decoded| decoded.len())
+        );
+        assert_matches!(
+            from_scheme("ripple+check", address),
+            Err(Error::InvalidChecksum { .. })
+        );
+    }
+    #[cfg(not(feature = "check"))]
+    assert_eq!(
+        Err(Error::UnknownScheme),
+        from_scheme("bitcoin+check", "he11owor1d")
+    );
+
+    #[cfg(feature = "cb58")]
+    {
+        let encoded = bs58::encode([0x01, 0x02, 0x03]).as_cb58(None).into_string();
+        assert_eq!(
+            Ok(vec![0x01, 0x02, 0x03]),
+            from_scheme("bitcoin+cb58", &encoded)
+        );
+    }
+}
+
+#[test]
+fn test_decode_hrp() {
+    use bs58::decode::Error;
+
+    for &(val, s) in cases::TEST_CASES.iter() {
+        for (hrp, separator) in [("xrb", '_'), ("cosmos", '1'), ("", ':'), ("nano", '·')] {
+            let encoded = bs58::encode(val).with_hrp(hrp, separator).into_string();
+            assert_eq!(format!("{hrp}{separator}{s}"), encoded);
+            assert_eq!(
+                Ok(val.to_vec()),
+                bs58::decode(&encoded).with_hrp(hrp, separator).into_vec(),
+                "{encoded:?}"
+            );
+
+            let encoded = bs58::encode(val).with_hrp(hrp, separator).into_string_rtl();
+            assert_eq!(
+                Ok(val.to_vec()),
+                bs58::decode(&encoded)
+                    .with_hrp(hrp, separator)
+                    .rtl()
+                    .into_vec()
+            );
+
+            let mut output = [0u8; 512];
+            let len = bs58::encode(val)
+                .with_hrp(hrp, separator)
+                .onto(&mut output[..])
+                .unwrap();
+            assert_eq!(encoded.len(), len);
+            assert_eq!(
+                Ok(true),
+                bs58::encode(val).with_hrp(hrp, separator).starts_with(hrp)
+            );
+        }
+    }
+
+    // The separator may be in the alphabet and the HRP
+    let encoded = bs58::encode([0x00, 0x00, 0x01])
+        .with_hrp("a1b1", '1')
+        .into_string();
+    assert_eq!("a1b11112", encoded);
+    assert_eq!(
+        Ok(vec![0x00, 0x00, 0x01]),
+        bs58::decode(&encoded).with_hrp("a1b1", '1').into_vec()
+    );
+    let encoder = || bs58::encode([0x00, 0x00, 0x01]).with_hrp("a1b1", '1');
+    assert_eq!(Ok(true), encoder().starts_with("a1b111"));
+    assert_eq!(Ok(false), encoder().starts_with("a1b2"));
+
+    for input in [
+        "xrbhe11owor1d",
+        "xrb-he11owor1d",
+        "nano_he11owor1d",
+        "_he11owor1d",
+        "xrb",
+    ] {
+        assert_eq!(
+            Err(Error::WrongPrefix),
+            bs58::decode(input).with_hrp("xrb", '_').into_vec(),
+            "{input:?}"
+        );
+    }
+    assert_eq!(
+        Err(Error::InvalidCharacter {
+            character: '0',
+            index: 9
+        }),
+        bs58::decode("xrb_he11o0").with_hrp("xrb", '_').into_vec()
+    );
+    assert_eq!(
+        "xrb_he11o",
+        bs58::decode("xrb_he11o0")
+            .with_hrp("xrb", '_')
+            .valid_prefix()
+    );
+
+    // A text prefix replaces the HRP and the other way around
+    assert_eq!(
+        "id:he11owor1d",
+        bs58::encode([0x04, 0x30, 0x5e, 0x2b, 0x24, 0x73, 0xf0, 0x58])
+            .with_hrp("xrb", '_')
+            .with_text_prefix("id:")
+            .into_string()
+    );
+    assert_eq!(
+        Ok(vec![0x04, 0x30, 0x5e, 0x2b, 0x24, 0x73, 0xf0, 0x58]),
+        bs58::decode("xrb_he11owor1d")
+            .with_text_prefix("id:")
+            .with_hrp("xrb", '_')
+            .into_vec()
+    );
+}
+
+#[test]
+#[cfg(feature = "generic-array")]
+fn test_decode_into_generic_array() {
+    use bs58::decode::Error;
+    use generic_array::{
+        typenum::{U0, U32, U7, U8, U9},
+        GenericArray,
+    };
+
+    let decoded: GenericArray<u8, U8> = bs58::decode("he11owor1d").into_generic_array().unwrap();
+    assert_eq!(
+        [0x04, 0x30, 0x5e, 0x2b, 0x24, 0x73, 0xf0, 0x58],
+        decoded.as_slice()
+    );
+
+    let key: GenericArray<u8, U32> = bs58::decode(bs58::encode([0x5a; 32]).into_string())
+        .into_generic_array()
+        .unwrap();
+    assert_eq!(GenericArray::from([0x5a; 32]), key);
+
+    let empty: GenericArray<u8, U0> = bs58::decode("").into_generic_array().unwrap();
+    assert!(empty.is_empty());
+
+    assert_eq!(
+        Err(Error::WrongLength {
+            expected: 7,
+            found: 8
+        }),
+        bs58::decode("he11owor1d").into_generic_array::<U7>()
+    );
+    assert_eq!(
+        Err(Error::WrongLength {
+            expected: 9,
+            found: 8
+        }),
+        bs58::decode("he11owor1d").into_generic_array::<U9>()
+    );
+    assert_eq!(
+        Err(Error::InvalidCharacter {
+            character: '0',
+            index: 5
+        }),
+        bs58::decode("he11o0").into_generic_array::<U8>()
+    );
+}
+
+#[test]
+#[cfg(feature = "crc")]
+fn test_decode_into_vec_with_crc32() {
+    /// Bit at a time reference implementation of the zlib CRC-32
+    fn crc32(bytes: &[u8]) -> u32 {
+        let mut crc = !0u32;
+        for &byte in bytes {
+            crc ^= u32::from(byte);
+            for _ in 0..8 {
+                crc = (crc >> 1) ^ (0xedb8_8320 & (crc & 1).wrapping_neg());
+            }
+        }
+        !crc
+    }
+
+    assert_eq!(0xcbf4_3926, crc32(b"123456789"));
+    let encoded = bs58::encode(b"123456789").into_string();
+    assert_eq!(
+        Ok((b"123456789".to_vec(), 0xcbf4_3926)),
+        bs58::decode(&encoded).into_vec_with_crc32()
+    );
+
+    for &(val, s) in cases::TEST_CASES.iter() {
+        assert_eq!(
+            Ok((val.to_vec(), crc32(val))),
+            bs58::decode(s).into_vec_with_crc32()
+        );
+    }
+
+    assert_eq!(
+        Err(bs58::decode::Error::InvalidCharacter {
+            character: '0',
+            index: 5
+        }),
+        bs58::decode("he11o0").into_vec_with_crc32()
+    );
+}
+
+#[test]
+fn test_decode_into_with_remaining() {
+    for &(val, s) in cases::TEST_CASES.iter() {
+        for extra in [0, 1, 10] {
+            let mut buf = vec![0xff; val.len() + extra];
+            assert_eq!(
+                Ok((val.len(), extra)),
+                bs58::decode(s).into_with_remaining(&mut buf)
+            );
+            assert_eq!(val, &buf[..val.len()]);
+            assert!(buf[val.len()..].iter().all(|&b| b == 0xff));
+        }
+    }
+
+    // Pack two values one after the other
+    let mut buf = [0; 16];
+    let (first, remaining) = bs58::decode("he11owor1d")
+        .into_with_remaining(&mut buf)
+        .unwrap();
+    assert_eq!(8, remaining);
+    let (second, remaining) = bs58::decode("EUYUqQf")
+        .into_with_remaining(&mut buf[first..])
+        .unwrap();
+    assert_eq!((8, 5, 3), (first, second, remaining));
+    assert_eq!(b"world", &buf[8..13]);
+
+    assert_eq!(
+        Err(bs58::decode::Error::BufferTooSmall),
+        bs58::decode("he11owor1d").into_with_remaining(&mut [0; 7])
+    );
+}
+
+#[test]
+#[cfg(feature = "subtle")]
+fn test_decode_into_vec_constant_time() {
+    use bs58::decode::Error;
+
+    for &(val, s) in cases::TEST_CASES.iter() {
+        assert_eq!(Ok(val.to_vec()), bs58::decode(s).into_vec_constant_time());
+    }
+
+    // The first invalid character is reported wherever it is, as by the usual decode
+    let valid = "he11owor1dhe11owor1d";
+    for index in 0..valid.len() {
+        for bad in ["0", "l", " ", "é"] {
+            let input = format!("{}{bad}{}0", &valid[..index], &valid[index..]);
+            let expected = bs58::decode(&input).into_vec();
+            assert!(expected.is_err(), "{input:?}");
+            assert_eq!(
+                expected,
+                bs58::decode(&input).into_vec_constant_time(),
+                "{input:?}"
+            );
+        }
+    }
+    assert_eq!(
+        Err(Error::NonAsciiCharacter { index: 1 }),
+        bs58::decode("1é").into_vec_constant_time()
+    );
+
+    assert_eq!(
+        Ok(vec![0x04, 0x30, 0x5e, 0x2b, 0x24, 0x73, 0xf0, 0x58]),
+        bs58::decode("id:he11owor1d")
+            .with_text_prefix("id:")
+            .into_vec_constant_time()
+    );
+    assert_eq!(
+        Err(Error::InvalidCharacter {
+            character: 'l',
+            index: 5
+        }),
+        bs58::decode("id:hello")
+            .with_text_prefix("id:")
+            .into_vec_constant_time()
+    );
+    assert_eq!(
+        Err(Error::WrongPrefix),
+        bs58::decode("he11owor1d")
+            .with_text_prefix("id:")
+            .into_vec_constant_time()
+    );
+
+    // Checks of the decoded value still apply
+    assert_eq!(
+        bs58::decode("he11owor1d").with_expected_len(4).into_vec(),
+        bs58::decode("he11owor1d")
+            .with_expected_len(4)
+            .into_vec_constant_time()
+    );
+    #[cfg(feature = "check")]
+    {
+        let encoded = bs58::encode(b"token").with_check().into_string();
+        assert_eq!(
+            Ok(b"token".to_vec()),
+            bs58::decode(&encoded)
+                .with_check(None)
+                .into_vec_constant_time()
+        );
+        assert_matches!(
+            bs58::decode("he11owor1d")
+                .with_check(None)
+                .into_vec_constant_time(),
+            Err(Error::InvalidChecksum { .. })
+        );
+    }
+
+    // Decoding right-to-left reports the same character as the usual decode
+    for input in ["1dro0wo11eh", "1dr0wo11eh0", "dr\u{e9}o11eh"] {
+        assert_eq!(
+            bs58::decode(input).rtl().into_vec(),
+            bs58::decode(input).rtl().into_vec_constant_time(),
+            "{input:?}"
+        );
+    }
+    assert_eq!(
+        Ok(vec![0x04, 0x30, 0x5e, 0x2b, 0x24, 0x73, 0xf0, 0x58]),
+        bs58::decode("d1rowo11eh").rtl().into_vec_constant_time()
+    );
+}
+
+#[test]
+#[cfg(feature = "subtle")]
+#[should_panic]
+fn test_decode_into_vec_constant_time_fold_panic() {
+    let _ = bs58::decode("HE11OWOR1D")
+        .display_fold(bs58::alphabet::Fold::Upper)
+        .into_vec_constant_time();
+}
+
+#[test]
+fn test_decode_into_fields() {
+    const GENESIS_HASH: [u8; 20] = [
+        0x62, 0xe9, 0x07, 0xb1, 0x5c, 0xbf, 0x27, 0xd5, 0x42, 0x53, 0x99, 0xeb, 0xf6, 0xf0, 0xfb,
+        0x50, 0xeb, 0xb8, 0x8f, 0x18,
+    ];
+    let address = "1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNa";
+
+    // Version, hash and checksum
+    let fields = bs58::decode(address).into_fields(&[1, 20, 4]).unwrap();
+    assert_eq!(3, fields.len());
+    assert_eq!([0x00], *fields[0]);
+    assert_eq!(GENESIS_HASH, *fields[1]);
+    assert_eq!(bs58::decode(address).into_vec().unwrap()[21..], *fields[2]);
+
+    // Empty fields are allowed anywhere
+    assert_eq!(
+        Ok(vec![
+            vec![],
+            vec![0x04, 0x30, 0x5e, 0x2b],
+            vec![],
+            vec![0x24, 0x73, 0xf0, 0x58]
+        ]),
+        bs58::decode("he11owor1d").into_fields(&[0, 4, 0, 4])
+    );
+    assert_eq!(Ok(vec![]), bs58::decode("").into_fields(&[]));
+
+    // Decoding stops once more bytes than the layout covers are decoded
+    for (layout, found) in [
+        (&[1, 20, 3][..], 25),
+        (&[1, 20, 5], 25),
+        (&[1, 20], 22),
+        (&[], 1),
+    ] {
+        assert_eq!(
+            Err(bs58::decode::Error::WrongLength {
+                expected: layout.iter().sum(),
+                found
+            }),
+            bs58::decode(address).into_fields(layout),
+            "{layout:?}"
+        );
+    }
+
+    #[cfg(feature = "check")]
+    {
+        let fields = bs58::decode(address)
+            .with_check(None)
+            .into_fields(&[1, 20])
+            .unwrap();
+        assert_eq!(vec![vec![0x00], GENESIS_HASH.to_vec()], fields);
+    }
+
+    assert_eq!(
+        Err(bs58::decode::Error::InvalidCharacter {
+            character: '0',
+            index: 5
+        }),
+        bs58::decode("he11o0").into_fields(&[1, 2, 1])
+    );
+}
+
+#[test]
+fn test_decode_disallow_empty_payload() {
+    use bs58::decode::Error;
+
+    assert_eq!(Ok(vec![]), bs58::decode("").into_vec());
+    assert_eq!(
+        Err(Error::EmptyPayload),
+        bs58::decode("").disallow_empty_payload().into_vec()
+    );
+    assert_eq!(
+        Err(Error::EmptyPayload),
+        bs58::decode("").disallow_empty_payload().onto(&mut [])
+    );
+    assert_eq!(
+        Err(Error::WrongPrefix),
+        bs58::decode("")
+            .with_text_prefix("id:")
+            .disallow_empty_payload()
+            .into_vec()
+    );
+    assert_eq!(
+        Err(Error::EmptyPayload),
+        bs58::decode("id:")
+            .with_text_prefix("id:")
+            .disallow_empty_payload()
+            .into_vec()
+    );
+
+    for &(val, s) in cases::TEST_CASES.iter().filter(|(val, _)| !val.is_empty()) {
+        assert_eq!(
+            Ok(val.to_vec()),
+            bs58::decode(s).disallow_empty_payload().into_vec()
+        );
+    }
+
+    let err = Error::EmptyPayload;
+    assert_eq!("decoded payload was empty", err.to_string());
+    assert_eq!("empty_payload", err.report().code.as_str());
+}
+
+#[test]
+fn test_decode_error_context() {
+    use bs58::decode::Error;
+
+    let context = |input: &str| bs58::decode(input).into_vec().unwrap_err().context(input);
+
+    // Up to 5 characters before and 4 after the error
+    assert_eq!(
+        Some("ZSi2U0xyz3".to_owned()),
+        context("2NEpo7TZRRrLZSi2U0xyz3m3FLi")
+    );
+    assert_eq!(Some("0he11".to_owned()), context("0he11owor1d"));
+    assert_eq!(Some("he110wor".to_owned()), context("he110wor"));
+    assert_eq!(Some("wor1d0".to_owned()), context("he11owor1d0"));
+    assert_eq!(Some("0".to_owned()), context("0"));
+
+    // Characters, not bytes, are counted
+    let err = Error::InvalidCharacter {
+        character: '0',
+        index: 12,
+    };
+    assert_eq!(Some("ééééé0éééé".to_owned()), err.context("éééééé0ééééé"));
+    assert_eq!(Some("b1cdéf2gh".to_owned()), context("b1cdéf2ghi"));
+
+    // Escaped to stay on one line
+    assert_eq!(Some(r#"he11\n\t\"1"#.to_owned()), context("he11\n\t\"1"));
+
+    let input = "id:HE11OWOR1D";
+    let err = bs58::decode(input)
+        .with_text_prefix("id:")
+        .display_fold(bs58::alphabet::Fold::Upper)
+        .into_vec()
+        .unwrap_err();
+    assert_eq!(
+        Error::AmbiguousFold {
+            character: 'H',
+            index: 3
+        },
+        err
+    );
+    assert_eq!(Some("id:HE11O".to_owned()), err.context(input));
+
+    // Only errors at a character of the input have a context
+    assert_eq!(None, Error::BufferTooSmall.context("he11owor1d"));
+    assert_eq!(
+        None,
+        Error::InvalidCharacter {
+            character: '0',
+            index: 10
+        }
+        .context("he11owor1d")
+    );
+    assert_eq!(None, Error::NonAsciiCharacter { index: 1 }.context("é"));
+}
+
+#[test]
+fn test_decode_read_all_length_prefixed() {
+    use bs58::decode::{read_all_length_prefixed, Error, LenPrefix};
+
+    let fields: [&[u8]; 4] = [
+        &[0x04, 0x30, 0x5e, 0x2b, 0x24, 0x73, 0xf0, 0x58],
+        b"",
+        b"world",
+        &[0; 3],
+    ];
+    let encoded: Vec<String> = fields
+        .iter()
+        .map(|field| bs58::encode(field).into_string())
+        .collect();
+    assert_eq!(["he11owor1d", "", "EUYUqQf", "111"], *encoded);
+
+    for (prefix, len) in [
+        (LenPrefix::U8, (|len| vec![len]) as fn(u8) -> Vec<u8>),
+        (LenPrefix::U16Be, |len| {
+            u16::from(len).to_be_bytes().to_vec()
+        }),
+        (LenPrefix::U16Le, |len| {
+            u16::from(len).to_le_bytes().to_vec()
+        }),
+        (LenPrefix::U32Be, |len| {
+            u32::from(len).to_be_bytes().to_vec()
+        }),
+        (LenPrefix::U32Le, |len| {
+            u32::from(len).to_le_bytes().to_vec()
+        }),
+    ] {
+        let mut buf = Vec::new();
+        let mut starts = Vec::new();
+        for field in &encoded {
+            starts.push(buf.len());
+            buf.extend(len(field.len() as u8));
+            buf.extend(field.as_bytes());
+        }
+        let expected = fields.map(<[u8]>::to_vec).to_vec();
+        assert_eq!(
+            Ok(expected),
+            read_all_length_prefixed(&buf, prefix),
+            "{prefix:?}"
+        );
+        assert_eq!(
+            Ok(vec![]),
+            read_all_length_prefixed(&[], prefix),
+            "{prefix:?}"
+        );
+
+        // Ending part way through any field, in its prefix or characters, is an error
+        for end in 1..buf.len() {
+            let field = starts.iter().rposition(|&start| start < end).unwrap();
+            let result = read_all_length_prefixed(&buf[..end], prefix);
+            match starts.get(field + 1) {
+                Some(&next) if next == end => assert_eq!(
+                    Ok(fields[..=field].iter().map(|f| f.to_vec()).collect()),
+                    result,
+                    "{prefix:?} {end}"
+                ),
+                _ => assert_eq!(
+                    Err(Error::Truncated {
+                        index: starts[field]
+                    }),
+                    result,
+                    "{prefix:?} {end}"
+                ),
+            }
+        }
+    }
+
+    // A length longer than the rest of the input
+    assert_eq!(
+        Err(Error::Truncated { index: 0 }),
+        read_all_length_prefixed(b"\xffhe11owor1d", LenPrefix::U8)
+    );
+    assert_eq!(
+        Err(Error::Truncated { index: 5 }),
+        read_all_length_prefixed(b"\x01\x00\x00\x002\xff\xff\xff\xff1", LenPrefix::U32Le)
+    );
+
+    // Character errors are at their index in the whole buffer
+    assert_eq!(
+        Err(Error::InvalidCharacter {
+            character: '0',
+            index: 15
+        }),
+        read_all_length_prefixed(b"\x0ahe11owor1d\x04he10", LenPrefix::U8)
+    );
+    let err = Error::Truncated { index: 12 };
+    assert_eq!(
+        "provided string ended part way through the field at byte 12",
+        err.to_string()
+    );
+    assert_eq!(Some(12), err.report().index);
+}
+
+#[test]
+fn test_decode_into_be_vec() {
+    use bs58::decode::Error;
+
+    let u16_values: [&[u16]; 5] = [
+        &[],
+        &[0],
+        &[0, 0, 1],
+        &[0x0430, 0x5e2b, 0x2473, 0xf058],
+        &[u16::MAX; 7],
+    ];
+    for values in u16_values {
+        let encoded = bs58::encode::from_u16_be(values).into_string();
+        assert_eq!(
+            Ok(values.to_vec()),
+            bs58::decode(&encoded).into_u16_be_vec(),
+            "{values:?}"
+        );
+        let bytes: Vec<u8> = values.iter().flat_map(|v| v.to_be_bytes()).collect();
+        assert_eq!(bs58::encode(&bytes).into_string(), encoded, "{values:?}");
+    }
+
+    let u32_values: [&[u32]; 5] = [
+        &[],
+        &[0],
+        &[0, 1],
+        &[0x0430_5e2b, 0x2473_f058],
+        &[u32::MAX; 5],
+    ];
+    for values in u32_values {
+        let encoded = bs58::encode::from_u32_be(values).into_string();
+        assert_eq!(
+            Ok(values.to_vec()),
+            bs58::decode(&encoded).into_u32_be_vec(),
+            "{values:?}"
+        );
+    }
+
+    // In network order whatever the platform
+    assert_eq!(Ok(vec![0x0001]), bs58::decode("12").into_u16_be_vec());
+    assert_eq!(
+        Ok(vec![0x0000_0102]),
+        bs58::decode("115T").into_u32_be_vec()
+    );
+
+    for (input, len) in [("2", 1), ("EUYUqQf", 5), ("111", 3)] {
+        assert_eq!(
+            Err(Error::WrongLength {
+                expected: len + 1,
+                found: len
+            }),
+            bs58::decode(input).into_u16_be_vec(),
+            "{input}"
+        );
+        assert_eq!(
+            Err(Error::WrongLength {
+                expected: len.next_multiple_of(4),
+                found: len
+            }),
+            bs58::decode(input).into_u32_be_vec(),
+            "{input}"
+        );
+    }
+    assert_eq!(
+        Err(Error::InvalidCharacter {
+            character: '0',
+            index: 1
+        }),
+        bs58::decode("10").into_u16_be_vec()
+    );
+}
+
+#[test]
+#[cfg(feature = "digest")]
+fn test_decode_to_digest() {
+    use sha2::{Digest, Sha256};
+
+    let large: Vec<u8> = (0..1000).map(|i| (i * 7 + i / 256) as u8).collect();
+    let large = bs58::encode(large).into_string();
+    let inputs = cases::TEST_CASES
+        .iter()
+        .map(|&(_, s)| s)
+        .chain([large.as_str()]);
+    for input in inputs {
+        let decoded = bs58::decode(input).into_vec().unwrap();
+        let mut hasher = Sha256::new();
+        assert_eq!(
+            Ok(decoded.len()),
+            bs58::decode(input).decode_to_digest(&mut hasher)
+        );
+        assert_eq!(Sha256::digest(&decoded), hasher.finalize(), "{input}");
+    }
+
+    // Hashing continues from what was already fed in
+    let mut hasher = Sha256::new();
+    hasher.update(b"prefix");
+    assert_eq!(Ok(5), bs58::decode("EUYUqQf").decode_to_digest(&mut hasher));
+    assert_eq!(Sha256::digest(b"prefixworld"), hasher.finalize());
+
+    // Nothing is fed in when decoding fails
+    let mut hasher = Sha256::new();
+    assert_eq!(
+        Err(bs58::decode::Error::InvalidCharacter {
+            character: '0',
+            index: 5
+        }),
+        bs58::decode("he11o0").decode_to_digest(&mut hasher)
+    );
+    assert_eq!(Sha256::digest(b""), hasher.finalize());
+
+    #[cfg(feature = "check")]
+    {
+        let encoded = bs58::encode(b"payload").with_check().into_string();
+        let mut hasher = Sha256::new();
+        assert_eq!(
+            Ok(7),
+            bs58::decode(&encoded)
+                .with_check(None)
+                .decode_to_digest(&mut hasher)
+        );
+        assert_eq!(Sha256::digest(b"payload"), hasher.finalize());
+    }
+}
+
+#[test]
+fn test_decode_allowed_lengths() {
+    use bs58::decode::Error;
+
+    let short = bs58::encode([1; 20]).into_string();
+    let long = bs58::encode([2; 32]).into_string();
+    let decode = |input: &str| bs58::decode(input).allowed_lengths(&[20, 32]).into_vec();
+
+    assert_eq!(Ok(vec![1; 20]), decode(&short));
+    assert_eq!(Ok(vec![2; 32]), decode(&long));
+    assert_eq!(
+        Err(Error::DisallowedLength {
+            allowed: &[20, 32],
+            found: 8
+        }),
+        decode("he11owor1d")
+    );
+    assert_eq!(
+        Err(Error::DisallowedLength {
+            allowed: &[20, 32],
+            found: 0
+        }),
+        decode("")
+    );
+
+    let mut output = [0; 32];
+    assert_eq!(
+        Ok(20),
+        bs58::decode(&short)
+            .allowed_lengths(&[20, 32])
+            .onto(&mut output[..])
+    );
+    assert_eq!(
+        Err(Error::DisallowedLength {
+            allowed: &[32],
+            found: 20
+        }),
+        bs58::decode(&short)
+            .allowed_lengths(&[32])
+            .onto(&mut output[..])
+    );
+
+    // An empty list rejects everything
+    assert_eq!(
+        Err(Error::DisallowedLength {
+            allowed: &[],
+            found: 20
+        }),
+        bs58::decode(&short).allowed_lengths(&[]).into_vec()
+    );
+
+    #[cfg(feature = "check")]
+    {
+        // The length is of the payload, after removing the checksum
+        let encoded = bs58::encode([3; 20]).with_check().into_string();
+        assert_eq!(
+            Ok(vec![3; 20]),
+            bs58::decode(&encoded)
+                .with_check(None)
+                .allowed_lengths(&[20, 32])
+                .into_vec()
+        );
+        assert_eq!(
+            Err(Error::DisallowedLength {
+                allowed: &[24],
+                found: 20
+            }),
+            bs58::decode(&encoded)
+                .with_check(None)
+                .allowed_lengths(&[24])
+                .into_vec()
+        );
+    }
+
+    let err = decode("he11owor1d").unwrap_err();
+    assert_eq!(
+        "decoded length 8 was not one of the allowed lengths [20, 32]",
+        err.to_string()
+    );
+    assert_eq!("disallowed_length", err.report().code.as_str());
+    assert_eq!(Some(8), err.report().found);
+    assert_eq!(Some(&[20, 32][..]), err.report().allowed);
+}
+
+#[test]
+fn test_decode_strip_salt() {
+    use bs58::decode::Error;
+
+    for &(val, _) in cases::TEST_CASES.iter() {
+        for salt in [&b""[..], b"\0", b"tenant-a"] {
+            let encoded = bs58::encode(val).with_salt(salt).into_string();
+            let decoded = bs58::decode(&encoded).strip_salt(salt).into_vec();
+            assert_eq!(Ok(val), decoded.as_deref(), "{encoded}");
+
+            let mut output = vec![0xff; encoded.len()];
+            let len = bs58::decode(&encoded)
+                .strip_salt(salt)
+                .onto(&mut output[..])
+                .unwrap();
+            assert_eq!(val, &output[..len], "{encoded}");
+        }
+    }
+
+    let a = bs58::encode(b"id").with_salt(b"tenant-a").into_string();
+    let b = bs58::encode(b"id").with_salt(b"tenant-b").into_string();
+    assert_ne!(a, b);
+    assert_eq!(
+        Err(Error::WrongSalt),
+        bs58::decode(&a).strip_salt(b"tenant-b").into_vec()
+    );
+    // Data shorter than the salt can't start with it
+    assert_eq!(
+        Err(Error::WrongSalt),
+        bs58::decode("2").strip_salt(b"\x01\x01").into_vec()
+    );
+
+    // Lengths include the salt
+    assert_eq!(
+        Ok(b"id".to_vec()),
+        bs58::decode(&a)
+            .with_expected_len(10)
+            .strip_salt(b"tenant-a")
+            .into_vec()
+    );
+
+    #[cfg(feature = "check")]
+    {
+        // The salt is covered by the checksum, after the version
+        let encoded = bs58::encode(b"id")
+            .with_salt(b"ns")
+            .with_check_version(42)
+            .into_string();
+        assert_eq!(
+            bs58::encode(b"nsid").with_check_version(42).into_string(),
+            encoded
+        );
+        assert_eq!(
+            Ok(b"*id".to_vec()),
+            bs58::decode(&encoded)
+                .with_check(Some(42))
+                .strip_salt(b"ns")
+                .into_vec()
+        );
+        assert_eq!(
+            Err(Error::WrongSalt),
+            bs58::decode(&encoded)
+                .with_check(Some(42))
+                .strip_salt(b"*n")
+                .into_vec()
+        );
+        let separated = bs58::encode(b"id")
+            .with_salt(b"ns")
+            .with_check_version(42)
+            .into_string_with_checksum_separator('-');
+        assert_eq!(
+            Ok(b"*id".to_vec()),
+            bs58::decode(&separated)
+                .with_check(Some(42))
+                .strip_salt(b"ns")
+                .into_vec_with_checksum_separator('-')
+        );
+    }
+
+    let err = bs58::decode(&a).strip_salt(b"x").into_vec().unwrap_err();
+    assert_eq!(
+        "decoded data did not start with the expected salt",
+        err.to_string()
+    );
+    assert_eq!("wrong_salt", err.report().code.as_str());
+}
+
+#[test]
+fn test_transcode() {
+    use bs58::{
+        decode::{transcode, Error},
+        Alphabet,
+    };
+
+    let alphabets = [
+        Alphabet::BITCOIN,
+        Alphabet::MONERO,
+        Alphabet::RIPPLE,
+        Alphabet::FLICKR,
+    ];
+    let large: Vec<u8> = (0..300).map(|i| (i * 7 + i / 256) as u8).collect();
+    let inputs = cases::TEST_CASES
+        .iter()
+        .map(|&(val, _)| val)
+        .chain([&large[..], &[0; 5]]);
+    for input in inputs {
+        for from in alphabets {
+            let encoded = bs58::encode(input).with_alphabet(from).into_string();
+            for to in alphabets {
+                // The same as decoding and re-encoding
+                let decoded = bs58::decode(&encoded)
+                    .with_alphabet(from)
+                    .into_vec()
+                    .unwrap();
+                let expected = bs58::encode(decoded).with_alphabet(to).into_string();
+                assert_eq!(Ok(expected), transcode(&encoded, from, to), "{encoded}");
+            }
+        }
+    }
+
+    assert_eq!(
+        Ok(String::new()),
+        transcode("", Alphabet::BITCOIN, Alphabet::RIPPLE)
+    );
+    assert_eq!(
+        Err(Error::InvalidCharacter {
+            character: 'l',
+            index: 2
+        }),
+        transcode("hel", Alphabet::RIPPLE, Alphabet::BITCOIN)
+    );
+    assert_eq!(
+        Err(Error::NonAsciiCharacter { index: 1 }),
+        transcode("1é", Alphabet::BITCOIN, Alphabet::RIPPLE)
+    );
+}
+
+#[test]
+fn test_decode_lenient_collect() {
+    use bs58::decode::Error;
+
+    let expected = vec![0x04, 0x30, 0x5e, 0x2b, 0x24, 0x73, 0xf0, 0x58];
+    assert_eq!(
+        Ok((expected.clone(), vec![])),
+        bs58::decode("he11owor1d").lenient_collect(0)
+    );
+
+    // Several invalid characters, including multi-byte ones, with indices in the original input
+    let input = "0he11éowIor1d!";
+    let errors = vec![
+        Error::InvalidCharacter {
+            character: '0',
+            index: 0,
+        },
+        Error::NonAsciiCharacter { index: 5 },
+        Error::InvalidCharacter {
+            character: 'I',
+            index: 9,
+        },
+        Error::InvalidCharacter {
+            character: '!',
+            index: 14,
+        },
+    ];
+    for max_errors in [4, 5, usize::MAX] {
+        assert_eq!(
+            Ok((expected.clone(), errors.clone())),
+            bs58::decode(input).lenient_collect(max_errors)
+        );
+    }
+    for (max_errors, &err) in errors.iter().enumerate() {
+        assert_eq!(Err(err), bs58::decode(input).lenient_collect(max_errors));
+    }
+
+    // A stray continuation byte is skipped on its own
+    assert_eq!(
+        Ok(vec![Error::NonAsciiCharacter { index: 3 }]),
+        bs58::decode(b"he1\x801owor1d")
+            .rtl()
+            .lenient_collect(1)
+            .map(|(_, errors)| errors)
+    );
+
+    // Read right-to-left the errors are in that order
+    let mut reversed = errors.clone();
+    reversed.reverse();
+    assert_eq!(
+        Ok((
+            bs58::decode("he11owor1d").rtl().into_vec().unwrap(),
+            reversed
+        )),
+        bs58::decode(input).rtl().lenient_collect(4)
+    );
+
+    // With a text prefix the indices include it
+    assert_eq!(
+        Ok((
+            expected.clone(),
+            vec![Error::InvalidCharacter {
+                character: '0',
+                index: 5
+            }]
+        )),
+        bs58::decode("id:he011owor1d")
+            .with_text_prefix("id:")
+            .lenient_collect(1)
+    );
+    assert_eq!(
+        Err(Error::WrongPrefix),
+        bs58::decode("he11owor1d")
+            .with_text_prefix("id:")
+            .lenient_collect(1)
+    );
+
+    // Other errors are returned as they are
+    assert_matches::assert_matches!(
+        bs58::decode("he11o0wor1d")
+            .with_expected_len(4)
+            .lenient_collect(1),
+        Err(Error::WrongLength { expected: 4, .. })
+    );
+    #[cfg(feature = "check")]
+    {
+        let encoded = bs58::encode(&expected).with_check().into_string();
+        let corrupted = format!("0{encoded}0");
+        assert_eq!(
+            Ok(expected.clone()),
+            bs58::decode(&corrupted)
+                .with_check(None)
+                .lenient_collect(2)
+                .map(|(output, _)| output)
+        );
+        assert_matches!(
+            bs58::decode(&corrupted[..corrupted.len() - 2])
+                .with_check(None)
+                .lenient_collect(2),
+            Err(Error::InvalidChecksum { .. })
+        );
+    }
+}
+
+#[test]
+fn test_decode_bit_length() {
+    use bs58::{
+        decode::{bit_length, Error},
+        Alphabet,
+    };
+
+    for bits in 1..=300u32 {
+        // The smallest and largest values with `bits` significant bits
+        let len = bits.div_ceil(8) as usize;
+        let mut low = vec![0; len];
+        low[0] = 1 << ((bits - 1) % 8);
+        let mut high = vec![0xff; len];
+        high[0] >>= (8 - bits % 8) % 8;
+        for value in [low, high] {
+            let encoded = bs58::encode(&value).into_string();
+            assert_eq!(
+                Ok(u64::from(bits)),
+                bit_length(&encoded, Alphabet::BITCOIN),
+                "{encoded}"
+            );
+            let padded = format!("11{encoded}");
+            assert_eq!(
+                Ok(u64::from(bits)),
+                bit_length(&padded, Alphabet::BITCOIN),
+                "{padded}"
+            );
+        }
+    }
+
+    assert_eq!(Ok(0), bit_length("", Alphabet::BITCOIN));
+    assert_eq!(Ok(0), bit_length("1111", Alphabet::BITCOIN));
+    assert_eq!(Ok(0), bit_length("rrr", Alphabet::RIPPLE));
+    assert_eq!(Ok(1), bit_length("rp", Alphabet::RIPPLE));
+
+    // Errors index into the whole input
+    assert_eq!(
+        Err(Error::InvalidCharacter {
+            character: '0',
+            index: 3
+        }),
+        bit_length("112013", Alphabet::BITCOIN)
+    );
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_decode_from_bufread() {
+    use bs58::decode::Error;
+    use std::io::{self, BufReader, Cursor, Read};
+
+    let input = "he11owor1d\r\n\n2g\nhe11o0\n\r\n1112";
+    let results: Vec<_> = bs58::decode::from_bufread(Cursor::new(input))
+        .map(Result::unwrap)
+        .collect();
+    assert_eq!(
+        vec![
+            Ok(vec![0x04, 0x30, 0x5e, 0x2b, 0x24, 0x73, 0xf0, 0x58]),
+            Ok(vec![]),
+            bs58::decode("2g").into_vec(),
+            Err(Error::InvalidCharacter {
+                character: '0',
+                index: 5
+            }),
+            Ok(vec![]),
+            Ok(vec![0, 0, 0, 1]),
+        ],
+        results
+    );
+    assert_eq!(0, bs58::decode::from_bufread(Cursor::new("")).count());
+
+    // Non-UTF-8 lines are decode errors, not I/O errors
+    let mut lines = bs58::decode::from_bufread(&b"2g\xff\n"[..]);
+    assert_eq!(
+        Some(Err(Error::NonAsciiCharacter { index: 2 })),
+        lines.next().map(Result::unwrap)
+    );
+    assert!(lines.next().is_none());
+
+    /// Gives `data` in reads of at most `step` bytes, failing once after the first `fail_at`.
+    struct FailOnce<'a> {
+        data: &'a [u8],
+        step: usize,
+        fail_at: Option<usize>,
+    }
+
+    impl Read for FailOnce<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            let mut len = buf.len().min(self.data.len()).min(self.step);
+            if let Some(fail_at) = self.fail_at {
+                if fail_at == 0 {
+                    self.fail_at = None;
+                    return Err(io::Error::other("injected"));
+                }
+                len = len.min(fail_at);
+                self.fail_at = Some(fail_at - len);
+            }
+            buf[..len].copy_from_slice(&self.data[..len]);
+            self.data = &self.data[len..];
+            Ok(len)
+        }
+    }
+
+    let data = b"he11owor1d\n2g\nhe11o0";
+    let reader = |fail_at| {
+        BufReader::with_capacity(
+            4,
+            FailOnce {
+                data,
+                step: 3,
+                fail_at: Some(fail_at),
+            },
+        )
+    };
+
+    // Failing between lines loses nothing
+    let results: Vec<_> = bs58::decode::from_bufread(reader(11)).collect();
+    assert_eq!(4, results.len());
+    assert_eq!(
+        Ok(vec![0x04, 0x30, 0x5e, 0x2b, 0x24, 0x73, 0xf0, 0x58]),
+        *results[0].as_ref().unwrap()
+    );
+    let err = results[1].as_ref().unwrap_err();
+    assert_eq!(io::ErrorKind::Other, err.kind());
+    assert_eq!("injected", err.to_string());
+    assert_eq!(bs58::decode("2g").into_vec(), *results[2].as_ref().unwrap());
+    assert_eq!(
+        Err(Error::InvalidCharacter {
+            character: '0',
+            index: 5
+        }),
+        *results[3].as_ref().unwrap()
+    );
+
+    // Failing part way through a line loses the part read before it
+    let results: Vec<_> = bs58::decode::from_bufread(reader(5))
+        .map(|result| result.map_err(|err| err.to_string()))
+        .collect();
+    assert_eq!(
+        vec![
+            Err("injected".to_string()),
+            Ok(bs58::decode("wor1d").into_vec()),
+            Ok(bs58::decode("2g").into_vec()),
+            Ok(Err(Error::InvalidCharacter {
+                character: '0',
+                index: 5
+            })),
+        ],
+        results
+    );
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn test_timestamp_millis_roundtrip() {
+    use bs58::decode::{to_timestamp_millis, Error};
+    use bs58::encode::from_timestamp_millis;
+
+    let mut values = vec![
+        0,
+        1,
+        57,
+        58,
+        255,
+        256,
+        1_700_000_000_000,
+        u64::MAX - 1,
+        u64::MAX,
+    ];
+    values.extend((0..64).map(|shift| 1 << shift));
+    values.extend((1..64).map(|shift| (1 << shift) - 1));
+    for ms in values {
+        let encoded = from_timestamp_millis(ms).into_string();
+        assert_eq!(Ok(ms), to_timestamp_millis(&encoded), "{ms}");
+        // Minimal bytes, so no leading zero characters
+        assert!(!encoded.starts_with('1'), "{ms} {encoded}");
+        let bytes = ms.to_be_bytes();
+        let zeros = bytes.iter().take_while(|&&b| b == 0).count();
+        assert_eq!(bs58::encode(&bytes[zeros..]).into_string(), encoded, "{ms}");
+    }
+    assert_eq!("", from_timestamp_millis(0).into_string());
+
+    // Leading zero bytes are accepted
+    assert_eq!(Ok(58), to_timestamp_millis("11121"));
+    assert_eq!(Ok(u64::MAX), to_timestamp_millis("jpXCZedGfVQ"));
+    assert_eq!(Err(Error::Overflow), to_timestamp_millis("jpXCZedGfVR"));
+    assert_eq!(Err(Error::Overflow), to_timestamp_millis("111111111"));
+    assert_eq!(
+        Err(Error::InvalidCharacter {
+            character: '0',
+            index: 1
+        }),
+        to_timestamp_millis("20")
+    );
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn test_decode_order_preserving() {
+    use bs58::decode::Error;
+
+    let mut output = [0xaa; 8];
+    assert_eq!(
+        Ok(4),
+        bs58::decode("111115R")
+            .order_preserving(4)
+            .onto(&mut output[..])
+    );
+    assert_eq!([0, 0, 1, 0], output[..4]);
+    assert_eq!(
+        Ok(4),
+        bs58::decode("111115R")
+            .order_preserving(4)
+            .onto(&mut output[..4])
+    );
+    assert_eq!(
+        Err(Error::InvalidCharacter {
+            character: '0',
+            index: 5
+        }),
+        bs58::decode("z:1110")
+            .with_text_prefix("z:")
+            .order_preserving(4)
+            .into_vec()
+    );
+    assert_eq!(
+        Ok(vec![0, 0, 1, 0]),
+        bs58::decode("5R").order_preserving(4).into_vec()
+    );
+    assert_eq!(
+        Ok(vec![0; 3]),
+        bs58::decode("").order_preserving(3).into_vec()
+    );
+    assert_eq!(
+        Ok(vec![0; 3]),
+        bs58::decode("1111").order_preserving(3).into_vec()
+    );
+    assert_eq!(
+        Ok(vec![]),
+        bs58::decode("111").order_preserving(0).into_vec()
+    );
+    assert_eq!(
+        Ok(vec![0xff; 2]),
+        bs58::decode("LUv").order_preserving(2).into_vec()
+    );
+    assert_eq!(
+        Err(Error::Overflow),
+        bs58::decode("LUw").order_preserving(2).into_vec()
+    );
+    assert_eq!(
+        Err(Error::BufferTooSmall),
+        bs58::decode("5R")
+            .order_preserving(4)
+            .onto(&mut output[..3])
+    );
+    assert_eq!(
+        Err(Error::InvalidCharacter {
+            character: '0',
+            index: 3
+        }),
+        bs58::decode("1110").order_preserving(4).into_vec()
+    );
+    assert_eq!(
+        Ok(vec![0, 0, 1, 0]),
+        bs58::decode("111115R")
+            .order_preserving(4)
+            .with_expected_len(4)
+            .into_vec()
+    );
+}
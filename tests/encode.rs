@@ -38,6 +38,33 @@ fn test_encode() {
             assert_eq!((PREFIX, s.as_bytes()), vec.split_at(3));
         }
 
+        {
+            let mut buffer = bs58::Buffer::<36>::new();
+            let res = bs58::encode(val).onto(&mut buffer);
+            if s.len() <= buffer.capacity() {
+                assert_eq!(Ok(s.len()), res);
+                assert_eq!(Some(s), buffer.as_str());
+
+                let res = bs58::encode(val).onto(&mut buffer);
+                if 2 * s.len() <= buffer.capacity() {
+                    assert_eq!(Ok(s.len()), res);
+                    assert_eq!((s.as_bytes(), s.as_bytes()), buffer.split_at(s.len()));
+                } else {
+                    assert_eq!(Err(bs58::encode::Error::BufferTooSmall), res);
+                    assert_eq!(Some(s), buffer.as_str());
+                }
+            } else {
+                assert_eq!(Err(bs58::encode::Error::BufferTooSmall), res);
+                assert!(buffer.is_empty());
+            }
+        }
+
+        {
+            let mut buffer = bs58::Buffer::<{ bs58::max_encoded_len(256) }>::new();
+            assert_eq!(Ok(s.len()), bs58::encode(val).onto(&mut buffer));
+            assert_eq!(Some(s), buffer.as_str());
+        }
+
         #[cfg(feature = "smallvec")]
         {
             let mut vec = smallvec::SmallVec::<[u8; 36]>::from(PREFIX);
@@ -135,7 +162,7 @@ fn append() {
 /// Verify that encode_into doesn’t try to write over provided buffer.
 #[test]
 fn test_buffer_too_small() {
-    let mut output = [0u8; 256];
+    let mut output = [0u8; 512];
     for &(val, s) in cases::TEST_CASES.iter() {
         let expected_len = s.len();
         if expected_len > 0 {
@@ -181,3 +208,868 @@ fn encode_stress_test() {
             .into_string();
     }
 }
+
+#[test]
+#[cfg(feature = "uuid")]
+fn test_encode_uuid() {
+    let uuid = uuid::Uuid::parse_str("67e55044-10b1-426f-9247-bb680e5fe0c8").unwrap();
+    assert_eq!(
+        "Dq7QdGPZBdz9vwjm3jLQSB",
+        bs58::encode::from_uuid(uuid).into_string()
+    );
+
+    let uuid = uuid::Uuid::parse_str("00e55044-10b1-426f-9247-bb680e5fe0c8").unwrap();
+    let encoded = bs58::encode::from_uuid(uuid).into_string();
+    assert_eq!("17R6gfUnt6jLeH7Bno7DMZ", encoded);
+    assert_eq!(Ok(uuid), bs58::decode::to_uuid(encoded));
+
+    assert_eq!(
+        "1111111111111111",
+        bs58::encode::from_uuid(uuid::Uuid::nil()).into_string()
+    );
+}
+
+#[test]
+fn test_encode_alphabets() {
+    for (alpha, cases) in [
+        (bs58::Alphabet::RIPPLE, cases::RIPPLE_TEST_CASES),
+        (bs58::Alphabet::FLICKR, cases::FLICKR_TEST_CASES),
+    ] {
+        for &(val, s) in cases {
+            assert_eq!(s, bs58::encode(val).with_alphabet(alpha).into_string());
+            #[cfg(feature = "test-util")]
+            bs58::testing::assert_roundtrip(val, alpha);
+        }
+    }
+}
+
+#[test]
+fn test_expansion_estimate() {
+    // Deterministic xorshift generator so the "random" inputs are reproducible
+    let mut state = 0x2545_f491_4f6c_dd1du64;
+    let mut next = move || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state
+    };
+
+    for len in 1..=256 {
+        let estimate = (bs58::encode::expansion_estimate(len) * len as f64).round();
+        for _ in 0..8 {
+            let mut input: Vec<u8> = (0..len).map(|_| next() as u8).collect();
+            input[0] |= 0x80;
+            let encoded = bs58::encode(&input).into_string().len() as f64;
+            assert!(
+                encoded <= estimate && encoded >= estimate - 1.0,
+                "len {}: estimated {} but encoded to {}",
+                len,
+                estimate,
+                encoded,
+            );
+        }
+    }
+
+    assert!((bs58::encode::expansion_estimate(0) - 1.37).abs() < 0.01);
+    assert!((bs58::encode::expansion_estimate(1_000_000) - 1.37).abs() < 0.01);
+}
+
+#[test]
+fn test_encode_from_fn() {
+    for &(val, s) in cases::TEST_CASES.iter() {
+        assert_eq!(
+            s,
+            bs58::encode::from_fn(val.len(), |i| val[i]).into_string()
+        );
+        assert_eq!(
+            bs58::encode(val).into_vec(),
+            bs58::encode::from_fn(val.len(), |i| val[i]).into_vec()
+        );
+
+        let mut output = [0; 512];
+        assert_eq!(
+            Ok(s.len()),
+            bs58::encode::from_fn(val.len(), |i| val[i]).onto(&mut output[..])
+        );
+        assert_eq!(s.as_bytes(), &output[..s.len()]);
+    }
+
+    // Computed bytes, never materialized
+    let computed: Vec<u8> = (0..100).map(|i| (i * 7 + 3) as u8).collect();
+    assert_eq!(
+        bs58::encode(&computed)
+            .with_alphabet(bs58::Alphabet::FLICKR)
+            .into_string(),
+        bs58::encode::from_fn(100, |i| (i * 7 + 3) as u8)
+            .with_alphabet(bs58::Alphabet::FLICKR)
+            .into_string()
+    );
+
+    // Each index is only needed again while scanning for leading zeros
+    let calls = std::cell::Cell::new(0);
+    let input = [0, 0, 1, 0, 2];
+    let encoded = bs58::encode::from_fn(input.len(), |i| {
+        calls.set(calls.get() + 1);
+        input[i]
+    })
+    .into_string();
+    assert_eq!(bs58::encode(input).into_string(), encoded);
+    assert_eq!(input.len() + 3, calls.get());
+}
+
+#[test]
+fn test_shuffled_alphabet() {
+    let shuffled = bs58::Alphabet::shuffled(bs58::Alphabet::DEFAULT, 0);
+    assert_eq!(
+        bs58::Alphabet::new(b"J5fEp4kZLWTexRY96ciUaPX8uK3CqVAbMG1sH2BDdgyo7nNShtmvjzFQwr").unwrap(),
+        shuffled
+    );
+    assert_eq!(
+        bs58::Alphabet::new(b"n4KdSFQ2ARCH3vcf7s1VXLMW9GexwEtNUk5aiPqzgJYZBTopD6mrhuyjb8").unwrap(),
+        bs58::Alphabet::shuffled(bs58::Alphabet::DEFAULT, 42)
+    );
+
+    for &(val, _) in cases::TEST_CASES.iter() {
+        let encoded = bs58::encode(val).with_alphabet(&shuffled).into_string();
+        assert_eq!(
+            val,
+            bs58::decode(encoded)
+                .with_alphabet(&shuffled)
+                .into_vec()
+                .unwrap()
+        );
+    }
+
+    let alphabets: Vec<_> = (0..32)
+        .map(|seed| bs58::Alphabet::shuffled(bs58::Alphabet::DEFAULT, seed))
+        .collect();
+    for (i, a) in alphabets.iter().enumerate() {
+        assert_ne!(bs58::Alphabet::DEFAULT, a);
+        for b in &alphabets[i + 1..] {
+            assert_ne!(a, b);
+        }
+    }
+
+    // Shuffling gives a permutation of the base alphabet's characters
+    let characters = |alpha| {
+        let mut characters: Vec<String> = (1..58u8)
+            .map(|i| bs58::encode([i]).with_alphabet(alpha).into_string())
+            .collect();
+        characters.push(bs58::encode([0]).with_alphabet(alpha).into_string());
+        characters.sort();
+        characters
+    };
+    assert_eq!(characters(bs58::Alphabet::DEFAULT), characters(&shuffled));
+}
+
+#[test]
+fn test_encode_all_zero() {
+    for len in [0, 1, 2, 31, 32, 256, 1024] {
+        let zeros = vec![0x00; len];
+        for alpha in [bs58::Alphabet::BITCOIN, bs58::Alphabet::RIPPLE] {
+            let general = bs58::encode::from_fn(len, |_| 0x00)
+                .with_alphabet(alpha)
+                .into_string();
+            assert_eq!(
+                general,
+                bs58::encode(&zeros).with_alphabet(alpha).into_string()
+            );
+            assert_eq!(len, general.len());
+        }
+
+        if len > 0 {
+            let mut output = vec![0; len - 1];
+            assert_eq!(
+                Err(bs58::encode::Error::BufferTooSmall),
+                bs58::encode(&zeros).onto(&mut output[..])
+            );
+        }
+    }
+}
+
+#[test]
+fn test_encode_into_at() {
+    for &(val, s) in cases::TEST_CASES.iter() {
+        let mut bytes = FILLER;
+        assert_eq!(Ok(s.len()), bs58::encode(val).into_at(&mut bytes, 100));
+        assert_eq!(FILLER[..100], bytes[..100]);
+        assert_eq!(s.as_bytes(), &bytes[100..][..s.len()]);
+        assert_eq!(FILLER[100 + s.len()..], bytes[100 + s.len()..]);
+    }
+
+    let input = [0x04, 0x30, 0x5e, 0x2b, 0x24, 0x73, 0xf0, 0x58];
+    for offset in [FILLER.len() - 10, FILLER.len() + 1] {
+        let mut bytes = FILLER;
+        assert_eq!(
+            Err(bs58::encode::Error::BufferTooSmall),
+            bs58::encode(input).into_at(&mut bytes, offset)
+        );
+        assert_eq!(FILLER, bytes);
+    }
+}
+
+#[test]
+fn test_encode_config() {
+    use bs58::{decode::DecodeConfig, encode::EncodeConfig, Alphabet};
+
+    const ENCODER: EncodeConfig = EncodeConfig::new(Alphabet::RIPPLE).with_text_prefix("r:");
+    const DECODER: DecodeConfig = DecodeConfig::new(Alphabet::RIPPLE).with_text_prefix("r:");
+
+    for &(val, _) in cases::TEST_CASES.iter() {
+        let expected = bs58::encode(val)
+            .with_alphabet(Alphabet::RIPPLE)
+            .with_text_prefix("r:")
+            .into_string();
+        assert_eq!(expected, ENCODER.encode(val));
+        assert_eq!(expected, ENCODER.with_input(val).into_string());
+        assert_eq!(Ok(val.to_vec()), DECODER.decode(&expected));
+    }
+
+    // The default configuration matches the free functions
+    let input = [0x04, 0x30, 0x5e, 0x2b, 0x24, 0x73, 0xf0, 0x58];
+    assert_eq!(
+        bs58::encode(input).into_string(),
+        EncodeConfig::new(Alphabet::DEFAULT).encode(input)
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_encode_cidv0_wrong_header() {
+    let mut multihash = [0; 34];
+    multihash[..2].copy_from_slice(&[0x12, 0x1f]);
+    bs58::encode::cidv0(&multihash);
+}
+
+#[test]
+fn test_encode_max_input() {
+    let input = [0x04, 0x30, 0x5e, 0x2b, 0x24, 0x73, 0xf0, 0x58];
+    let too_large = Err(bs58::encode::Error::InputTooLarge {
+        limit: 7,
+        actual: 8,
+    });
+
+    assert_eq!(
+        "he11owor1d",
+        bs58::encode(input).with_max_input(8).into_string()
+    );
+
+    let mut bytes = FILLER;
+    assert_eq!(
+        too_large,
+        bs58::encode(input).with_max_input(7).onto(&mut bytes[..])
+    );
+    assert_eq!(
+        too_large,
+        bs58::encode(input).with_max_input(7).into_at(&mut bytes, 0)
+    );
+    assert_eq!(FILLER, bytes);
+}
+
+#[test]
+#[cfg(feature = "check")]
+fn test_encode_max_input_check() {
+    let input = [0x04, 0x30, 0x5e, 0x2b, 0x24, 0x73, 0xf0, 0x58];
+
+    // The version and checksum bytes don't count towards the limit
+    assert_eq!(
+        Ok(bs58::encode(input).with_check_version(1).into_string()),
+        bs58::encode(input)
+            .with_check_version(1)
+            .with_max_input(8)
+            .try_into_string()
+    );
+    assert_eq!(
+        Err(bs58::encode::Error::InputTooLarge {
+            limit: 7,
+            actual: 8
+        }),
+        bs58::encode(input)
+            .with_check_version(1)
+            .with_max_input(7)
+            .try_into_string()
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_encode_max_input_into_string() {
+    bs58::encode([0; 8]).with_max_input(7).into_string();
+}
+
+#[test]
+fn test_static_alphabet() {
+    bs58::static_alphabet!(Flickr = b"123456789abcdefghijkmnopqrstuvwxyzABCDEFGHJKLMNPQRSTUVWXYZ");
+
+    for &(val, _) in cases::TEST_CASES.iter() {
+        let encoded = bs58::encode(val)
+            .with_alphabet(bs58::Alphabet::FLICKR)
+            .into_string();
+        assert_eq!(
+            encoded,
+            bs58::encode(val)
+                .with_static_alphabet::<Flickr>()
+                .into_string()
+        );
+        assert_eq!(
+            Ok(val.to_vec()),
+            bs58::decode(&encoded)
+                .with_static_alphabet::<Flickr>()
+                .into_vec()
+        );
+    }
+}
+
+#[test]
+fn test_encode_starts_with() {
+    for &(val, s) in cases::TEST_CASES.iter() {
+        for len in 0..=s.len() {
+            assert_eq!(Ok(true), bs58::encode(val).starts_with(&s[..len]));
+        }
+        assert_eq!(Ok(false), bs58::encode(val).starts_with(&format!("{s}1")));
+        assert_eq!(Ok(false), bs58::encode(val).starts_with(&format!("1{s}")));
+    }
+
+    let input = [0x00, 0x00, 0x04, 0x30, 0x5e, 0x2b, 0x24, 0x73, 0xf0, 0x58];
+    for (prefix, expected) in [
+        ("11he11o", true),
+        ("11", true),
+        ("1", true),
+        ("111", false),
+        ("1he", false),
+        ("11hello", false),
+        ("11he0", false),
+    ] {
+        assert_eq!(
+            Ok(expected),
+            bs58::encode(input).starts_with(prefix),
+            "{prefix}"
+        );
+    }
+
+    let builder = || bs58::encode(&input[2..]).with_text_prefix("id:");
+    assert_eq!(Ok(true), builder().starts_with("id"));
+    assert_eq!(Ok(true), builder().starts_with("id:he11o"));
+    assert_eq!(Ok(false), builder().starts_with("he11o"));
+
+    // Long enough to need an allocated buffer
+    let input = [0xff; 200];
+    let encoded = bs58::encode(input).into_string();
+    assert_eq!(Ok(true), bs58::encode(input).starts_with(&encoded));
+    assert_eq!(
+        Ok(false),
+        bs58::encode(input).starts_with(&encoded.replace('5', "6"))
+    );
+}
+
+#[test]
+fn test_encode_into_segments() {
+    for &(val, s) in cases::TEST_CASES.iter() {
+        for segment_len in [1, 7, 64] {
+            let mut buffer = FILLER;
+            let mut segments: Vec<&mut [u8]> = buffer.chunks_mut(segment_len).collect();
+            assert_eq!(Ok(s.len()), bs58::encode(val).into_segments(&mut segments));
+            assert_eq!(s.as_bytes(), &buffer[..s.len()]);
+            assert_eq!(FILLER[s.len()..], buffer[s.len()..]);
+
+            if !s.is_empty() {
+                let mut buffer = FILLER;
+                let mut segments: Vec<&mut [u8]> =
+                    buffer[..s.len() - 1].chunks_mut(segment_len).collect();
+                assert_eq!(
+                    Err(bs58::encode::Error::BufferTooSmall),
+                    bs58::encode(val).into_segments(&mut segments)
+                );
+                assert_eq!(FILLER, buffer);
+            }
+        }
+    }
+}
+
+#[test]
+#[cfg(feature = "sha2")]
+fn test_encode_into_string_with_hash() {
+    use sha2::{Digest, Sha256};
+
+    for &(val, s) in cases::TEST_CASES.iter() {
+        let (encoded, hash) = bs58::encode(val).into_string_with_hash();
+        assert_eq!(s, encoded);
+        assert_eq!(<[u8; 32]>::from(Sha256::digest(s.as_bytes())), hash);
+    }
+
+    let (encoded, hash) = bs58::encode([0x04, 0x30, 0x5e])
+        .with_text_prefix("z")
+        .into_string_with_hash();
+    assert_eq!(<[u8; 32]>::from(Sha256::digest(encoded.as_bytes())), hash);
+}
+
+#[test]
+fn test_encode_join() {
+    let items: Vec<&[u8]> = cases::TEST_CASES.iter().map(|&(val, _)| val).collect();
+    let expected: Vec<&str> = cases::TEST_CASES.iter().map(|&(_, s)| s).collect();
+    assert_eq!(expected.join(", "), bs58::encode_join(&items, ", "));
+
+    let mut output = String::from("keys=");
+    bs58::encode_join_to(&items, ";", &mut output).unwrap();
+    assert_eq!(format!("keys={}", expected.join(";")), output);
+
+    assert_eq!("", bs58::encode_join(Vec::<Vec<u8>>::new(), ","));
+    assert_eq!(
+        "he11owor1d",
+        bs58::encode_join([[0x04, 0x30, 0x5e, 0x2b, 0x24, 0x73, 0xf0, 0x58]], ",")
+    );
+    assert_eq!("-", bs58::encode_join([[0u8; 0]; 2], "-"));
+
+    let encoder = bs58::encode::EncodeConfig::new(bs58::Alphabet::FLICKR).with_text_prefix("f:");
+    assert_eq!(
+        items
+            .iter()
+            .map(|item| encoder.with_input(item).into_string())
+            .collect::<Vec<_>>()
+            .join(" "),
+        encoder.join(&items, " ")
+    );
+}
+
+#[test]
+#[should_panic(expected = "separator \"x\" must not contain characters of the alphabet")]
+fn test_encode_join_separator_in_alphabet() {
+    bs58::encode_join([b"hello"], "x");
+}
+
+#[test]
+#[should_panic(expected = "separator must not be empty")]
+fn test_encode_join_empty_separator() {
+    bs58::encode_join([b"hello"], "");
+}
+
+#[test]
+#[should_panic(expected = "must not appear in the text prefix")]
+fn test_encode_join_separator_in_prefix() {
+    bs58::encode::EncodeConfig::new(bs58::Alphabet::DEFAULT)
+        .with_text_prefix("a:")
+        .join([b"hello"], ":");
+}
+
+#[test]
+fn test_encode_error_into_io_error() {
+    use std::io::ErrorKind;
+
+    fn encode(input: &[u8], output: &mut [u8]) -> std::io::Result<usize> {
+        Ok(bs58::encode(input).with_max_input(8).onto(output)?)
+    }
+
+    let err = encode(&[0x04, 0x30, 0x5e], &mut [0; 2]).unwrap_err();
+    assert_eq!(ErrorKind::WriteZero, err.kind());
+    assert_eq!(
+        Some(&bs58::encode::Error::BufferTooSmall),
+        err.get_ref().and_then(|err| err.downcast_ref())
+    );
+
+    let err = encode(&[0; 9], &mut [0; 32]).unwrap_err();
+    assert_eq!(ErrorKind::InvalidInput, err.kind());
+    assert_eq!(
+        bs58::encode::Error::InputTooLarge {
+            limit: 8,
+            actual: 9
+        }
+        .to_string(),
+        err.to_string()
+    );
+
+    let err = std::io::Error::from(bs58::encode::Error::AllocationFailed);
+    assert_eq!(ErrorKind::OutOfMemory, err.kind());
+}
+
+#[test]
+fn test_encode_into_string_with_capacity() {
+    for &(val, s) in cases::TEST_CASES.iter() {
+        for extra in [0, 1, 7, 100] {
+            let output = bs58::encode(val).into_string_with_capacity(extra);
+            assert_eq!(s, output);
+            assert!(output.capacity() >= s.len() + extra, "{s:?} {extra}");
+        }
+    }
+
+    let output = bs58::encode([0xff; 8])
+        .with_text_prefix("id:")
+        .into_string_with_capacity(4);
+    assert_eq!("id:jpXCZedGfVQ", output);
+    assert!(output.capacity() >= output.len() + 4);
+}
+
+#[tokio::test]
+#[cfg(feature = "tokio")]
+async fn test_encode_write_async() {
+    for &(val, s) in cases::TEST_CASES.iter() {
+        let mut output = Vec::new();
+        bs58::encode::write_async(val, &mut output).await.unwrap();
+        assert_eq!(s.as_bytes(), output.as_slice());
+    }
+
+    // Appends to what has already been written
+    let mut output = b"id:".to_vec();
+    bs58::encode::write_async(b"hello world", &mut output)
+        .await
+        .unwrap();
+    assert_eq!(b"id:StV1DL6CwTryKyV", output.as_slice());
+
+    let mut buf = [0; 5];
+    let mut cursor = std::io::Cursor::new(&mut buf[..]);
+    let err = bs58::encode::write_async(b"hello world", &mut cursor)
+        .await
+        .unwrap_err();
+    assert_eq!(std::io::ErrorKind::WriteZero, err.kind());
+    assert_eq!(b"StV1D", &buf);
+}
+
+#[test]
+#[cfg(feature = "unicode-normalization")]
+fn test_encode_normalize_prefix_nfc() {
+    let input = [0x04, 0x30, 0x5e, 0x2b, 0x24, 0x73, 0xf0, 0x58];
+    // "é" as a single code point and as "e" followed by a combining acute accent, and a Hangul
+    // syllable as a single code point and as its two jamo
+    let prefixes = [
+        ("caf\u{e9}:", "cafe\u{301}:"),
+        ("\u{ac00}_", "\u{1100}\u{1161}_"),
+    ];
+    for (composed, decomposed) in prefixes {
+        assert_ne!(composed, decomposed);
+        let expected = format!("{composed}he11owor1d");
+
+        for prefix in [composed, decomposed] {
+            let builder = || {
+                bs58::encode(input)
+                    .with_text_prefix(prefix)
+                    .normalize_prefix_nfc()
+            };
+            assert_eq!(expected, builder().into_string(), "{prefix:?}");
+            assert_eq!(expected.as_bytes(), builder().into_vec(), "{prefix:?}");
+
+            let mut output = [0; 32];
+            let len = builder().onto(&mut output[..]).unwrap();
+            assert_eq!(expected.as_bytes(), &output[..len], "{prefix:?}");
+            assert_eq!(
+                Err(bs58::encode::Error::BufferTooSmall),
+                builder().onto(&mut output[..expected.len() - 1]),
+                "{prefix:?}"
+            );
+
+            assert_eq!(Ok(true), builder().starts_with(composed), "{prefix:?}");
+            assert_eq!(Ok(true), builder().starts_with(&expected), "{prefix:?}");
+            assert_eq!(Ok(false), builder().starts_with(decomposed), "{prefix:?}");
+        }
+
+        // Without normalizing the prefix is kept as it is
+        assert_eq!(
+            format!("{decomposed}he11owor1d"),
+            bs58::encode(input)
+                .with_text_prefix(decomposed)
+                .into_string()
+        );
+    }
+
+    // Only the HRP is normalized, not the separator
+    assert_eq!(
+        "caf\u{e9}\u{301}he11owor1d",
+        bs58::encode(input)
+            .with_hrp("cafe\u{301}", '\u{301}')
+            .normalize_prefix_nfc()
+            .into_string()
+    );
+    assert_eq!(
+        "id:he11owor1d",
+        bs58::encode(input)
+            .with_text_prefix("id:")
+            .normalize_prefix_nfc()
+            .into_string()
+    );
+}
+
+#[test]
+fn test_encode_analyze() {
+    let large: Vec<u8> = (0..500).map(|i| (i * 7 + i / 256) as u8).collect();
+    let inputs = cases::TEST_CASES
+        .iter()
+        .map(|&(val, _)| val)
+        .chain([&large[..], &[0; 200]]);
+    for input in inputs {
+        let stats = bs58::encode(input).analyze();
+        let encoded = bs58::encode(input).into_string();
+        assert_eq!(input.len(), stats.input_len);
+        assert_eq!(encoded.len(), stats.output_len, "{input:?}");
+        assert_eq!(
+            encoded.bytes().take_while(|&c| c == b'1').count(),
+            stats.leading_zero_bytes,
+            "{input:?}"
+        );
+        if !input.is_empty() {
+            assert_eq!(
+                encoded.len() as f64 / input.len() as f64,
+                stats.expansion_ratio
+            );
+        }
+
+        let builder = bs58::encode(input).with_text_prefix("id:");
+        assert_eq!(
+            builder.analyze().output_len,
+            builder.into_string().len(),
+            "{input:?}"
+        );
+    }
+
+    let stats = bs58::encode([]).analyze();
+    assert_eq!(
+        (0, 0, 0),
+        (stats.input_len, stats.output_len, stats.leading_zero_bytes)
+    );
+    assert_eq!(0.0, stats.expansion_ratio);
+
+    #[cfg(feature = "check")]
+    {
+        let builder = bs58::encode([0x00, 0x2d, 0x31]).with_check_version(0x01);
+        let stats = builder.analyze();
+        assert_eq!(builder.into_string().len(), stats.output_len);
+        assert_eq!(3, stats.input_len);
+        assert_eq!(1, stats.leading_zero_bytes);
+    }
+}
+
+#[test]
+#[should_panic(expected = "InputTooLarge")]
+fn test_encode_analyze_too_large() {
+    bs58::encode([0; 10]).with_max_input(9).analyze();
+}
+
+#[test]
+fn test_encode_charset_usage() {
+    use bs58::Alphabet;
+
+    let input = [0x04, 0x30, 0x5e, 0x2b, 0x24, 0x73, 0xf0, 0x58];
+    let used: Vec<u8> = bs58::encode::charset_usage(&input, Alphabet::BITCOIN)
+        .iter()
+        .zip(Alphabet::BITCOIN.encode_table())
+        .filter_map(|(&used, &c)| used.then_some(c))
+        .collect();
+    assert_eq!(b"1dehorw", &used[..]);
+
+    // Usage is of the encoded characters, which depend on the alphabet
+    let usage = bs58::encode::charset_usage(&input, Alphabet::FLICKR);
+    for (i, &c) in Alphabet::FLICKR.encode_table().iter().enumerate() {
+        let encoded = bs58::encode(input)
+            .with_alphabet(Alphabet::FLICKR)
+            .into_string();
+        assert_eq!(encoded.contains(c as char), usage[i], "{:?}", c as char);
+    }
+
+    assert_eq!(
+        [false; 58],
+        bs58::encode::charset_usage(&[], Alphabet::BITCOIN)
+    );
+    let zeros = bs58::encode::charset_usage(&[0; 200], Alphabet::BITCOIN);
+    assert_eq!(vec![0], (0..58).filter(|&i| zeros[i]).collect::<Vec<_>>());
+
+    // Long enough inputs use every character
+    let large: Vec<u8> = (0..500).map(|i| (i * 7 + i / 256) as u8).collect();
+    assert_eq!(
+        [true; 58],
+        bs58::encode::charset_usage(&large, Alphabet::BITCOIN)
+    );
+}
+
+#[test]
+fn test_encode_payload_builder() {
+    use bs58::encode::PayloadBuilder;
+
+    let hash = [0x9b; 20];
+    let payload = PayloadBuilder::new()
+        .push_u8(0x01)
+        .push_u16_be(0x0203)
+        .push_u32_be(0x0405_0607)
+        .push_u64_be(0x0809_0a0b_0c0d_0e0f)
+        .push_bytes(&hash)
+        .push_bytes(&[]);
+
+    let mut manual = vec![0x01];
+    manual.extend_from_slice(&0x0203u16.to_be_bytes());
+    manual.extend_from_slice(&0x0405_0607u32.to_be_bytes());
+    manual.extend_from_slice(&0x0809_0a0b_0c0d_0e0fu64.to_be_bytes());
+    manual.extend_from_slice(&hash);
+    assert_eq!(&manual[..], payload.as_bytes());
+    assert_eq!(
+        bs58::encode(&manual).into_string(),
+        payload.clone().finish().into_string()
+    );
+
+    #[cfg(feature = "check")]
+    assert_eq!(
+        bs58::encode(&manual).with_check_version(0x2a).into_string(),
+        payload.finish().with_check_version(0x2a).into_string()
+    );
+
+    assert_eq!("", PayloadBuilder::new().finish().into_string());
+    assert_eq!(
+        "11",
+        PayloadBuilder::default()
+            .push_u16_be(0)
+            .finish()
+            .into_string()
+    );
+}
+
+#[test]
+fn test_encode_into_uninit() {
+    use core::mem::MaybeUninit;
+
+    let large: Vec<u8> = (0..500).map(|i| (i * 7 + i / 256) as u8).collect();
+    let inputs = cases::TEST_CASES
+        .iter()
+        .map(|&(val, _)| val)
+        .chain([&large[..], &[0; 20]]);
+    for input in inputs {
+        let expected = bs58::encode(input).into_string();
+        let mut buf = vec![MaybeUninit::uninit(); bs58::max_encoded_len(input.len())];
+        let encoded = bs58::encode(input).into_uninit(&mut buf).unwrap();
+        assert_eq!(expected.as_bytes(), encoded);
+
+        let builder = bs58::encode(input).with_text_prefix("id:");
+        let mut buf = vec![MaybeUninit::uninit(); bs58::max_encoded_len(input.len()) + 3];
+        let encoded = builder.into_uninit(&mut buf).unwrap();
+        assert_eq!(format!("id:{expected}").as_bytes(), encoded);
+    }
+
+    // Only the encoded characters are written, so a buffer of exactly their length is enough
+    let input = [0x04, 0x30, 0x5e, 0x2b, 0x24, 0x73, 0xf0, 0x58];
+    let mut buf = [MaybeUninit::new(b'.'); 12];
+    assert_eq!(
+        b"he11owor1d",
+        bs58::encode(input).into_uninit(&mut buf[..10]).unwrap()
+    );
+    // SAFETY: the buffer was initialized when it was created
+    assert!(buf[10..]
+        .iter()
+        .all(|byte| unsafe { byte.assume_init() } == b'.'));
+
+    // Errors leave the buffer untouched
+    let mut buf = [MaybeUninit::new(b'.'); 12];
+    assert_eq!(
+        Err(bs58::encode::Error::BufferTooSmall),
+        bs58::encode(input).into_uninit(&mut buf[..9])
+    );
+    assert_eq!(
+        Err(bs58::encode::Error::InputTooLarge {
+            limit: 4,
+            actual: 8
+        }),
+        bs58::encode(input).with_max_input(4).into_uninit(&mut buf)
+    );
+    // SAFETY: the buffer was initialized when it was created
+    assert!(buf.iter().all(|byte| unsafe { byte.assume_init() } == b'.'));
+
+    #[cfg(feature = "check")]
+    {
+        let builder = bs58::encode(input).with_check_version(42);
+        let mut buf = [MaybeUninit::uninit(); 24];
+        let expected = bs58::encode(input).with_check_version(42).into_string();
+        assert_eq!(expected.as_bytes(), builder.into_uninit(&mut buf).unwrap());
+    }
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn test_encode_order_preserving() {
+    use bs58::encode::Error;
+
+    // Simple xorshift so the values cover the whole range
+    let mut state = 0x2545_f491_4f6c_dd1du64;
+    let mut values: Vec<u64> = (0..2000)
+        .map(|i| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            // Spread across every magnitude, not just the largest
+            state >> (i % 64)
+        })
+        .collect();
+    values.extend([0, 1, 57, 58, 255, 256, u64::MAX - 1, u64::MAX]);
+
+    let mut encoded: Vec<(String, u64)> = values
+        .iter()
+        .map(|&value| {
+            let key = bs58::encode(value.to_be_bytes())
+                .order_preserving(8)
+                .into_string();
+            (key, value)
+        })
+        .collect();
+    for (key, value) in &encoded {
+        assert_eq!(11, key.len(), "{value}");
+        assert_eq!(
+            Ok(value.to_be_bytes().to_vec()),
+            bs58::decode(key).order_preserving(8).into_vec(),
+            "{key}"
+        );
+    }
+    encoded.sort();
+    assert!(encoded.windows(2).all(|pair| pair[0].1 <= pair[1].1));
+
+    // Inputs of different lengths compare by value
+    let mut keys: Vec<(String, u128)> = (0..=16)
+        .flat_map(|len| {
+            values.iter().take(100).map(move |&value| {
+                let bytes = (u128::from(value) << 64 | u128::from(!value)).to_be_bytes();
+                bytes[16 - len..].to_vec()
+            })
+        })
+        .map(|bytes| {
+            let value = bytes.iter().fold(0, |value, &b| value << 8 | u128::from(b));
+            (
+                bs58::encode(&bytes).order_preserving(16).into_string(),
+                value,
+            )
+        })
+        .collect();
+    keys.sort();
+    assert!(keys.windows(2).all(|pair| pair[0].1 <= pair[1].1));
+    assert!(keys.iter().all(|(key, _)| key.len() == 22));
+
+    // The width is the smallest that fits every value
+    for max_bytes in 0..100 {
+        let width = bs58::encode(vec![0xff; max_bytes]).into_string().len();
+        let mut output = vec![0; bs58::max_encoded_len(max_bytes)];
+        let inputs = [&[][..], &[0], &[1], &[0xff; 100][..max_bytes]];
+        for input in inputs.into_iter().filter(|input| input.len() <= max_bytes) {
+            let len = bs58::encode(input)
+                .order_preserving(max_bytes)
+                .onto(&mut output[..])
+                .unwrap();
+            assert_eq!(width, len, "{max_bytes} {input:?}");
+        }
+    }
+
+    assert_eq!(
+        Err(Error::InputTooLarge {
+            limit: 3,
+            actual: 4
+        }),
+        bs58::encode([0, 0, 0, 1])
+            .order_preserving(3)
+            .try_into_string()
+    );
+    assert_eq!(
+        "1he11owor1d",
+        bs58::encode([0x04, 0x30, 0x5e, 0x2b, 0x24, 0x73, 0xf0, 0x58])
+            .order_preserving(8)
+            .into_string()
+    );
+    assert_eq!(
+        Ok(true),
+        bs58::encode([0x01])
+            .order_preserving(8)
+            .starts_with("1111111111")
+    );
+    assert_eq!(
+        Ok(false),
+        bs58::encode([0x01]).order_preserving(8).starts_with("2")
+    );
+}
@@ -0,0 +1,121 @@
+mod cases;
+
+/// Deterministic xorshift generator so the "random" inputs are reproducible.
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn bytes(&mut self, max_len: usize) -> Vec<u8> {
+        let len = self.next() as usize % (max_len + 1);
+        let leading_zeros = self.next() as usize % 3;
+        (0..len)
+            .map(|i| {
+                if i < leading_zeros {
+                    0
+                } else {
+                    self.next() as u8
+                }
+            })
+            .collect()
+    }
+}
+
+const ALPHABET: &[u8] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+#[test]
+fn test_eq() {
+    for &(val, s) in cases::TEST_CASES.iter() {
+        assert!(bs58::eq(val, s, bs58::Alphabet::DEFAULT));
+        assert!(!bs58::eq(val, format!("{}1", s), bs58::Alphabet::DEFAULT));
+        assert!(!bs58::eq(val, format!("1{}", s), bs58::Alphabet::DEFAULT));
+        assert!(!bs58::eq(val, format!("{}0", s), bs58::Alphabet::DEFAULT));
+    }
+}
+
+#[test]
+fn test_eq_matches_encode() {
+    let mut rng = Rng(0x2545_f491_4f6c_dd1d);
+    for _ in 0..500 {
+        let bytes = rng.bytes(200);
+        let other = rng.bytes(200);
+        let encoded = bs58::encode(&bytes).into_string();
+        assert!(bs58::eq(&bytes, &encoded, bs58::Alphabet::DEFAULT));
+        assert_eq!(
+            bytes == other,
+            bs58::eq(&other, &encoded, bs58::Alphabet::DEFAULT)
+        );
+
+        if !encoded.is_empty() {
+            let mut near_miss = encoded.clone().into_bytes();
+            let index = rng.next() as usize % near_miss.len();
+            near_miss[index] = ALPHABET[rng.next() as usize % ALPHABET.len()];
+            let expected = bs58::encode(&bytes).into_vec() == near_miss;
+            assert_eq!(
+                expected,
+                bs58::eq(&bytes, &near_miss, bs58::Alphabet::DEFAULT)
+            );
+        }
+    }
+}
+
+#[test]
+fn test_eq_hostile() {
+    let key = [0xff; 32];
+    assert!(!bs58::eq(
+        &key,
+        "z".repeat(100_000),
+        bs58::Alphabet::DEFAULT
+    ));
+    assert!(!bs58::eq(&key, "", bs58::Alphabet::DEFAULT));
+    assert!(!bs58::eq(&key, [0xff; 44], bs58::Alphabet::DEFAULT));
+    assert!(!bs58::eq(&[], "1", bs58::Alphabet::DEFAULT));
+    assert!(bs58::eq(&[], "", bs58::Alphabet::DEFAULT));
+}
+
+#[test]
+fn test_eq_long_congruent() {
+    // Payloads longer than the stack buffer are compared by remainders modulo primes below 2^32,
+    // values differing by a multiple of the first primes must still be told apart
+    let bytes = [0xab; 200];
+    for diff in [4_294_967_291, 4_294_967_291 * 4_294_967_279] {
+        let mut other = bytes;
+        let (head, tail) = other.split_at_mut(192);
+        let (sum, carry) = u64::from_be_bytes(tail.try_into().unwrap()).overflowing_add(diff);
+        tail.copy_from_slice(&sum.to_be_bytes());
+        head[191] += u8::from(carry);
+
+        let encoded = bs58::encode(other).into_string();
+        assert!(bs58::eq(&other, &encoded, bs58::Alphabet::DEFAULT));
+        assert!(!bs58::eq(&bytes, &encoded, bs58::Alphabet::DEFAULT));
+    }
+}
+
+#[test]
+#[cfg(feature = "check")]
+fn test_eq_check() {
+    for &(val, s) in cases::CHECK_TEST_CASES.iter() {
+        assert!(bs58::eq_check(val, s, bs58::Alphabet::DEFAULT));
+        assert!(!bs58::eq(val, s, bs58::Alphabet::DEFAULT));
+    }
+
+    let mut rng = Rng(0x9e37_79b9_7f4a_7c15);
+    for _ in 0..200 {
+        let bytes = rng.bytes(200);
+        let encoded = bs58::encode(&bytes).with_check().into_vec();
+        assert!(bs58::eq_check(&bytes, &encoded, bs58::Alphabet::DEFAULT));
+
+        let mut near_miss = encoded.clone();
+        let index = rng.next() as usize % near_miss.len();
+        near_miss[index] = ALPHABET[rng.next() as usize % ALPHABET.len()];
+        assert_eq!(
+            encoded == near_miss,
+            bs58::eq_check(&bytes, &near_miss, bs58::Alphabet::DEFAULT)
+        );
+    }
+}
@@ -0,0 +1,222 @@
+use bs58::{alphabet::Fold, decode::Error, Alphabet};
+
+/// The built-in alphabets along with the characters that are ambiguous in their uppercase display
+/// form, because both cases of the letter are in the alphabet.
+const UPPER_AMBIGUOUS: [(&str, &Alphabet, &[u8]); 4] = [
+    ("bitcoin", Alphabet::BITCOIN, b"ABCDEFGHJKMNPQRSTUVWXYZ"),
+    ("monero", Alphabet::MONERO, b"ABCDEFGHJKMNPQRSTUVWXYZ"),
+    ("ripple", Alphabet::RIPPLE, b"ABCDEFGHJKMNPQRSTUVWXYZ"),
+    ("flickr", Alphabet::FLICKR, b"ABCDEFGHJKMNPQRSTUVWXYZ"),
+];
+
+fn decode_upper(input: &[u8], alphabet: &Alphabet) -> Result<Vec<u8>, Error> {
+    bs58::decode(input)
+        .with_alphabet(alphabet)
+        .display_fold(Fold::Upper)
+        .into_vec()
+}
+
+#[test]
+fn test_fold_upper_roundtrip() {
+    for (name, alphabet, ambiguous) in UPPER_AMBIGUOUS {
+        let unambiguous: Vec<u8> = alphabet
+            .encode_table()
+            .iter()
+            .copied()
+            .filter(|c| !ambiguous.contains(&c.to_ascii_uppercase()))
+            .collect();
+        // Digits and the three letters with only one case in the alphabet
+        assert_eq!(58 - 2 * ambiguous.len(), unambiguous.len(), "{name}");
+
+        for len in 0..unambiguous.len() {
+            let canonical: Vec<u8> = unambiguous
+                .iter()
+                .cycle()
+                .skip(len)
+                .take(len)
+                .copied()
+                .collect();
+            let bytes = bs58::decode(&canonical)
+                .with_alphabet(alphabet)
+                .into_vec()
+                .unwrap();
+            let display = bs58::encode(&bytes)
+                .with_alphabet(alphabet)
+                .display_fold(Fold::Upper)
+                .into_vec();
+            assert_eq!(canonical.to_ascii_uppercase(), display, "{name}");
+            assert_eq!(
+                Ok(&bytes),
+                decode_upper(&display, alphabet).as_ref(),
+                "{name}"
+            );
+            // Either case is accepted
+            let lower = display.to_ascii_lowercase();
+            assert_eq!(
+                Ok(&bytes),
+                decode_upper(&lower, alphabet).as_ref(),
+                "{name}"
+            );
+        }
+    }
+}
+
+#[test]
+fn test_fold_upper_ambiguous() {
+    for (name, alphabet, ambiguous) in UPPER_AMBIGUOUS {
+        let nonzero = *alphabet.encode_table()[1..]
+            .iter()
+            .find(|c| c.is_ascii_digit())
+            .unwrap();
+        for c in (0..128u8).filter(u8::is_ascii_alphabetic) {
+            let input = [nonzero, c, nonzero];
+            let result = decode_upper(&input, alphabet);
+            if ambiguous.contains(&c.to_ascii_uppercase()) {
+                assert_eq!(
+                    Err(Error::AmbiguousFold {
+                        character: c as char,
+                        index: 1
+                    }),
+                    result,
+                    "{name} {:?}",
+                    c as char
+                );
+            } else if let Some(&member) = [c, c ^ 0x20]
+                .iter()
+                .find(|&&c| alphabet.would_conflict_with(c))
+            {
+                let canonical = [nonzero, member, nonzero];
+                let expected = bs58::decode(&canonical).with_alphabet(alphabet).into_vec();
+                assert_eq!(expected, result, "{name} {:?}", c as char);
+            } else {
+                assert!(
+                    matches!(result, Err(Error::InvalidCharacter { index: 1, .. })),
+                    "{name} {:?}",
+                    c as char
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn test_fold_upper_errors() {
+    let encoded = bs58::encode([0x04, 0x30, 0x5e, 0x2b, 0x24, 0x73, 0xf0, 0x58])
+        .with_text_prefix("id:")
+        .display_fold(Fold::Upper)
+        .into_string();
+    assert_eq!("id:HE11OWOR1D", encoded);
+    assert_eq!(
+        Err(Error::AmbiguousFold {
+            character: 'H',
+            index: 3
+        }),
+        bs58::decode(&encoded)
+            .with_text_prefix("id:")
+            .display_fold(Fold::Upper)
+            .into_vec()
+    );
+
+    // Characters are reported in the order they are decoded
+    assert_eq!(
+        Err(Error::InvalidCharacter {
+            character: '0',
+            index: 1
+        }),
+        decode_upper(b"10H", Alphabet::BITCOIN)
+    );
+    assert_eq!(
+        Err(Error::AmbiguousFold {
+            character: 'H',
+            index: 2
+        }),
+        bs58::decode("10H")
+            .rtl()
+            .display_fold(Fold::Upper)
+            .into_vec()
+    );
+    assert_eq!(
+        Err(Error::NonAsciiCharacter { index: 1 }),
+        decode_upper("1é".as_bytes(), Alphabet::BITCOIN)
+    );
+
+    let err = decode_upper(b"1H", Alphabet::BITCOIN).unwrap_err();
+    assert_eq!(
+        "provided string contained character 'H' at byte 1 which is the display form of more \
+         than one character of the alphabet",
+        err.to_string()
+    );
+    assert_eq!("ambiguous_fold", err.report().code.as_str());
+}
+
+#[test]
+fn test_fold_upper_custom_alphabet() {
+    // Only digits and uppercase letters, so every character maps back
+    let alphabet =
+        Alphabet::new(b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ!#$%&()*+,-./:;<=>?@[]").unwrap();
+    let input = [0x04, 0x30, 0x5e, 0x2b, 0x24, 0x73, 0xf0, 0x58, 0xff];
+    let encoded = bs58::encode(input).with_alphabet(&alphabet).into_string();
+    let display = bs58::encode(input)
+        .with_alphabet(&alphabet)
+        .display_fold(Fold::Upper)
+        .into_string();
+    assert_eq!(encoded, display);
+    assert_eq!(
+        Ok(input.to_vec()),
+        decode_upper(encoded.to_ascii_lowercase().as_bytes(), &alphabet)
+    );
+}
+
+#[test]
+fn test_fold_upper_folded_zeros() {
+    // The zero character `a` only appears as `A` in the display form
+    let alphabet =
+        Alphabet::new(b"abcdefghijkmnopqrstuvwxyz123456789!#$%&()*+,-./:;<=>?@[]^_").unwrap();
+    let input = [0u8; 10];
+    let display = bs58::encode(input)
+        .with_alphabet(&alphabet)
+        .display_fold(Fold::Upper)
+        .into_string();
+    assert_eq!("AAAAAAAAAA", display);
+
+    let decode = || {
+        bs58::decode(&display)
+            .with_alphabet(&alphabet)
+            .display_fold(Fold::Upper)
+    };
+    assert_eq!(Ok(input.to_vec()), decode().into_vec());
+    assert_eq!(
+        Ok(input.to_vec()),
+        decode().reserve_exact_output().into_vec()
+    );
+    assert_eq!(
+        Ok(input.to_vec()),
+        decode().with_expected_len(10).into_vec()
+    );
+    let mut output = vec![0xff];
+    assert_eq!(Ok(10), decode().extend_into(&mut output));
+    assert_eq!([&[0xff][..], &input].concat(), output);
+    // The fold is reversed for the alphabet set after it too
+    assert_eq!(
+        Ok(input.to_vec()),
+        bs58::decode(&display)
+            .display_fold(Fold::Upper)
+            .with_alphabet(&alphabet)
+            .with_expected_len(10)
+            .into_vec()
+    );
+
+    let display = bs58::encode(input)
+        .with_alphabet(&alphabet)
+        .order_preserving(12)
+        .display_fold(Fold::Upper)
+        .into_string();
+    assert_eq!(
+        Ok(vec![0; 12]),
+        bs58::decode(&display)
+            .with_alphabet(&alphabet)
+            .order_preserving(12)
+            .display_fold(Fold::Upper)
+            .into_vec()
+    );
+}
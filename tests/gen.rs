@@ -0,0 +1,81 @@
+#![cfg(feature = "rand")]
+
+use rand::SeedableRng;
+
+const ALPHABETS: &[&bs58::Alphabet] = &[
+    bs58::Alphabet::BITCOIN,
+    bs58::Alphabet::RIPPLE,
+    bs58::Alphabet::FLICKR,
+];
+
+#[test]
+fn test_gen_encoded_with_len() {
+    let mut rng = rand::rngs::SmallRng::seed_from_u64(0x5eed);
+    for &alphabet in ALPHABETS {
+        for decoded_len in [0, 1, 2, 5, 32, 100] {
+            let mut leading_zeros = false;
+            for _ in 0..200 {
+                let s = bs58::gen::encoded_with_len(&mut rng, decoded_len, alphabet);
+                let decoded = bs58::decode(&s).with_alphabet(alphabet).into_vec();
+                assert_eq!(Ok(decoded_len), decoded.map(|d| d.len()), "{s:?}");
+                leading_zeros |= s.as_bytes().first() == Some(&alphabet.encode_table()[0]);
+            }
+            assert_eq!(decoded_len > 0, leading_zeros);
+        }
+    }
+}
+
+#[test]
+fn test_gen_edge_cases() {
+    for &alphabet in ALPHABETS {
+        assert_eq!(
+            vec![String::new()],
+            bs58::gen::edge_cases(0, alphabet).collect::<Vec<_>>()
+        );
+
+        for decoded_len in [1, 2, 5, 32] {
+            let cases: Vec<Vec<u8>> = bs58::gen::edge_cases(decoded_len, alphabet)
+                .map(|s| bs58::decode(s).with_alphabet(alphabet).into_vec().unwrap())
+                .collect();
+            assert_eq!(2 + 8 * decoded_len, cases.len());
+            assert!(cases.iter().all(|case| case.len() == decoded_len));
+            assert_eq!(vec![0; decoded_len], cases[0]);
+            assert_eq!(vec![0xFF; decoded_len], cases[1]);
+            for (bit, case) in cases[2..].iter().enumerate() {
+                let ones: u32 = case.iter().map(|b| b.count_ones()).sum();
+                assert_eq!(1, ones);
+                assert_eq!(1 << (bit % 8), case[decoded_len - 1 - bit / 8]);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_gen_chars_with_len() {
+    let mut rng = rand::rngs::SmallRng::seed_from_u64(0x5eed);
+    for &alphabet in ALPHABETS {
+        for len in [0, 1, 2, 10, 44, 200] {
+            let s = bs58::gen::chars_with_len(&mut rng, len, alphabet);
+            assert_eq!(len, s.len());
+            assert!(
+                bs58::decode(&s).with_alphabet(alphabet).into_vec().is_ok(),
+                "{s:?}"
+            );
+        }
+
+        // Every character turns up, roughly equally often
+        let mut counts = [0; 58];
+        for c in bs58::gen::chars_with_len(&mut rng, 58 * 1000, alphabet).bytes() {
+            let digit = alphabet
+                .encode_table()
+                .iter()
+                .position(|&d| d == c)
+                .unwrap();
+            counts[digit] += 1;
+        }
+        assert!(
+            counts.iter().all(|&count| (800..1200).contains(&count)),
+            "{counts:?}"
+        );
+    }
+}
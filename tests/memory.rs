@@ -0,0 +1,102 @@
+//! Measures the heap used while encoding a large input, to check that it is only the output.
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+};
+
+/// Wraps the system allocator, tracking the bytes allocated by the current thread and the peak
+/// of that since it was last reset.
+struct PeakAlloc;
+
+thread_local! {
+    static CURRENT: Cell<isize> = const { Cell::new(0) };
+    static PEAK: Cell<isize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for PeakAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let _ = CURRENT.try_with(|current| {
+            current.set(current.get() + layout.size() as isize);
+            let _ = PEAK.try_with(|peak| peak.set(peak.get().max(current.get())));
+        });
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        // Memory from other threads may be freed here, so this can go negative
+        let _ = CURRENT.try_with(|current| current.set(current.get() - layout.size() as isize));
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: PeakAlloc = PeakAlloc;
+
+/// The peak number of bytes allocated while running `f`, beyond what was allocated before.
+fn peak_allocated<R>(f: impl FnOnce() -> R) -> (R, usize) {
+    let start = CURRENT.with(Cell::get);
+    PEAK.with(|peak| peak.set(start));
+    let result = f();
+    (result, (PEAK.with(Cell::get) - start) as usize)
+}
+
+/// Large enough to show any copies of the input, small enough to encode quickly in debug builds.
+const LEN: usize = 8 * 1024;
+
+fn large_input() -> Vec<u8> {
+    (0..LEN).map(|i| (i * 7 + i / 256) as u8).collect()
+}
+
+#[test]
+fn test_encode_large_input_memory() {
+    let input = large_input();
+    let max_len = bs58::max_encoded_len(LEN);
+
+    let (encoded, peak) = peak_allocated(|| bs58::encode(&input).into_string());
+    assert_eq!(max_len, peak);
+
+    let (streamed, peak) = peak_allocated(|| {
+        let mut len = 0;
+        bs58::encode::from_slice_streaming(&input, |chunk| {
+            assert!(chunk.len() <= 64 * 1024);
+            assert_eq!(&encoded[len..len + chunk.len()], chunk);
+            len += chunk.len();
+        });
+        len
+    });
+    assert_eq!(encoded.len(), streamed);
+    assert_eq!(max_len, peak);
+
+    let mut output = vec![0; max_len];
+    let (len, peak) = peak_allocated(|| bs58::encode(&input).onto(&mut output[..]).unwrap());
+    assert_eq!(encoded.as_bytes(), &output[..len]);
+    assert_eq!(0, peak);
+}
+
+#[test]
+#[cfg(feature = "check")]
+fn test_encode_check_large_input_memory() {
+    let input = large_input();
+
+    let (_, peak) = peak_allocated(|| bs58::encode(&input).with_check().into_string());
+    assert_eq!(bs58::max_encoded_len(LEN + 4), peak);
+}
+
+#[test]
+fn test_encode_with_scratch_memory() {
+    let input = large_input();
+
+    let mut scratch = bs58::encode::Scratch::new();
+    let (_, peak) = peak_allocated(|| bs58::encode(&input).with_scratch(&mut scratch).len());
+    assert_eq!(bs58::max_encoded_len(LEN), peak);
+
+    // Once grown, encoding the same or shorter inputs reuses the buffer
+    for len in [LEN, LEN / 2, 1, 0, LEN] {
+        let expected = bs58::encode(&input[..len]).into_string();
+        let (encoded, peak) =
+            peak_allocated(|| bs58::encode(&input[..len]).with_scratch(&mut scratch) == expected);
+        assert!(encoded, "{len}");
+        assert_eq!(0, peak, "{len}");
+    }
+}
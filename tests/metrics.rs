@@ -0,0 +1,84 @@
+#![cfg(feature = "metrics")]
+
+use bs58::metrics::Stats;
+
+fn decode_stats(input: &str) -> Stats {
+    let mut stats = Stats::default();
+    let _ = bs58::decode(input).with_stats(&mut stats).into_vec();
+    stats
+}
+
+fn encode_stats(input: &[u8]) -> Stats {
+    let mut stats = Stats::default();
+    bs58::encode(input).with_stats(&mut stats).into_string();
+    stats
+}
+
+#[test]
+fn test_decode_stats() {
+    let stats = decode_stats("he11owor1d");
+    assert_eq!(10, stats.input_len);
+    assert_eq!(8, stats.output_len);
+    assert_eq!(37, stats.iterations);
+    assert!(!stats.fast_path);
+
+    // Leading zeros are not part of the value so don't add iterations
+    let stats = decode_stats("11he11owor1d");
+    assert_eq!(
+        (12, 10, 37),
+        (stats.input_len, stats.output_len, stats.iterations)
+    );
+
+    // Failures record the work done up to them
+    let stats = decode_stats("he11o0or1d");
+    assert_eq!((10, 0), (stats.input_len, stats.output_len));
+    assert_eq!(decode_stats("he11o").iterations, stats.iterations);
+
+    // A prefix counts towards the input but isn't decoded
+    let mut stats = Stats::default();
+    bs58::decode("id:he11owor1d")
+        .with_text_prefix("id:")
+        .with_stats(&mut stats)
+        .into_vec()
+        .unwrap();
+    assert_eq!(
+        (13, 8, 37),
+        (stats.input_len, stats.output_len, stats.iterations)
+    );
+}
+
+#[test]
+fn test_stats_reset() {
+    let mut stats = decode_stats("he11owor1d");
+    bs58::decode("").with_stats(&mut stats).into_vec().unwrap();
+    assert_eq!(Stats::default(), stats);
+}
+
+#[test]
+fn test_stats_quadratic() {
+    let input = |len: usize| (0..len).map(|i| (i * 7 + 1) as u8).collect::<Vec<_>>();
+    let (short, long) = (encode_stats(&input(64)), encode_stats(&input(256)));
+    let ratio = long.iterations as f64 / short.iterations as f64;
+    assert!((14.0..18.0).contains(&ratio), "{ratio}");
+
+    let short = decode_stats(&bs58::encode(input(64)).into_string());
+    let long = decode_stats(&bs58::encode(input(256)).into_string());
+    let ratio = long.iterations as f64 / short.iterations as f64;
+    assert!((14.0..18.0).contains(&ratio), "{ratio}");
+}
+
+#[test]
+fn test_encode_stats() {
+    let stats = encode_stats(&[0x04, 0x30, 0x5e, 0x2b, 0x24, 0x73, 0xf0, 0x58]);
+    assert_eq!(8, stats.input_len);
+    assert_eq!(10, stats.output_len);
+    assert_eq!(35, stats.iterations);
+    assert!(!stats.fast_path);
+
+    let stats = encode_stats(&[0; 32]);
+    assert_eq!(
+        (32, 32, 0),
+        (stats.input_len, stats.output_len, stats.iterations)
+    );
+    assert!(stats.fast_path);
+}
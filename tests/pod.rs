@@ -0,0 +1,66 @@
+#![cfg(feature = "bytemuck")]
+
+#[derive(Clone, Copy, Debug, PartialEq, bytemuck::Pod, bytemuck::Zeroable)]
+#[repr(C)]
+struct Header {
+    magic: [u8; 4],
+    version: u32,
+    length: u64,
+}
+
+#[test]
+fn test_pod_struct() {
+    let header = Header {
+        magic: *b"bs58",
+        version: 1,
+        length: 0x0123_4567_89ab_cdef,
+    };
+    let encoded = bs58::encode_pod(&header).into_string();
+    assert_eq!(
+        bs58::encode(bytemuck::bytes_of(&header)).into_string(),
+        encoded
+    );
+    assert_eq!(Ok(header), bs58::decode(&encoded).into_pod::<Header>());
+}
+
+#[test]
+fn test_pod_array() {
+    let commitment: [u64; 4] = [0, 1, u64::MAX, 0x0123_4567_89ab_cdef];
+    let encoded = bs58::encode_pod(&commitment).into_string();
+    assert_eq!(
+        Ok(commitment),
+        bs58::decode(&encoded).into_pod::<[u64; 4]>()
+    );
+
+    // Leading zero bytes are kept as leading zero characters
+    let zeros = [0u64; 4];
+    let encoded = bs58::encode_pod(&zeros).into_string();
+    assert_eq!("1".repeat(32), encoded);
+    assert_eq!(Ok(zeros), bs58::decode(&encoded).into_pod::<[u64; 4]>());
+}
+
+#[test]
+fn test_pod_wrong_size() {
+    let encoded = bs58::encode_pod(&[1u64; 3]).into_string();
+    assert_eq!(
+        Err(bs58::decode::Error::WrongLength {
+            expected: 32,
+            found: 24,
+        }),
+        bs58::decode(&encoded).into_pod::<[u64; 4]>()
+    );
+    assert_eq!(
+        Err(bs58::decode::Error::WrongLength {
+            expected: 16,
+            found: 24,
+        }),
+        bs58::decode(&encoded).into_pod::<[u64; 2]>()
+    );
+    assert_eq!(
+        Err(bs58::decode::Error::InvalidCharacter {
+            character: '0',
+            index: 0,
+        }),
+        bs58::decode("0").into_pod::<u8>()
+    );
+}
@@ -0,0 +1,84 @@
+use rand::{Rng, SeedableRng};
+
+use bs58::{raw, Alphabet};
+
+/// Decode using only the raw building blocks, returning `None` for invalid input.
+fn raw_decode(input: &[u8], alphabet: &Alphabet) -> Option<Vec<u8>> {
+    let mut buf = vec![0; input.len()];
+    let mut len = 0;
+    for &c in input {
+        len = raw::push_digit(&mut buf, len, raw::digit(alphabet, c)?).unwrap();
+    }
+    let zero = raw::character(alphabet, 0);
+    let zeros = input.iter().take_while(|&&c| c == zero).count();
+    buf.truncate(len);
+    buf.resize(len + zeros, 0);
+    buf.reverse();
+    Some(buf)
+}
+
+/// Encode using only the raw building blocks.
+fn raw_encode(input: &[u8], alphabet: &Alphabet) -> Vec<u8> {
+    let mut buf = vec![0; input.len() * 2];
+    let mut len = 0;
+    for &byte in input {
+        len = raw::push_byte(&mut buf, len, byte).unwrap();
+    }
+    let zeros = input.iter().take_while(|&&byte| byte == 0).count();
+    buf.truncate(len);
+    buf.resize(len + zeros, 0);
+    for digit in &mut buf {
+        *digit = raw::character(alphabet, *digit);
+    }
+    buf.reverse();
+    buf
+}
+
+#[test]
+fn test_raw_decode_matches() {
+    let mut rng = rand::rngs::SmallRng::seed_from_u64(58);
+    for alphabet in [Alphabet::BITCOIN, Alphabet::RIPPLE, Alphabet::FLICKR] {
+        let chars = alphabet.encode_table();
+        for _ in 0..2000 {
+            let len = rng.gen_range(0..100);
+            let zeros = rng.gen_range(0..4).min(len);
+            let mut input: Vec<u8> = (0..len)
+                .map(|i| chars[if i < zeros { 0 } else { rng.gen_range(0..58) }])
+                .collect();
+            if rng.gen_ratio(1, 10) && len > 0 {
+                input[rng.gen_range(0..len)] = rng.gen();
+            }
+
+            let expected = bs58::decode(&input).with_alphabet(alphabet).into_vec();
+            assert_eq!(expected.ok(), raw_decode(&input, alphabet), "{input:?}");
+        }
+    }
+}
+
+#[test]
+fn test_raw_encode_matches() {
+    let mut rng = rand::rngs::SmallRng::seed_from_u64(58);
+    for alphabet in [Alphabet::BITCOIN, Alphabet::RIPPLE, Alphabet::FLICKR] {
+        for _ in 0..2000 {
+            let len = rng.gen_range(0..100);
+            let zeros = rng.gen_range(0..4).min(len);
+            let mut input = vec![0; len];
+            rng.fill(&mut input[zeros..]);
+
+            let expected = bs58::encode(&input).with_alphabet(alphabet).into_vec();
+            assert_eq!(expected, raw_encode(&input, alphabet), "{input:?}");
+        }
+    }
+}
+
+#[test]
+fn test_raw_push_overflow() {
+    let mut buf = [0xff];
+    assert_eq!(Some(1), raw::push_digit(&mut buf, 0, 57));
+    assert_eq!(None, raw::push_digit(&mut buf, 1, 57));
+    assert_eq!(Some(2), raw::push_byte(&mut [0; 2], 0, 0xff));
+    assert_eq!(None, raw::push_byte(&mut [0; 1], 0, 0xff));
+    assert_eq!(None, raw::digit(Alphabet::DEFAULT, b'0'));
+    assert_eq!(None, raw::digit(Alphabet::DEFAULT, 0xe9));
+    assert_eq!(Some(57), raw::digit(Alphabet::DEFAULT, b'z'));
+}
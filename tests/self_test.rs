@@ -0,0 +1,4 @@
+#[test]
+fn test_self_test() {
+    assert_eq!(Ok(()), bs58::self_test());
+}
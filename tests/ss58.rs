@@ -0,0 +1,78 @@
+#![cfg(feature = "ss58")]
+
+use assert_matches::assert_matches;
+
+use bs58::ss58::Error;
+
+const ALICE: [u8; 32] = [
+    0xd4, 0x35, 0x93, 0xc7, 0x15, 0xfd, 0xd3, 0x1c, 0x61, 0x14, 0x1a, 0xbd, 0x04, 0xa9, 0x9f, 0xd6,
+    0x82, 0x2c, 0x85, 0x58, 0x85, 0x4c, 0xcd, 0xe3, 0x9a, 0x56, 0x84, 0xe7, 0xa5, 0x6d, 0xa2, 0x7d,
+];
+
+const CASES: &[(u16, &str)] = &[
+    (0, "15oF4uVJwmo4TdGW7VfQxNLavjCXviqxT9S1MgbjMNHr6Sp5"),
+    (2, "HNZata7iMYWmk5RvZRTiAsSDhV8366zq2YGb3tLH5Upf74F"),
+    (42, "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY"),
+    (66, "cTM8suyN19VZb7JEPRNvtezyfpEAJyYxHkk1n5J4XEr6XroRa"),
+    (255, "yGHXkYLYqxijLKKfd9Q2CB9shRVu8rPNBS53wvwGTutYg4zTg"),
+    (10041, "bXmPf7DcVmFuHEmzH3UX8t6AUkfNQW8pnTeXGhFhqbfngjAak"),
+    (16383, "yNa8JpqfFB3q8A29rCwSgxvdU94ufJw2yKKxDgznS5m1PoFvn"),
+];
+
+#[test]
+fn test_ss58_encode() {
+    for &(network, expected) in CASES {
+        assert_eq!(expected, bs58::ss58::encode(network, &ALICE));
+    }
+}
+
+#[test]
+fn test_ss58_decode() {
+    for &(network, address) in CASES {
+        assert_eq!(Ok((network, ALICE)), bs58::ss58::decode(address));
+    }
+}
+
+#[test]
+#[should_panic]
+fn test_ss58_encode_network_out_of_range() {
+    bs58::ss58::encode(16384, &ALICE);
+}
+
+#[test]
+fn test_ss58_decode_errors() {
+    let mut data = bs58::decode(CASES[2].1).into_vec().unwrap();
+    data[34] ^= 1;
+    assert_matches!(
+        bs58::ss58::decode(&bs58::encode(&data).into_string()),
+        Err(Error::InvalidChecksum { .. })
+    );
+
+    assert_eq!(
+        Err(Error::WrongLength {
+            expected: 35,
+            found: 34
+        }),
+        bs58::ss58::decode(&bs58::encode(&data[..34]).into_string()),
+    );
+
+    data.push(0);
+    assert_eq!(
+        Err(Error::WrongLength {
+            expected: 35,
+            found: 36
+        }),
+        bs58::ss58::decode(&bs58::encode(&data).into_string()),
+    );
+
+    data[0] = 0x80;
+    assert_eq!(
+        Err(Error::InvalidPrefix { prefix: 0x80 }),
+        bs58::ss58::decode(&bs58::encode(&data).into_string()),
+    );
+
+    assert_matches!(
+        bs58::ss58::decode("0"),
+        Err(Error::Decode(bs58::decode::Error::InvalidCharacter { .. }))
+    );
+}
@@ -0,0 +1,9 @@
+//! The messages of the `testing` assertions, which the other tests use with `test-util`.
+
+#[test]
+#[should_panic(
+    expected = "first difference at byte 2\n    string: \"he11owor1d\"\n   decoded: [04 30 5e 2b"
+)]
+fn test_assert_decode_matches_message() {
+    bs58::testing::assert_decode_matches("he11owor1d", &[0x04, 0x30, 0x5f]);
+}
@@ -0,0 +1,141 @@
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+};
+
+/// Wraps the system allocator, failing allocations on the current thread above a set size.
+struct CappedAlloc;
+
+thread_local! {
+    static CAP: Cell<usize> = const { Cell::new(usize::MAX) };
+}
+
+unsafe impl GlobalAlloc for CappedAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        if layout.size() > CAP.try_with(|cap| cap.get()).unwrap_or(usize::MAX) {
+            return std::ptr::null_mut();
+        }
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CappedAlloc = CappedAlloc;
+
+/// Run `f` with allocations larger than `cap` bytes failing.
+fn with_cap<T>(cap: usize, f: impl FnOnce() -> T) -> T {
+    CAP.with(|c| c.set(cap));
+    let result = f();
+    CAP.with(|c| c.set(usize::MAX));
+    result
+}
+
+#[test]
+fn test_try_into_vec() {
+    let encoded = "z".repeat(1000);
+    let decoded = bs58::decode(&encoded).into_vec().unwrap();
+
+    assert_eq!(
+        Err(bs58::decode::Error::AllocationFailed),
+        with_cap(100, || bs58::decode(&encoded).try_into_vec())
+    );
+    assert_eq!(
+        Ok(&decoded),
+        with_cap(1000, || bs58::decode(&encoded).try_into_vec()).as_ref()
+    );
+    assert_eq!(
+        Ok(&decoded),
+        with_cap(decoded.len() + 1, || bs58::decode(&encoded)
+            .reserve_exact_output()
+            .try_into_vec())
+        .as_ref()
+    );
+}
+
+#[test]
+fn test_try_into_string() {
+    let input = [0xff; 1000];
+    let encoded = bs58::encode(input).into_string();
+    let max_len = bs58::max_encoded_len(input.len());
+
+    assert_eq!(
+        Err(bs58::encode::Error::AllocationFailed),
+        with_cap(max_len - 1, || bs58::encode(input).try_into_string())
+    );
+    assert_eq!(
+        Err(bs58::encode::Error::AllocationFailed),
+        with_cap(max_len - 1, || bs58::encode(input).try_into_vec())
+    );
+    assert_eq!(
+        Ok(&encoded),
+        with_cap(max_len, || bs58::encode(input).try_into_string()).as_ref()
+    );
+    assert_eq!(
+        Ok(encoded.as_bytes()),
+        with_cap(max_len, || bs58::encode(input).try_into_vec()).as_deref()
+    );
+}
+
+#[test]
+#[cfg(feature = "check")]
+fn test_try_into_vec_check() {
+    let input = [0xff; 1000];
+    let encoded = bs58::encode(input).with_check().into_string();
+    let max_len = bs58::max_encoded_len(input.len() + 4);
+
+    assert_eq!(
+        Err(bs58::encode::Error::AllocationFailed),
+        with_cap(max_len - 1, || bs58::encode(input)
+            .with_check()
+            .try_into_string())
+    );
+    assert_eq!(
+        Ok(&encoded),
+        with_cap(max_len, || bs58::encode(input)
+            .with_check()
+            .try_into_string())
+        .as_ref()
+    );
+
+    assert_eq!(
+        Err(bs58::decode::Error::AllocationFailed),
+        with_cap(encoded.len() - 1, || bs58::decode(&encoded)
+            .with_check(None)
+            .try_into_vec())
+    );
+    assert_eq!(
+        Ok(&input[..]),
+        with_cap(encoded.len(), || bs58::decode(&encoded)
+            .with_check(None)
+            .try_into_vec())
+        .as_deref()
+    );
+}
+
+#[test]
+fn test_try_into_string_max_input() {
+    let input = vec![0xff; 1 << 20];
+    let too_large = Err(bs58::encode::Error::InputTooLarge {
+        limit: 1000,
+        actual: 1 << 20,
+    });
+
+    // With every allocation failing the only way to get this error is from the length check made
+    // before any output or scratch space is allocated.
+    assert_eq!(
+        too_large,
+        with_cap(0, || bs58::encode(&input)
+            .with_max_input(1000)
+            .try_into_string())
+    );
+    assert_eq!(
+        too_large.map(String::into_bytes),
+        with_cap(0, || bs58::encode(&input)
+            .with_max_input(1000)
+            .try_into_vec())
+    );
+}